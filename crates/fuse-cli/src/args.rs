@@ -0,0 +1,204 @@
+//! Command-line arguments
+//!
+//! Arguments are parsed by hand to keep the binary free of dependencies
+//! beyond the library itself.
+
+use std::path::PathBuf;
+
+use crate::input::InputFormat;
+
+//----------------------------------------------------------------------
+// Types & Constants
+//----------------------------------------------------------------------
+
+/// Usage text printed by `fuse help` and on argument errors
+pub const USAGE: &str = "\
+Usage: fuse search --pattern <PATTERN> [OPTIONS] [FILE]
+
+Searches FILE (or standard input) and prints the matching items.
+
+Options:
+  -p, --pattern <PATTERN>  Pattern to search for
+  -k, --keys <KEYS>        Comma-separated keys to search in (e.g. title,author.name)
+  -n, --limit <N>          Maximum number of results
+  -t, --threshold <T>      Match threshold between 0.0 (exact) and 1.0 (anything)
+  -e, --extended           Use the extended search syntax (^prefix, suffix$, 'include, ...)
+  -m, --include-matches    Include the matched character ranges
+  -s, --include-score      Include the score of each result
+      --input <FORMAT>     Input format: json, jsonl or csv (default: from the file extension, else json)
+      --output <FORMAT>    Output format: json or jsonl (default: json)
+  -h, --help               Print this help
+  -V, --version            Print the version";
+
+/// A parsed command line
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    /// Search a file
+    Search(SearchArgs),
+    /// Print the usage
+    Help,
+    /// Print the version
+    Version,
+}
+
+/// Arguments of `fuse search`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SearchArgs {
+    /// Pattern to search for
+    pub pattern: String,
+
+    /// Keys to search in; empty for collections of strings
+    pub keys: Vec<String>,
+
+    /// File to read, or standard input when `None`
+    pub file: Option<PathBuf>,
+
+    /// Maximum number of results
+    pub limit: Option<usize>,
+
+    /// Match threshold
+    pub threshold: Option<f64>,
+
+    /// Whether the pattern uses the extended search syntax
+    pub extended: bool,
+
+    /// Whether matched ranges are included
+    pub include_matches: bool,
+
+    /// Whether scores are included
+    pub include_score: bool,
+
+    /// Input format, detected from the file name when `None`
+    pub input: Option<InputFormat>,
+
+    /// Output format
+    pub output: OutputFormat,
+}
+
+/// Format of the printed results
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum OutputFormat {
+    /// A pretty-printed JSON array
+    #[default]
+    Json,
+    /// One JSON object per line
+    JsonLines,
+}
+
+//----------------------------------------------------------------------
+// Public API
+//----------------------------------------------------------------------
+
+/// Parses the arguments following the program name.
+pub fn parse<I>(args: I) -> Result<Command, String>
+where
+    I: IntoIterator<Item = String>,
+{
+    let mut args = args.into_iter();
+
+    match args.next().as_deref() {
+        Some("search") => parse_search(args).map(Command::Search),
+        Some("help" | "-h" | "--help") | None => Ok(Command::Help),
+        Some("-V" | "--version") => Ok(Command::Version),
+        Some(other) => Err(format!("unknown command '{}'", other)),
+    }
+}
+
+//----------------------------------------------------------------------
+// Implementation Details
+//----------------------------------------------------------------------
+
+/// Parses the arguments of `fuse search`.
+fn parse_search<I: Iterator<Item = String>>(mut args: I) -> Result<SearchArgs, String> {
+    let mut search = SearchArgs::default();
+    let mut pattern = None;
+
+    while let Some(arg) = args.next() {
+        // Accept both `--limit 5` and `--limit=5`
+        let (flag, inline) = match arg.split_once('=') {
+            Some((flag, value)) if flag.starts_with("--") => (flag.to_string(), Some(value.to_string())),
+            _ => (arg.clone(), None),
+        };
+        let mut value = || inline.clone().or_else(|| args.next()).ok_or(format!("missing value for {}", flag));
+
+        match flag.as_str() {
+            "-p" | "--pattern" => pattern = Some(value()?),
+            "-k" | "--keys" => {
+                search.keys = value()?
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|key| !key.is_empty())
+                    .map(String::from)
+                    .collect();
+            },
+            "-n" | "--limit" => search.limit = Some(parse_number(&flag, &value()?)?),
+            "-t" | "--threshold" => search.threshold = Some(parse_number(&flag, &value()?)?),
+            "-e" | "--extended" => search.extended = true,
+            "-m" | "--include-matches" => search.include_matches = true,
+            "-s" | "--include-score" => search.include_score = true,
+            "--input" => search.input = Some(InputFormat::parse(&value()?)?),
+            "--output" => {
+                search.output = match value()?.as_str() {
+                    "json" => OutputFormat::Json,
+                    "jsonl" => OutputFormat::JsonLines,
+                    other => return Err(format!("unknown output format '{}'", other)),
+                }
+            },
+            "-" => search.file = None,
+            _ if flag.starts_with('-') => return Err(format!("unknown option '{}'", flag)),
+            _ if search.file.is_some() => return Err(format!("unexpected argument '{}'", arg)),
+            _ => search.file = Some(PathBuf::from(arg)),
+        }
+    }
+
+    search.pattern = pattern.ok_or("missing --pattern")?;
+    Ok(search)
+}
+
+/// Parses the numeric value of `flag`.
+fn parse_number<T: std::str::FromStr>(flag: &str, value: &str) -> Result<T, String> {
+    value.parse().map_err(|_| format!("invalid value '{}' for {}", value, flag))
+}
+
+//----------------------------------------------------------------------
+// Tests
+//----------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_args(args: &[&str]) -> Result<Command, String> {
+        parse(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn test_parse_search() {
+        let command = parse_args(&[
+            "search", "--keys", "title, author", "--pattern", "rust", "-n", "5", "--output=jsonl", "-e", "books.json",
+        ])
+        .unwrap();
+
+        assert_eq!(
+            command,
+            Command::Search(SearchArgs {
+                pattern: "rust".into(),
+                keys: vec!["title".into(), "author".into()],
+                file: Some(PathBuf::from("books.json")),
+                limit: Some(5),
+                extended: true,
+                output: OutputFormat::JsonLines,
+                ..Default::default()
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(parse_args(&["search", "data.json"]), Err("missing --pattern".into()));
+        assert_eq!(parse_args(&["search", "-p"]), Err("missing value for -p".into()));
+        assert_eq!(parse_args(&["search", "-p", "x", "-n", "many"]), Err("invalid value 'many' for -n".into()));
+        assert_eq!(parse_args(&["find"]), Err("unknown command 'find'".into()));
+        assert_eq!(parse_args(&[]), Ok(Command::Help));
+    }
+}
@@ -0,0 +1,169 @@
+//! Reading collections from JSON, JSON Lines and CSV
+//!
+//! CSV files must start with a header row; every following row becomes an
+//! object mapping the header names to the row's (string) values.
+
+use std::path::Path;
+
+use serde_json::{Map, Value};
+
+//----------------------------------------------------------------------
+// Types
+//----------------------------------------------------------------------
+
+/// Format of the searched file
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum InputFormat {
+    /// A JSON array of items
+    Json,
+    /// One JSON item per line
+    JsonLines,
+    /// Comma-separated values with a header row
+    Csv,
+}
+
+//----------------------------------------------------------------------
+// Public API
+//----------------------------------------------------------------------
+
+impl InputFormat {
+    /// Parses a format name (`json`, `jsonl` or `csv`).
+    pub fn parse(name: &str) -> Result<Self, String> {
+        match name {
+            "json" => Ok(Self::Json),
+            "jsonl" | "ndjson" => Ok(Self::JsonLines),
+            "csv" => Ok(Self::Csv),
+            other => Err(format!("unknown input format '{}'", other)),
+        }
+    }
+
+    /// Detects the format from a file extension, defaulting to JSON.
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some(ext) => Self::parse(&ext.to_ascii_lowercase()).unwrap_or(Self::Json),
+            None => Self::Json,
+        }
+    }
+
+    /// Parses `text` into the items of the collection.
+    pub fn read(self, text: &str) -> Result<Vec<Value>, String> {
+        match self {
+            Self::Json => match serde_json::from_str(text).map_err(|e| format!("invalid JSON: {}", e))? {
+                Value::Array(items) => Ok(items),
+                _ => Err("expected a JSON array of items".to_string()),
+            },
+            Self::JsonLines => text
+                .lines()
+                .enumerate()
+                .filter(|(_, line)| !line.trim().is_empty())
+                .map(|(i, line)| serde_json::from_str(line).map_err(|e| format!("invalid JSON on line {}: {}", i + 1, e)))
+                .collect(),
+            Self::Csv => read_csv(text),
+        }
+    }
+}
+
+//----------------------------------------------------------------------
+// Implementation Details
+//----------------------------------------------------------------------
+
+/// Reads CSV rows into objects keyed by the header row.
+fn read_csv(text: &str) -> Result<Vec<Value>, String> {
+    let mut rows = parse_csv(text)?.into_iter();
+    let Some(header) = rows.next() else {
+        return Ok(Vec::new());
+    };
+
+    Ok(rows
+        .map(|row| {
+            let item: Map<String, Value> = header
+                .iter()
+                .cloned()
+                .zip(row.into_iter().map(Value::String))
+                .collect();
+            Value::Object(item)
+        })
+        .collect())
+}
+
+/// Splits CSV text into rows of fields.
+///
+/// Fields may be quoted with `"`, in which case they can contain commas,
+/// line breaks and doubled quotes (`""`).
+fn parse_csv(text: &str) -> Result<Vec<Vec<String>>, String> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match (quoted, c) {
+            (true, '"') if chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            },
+            (true, '"') => quoted = false,
+            (true, c) => field.push(c),
+            (false, '"') if field.is_empty() => quoted = true,
+            (false, ',') => row.push(std::mem::take(&mut field)),
+            (false, '\r') => {},
+            (false, '\n') => {
+                row.push(std::mem::take(&mut field));
+                rows.push(std::mem::take(&mut row));
+            },
+            (false, c) => field.push(c),
+        }
+    }
+
+    if quoted {
+        return Err("unterminated quoted CSV field".to_string());
+    }
+
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field);
+        rows.push(row);
+    }
+
+    Ok(rows)
+}
+
+//----------------------------------------------------------------------
+// Tests
+//----------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_from_path() {
+        assert_eq!(InputFormat::from_path(Path::new("books.CSV")), InputFormat::Csv);
+        assert_eq!(InputFormat::from_path(Path::new("books.jsonl")), InputFormat::JsonLines);
+        assert_eq!(InputFormat::from_path(Path::new("books")), InputFormat::Json);
+    }
+
+    #[test]
+    fn test_read_json_lines() {
+        let items = InputFormat::JsonLines.read("{\"a\": 1}\n\n\"b\"\n").unwrap();
+        assert_eq!(items, vec![json!({"a": 1}), json!("b")]);
+
+        assert!(InputFormat::JsonLines.read("{}\n{").unwrap_err().contains("line 2"));
+    }
+
+    #[test]
+    fn test_read_csv() {
+        let csv = "title,author\r\n\"Old Man's War\",John Scalzi\n\"The \"\"Lock\"\", Artist\",\"Steve\nHamilton\"\n";
+        let items = InputFormat::Csv.read(csv).unwrap();
+
+        assert_eq!(
+            items,
+            vec![
+                json!({"title": "Old Man's War", "author": "John Scalzi"}),
+                json!({"title": "The \"Lock\", Artist", "author": "Steve\nHamilton"}),
+            ]
+        );
+        assert!(InputFormat::Csv.read("a\n\"b").is_err());
+    }
+}
@@ -0,0 +1,170 @@
+//! `fuse`: fuzzy search over JSON, JSON Lines and CSV files
+//!
+//! ```text
+//! fuse search --keys title,author --pattern "rust" books.json
+//! cat books.jsonl | fuse search -p "^old" --extended --output jsonl
+//! ```
+//!
+//! Exits with status 0 when something matched, 1 when nothing did and 2 on
+//! errors, like `grep`.
+
+mod args;
+mod input;
+
+use std::borrow::Cow;
+use std::fs;
+use std::io::{self, Read, Write};
+use std::process::ExitCode;
+
+use fuse_rs::{Fuse, FuseOptionKey, FuseOptions, FuseResult, FuseSearchOptions, JsonLinesWriter};
+use serde_json::Value;
+
+use args::{Command, OutputFormat, SearchArgs, USAGE};
+use input::InputFormat;
+
+//----------------------------------------------------------------------
+// Entry Point
+//----------------------------------------------------------------------
+
+fn main() -> ExitCode {
+    let command = match args::parse(std::env::args().skip(1)) {
+        Ok(command) => command,
+        Err(message) => {
+            eprintln!("fuse: {}\n\n{}", message, USAGE);
+            return ExitCode::from(2);
+        },
+    };
+
+    match command {
+        Command::Help => println!("{}", USAGE),
+        Command::Version => println!("fuse {}", env!("CARGO_PKG_VERSION")),
+        Command::Search(search) => {
+            return match run_search(&search, &mut io::stdout().lock()) {
+                Ok(0) => ExitCode::from(1),
+                Ok(_) => ExitCode::SUCCESS,
+                Err(message) => {
+                    eprintln!("fuse: {}", message);
+                    ExitCode::from(2)
+                },
+            };
+        },
+    }
+
+    ExitCode::SUCCESS
+}
+
+//----------------------------------------------------------------------
+// Search
+//----------------------------------------------------------------------
+
+/// Runs `fuse search`, writing the results to `out`.
+///
+/// Returns the number of results.
+fn run_search<W: Write>(search: &SearchArgs, out: &mut W) -> Result<usize, String> {
+    let (text, format) = match &search.file {
+        Some(path) => {
+            let text = fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+            (text, search.input.unwrap_or_else(|| InputFormat::from_path(path)))
+        },
+        None => {
+            let mut text = String::new();
+            io::stdin().read_to_string(&mut text).map_err(|e| e.to_string())?;
+            (text, search.input.unwrap_or(InputFormat::Json))
+        },
+    };
+
+    let docs = format.read(&text)?;
+    let results = search_docs(&docs, search)?;
+
+    write_results(&results, search.output, out).map_err(|e| e.to_string())?;
+    Ok(results.len())
+}
+
+/// Searches `docs` with the options given on the command line.
+fn search_docs(docs: &[Value], search: &SearchArgs) -> Result<Vec<FuseResult<Value>>, String> {
+    let defaults = FuseOptions::default();
+    let options = FuseOptions {
+        keys: search.keys.iter().map(|key| FuseOptionKey::String(Cow::Owned(key.clone()))).collect(),
+        threshold: search.threshold.unwrap_or(defaults.threshold),
+        use_extended_search: search.extended,
+        include_matches: search.include_matches,
+        include_score: search.include_score,
+        ..defaults
+    };
+
+    let search_options = FuseSearchOptions {
+        limit: search.limit.unwrap_or(usize::MAX),
+        ..Default::default()
+    };
+
+    Fuse::new(docs, &options, None)
+        .search_results(&search.pattern, &search_options)
+        .map_err(|e| e.to_string())
+}
+
+/// Prints `results` in the requested format.
+fn write_results<W: Write>(results: &[FuseResult<Value>], format: OutputFormat, out: &mut W) -> io::Result<()> {
+    match format {
+        OutputFormat::Json => {
+            serde_json::to_writer_pretty(&mut *out, results)?;
+            writeln!(out)
+        },
+        OutputFormat::JsonLines => JsonLinesWriter::new(out).write_all(results),
+    }
+}
+
+//----------------------------------------------------------------------
+// Tests
+//----------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn books() -> Vec<Value> {
+        vec![
+            json!({"title": "Old Man's War", "author": "John Scalzi"}),
+            json!({"title": "The Lock Artist", "author": "Steve Hamilton"}),
+            json!({"title": "Rust in Action", "author": "Tim McNamara"}),
+        ]
+    }
+
+    fn search(pattern: &str) -> SearchArgs {
+        SearchArgs {
+            pattern: pattern.into(),
+            keys: vec!["title".into(), "author".into()],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_search_docs() {
+        let results = search_docs(&books(), &search("rust in")).unwrap();
+
+        assert_eq!(results[0].ref_index, 2);
+        assert!(results[0].score.is_none());
+    }
+
+    #[test]
+    fn test_search_docs_extended() {
+        let args = SearchArgs { extended: true, ..search("^old | artist$") };
+        let results = search_docs(&books(), &args).unwrap();
+
+        let indices: Vec<usize> = results.iter().map(|result| result.ref_index).collect();
+        assert_eq!(indices, vec![0, 1]);
+    }
+
+    #[test]
+    fn test_write_json_lines() {
+        let args = SearchArgs { include_score: true, limit: Some(1), ..search("lock") };
+        let results = search_docs(&books(), &args).unwrap();
+
+        let mut out = Vec::new();
+        write_results(&results, OutputFormat::JsonLines, &mut out).unwrap();
+
+        let line: Value = serde_json::from_slice(&out).unwrap();
+        assert_eq!(line["refIndex"], 1);
+        assert!(line["score"].is_number());
+    }
+}
@@ -0,0 +1,146 @@
+//! `#[derive(Searchable)]` for fuse-rs
+//!
+//! Fields marked with `#[fuse]` are searchable, with a weight of 1.0 unless
+//! one is given with `#[fuse(weight = ...)]`. Field types must implement
+//! `serde::Serialize`. Use it through the `derive` feature of `fuse-rs`:
+//!
+//! ```ignore
+//! use fuse_rs::{Fuse, FuseOptions, Searchable};
+//!
+//! #[derive(Searchable)]
+//! struct Book {
+//!     #[fuse(weight = 2.0)]
+//!     title: String,
+//!     #[fuse]
+//!     tags: Vec<String>,
+//!     isbn: String,
+//! }
+//!
+//! let fuse = Fuse::from_items(&books, &FuseOptions::default());
+//! ```
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{Data, DeriveInput, Field, Fields, Ident, Lit, parse_macro_input};
+
+//----------------------------------------------------------------------
+// Types
+//----------------------------------------------------------------------
+
+/// A field marked with `#[fuse]`
+struct SearchableField {
+    /// The field's name, also used as the key
+    ident: Ident,
+
+    /// Weight of the key
+    weight: f64,
+}
+
+//----------------------------------------------------------------------
+// Entry Point
+//----------------------------------------------------------------------
+
+/// Implements `fuse_rs::Searchable` with the fields marked `#[fuse]`.
+#[proc_macro_derive(Searchable, attributes(fuse))]
+pub fn derive_searchable(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    expand(&input).unwrap_or_else(syn::Error::into_compile_error).into()
+}
+
+//----------------------------------------------------------------------
+// Implementation Details
+//----------------------------------------------------------------------
+
+/// Generates the `Searchable` implementation of `input`.
+fn expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => return Err(syn::Error::new_spanned(input, "Searchable can only be derived for structs with named fields")),
+        },
+        _ => return Err(syn::Error::new_spanned(input, "Searchable can only be derived for structs")),
+    };
+
+    let mut searchable = Vec::new();
+    for field in fields {
+        if let Some(field) = parse_field(field)? {
+            searchable.push(field);
+        }
+    }
+
+    if searchable.is_empty() {
+        return Err(syn::Error::new_spanned(&input.ident, "no field is marked #[fuse]"));
+    }
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let keys = searchable.iter().map(|field| {
+        let key = field.ident.to_string();
+        let weight = field.weight;
+        quote! { ::fuse_rs::__private::field_key(#key, #weight) }
+    });
+
+    let values = searchable.iter().map(|field| {
+        let ident = &field.ident;
+        let key = ident.to_string();
+        quote! {
+            document.insert(#key.to_string(), ::fuse_rs::__private::field_value(&self.#ident));
+        }
+    });
+
+    Ok(quote! {
+        impl #impl_generics ::fuse_rs::Searchable for #name #ty_generics #where_clause {
+            fn search_keys() -> ::std::vec::Vec<::fuse_rs::FuseOptionKey<'static>> {
+                ::std::vec![#(#keys),*]
+            }
+
+            fn to_document(&self) -> ::fuse_rs::__private::Value {
+                let mut document = ::fuse_rs::__private::Map::new();
+                #(#values)*
+                ::fuse_rs::__private::Value::Object(document)
+            }
+        }
+    })
+}
+
+/// Reads the `#[fuse]` attribute of `field`.
+///
+/// # Returns
+///
+/// `None` if the field is not marked, or an error if the attribute is
+/// malformed.
+fn parse_field(field: &Field) -> syn::Result<Option<SearchableField>> {
+    let Some(attr) = field.attrs.iter().find(|attr| attr.path().is_ident("fuse")) else {
+        return Ok(None);
+    };
+
+    let mut weight = 1.0;
+
+    // `#[fuse]` alone takes no arguments
+    if !matches!(attr.meta, syn::Meta::Path(_)) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("weight") {
+                let lit: Lit = meta.value()?.parse()?;
+                weight = match &lit {
+                    Lit::Float(f) => f.base10_parse()?,
+                    Lit::Int(i) => i.base10_parse()?,
+                    _ => return Err(syn::Error::new_spanned(lit, "expected a number")),
+                };
+
+                if weight <= 0.0 {
+                    return Err(syn::Error::new_spanned(lit, "weight must be positive"));
+                }
+
+                Ok(())
+            } else {
+                Err(meta.error("unknown fuse attribute; expected `weight`"))
+            }
+        })?;
+    }
+
+    let ident = field.ident.clone().expect("named fields have identifiers");
+    Ok(Some(SearchableField { ident, weight }))
+}
@@ -0,0 +1,63 @@
+use fuse_rs::{Fuse, FuseOptionKey, FuseOptionKeyName, FuseOptions, Searchable};
+use serde_json::json;
+
+#[derive(Searchable)]
+struct Book {
+    #[fuse(weight = 2.0)]
+    title: String,
+    #[fuse]
+    tags: Vec<String>,
+    #[allow(dead_code)]
+    isbn: String,
+}
+
+#[derive(Searchable)]
+struct Labeled<T: serde::Serialize> {
+    #[fuse(weight = 3)]
+    label: T,
+}
+
+fn book(title: &str, tags: &[&str]) -> Book {
+    Book {
+        title: title.to_string(),
+        tags: tags.iter().map(|tag| tag.to_string()).collect(),
+        isbn: "978-0".to_string(),
+    }
+}
+
+fn key_weights<T: Searchable>() -> Vec<(String, f64)> {
+    T::search_keys()
+        .into_iter()
+        .map(|key| match key {
+            FuseOptionKey::KeyObject(key) => match key.name.as_ref() {
+                FuseOptionKeyName::String(name) => (name.to_string(), key.weight.unwrap()),
+                _ => panic!("expected a single key name"),
+            },
+            _ => panic!("expected a key object"),
+        })
+        .collect()
+}
+
+#[test]
+fn test_search_keys() {
+    assert_eq!(key_weights::<Book>(), vec![("title".to_string(), 2.0), ("tags".to_string(), 1.0)]);
+    assert_eq!(key_weights::<Labeled<u32>>(), vec![("label".to_string(), 3.0)]);
+}
+
+#[test]
+fn test_to_document_skips_unmarked_fields() {
+    let document = book("Dune", &["desert"]).to_document();
+
+    assert_eq!(document, json!({"title": "Dune", "tags": ["desert"]}));
+    assert_eq!(Labeled { label: 7 }.to_document(), json!({"label": 7}));
+}
+
+#[test]
+fn test_from_items() {
+    let books = [book("Dune", &["desert", "spicy"]), book("Spice World", &["music"])];
+    let fuse = Fuse::from_items(&books, &FuseOptions::default());
+
+    let results = fuse.search_results("spice", &Default::default()).unwrap();
+    let indices: Vec<usize> = results.iter().map(|result| result.ref_index).collect();
+    assert_eq!(indices, vec![1, 0]);
+}
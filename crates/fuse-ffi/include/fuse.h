@@ -0,0 +1,68 @@
+/*
+ * C bindings for fuse-rs
+ *
+ * Collections and options are passed as JSON; strings are NUL-terminated
+ * UTF-8. Functions returning pointers return NULL on error, and
+ * fuse_last_error() then describes the failure.
+ */
+
+#ifndef FUSE_H
+#define FUSE_H
+
+#include <stddef.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* A search engine over a collection */
+typedef struct FuseHandle FuseHandle;
+
+/* A single search result */
+typedef struct FuseMatch {
+    /* Index of the matching item in the collection */
+    size_t ref_index;
+    /* Relevance score (lower is better); NaN unless includeScore is set */
+    double score;
+} FuseMatch;
+
+/*
+ * Creates a search engine over docs_json, a JSON array. options_json is a
+ * JSON object of options (e.g. {"keys": ["title"], "includeScore": true}) or
+ * NULL for the defaults. Release with fuse_free().
+ */
+FuseHandle *fuse_new(const char *docs_json, const char *options_json);
+
+/*
+ * Searches for pattern, returning at most limit results (0 for all) as a JSON
+ * array of {item, refIndex, score?, matches?} objects. Release with
+ * fuse_string_free().
+ */
+char *fuse_search(const FuseHandle *fuse, const char *pattern, size_t limit);
+
+/*
+ * Searches for pattern like fuse_search(), returning the results as an array
+ * whose length is written to len. Release with fuse_matches_free().
+ */
+FuseMatch *fuse_search_matches(const FuseHandle *fuse, const char *pattern, size_t limit, size_t *len);
+
+/*
+ * Returns the message of the last error on the calling thread, or NULL. Valid
+ * until the next call into the library on the same thread.
+ */
+const char *fuse_last_error(void);
+
+/* Releases a string returned by fuse_search() */
+void fuse_string_free(char *string);
+
+/* Releases an array returned by fuse_search_matches() */
+void fuse_matches_free(FuseMatch *matches, size_t len);
+
+/* Releases a handle returned by fuse_new() */
+void fuse_free(FuseHandle *fuse);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* FUSE_H */
@@ -0,0 +1,287 @@
+//! C bindings for fuse-rs
+//!
+//! The interface only passes an opaque handle, UTF-8 strings and plain
+//! structs across the boundary, so it stays stable as the Rust types evolve.
+//! Collections and options are given as JSON (option names as in
+//! [`FuseOptions::from_json`]); results come back either as a JSON array or
+//! as an array of [`FuseMatch`]. See `include/fuse.h` for the C declarations.
+//!
+//! Functions that fail return `NULL` and record a message that
+//! [`fuse_last_error`] returns.
+
+use std::cell::RefCell;
+use std::error::Error;
+use std::ffi::{CStr, CString, c_char};
+use std::panic::{AssertUnwindSafe, catch_unwind};
+use std::ptr;
+
+use fuse_rs::{Fuse, FuseOptions, FuseResult, FuseSearchOptions};
+use serde_json::Value;
+
+//----------------------------------------------------------------------
+// Types
+//----------------------------------------------------------------------
+
+/// A search engine over a collection, owned by the caller
+pub struct FuseHandle {
+    engine: Fuse<'static>,
+}
+
+/// A single search result
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FuseMatch {
+    /// Index of the matching item in the collection
+    pub ref_index: usize,
+
+    /// Relevance score (lower is better); `NaN` unless `includeScore` is set
+    pub score: f64,
+}
+
+type FfiResult<T> = Result<T, Box<dyn Error>>;
+
+thread_local! {
+    /// Message of the last failure on this thread
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+//----------------------------------------------------------------------
+// Public API
+//----------------------------------------------------------------------
+
+/// Creates a search engine over a collection.
+///
+/// `options_json` may be `NULL` for the default options. Returns `NULL` on
+/// error. The handle must be released with [`fuse_free`].
+///
+/// # Safety
+///
+/// `docs_json` must be a NUL-terminated string holding a JSON array, and
+/// `options_json` `NULL` or a NUL-terminated string holding a JSON object.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn fuse_new(docs_json: *const c_char, options_json: *const c_char) -> *mut FuseHandle {
+    guard(ptr::null_mut(), || {
+        let docs: Vec<Value> = serde_json::from_str(unsafe { read_str(docs_json) }?)?;
+        let options = if options_json.is_null() {
+            FuseOptions::default()
+        } else {
+            FuseOptions::from_json(serde_json::from_str(unsafe { read_str(options_json) }?)?)?
+        };
+
+        let engine = Fuse::from_docs(docs, &options, None);
+        Ok(Box::into_raw(Box::new(FuseHandle { engine })))
+    })
+}
+
+/// Searches for `pattern`, returning at most `limit` results (`0` for all).
+///
+/// Returns the results as a JSON array of `{ item, refIndex, score?,
+/// matches? }` objects, or `NULL` on error. The string must be released
+/// with [`fuse_string_free`].
+///
+/// # Safety
+///
+/// `fuse` must be a handle returned by [`fuse_new`] and `pattern` a
+/// NUL-terminated string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn fuse_search(fuse: *const FuseHandle, pattern: *const c_char, limit: usize) -> *mut c_char {
+    guard(ptr::null_mut(), || {
+        let results = unsafe { search(fuse, pattern, limit) }?;
+        Ok(CString::new(serde_json::to_string(&results)?)?.into_raw())
+    })
+}
+
+/// Searches for `pattern` like [`fuse_search`], returning the results as an
+/// array of [`FuseMatch`].
+///
+/// The number of results is written to `len`. Returns `NULL` on error; the
+/// array (possibly empty) must be released with [`fuse_matches_free`].
+///
+/// # Safety
+///
+/// `fuse` must be a handle returned by [`fuse_new`], `pattern` a
+/// NUL-terminated string and `len` a valid pointer.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn fuse_search_matches(
+    fuse: *const FuseHandle,
+    pattern: *const c_char,
+    limit: usize,
+    len: *mut usize,
+) -> *mut FuseMatch {
+    guard(ptr::null_mut(), || {
+        if len.is_null() {
+            return Err("len is NULL".into());
+        }
+
+        let matches: Box<[FuseMatch]> = unsafe { search(fuse, pattern, limit) }?
+            .iter()
+            .map(|result| FuseMatch {
+                ref_index: result.ref_index,
+                score: result.score.unwrap_or(f64::NAN),
+            })
+            .collect();
+
+        unsafe { *len = matches.len() };
+        Ok(Box::into_raw(matches).cast::<FuseMatch>())
+    })
+}
+
+/// Returns the message of the last error on the calling thread, or `NULL`.
+///
+/// The string stays valid until the next call into the library on the same
+/// thread.
+#[unsafe(no_mangle)]
+pub extern "C" fn fuse_last_error() -> *const c_char {
+    LAST_ERROR.with(|error| error.borrow().as_ref().map_or(ptr::null(), |message| message.as_ptr()))
+}
+
+/// Releases a string returned by the library.
+///
+/// # Safety
+///
+/// `string` must be `NULL` or a string returned by the library that hasn't
+/// been released yet.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn fuse_string_free(string: *mut c_char) {
+    if !string.is_null() {
+        drop(unsafe { CString::from_raw(string) });
+    }
+}
+
+/// Releases an array returned by [`fuse_search_matches`].
+///
+/// # Safety
+///
+/// `matches` must be `NULL` or an array returned by [`fuse_search_matches`]
+/// together with its length, and not released yet.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn fuse_matches_free(matches: *mut FuseMatch, len: usize) {
+    if !matches.is_null() {
+        drop(unsafe { Box::from_raw(ptr::slice_from_raw_parts_mut(matches, len)) });
+    }
+}
+
+/// Releases a handle returned by [`fuse_new`].
+///
+/// # Safety
+///
+/// `fuse` must be `NULL` or a handle returned by [`fuse_new`] that hasn't
+/// been released yet.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn fuse_free(fuse: *mut FuseHandle) {
+    if !fuse.is_null() {
+        drop(unsafe { Box::from_raw(fuse) });
+    }
+}
+
+//----------------------------------------------------------------------
+// Implementation Details
+//----------------------------------------------------------------------
+
+/// Runs `f`, turning errors and panics into `fallback` and a recorded message.
+fn guard<T>(fallback: T, f: impl FnOnce() -> FfiResult<T>) -> T {
+    LAST_ERROR.with(|error| error.borrow_mut().take());
+
+    let message = match catch_unwind(AssertUnwindSafe(f)) {
+        Ok(Ok(value)) => return value,
+        Ok(Err(error)) => error.to_string(),
+        Err(_) => "fuse-rs panicked".to_string(),
+    };
+
+    let message = CString::new(message.replace('\0', " ")).unwrap_or_default();
+    LAST_ERROR.with(|error| *error.borrow_mut() = Some(message));
+    fallback
+}
+
+/// Searches a handle for `pattern`.
+unsafe fn search(fuse: *const FuseHandle, pattern: *const c_char, limit: usize) -> FfiResult<Vec<FuseResult<Value>>> {
+    let Some(fuse) = (unsafe { fuse.as_ref() }) else {
+        return Err("fuse is NULL".into());
+    };
+
+    let search_options = FuseSearchOptions {
+        limit: if limit == 0 { usize::MAX } else { limit },
+        ..Default::default()
+    };
+
+    Ok(fuse.engine.search_results(unsafe { read_str(pattern) }?, &search_options)?)
+}
+
+/// Borrows a NUL-terminated UTF-8 string.
+unsafe fn read_str<'s>(string: *const c_char) -> FfiResult<&'s str> {
+    if string.is_null() {
+        return Err("string is NULL".into());
+    }
+    Ok(unsafe { CStr::from_ptr(string) }.to_str()?)
+}
+
+//----------------------------------------------------------------------
+// Tests
+//----------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn c(text: &str) -> CString {
+        CString::new(text).unwrap()
+    }
+
+    fn last_error() -> String {
+        unsafe { CStr::from_ptr(fuse_last_error()) }.to_string_lossy().into_owned()
+    }
+
+    #[test]
+    fn test_search_json() {
+        let docs = c(r#"[{"title": "Old Man's War"}, {"title": "The Lock Artist"}]"#);
+        let options = c(r#"{"keys": ["title"], "includeScore": true}"#);
+
+        unsafe {
+            let fuse = fuse_new(docs.as_ptr(), options.as_ptr());
+            assert!(!fuse.is_null());
+
+            let json = fuse_search(fuse, c("lock").as_ptr(), 0);
+            let results: Value = serde_json::from_str(CStr::from_ptr(json).to_str().unwrap()).unwrap();
+            assert_eq!(results[0]["refIndex"], 1);
+            assert!(results[0]["score"].is_number());
+
+            fuse_string_free(json);
+            fuse_free(fuse);
+        }
+    }
+
+    #[test]
+    fn test_search_matches() {
+        let docs = c(r#"["apple", "banana", "grape"]"#);
+
+        unsafe {
+            let fuse = fuse_new(docs.as_ptr(), ptr::null());
+            let mut len = 0;
+
+            let matches = fuse_search_matches(fuse, c("banana").as_ptr(), 1, &mut len);
+            assert_eq!(len, 1);
+            assert_eq!((*matches).ref_index, 1);
+            assert!((*matches).score.is_nan());
+
+            fuse_matches_free(matches, len);
+            fuse_free(fuse);
+        }
+    }
+
+    #[test]
+    fn test_errors() {
+        unsafe {
+            assert!(fuse_new(c("not json").as_ptr(), ptr::null()).is_null());
+            assert!(!last_error().is_empty());
+
+            let fuse = fuse_new(c("[]").as_ptr(), ptr::null());
+            assert!(!fuse.is_null());
+            assert!(fuse_last_error().is_null());
+
+            assert!(fuse_search(fuse, ptr::null(), 0).is_null());
+            assert_eq!(last_error(), "string is NULL");
+
+            fuse_free(fuse);
+        }
+    }
+}
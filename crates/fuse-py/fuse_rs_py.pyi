@@ -0,0 +1,18 @@
+from typing import Any, Iterable, Optional
+
+class Match:
+    indices: list[tuple[int, int]]
+    key: Optional[str]
+    ref_index: Optional[int]
+    value: Optional[str]
+
+class SearchResult:
+    item: Any
+    ref_index: int
+    score: Optional[float]
+    matches: Optional[list[Match]]
+
+class Fuse:
+    def __init__(self, items: Iterable[Any], **options: Any) -> None: ...
+    def search(self, pattern: str, limit: Optional[int] = None) -> list[SearchResult]: ...
+    def __len__(self) -> int: ...
@@ -0,0 +1,210 @@
+//! Python bindings for fuse-rs
+//!
+//! Exposes a `Fuse` class that searches lists of Python values (strings,
+//! dicts, lists, numbers) directly, without serializing them to JSON first.
+//! Options are keyword arguments named like the fields of `FuseOptions`, and
+//! results are typed `SearchResult` objects whose `item` is the original
+//! Python object.
+//!
+//! ```python
+//! from fuse_rs_py import Fuse
+//!
+//! fuse = Fuse(books, keys=["title", "author"], include_score=True)
+//! for result in fuse.search("old man", limit=10):
+//!     print(result.ref_index, result.score, result.item["title"])
+//! ```
+
+use fuse_rs::{Fuse, FuseOptions, FuseResult, FuseResultMatch, FuseSearchOptions};
+use pyo3::exceptions::{PyTypeError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::{PyBool, PyDict, PyFloat, PyInt, PyList, PyString, PyTuple};
+use serde_json::{Map, Number, Value};
+
+//----------------------------------------------------------------------
+// Types
+//----------------------------------------------------------------------
+
+/// Fuzzy searcher over a list of Python values
+#[pyclass(name = "Fuse", module = "fuse_rs_py", frozen)]
+pub struct PyFuse {
+    /// The searched items, returned as is in results
+    items: Vec<PyObject>,
+
+    /// The search engine, over the items converted to JSON
+    engine: Fuse<'static>,
+}
+
+/// A matching item with its position and scoring details
+#[pyclass(name = "SearchResult", module = "fuse_rs_py", frozen, get_all)]
+pub struct PySearchResult {
+    /// The original item
+    item: PyObject,
+
+    /// Index of the item in the searched list
+    ref_index: usize,
+
+    /// Relevance score (lower is better), when `include_score` is set
+    score: Option<f64>,
+
+    /// The matched values, when `include_matches` is set
+    matches: Option<Vec<PyMatch>>,
+}
+
+/// A matched value within an item
+#[pyclass(name = "Match", module = "fuse_rs_py", frozen, get_all)]
+#[derive(Clone)]
+pub struct PyMatch {
+    /// Inclusive character ranges that matched
+    indices: Vec<(usize, usize)>,
+
+    /// Path of the key the value was found at
+    key: Option<String>,
+
+    /// Index of the value within an array of values
+    ref_index: Option<usize>,
+
+    /// The matched value
+    value: Option<String>,
+}
+
+//----------------------------------------------------------------------
+// Public API
+//----------------------------------------------------------------------
+
+#[pymethods]
+impl PyFuse {
+    /// Creates a searcher for `items`, configured by keyword options
+    /// (e.g. `keys=["title"]`, `threshold=0.4`, `include_score=True`).
+    #[new]
+    #[pyo3(signature = (items, **options))]
+    fn new(items: &Bound<'_, PyAny>, options: Option<&Bound<'_, PyDict>>) -> PyResult<Self> {
+        let items: Vec<Bound<'_, PyAny>> = items.try_iter()?.collect::<PyResult<_>>()?;
+        let docs = items.iter().map(to_json).collect::<PyResult<Vec<Value>>>()?;
+
+        let options = match options {
+            Some(options) => to_json(options.as_any())?,
+            None => Value::Null,
+        };
+        let options = FuseOptions::from_json(options).map_err(|e| PyValueError::new_err(e.to_string()))?;
+
+        Ok(Self {
+            engine: Fuse::from_docs(docs, &options, None),
+            items: items.into_iter().map(Bound::unbind).collect(),
+        })
+    }
+
+    /// Searches the items for `pattern`, returning at most `limit` results.
+    ///
+    /// The search runs without holding the GIL.
+    #[pyo3(signature = (pattern, limit = None))]
+    fn search(&self, py: Python<'_>, pattern: &str, limit: Option<usize>) -> PyResult<Vec<PySearchResult>> {
+        let results = py
+            .allow_threads(|| {
+                let search_options = FuseSearchOptions {
+                    limit: limit.unwrap_or(usize::MAX),
+                    ..Default::default()
+                };
+                self.engine.search_results(pattern, &search_options)
+            })
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+
+        Ok(results.into_iter().map(|result| self.to_result(py, result)).collect())
+    }
+
+    fn __len__(&self) -> usize {
+        self.items.len()
+    }
+}
+
+#[pymethods]
+impl PySearchResult {
+    fn __repr__(&self) -> String {
+        format!("SearchResult(ref_index={}, score={:?})", self.ref_index, self.score)
+    }
+}
+
+#[pymethods]
+impl PyMatch {
+    fn __repr__(&self) -> String {
+        format!("Match(key={:?}, value={:?}, indices={:?})", self.key, self.value, self.indices)
+    }
+}
+
+#[pymodule]
+fn fuse_rs_py(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyFuse>()?;
+    m.add_class::<PySearchResult>()?;
+    m.add_class::<PyMatch>()?;
+    Ok(())
+}
+
+//----------------------------------------------------------------------
+// Implementation Details
+//----------------------------------------------------------------------
+
+impl PyFuse {
+    /// Converts an engine result, pointing back at the original item.
+    fn to_result(&self, py: Python<'_>, result: FuseResult<Value>) -> PySearchResult {
+        PySearchResult {
+            item: self.items[result.ref_index].clone_ref(py),
+            ref_index: result.ref_index,
+            score: result.score,
+            matches: result.matches.map(|matches| matches.into_iter().map(PyMatch::from).collect()),
+        }
+    }
+}
+
+impl From<FuseResultMatch> for PyMatch {
+    fn from(m: FuseResultMatch) -> Self {
+        Self {
+            indices: m.indices,
+            key: m.key,
+            ref_index: m.ref_index,
+            value: m.value,
+        }
+    }
+}
+
+/// Converts a Python value into JSON.
+///
+/// Supports `None`, booleans, integers, floats, strings, lists, tuples and
+/// dicts; dict keys are converted with `str()`.
+fn to_json(value: &Bound<'_, PyAny>) -> PyResult<Value> {
+    if value.is_none() {
+        return Ok(Value::Null);
+    }
+
+    // `bool` is a subclass of `int`, so it must be checked first
+    if let Ok(b) = value.downcast::<PyBool>() {
+        return Ok(Value::Bool(b.is_true()));
+    }
+
+    if let Ok(i) = value.downcast::<PyInt>() {
+        return Ok(match i.extract::<i64>() {
+            Ok(i) => Value::from(i),
+            Err(_) => Value::from(i.extract::<f64>()?),
+        });
+    }
+
+    if let Ok(f) = value.downcast::<PyFloat>() {
+        return Ok(Number::from_f64(f.value()).map_or(Value::Null, Value::Number));
+    }
+
+    if let Ok(s) = value.downcast::<PyString>() {
+        return Ok(Value::String(s.to_cow()?.into_owned()));
+    }
+
+    if let Ok(dict) = value.downcast::<PyDict>() {
+        let mut map = Map::with_capacity(dict.len());
+        for (k, v) in dict.iter() {
+            map.insert(k.str()?.to_cow()?.into_owned(), to_json(&v)?);
+        }
+        return Ok(Value::Object(map));
+    }
+
+    if value.is_instance_of::<PyList>() || value.is_instance_of::<PyTuple>() {
+        return value.try_iter()?.map(|item| to_json(&item?)).collect::<PyResult<Vec<Value>>>().map(Value::Array);
+    }
+
+    Err(PyTypeError::new_err(format!("cannot search values of type {}", value.get_type())))
+}
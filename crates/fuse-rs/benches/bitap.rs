@@ -0,0 +1,63 @@
+//! Times the bitap matcher on long texts
+//!
+//! Run with `cargo bench --bench bitap`. Each case prints the mean time of a
+//! match over a few hundred iterations.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use fuse_rs::{BitapOptions, BitapPattern};
+
+/// Number of matches timed per case
+const ITERATIONS: u32 = 200;
+
+fn main() {
+    let ascii = "the quick brown fox jumps over the lazy dog ".repeat(500);
+    let mixed = "żółta łódź płynie po jeziorze, zaś kot śpi ".repeat(500);
+    let prose = prose(4000);
+    let everywhere = BitapOptions { ignore_location: true, include_matches: true, ..Default::default() };
+
+    bench("ascii, exact", &ascii, "lazy dog", &BitapOptions::default());
+    bench("ascii, exact, ignore location", &ascii, "lazy dog", &BitapOptions { ignore_location: true, ..Default::default() });
+    bench("ascii, fuzzy", &ascii, "lazy dgo", &BitapOptions::default());
+    bench("ascii, fuzzy, ignore location", &ascii, "lazy dgo", &everywhere);
+    bench("ascii, no match", &ascii, "xylophone", &everywhere);
+    bench("non-ascii, fuzzy, ignore location", &mixed, "jezioze", &everywhere);
+
+    let strict = BitapOptions { threshold: 0.3, include_matches: false, ..everywhere };
+    bench("prose, fuzzy, ignore location", &prose, "qeury", &everywhere);
+    bench("prose, fuzzy, search window", &prose, "qeury", &BitapOptions { search_window: true, ..everywhere });
+    bench("prose, strict, ignore location", &prose, "qeury", &strict);
+    bench("prose, strict, search window", &prose, "qeury", &BitapOptions { search_window: true, ..strict });
+}
+
+/// Prints the mean time of matching `pattern` against `text`.
+fn bench(name: &str, text: &str, pattern: &str, options: &BitapOptions) {
+    let pattern = BitapPattern::new(pattern);
+    let mut total = Duration::ZERO;
+
+    for _ in 0..ITERATIONS {
+        let start = Instant::now();
+        black_box(pattern.search_in(black_box(text), options).unwrap());
+        total += start.elapsed();
+    }
+
+    println!("{:<36} {:>10.1?}", name, total / ITERATIONS);
+}
+
+/// Returns `len` words drawn pseudo-randomly from a small vocabulary.
+fn prose(len: usize) -> String {
+    const WORDS: [&str; 16] = [
+        "search", "index", "record", "value", "field", "score", "pattern", "match",
+        "token", "query", "result", "option", "weight", "string", "number", "bitap",
+    ];
+
+    let mut state: u32 = 1;
+    let mut text = String::new();
+    for _ in 0..len {
+        state = state.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+        text.push_str(WORDS[(state >> 16) as usize % WORDS.len()]);
+        text.push(' ');
+    }
+    text
+}
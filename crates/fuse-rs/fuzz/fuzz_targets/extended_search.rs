@@ -0,0 +1,21 @@
+//! Searches arbitrary documents with well-formed extended queries
+
+#![no_main]
+
+use fuse_rs::{ExtendedQuery, FuseOptions, try_search};
+use libfuzzer_sys::fuzz_target;
+use serde_json::json;
+
+#[derive(Debug, arbitrary::Arbitrary)]
+struct Input<'a> {
+    options: FuseOptions<'a>,
+    docs: Vec<String>,
+    query: ExtendedQuery,
+}
+
+fuzz_target!(|input: Input| {
+    let options = FuseOptions { use_extended_search: true, ..input.options };
+    let docs: Vec<_> = input.docs.iter().map(|doc| json!(doc)).collect();
+
+    let _ = try_search(&docs, &options, &input.query.to_string());
+});
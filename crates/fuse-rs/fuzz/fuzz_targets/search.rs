@@ -0,0 +1,25 @@
+//! Searches arbitrary documents with arbitrary options and patterns
+
+#![no_main]
+
+use fuse_rs::{FuseOptions, try_search};
+use libfuzzer_sys::fuzz_target;
+use serde_json::json;
+
+#[derive(Debug, arbitrary::Arbitrary)]
+struct Input<'a> {
+    options: FuseOptions<'a>,
+    docs: Vec<String>,
+    pattern: String,
+}
+
+fuzz_target!(|input: Input| {
+    // Every document is searchable both as a string and through nested keys
+    let docs: Vec<_> = input
+        .docs
+        .iter()
+        .flat_map(|doc| [json!(doc), json!({"title": doc, "author": {"name": doc}, "tags": [doc]})])
+        .collect();
+
+    let _ = try_search(&docs, &input.options, &input.pattern);
+});
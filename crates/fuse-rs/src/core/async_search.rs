@@ -0,0 +1,158 @@
+//! Searching from async code
+//!
+//! Scoring a large collection can hold a thread for a while, which would
+//! stall every other task of an async executor. [`Fuse::search_async`] runs
+//! the search on tokio's blocking thread pool and sends the results back
+//! through a bounded channel, so they can be consumed as a [`Stream`] while
+//! the search is still running.
+
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use futures_core::Stream;
+use serde_json::Value;
+use tokio::sync::mpsc;
+
+use crate::core::error_messages::FuseError;
+use crate::core::fuse::Fuse;
+use crate::core::results::search_result::FuseResult;
+
+//----------------------------------------------------------------------
+// Constants
+//----------------------------------------------------------------------
+
+/// Number of results buffered before the search waits for the consumer
+const CHANNEL_CAPACITY: usize = 64;
+
+//----------------------------------------------------------------------
+// Types
+//----------------------------------------------------------------------
+
+/// A stream of the results of a search running on tokio's blocking pool
+///
+/// Created with [`Fuse::search_async`]. Dropping the stream stops the search
+/// at the next result it produces.
+#[derive(Debug)]
+pub struct SearchStream {
+    /// Results sent by the search
+    receiver: mpsc::Receiver<Result<FuseResult<Value>, FuseError>>,
+}
+
+//----------------------------------------------------------------------
+// Implementation
+//----------------------------------------------------------------------
+
+impl Fuse<'static> {
+    /// Searches the data for `term` on tokio's blocking thread pool.
+    ///
+    /// Results come in the same order as with [`Fuse::search_iter`]; an
+    /// invalid term is reported as the only item of the stream.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside of a tokio runtime.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    /// use fuse_rs::{Fuse, FuseOptions};
+    /// use serde_json::json;
+    ///
+    /// # let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+    /// # runtime.block_on(async {
+    /// let docs = vec![json!("apple"), json!("banana")];
+    /// let fuse = Arc::new(Fuse::new(&docs, &FuseOptions::default(), None));
+    ///
+    /// let mut stream = fuse.search_async("banana");
+    /// let result = std::future::poll_fn(|cx| stream.poll_recv(cx)).await.unwrap().unwrap();
+    /// assert_eq!(result.item, json!("banana"));
+    /// # });
+    /// ```
+    pub fn search_async(self: &Arc<Self>, term: &str) -> SearchStream {
+        let (sender, receiver) = mpsc::channel(CHANNEL_CAPACITY);
+        let fuse = Arc::clone(self);
+        let term = term.to_string();
+
+        tokio::task::spawn_blocking(move || {
+            let results = match fuse.search_iter(&term) {
+                Ok(results) => results,
+                Err(e) => {
+                    let _ = sender.blocking_send(Err(e));
+                    return;
+                }
+            };
+
+            for result in results {
+                // The stream was dropped, nobody is left to read the results
+                if sender.blocking_send(result).is_err() {
+                    return;
+                }
+            }
+        });
+
+        SearchStream { receiver }
+    }
+}
+
+impl SearchStream {
+    /// Polls for the next result, like [`Stream::poll_next`], without
+    /// requiring the stream to be pinned.
+    pub fn poll_recv(&mut self, cx: &mut Context<'_>) -> Poll<Option<Result<FuseResult<Value>, FuseError>>> {
+        self.receiver.poll_recv(cx)
+    }
+}
+
+impl Stream for SearchStream {
+    type Item = Result<FuseResult<Value>, FuseError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.receiver.poll_recv(cx)
+    }
+}
+
+//----------------------------------------------------------------------
+// Tests
+//----------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::options::config::FuseOptions;
+    use crate::core::results::search_result::FuseSearchOptions;
+    use serde_json::json;
+
+    fn search(fuse: &Arc<Fuse<'static>>, term: &str) -> Vec<Result<FuseResult<Value>, FuseError>> {
+        let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+        runtime.block_on(async {
+            let mut stream = fuse.search_async(term);
+            let mut results = Vec::new();
+            while let Some(result) = std::future::poll_fn(|cx| Pin::new(&mut stream).poll_next(cx)).await {
+                results.push(result);
+            }
+            results
+        })
+    }
+
+    #[test]
+    fn test_stream_matches_search() {
+        let docs: Vec<Value> = (0..200).map(|i| json!(format!("apple {}", i))).collect();
+        let fuse = Arc::new(Fuse::new(&docs, &FuseOptions::default(), None));
+
+        let results: Vec<usize> = search(&fuse, "apple 1").into_iter().map(|r| r.unwrap().ref_index).collect();
+
+        let expected = fuse.search_results("apple 1", &FuseSearchOptions::default()).unwrap();
+        assert_eq!(results, expected.iter().map(|r| r.ref_index).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_stream_reports_invalid_term() {
+        let docs = vec![json!("apple")];
+        let options = FuseOptions { max_pattern_length: Some(2), ..Default::default() };
+        let fuse = Arc::new(Fuse::new(&docs, &options, None));
+
+        let results = search(&fuse, "apple");
+        assert!(matches!(results.as_slice(), [Err(FuseError::PatternLengthTooLarge(2))]));
+    }
+}
@@ -0,0 +1,181 @@
+//! Fluent construction of `Fuse` instances
+//!
+//! [`FuseBuilder`] collects the documents, options and an optional prebuilt
+//! index, and checks them in [`FuseBuilder::build`], so invalid keys or
+//! option values are reported as errors when the instance is created rather
+//! than surfacing later.
+
+use serde_json::Value;
+
+use crate::core::error_messages::FuseError;
+use crate::core::fuse::Fuse;
+use crate::core::options::config::FuseOptions;
+use crate::core::options::keys::FuseOptionKey;
+use crate::tools::fuse_index::FuseIndex;
+
+//----------------------------------------------------------------------
+// Types
+//----------------------------------------------------------------------
+
+/// Builder of [`Fuse`] instances
+///
+/// # Example
+///
+/// ```
+/// use fuse_rs::FuseBuilder;
+/// use serde_json::json;
+///
+/// let fuse = FuseBuilder::new()
+///     .docs(vec![json!({"title": "Old Man's War"}), json!({"title": "The Lock Artist"})])
+///     .keys(["title"])
+///     .threshold(0.3)
+///     .include_matches(true)
+///     .build()
+///     .unwrap();
+///
+/// assert_eq!(fuse.search("lock").unwrap()[0].item, json!({"title": "The Lock Artist"}));
+/// ```
+#[derive(Debug, Default)]
+pub struct FuseBuilder<'a> {
+    /// The documents to search
+    docs: Vec<Value>,
+
+    /// The options of the instance
+    options: FuseOptions<'a>,
+
+    /// A prebuilt index of `docs`
+    index: Option<FuseIndex<'a>>,
+}
+
+//----------------------------------------------------------------------
+// Implementation
+//----------------------------------------------------------------------
+
+impl<'a> FuseBuilder<'a> {
+    /// Creates a builder with no documents and the default options.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the documents to search.
+    pub fn docs(mut self, docs: Vec<Value>) -> Self {
+        self.docs = docs;
+        self
+    }
+
+    /// Replaces all the options; later calls still change single options.
+    pub fn options(mut self, options: FuseOptions<'a>) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Sets the keys searched in object documents.
+    pub fn keys<K: Into<FuseOptionKey<'a>>>(mut self, keys: impl IntoIterator<Item = K>) -> Self {
+        self.options.keys = keys.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Sets the match threshold, between `0.0` (exact) and `1.0` (anything).
+    pub fn threshold(mut self, threshold: f64) -> Self {
+        self.options.threshold = threshold;
+        self
+    }
+
+    /// Sets whether results include their score.
+    pub fn include_score(mut self, include_score: bool) -> Self {
+        self.options.include_score = include_score;
+        self
+    }
+
+    /// Sets whether results include the matched character ranges.
+    pub fn include_matches(mut self, include_matches: bool) -> Self {
+        self.options.include_matches = include_matches;
+        self
+    }
+
+    /// Sets whether the extended search syntax is enabled.
+    pub fn use_extended_search(mut self, use_extended_search: bool) -> Self {
+        self.options.use_extended_search = use_extended_search;
+        self
+    }
+
+    /// Uses a prebuilt index of the documents instead of indexing them.
+    pub fn index(mut self, index: FuseIndex<'a>) -> Self {
+        self.index = Some(index);
+        self
+    }
+
+    /// Checks the options and creates the instance.
+    ///
+    /// # Returns
+    ///
+    /// The instance, `FuseError::InvalidOption` if an option is out of
+    /// range, the error of the first invalid key, or
+    /// `FuseError::IncorrectIndexType` if the index was built with other keys.
+    pub fn build(self) -> Result<Fuse<'a>, FuseError> {
+        let options = &self.options;
+
+        if !(0.0..=1.0).contains(&options.threshold) {
+            return Err(FuseError::InvalidOption(format!("threshold must be between 0 and 1, got {}", options.threshold)));
+        }
+
+        if !options.field_norm_weight.is_finite() || options.field_norm_weight < 0.0 {
+            return Err(FuseError::InvalidOption(format!(
+                "field_norm_weight must be a non-negative number, got {}",
+                options.field_norm_weight
+            )));
+        }
+
+        if options.min_match_char_length == 0 {
+            return Err(FuseError::InvalidOption("min_match_char_length must be at least 1".into()));
+        }
+
+        Fuse::try_new(&self.docs, options, self.index)
+    }
+}
+
+//----------------------------------------------------------------------
+// Tests
+//----------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::options::keys::{FuseOptionKeyName, FuseOptionKeyObject};
+    use serde_json::json;
+    use std::borrow::Cow;
+
+    #[test]
+    fn test_build_with_index() {
+        let docs = vec![json!({"title": "Dune", "author": "Frank Herbert"})];
+        let index = FuseIndex::create_index(&["title".into()], &docs, None, None);
+
+        let fuse = FuseBuilder::new().docs(docs.clone()).keys(["title"]).index(index).build().unwrap();
+        assert_eq!(fuse.search_with_options("dune", &Default::default()).unwrap(), docs);
+
+        let index = FuseIndex::create_index(&["author".into()], &docs, None, None);
+        let result = FuseBuilder::new().docs(docs).keys(["title"]).index(index).build();
+        assert!(matches!(result, Err(FuseError::IncorrectIndexType)));
+    }
+
+    #[test]
+    fn test_invalid_options() {
+        let result = FuseBuilder::new().threshold(1.5).build();
+        assert!(matches!(result, Err(FuseError::InvalidOption(_))));
+
+        let result = FuseBuilder::new().threshold(f64::NAN).build();
+        assert!(matches!(result, Err(FuseError::InvalidOption(_))));
+
+        let key = FuseOptionKey::KeyObject(FuseOptionKeyObject {
+            name: Cow::Owned(FuseOptionKeyName::String("title".into())),
+            weight: Some(-1.0),
+            get_fn: None,
+            fallback: None,
+            analyzer: None,
+            index: true,
+            searchable: true,
+        });
+        let result = FuseBuilder::new().keys([key]).build();
+        assert!(matches!(result, Err(FuseError::InvalidKeyWeightValue(_))));
+    }
+}
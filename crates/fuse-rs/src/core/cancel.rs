@@ -0,0 +1,163 @@
+//! Cancelling stale searches
+//!
+//! With typeahead, each keystroke starts a search that makes the previous
+//! one useless. A [`CancellationToken`] shared with the searching thread
+//! lets the caller abort it: [`Fuse::search_with_cancel`] checks the token
+//! after every record and gives up as soon as it is cancelled.
+
+use std::ops::ControlFlow;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use serde_json::Value;
+
+use crate::core::error_messages::FuseError;
+use crate::core::fuse::Fuse;
+use crate::core::observer::SearchProgress;
+use crate::core::results::search_result::{FuseResult, FuseSearchOptions};
+
+//----------------------------------------------------------------------
+// Types
+//----------------------------------------------------------------------
+
+/// A flag cancelling the searches it is passed to
+///
+/// Clones share the flag, so a clone can be kept to cancel a search running
+/// on another thread. Once cancelled, a token stays cancelled.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    /// Whether the token was cancelled
+    cancelled: Arc<AtomicBool>,
+}
+
+//----------------------------------------------------------------------
+// Implementation
+//----------------------------------------------------------------------
+
+impl CancellationToken {
+    /// Creates a token that is not cancelled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancels the searches using this token or one of its clones.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    /// Returns `true` if the token was cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+}
+
+impl SearchProgress for CancellationToken {
+    fn on_record_scored(&self, _scanned: usize, _total: usize) -> ControlFlow<()> {
+        if self.is_cancelled() {
+            ControlFlow::Break(())
+        } else {
+            ControlFlow::Continue(())
+        }
+    }
+}
+
+impl<'a> Fuse<'a> {
+    /// Searches the data like [`Fuse::search_results`], unless `token` is
+    /// cancelled first.
+    ///
+    /// # Returns
+    ///
+    /// The results, `FuseError::Cancelled` if the token was cancelled before
+    /// the search finished, or an error if the term cannot be searched with
+    /// the current options.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fuse_rs::{CancellationToken, Fuse, FuseError, FuseOptions};
+    /// use serde_json::json;
+    ///
+    /// let docs = vec![json!("apple"), json!("banana")];
+    /// let fuse = Fuse::new(&docs, &FuseOptions::default(), None);
+    ///
+    /// let token = CancellationToken::new();
+    /// assert_eq!(fuse.search_with_cancel("apple", &token).unwrap().len(), 1);
+    ///
+    /// // Typically from another thread, when a newer search starts
+    /// token.cancel();
+    /// assert!(matches!(fuse.search_with_cancel("apple", &token), Err(FuseError::Cancelled)));
+    /// ```
+    pub fn search_with_cancel(
+        &self,
+        term: &str,
+        token: &CancellationToken,
+    ) -> Result<Vec<FuseResult<Value>>, FuseError> {
+        if token.is_cancelled() {
+            return Err(FuseError::Cancelled);
+        }
+
+        let search_options = FuseSearchOptions {
+            progress: Some(Arc::new(token.clone())),
+            ..Default::default()
+        };
+        let results = self.search_results(term, &search_options)?;
+
+        if token.is_cancelled() {
+            return Err(FuseError::Cancelled);
+        }
+
+        Ok(results)
+    }
+}
+
+//----------------------------------------------------------------------
+// Tests
+//----------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::options::config::FuseOptions;
+    use serde_json::json;
+
+    /// Cancels a token once a number of records were scanned
+    #[derive(Debug)]
+    struct CancelAfter {
+        token: CancellationToken,
+        records: usize,
+    }
+
+    impl SearchProgress for CancelAfter {
+        fn on_record_scored(&self, scanned: usize, total: usize) -> ControlFlow<()> {
+            if scanned == self.records {
+                self.token.cancel();
+            }
+            self.token.on_record_scored(scanned, total)
+        }
+    }
+
+    #[test]
+    fn test_cancel_during_search() {
+        let docs: Vec<Value> = (0..50).map(|i| json!(format!("apple {}", i))).collect();
+        let fuse = Fuse::new(&docs, &FuseOptions::default(), None);
+
+        let token = CancellationToken::new();
+        let progress = Arc::new(CancelAfter { token: token.clone(), records: 10 });
+        let search_options = FuseSearchOptions { progress: Some(progress), ..Default::default() };
+
+        let results = fuse.search_results("apple", &search_options).unwrap();
+        assert_eq!(results.len(), 10);
+        assert!(token.is_cancelled());
+        assert!(matches!(fuse.search_with_cancel("apple", &token), Err(FuseError::Cancelled)));
+    }
+
+    #[test]
+    fn test_clones_share_the_flag() {
+        let token = CancellationToken::new();
+        let clone = token.clone();
+        assert!(!token.is_cancelled());
+
+        clone.cancel();
+        assert!(token.is_cancelled());
+    }
+}
@@ -0,0 +1,378 @@
+//! Compatibility fixtures recorded with Fuse.js
+//!
+//! A fixture holds a collection, the options and query of a search, and the
+//! results Fuse.js returned for it. [`Fixture::run`] repeats the search with
+//! this library and lists every divergence, so parity with Fuse.js can be
+//! checked as features land.
+
+use std::fmt;
+use std::io::Read;
+
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::core::error_messages::FuseError;
+use crate::core::fuse::Fuse;
+use crate::core::options::config::FuseOptions;
+use crate::core::results::search_result::{FuseResult, FuseSearchOptions, RangeTuple};
+
+//----------------------------------------------------------------------
+// Constants
+//----------------------------------------------------------------------
+
+/// Largest difference between two scores that are considered equal
+pub const DEFAULT_SCORE_TOLERANCE: f64 = 1e-6;
+
+//----------------------------------------------------------------------
+// Types
+//----------------------------------------------------------------------
+
+/// A search recorded with Fuse.js
+///
+/// Fixtures are JSON objects with the fields `name` (optional),
+/// `collection`, `options`, `query` and `expected`, the latter being the
+/// output of Fuse.js' `search` as is. A file holds a fixture or an array of
+/// them. They can be recorded with a script such as:
+///
+/// ```js
+/// const Fuse = require('fuse.js')
+///
+/// const options = { keys: ['title'], includeScore: true, includeMatches: true }
+/// const fuse = new Fuse(collection, options)
+/// const fixture = { name: 'title', collection, options, query, expected: fuse.search(query) }
+/// ```
+///
+/// Fuse.js counts match indices in UTF-16 code units while this library
+/// counts characters, so indices differ after characters outside the Basic
+/// Multilingual Plane.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Fixture {
+    /// Name reported with the divergences
+    #[serde(default)]
+    pub name: String,
+
+    /// The searched documents
+    pub collection: Vec<Value>,
+
+    /// The Fuse.js options, in camelCase
+    #[serde(default)]
+    pub options: Value,
+
+    /// The searched pattern
+    pub query: String,
+
+    /// The results returned by Fuse.js
+    pub expected: Vec<FixtureResult>,
+}
+
+/// A result returned by Fuse.js
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FixtureResult {
+    /// Index of the document in the collection
+    pub ref_index: usize,
+
+    /// Score, with `includeScore`
+    #[serde(default)]
+    pub score: Option<f64>,
+
+    /// Matches, with `includeMatches`
+    #[serde(default)]
+    pub matches: Option<Vec<FixtureMatch>>,
+}
+
+/// A match of a result returned by Fuse.js
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FixtureMatch {
+    /// The key the match was found in
+    #[serde(default)]
+    pub key: Option<String>,
+
+    /// Index of the value in an array of values
+    #[serde(default)]
+    pub ref_index: Option<usize>,
+
+    /// The matched value
+    #[serde(default)]
+    pub value: Option<String>,
+
+    /// Inclusive ranges of the matched characters
+    pub indices: Vec<RangeTuple>,
+}
+
+/// A difference between the results of Fuse.js and of this library
+#[derive(Debug, Clone, PartialEq)]
+pub enum Divergence {
+    /// The searches returned a different number of results
+    ResultCount { expected: usize, actual: usize },
+
+    /// Another document was returned at `position`
+    RefIndex { position: usize, expected: usize, actual: usize },
+
+    /// The score at `position` differs by more than the tolerance, or is
+    /// missing
+    Score { position: usize, expected: f64, actual: Option<f64> },
+
+    /// The matches at `position` differ (compared regardless of order)
+    Matches { position: usize, expected: Vec<FixtureMatch>, actual: Vec<FixtureMatch> },
+}
+
+//----------------------------------------------------------------------
+// Implementation
+//----------------------------------------------------------------------
+
+impl Fixture {
+    /// Runs the search of the fixture, comparing scores with
+    /// [`DEFAULT_SCORE_TOLERANCE`].
+    ///
+    /// # Returns
+    ///
+    /// The divergences from the recorded results (empty when they agree), or
+    /// an error if the options are invalid or the search fails.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fuse_rs::load_fixtures;
+    ///
+    /// let fixtures = r#"{
+    ///     "collection": ["apple", "banana"],
+    ///     "options": {"includeScore": true},
+    ///     "query": "banana",
+    ///     "expected": [{"item": "banana", "refIndex": 1, "score": 0}]
+    /// }"#;
+    ///
+    /// for fixture in load_fixtures(fixtures.as_bytes()).unwrap() {
+    ///     assert_eq!(fixture.run().unwrap(), vec![]);
+    /// }
+    /// ```
+    pub fn run(&self) -> Result<Vec<Divergence>, FuseError> {
+        self.run_with_tolerance(DEFAULT_SCORE_TOLERANCE)
+    }
+
+    /// Runs the search of the fixture, considering scores within
+    /// `tolerance` of each other equal.
+    pub fn run_with_tolerance(&self, tolerance: f64) -> Result<Vec<Divergence>, FuseError> {
+        let options = FuseOptions::from_json(self.options.clone()).map_err(|e| FuseError::InvalidFixture(e.to_string()))?;
+        let fuse = Fuse::try_new(&self.collection, &options, None)?;
+        let actual = fuse.search_results(&self.query, &FuseSearchOptions::default())?;
+
+        let mut divergences = Vec::new();
+        if actual.len() != self.expected.len() {
+            divergences.push(Divergence::ResultCount { expected: self.expected.len(), actual: actual.len() });
+        }
+
+        for (position, (expected, actual)) in self.expected.iter().zip(&actual).enumerate() {
+            compare_result(position, expected, actual, tolerance, &mut divergences);
+        }
+
+        Ok(divergences)
+    }
+}
+
+impl fmt::Display for Divergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ResultCount { expected, actual } => write!(f, "expected {} results, got {}", expected, actual),
+            Self::RefIndex { position, expected, actual } => {
+                write!(f, "result {}: expected document {}, got {}", position, expected, actual)
+            },
+            Self::Score { position, expected, actual: Some(actual) } => {
+                write!(f, "result {}: expected score {}, got {}", position, expected, actual)
+            },
+            Self::Score { position, expected, actual: None } => {
+                write!(f, "result {}: expected score {}, got none", position, expected)
+            },
+            Self::Matches { position, expected, actual } => {
+                write!(f, "result {}: expected matches {:?}, got {:?}", position, expected, actual)
+            },
+        }
+    }
+}
+
+//----------------------------------------------------------------------
+// Free Functions
+//----------------------------------------------------------------------
+
+/// Reads the fixtures of a JSON file holding a fixture or an array of them.
+///
+/// # Returns
+///
+/// The fixtures, or `FuseError::InvalidFixture` if they cannot be read.
+pub fn load_fixtures<R: Read>(reader: R) -> Result<Vec<Fixture>, FuseError> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Fixtures {
+        Many(Vec<Fixture>),
+        One(Box<Fixture>),
+    }
+
+    match serde_json::from_reader(reader).map_err(|e| FuseError::InvalidFixture(e.to_string()))? {
+        Fixtures::Many(fixtures) => Ok(fixtures),
+        Fixtures::One(fixture) => Ok(vec![*fixture]),
+    }
+}
+
+//----------------------------------------------------------------------
+// Implementation Details
+//----------------------------------------------------------------------
+
+/// Compares the result at `position` with the recorded one.
+fn compare_result(
+    position: usize,
+    expected: &FixtureResult,
+    actual: &FuseResult<Value>,
+    tolerance: f64,
+    divergences: &mut Vec<Divergence>,
+) {
+    if expected.ref_index != actual.ref_index {
+        divergences.push(Divergence::RefIndex { position, expected: expected.ref_index, actual: actual.ref_index });
+        return;
+    }
+
+    if let Some(score) = expected.score
+        && !actual.score.is_some_and(|actual| (actual - score).abs() <= tolerance)
+    {
+        divergences.push(Divergence::Score { position, expected: score, actual: actual.score });
+    }
+
+    if let Some(matches) = &expected.matches {
+        let mut expected = matches.clone();
+        let mut actual: Vec<FixtureMatch> = actual
+            .matches
+            .iter()
+            .flatten()
+            .map(|m| FixtureMatch {
+                key: m.key.clone(),
+                ref_index: m.ref_index,
+                value: m.value.clone(),
+                indices: m.indices.clone(),
+            })
+            .collect();
+
+        expected.sort();
+        actual.sort();
+        if expected != actual {
+            divergences.push(Divergence::Matches { position, expected, actual });
+        }
+    }
+}
+
+//----------------------------------------------------------------------
+// Tests
+//----------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BOOKS: &str = r#"[
+        {
+            "name": "title and author",
+            "collection": [
+                {"title": "Old Man's War", "author": "John Scalzi"},
+                {"title": "The Lock Artist", "author": "Steve Hamilton"}
+            ],
+            "options": {"keys": ["title", "author"], "includeMatches": true},
+            "query": "hamilton",
+            "expected": [{
+                "item": {"title": "The Lock Artist", "author": "Steve Hamilton"},
+                "refIndex": 1,
+                "matches": [{"indices": [[1, 1], [6, 13]], "value": "Steve Hamilton", "key": "author"}]
+            }]
+        },
+        {
+            "collection": ["apple", "banana"],
+            "query": "zzz",
+            "expected": []
+        }
+    ]"#;
+
+    // Fuse.js raises the score of each matched value to its key weight, as
+    // normalized by its key store (2/3 and 1/3 here), times its field-length
+    // norm: "old" scores 0.001 in "Old Times" and 0.05 in "Gary Oldman"
+    const WEIGHTED_KEYS: &str = r#"{
+        "collection": [
+            {"title": "Old Man's War", "author": "John Scalzi"},
+            {"title": "The Lock Artist", "author": "Gary Oldman"},
+            {"title": "Old Times", "author": "Gary Oldman"}
+        ],
+        "options": {"keys": [{"name": "title", "weight": 2}, {"name": "author", "weight": 1}], "includeScore": true},
+        "query": "old",
+        "expected": [
+            {"refIndex": 2, "score": 0.019027888492142354},
+            {"refIndex": 0, "score": 0.07014552984199715},
+            {"refIndex": 1, "score": 0.49361755631013754}
+        ]
+    }"#;
+
+    // "War" equals the pattern, scoring 0 and so Number.EPSILON ^ (2/3) in
+    // Fuse.js; "Old Man's War" contains it at 10, scoring 0.1 ^ (2/3 * 0.577)
+    const EXACT_MATCH: &str = r#"{
+        "collection": [
+            {"title": "Old Man's War", "author": "John Scalzi"},
+            {"title": "War", "author": "Sebastian Junger"}
+        ],
+        "options": {"keys": [{"name": "title", "weight": 2}, {"name": "author", "weight": 1}], "includeScore": true},
+        "query": "war",
+        "expected": [
+            {"refIndex": 1, "score": 3.666852862501036e-11},
+            {"refIndex": 0, "score": 0.4124139370464501}
+        ]
+    }"#;
+
+    #[test]
+    fn test_matching_fixtures() {
+        let fixtures = load_fixtures(BOOKS.as_bytes()).unwrap();
+
+        assert_eq!(fixtures.len(), 2);
+        assert_eq!(fixtures[0].name, "title and author");
+        for fixture in fixtures {
+            assert_eq!(fixture.run().unwrap(), vec![], "{}", fixture.name);
+        }
+    }
+
+    #[test]
+    fn test_weighted_keys_fixture() {
+        let fixture = &load_fixtures(WEIGHTED_KEYS.as_bytes()).unwrap()[0];
+        assert_eq!(fixture.run().unwrap(), vec![]);
+    }
+
+    #[test]
+    fn test_exact_match_fixture() {
+        let fixture = &load_fixtures(EXACT_MATCH.as_bytes()).unwrap()[0];
+
+        // Tight enough to tell the exact match apart from a score of 0
+        assert_eq!(fixture.run_with_tolerance(1e-15).unwrap(), vec![]);
+    }
+
+    #[test]
+    fn test_divergences() {
+        let fixture = r#"{
+            "collection": ["apple", "banana", "cherry"],
+            "options": {"includeScore": true, "includeMatches": true},
+            "query": "banana",
+            "expected": [
+                {"refIndex": 1, "score": 0.5, "matches": [{"indices": [[0, 1]], "value": "banana"}]},
+                {"refIndex": 2}
+            ]
+        }"#;
+        let divergences = load_fixtures(fixture.as_bytes()).unwrap()[0].run().unwrap();
+
+        assert_eq!(divergences.len(), 3);
+        assert_eq!(divergences[0], Divergence::ResultCount { expected: 2, actual: 1 });
+        assert_eq!(divergences[1], Divergence::Score { position: 0, expected: 0.5, actual: Some(0.0) });
+        assert!(matches!(&divergences[2], Divergence::Matches { actual, .. } if actual[0].indices == vec![(0, 5)]));
+        assert_eq!(divergences[1].to_string(), "result 0: expected score 0.5, got 0");
+    }
+
+    #[test]
+    fn test_invalid_fixtures() {
+        assert!(matches!(load_fixtures(&b"{}"[..]), Err(FuseError::InvalidFixture(_))));
+
+        let fixture = r#"{"collection": [], "options": {"threshold": "high"}, "query": "a", "expected": []}"#;
+        let result = load_fixtures(fixture.as_bytes()).unwrap()[0].run();
+        assert!(matches!(result, Err(FuseError::InvalidFixture(_))));
+    }
+}
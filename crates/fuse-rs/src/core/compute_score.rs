@@ -0,0 +1,251 @@
+//! Combined relevance scoring for search results
+//!
+//! Each record may match the pattern in several keys. This module folds the
+//! per-value bitmap scores into a single score per record, taking key
+//! weights and field-length norms into account. How is up to the
+//! [`Scorer`] of the options; [`DefaultScorer`] scores like Fuse.js.
+
+use std::fmt;
+use std::sync::Arc;
+
+use crate::core::results::search_result::{ScoredMatch, ScoredRecord};
+use crate::tools::key_store::Key;
+
+//----------------------------------------------------------------------
+// Types
+//----------------------------------------------------------------------
+
+/// A matched value, as scored by a [`Scorer`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MatchScore<'k> {
+    /// The fuzzy score of the value (`0.0` is an exact match)
+    pub score: f64,
+
+    /// The id of the key the value was found in (`None` for string
+    /// collections)
+    pub key: Option<&'k str>,
+
+    /// The weight of the key, normalized so that the weights of all keys
+    /// add up to 1 (`None` for string collections)
+    pub weight: Option<f64>,
+
+    /// The field-length norm of the value (`1.0` when `ignore_field_norm`
+    /// is set)
+    pub norm: f64,
+}
+
+/// Folds the scores of the values a document matched in into the score of
+/// the document
+///
+/// Lower scores rank first. Scorers are set with
+/// [`FuseOptions::scorer`](crate::FuseOptions::scorer).
+///
+/// # Example
+///
+/// ```
+/// use std::sync::Arc;
+/// use fuse_rs::{Fuse, FuseOptions, FuseSearchOptions, MatchScore, Scorer};
+/// use serde_json::json;
+///
+/// /// Ranks documents by their best value alone
+/// #[derive(Debug)]
+/// struct BestValue;
+///
+/// impl Scorer for BestValue {
+///     fn match_factor(&self, m: &MatchScore) -> f64 {
+///         m.score
+///     }
+///
+///     fn combine(&self, factors: &[f64]) -> f64 {
+///         factors.iter().copied().fold(1.0, f64::min)
+///     }
+/// }
+///
+/// let docs = vec![json!({"title": "Old Man's War", "author": "John Scalzi"})];
+/// let options = FuseOptions {
+///     keys: vec!["title".into(), "author".into()],
+///     include_score: true,
+///     scorer: Arc::new(BestValue),
+///     ..Default::default()
+/// };
+/// let fuse = Fuse::new(&docs, &options, None);
+///
+/// let results = fuse.search_results("old man", &FuseSearchOptions::default()).unwrap();
+/// assert_eq!(results[0].score, Some(0.001));
+/// ```
+pub trait Scorer: fmt::Debug + Send + Sync {
+    /// Returns the factor a matched value contributes to the score of its
+    /// document.
+    fn match_factor(&self, m: &MatchScore) -> f64;
+
+    /// Combines the factors of the values a document matched in into its
+    /// score. Defaults to their product.
+    fn combine(&self, factors: &[f64]) -> f64 {
+        factors.iter().product()
+    }
+}
+
+/// Scores documents like Fuse.js (the default)
+///
+/// A value contributes its score raised to the power of the key weight times
+/// the field-length norm, and a document scores the product of those.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DefaultScorer;
+
+//----------------------------------------------------------------------
+// Implementation
+//----------------------------------------------------------------------
+
+impl Scorer for DefaultScorer {
+    /// Exact matches in a weighted key count as `f64::EPSILON` (2^-52), as
+    /// in Fuse.js' `computeScore` (`score === 0 && weight ? Number.EPSILON :
+    /// score`), so that the weights of the keys still order records matching
+    /// exactly. Only values equal to the pattern score `0.0`: the bitap
+    /// search floors the other scores at `0.001`, as Fuse.js does.
+    fn match_factor(&self, m: &MatchScore) -> f64 {
+        let score = if m.score == 0.0 && m.weight.is_some() { f64::EPSILON } else { m.score };
+        score.powf(m.weight.unwrap_or(1.0) * m.norm)
+    }
+}
+
+//----------------------------------------------------------------------
+// Public API
+//----------------------------------------------------------------------
+
+/// Returns the default scorer used by `FuseOptions`
+pub fn default_scorer() -> Arc<dyn Scorer> {
+    Arc::new(DefaultScorer)
+}
+
+/// Computes the final score of every result.
+///
+/// # Arguments
+///
+/// * `results` - The matched records to score
+/// * `keys` - The index keys referenced by the matches
+/// * `weights` - The normalized weights of `keys`, by key position
+/// * `ignore_field_norm` - When `true`, field-length norms are not applied
+/// * `scorer` - Folds the scores of the matches of a record
+pub fn compute_score(
+    results: &mut [ScoredRecord],
+    keys: &[Key],
+    weights: &[f64],
+    ignore_field_norm: bool,
+    scorer: &dyn Scorer,
+) {
+    let mut factors = Vec::new();
+
+    for result in results.iter_mut() {
+        factors.clear();
+        factors.extend(
+            result
+                .matches
+                .iter()
+                .map(|m| scorer.match_factor(&match_score(m, keys, weights, ignore_field_norm))),
+        );
+        result.score = scorer.combine(&factors);
+    }
+}
+
+/// Returns what a `Scorer` knows of the match `m`, whose key is weighted
+/// by `weights`.
+pub fn match_score<'k>(m: &ScoredMatch, keys: &'k [Key], weights: &[f64], ignore_field_norm: bool) -> MatchScore<'k> {
+    let key = m.key.and_then(|k| keys.get(k));
+
+    MatchScore {
+        score: m.score,
+        key: key.map(|k| k.id.as_str()),
+        weight: m.key.and_then(|k| weights.get(k).copied()),
+        norm: if ignore_field_norm { 1.0 } else { m.norm },
+    }
+}
+
+//----------------------------------------------------------------------
+// Tests
+//----------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::path::Path;
+
+    fn key() -> Key<'static> {
+        Key {
+            path: vec!["title".to_string()],
+            compiled_path: Path::from_segments(["title".to_string()]),
+            id: "title".to_string(),
+            weight: 1.0,
+            src: "title".into(),
+            get_fn: None,
+            fallback: None,
+            index: true,
+            searchable: true,
+        }
+    }
+
+    fn scored_match(score: f64, key: Option<usize>, norm: f64) -> ScoredMatch {
+        ScoredMatch {
+            score,
+            key,
+            value: String::new(),
+            idx: None,
+            norm,
+            indices: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_compute_score_combines_matches() {
+        let keys = vec![key(), key()];
+        let mut results = vec![ScoredRecord {
+            idx: 0,
+            score: 1.0,
+            matches: vec![scored_match(0.5, Some(0), 1.0), scored_match(0.5, Some(1), 0.5)],
+        }];
+
+        compute_score(&mut results, &keys, &[1.0, 2.0], false, &DefaultScorer);
+        assert!((results[0].score - 0.25).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_compute_score_ignore_field_norm() {
+        let keys = vec![key()];
+        let mut results = vec![ScoredRecord {
+            idx: 0,
+            score: 1.0,
+            matches: vec![scored_match(0.5, Some(0), 0.25)],
+        }];
+
+        compute_score(&mut results, &keys, &[1.0], true, &DefaultScorer);
+        assert!((results[0].score - 0.5).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_compute_score_with_custom_scorer() {
+        /// Adds up the unweighted scores
+        #[derive(Debug)]
+        struct Sum;
+
+        impl Scorer for Sum {
+            fn match_factor(&self, m: &MatchScore) -> f64 {
+                assert_eq!((m.key, m.weight), (Some("title"), Some(2.0)));
+                m.score
+            }
+
+            fn combine(&self, factors: &[f64]) -> f64 {
+                factors.iter().sum()
+            }
+        }
+
+        // The weights passed in are used, not those the keys were indexed with
+        let keys = vec![key()];
+        let mut results = vec![ScoredRecord {
+            idx: 0,
+            score: 1.0,
+            matches: vec![scored_match(0.25, Some(0), 0.5), scored_match(0.5, Some(0), 1.0)],
+        }];
+
+        compute_score(&mut results, &keys, &[2.0], false, &Sum);
+        assert_eq!(results[0].score, 0.75);
+    }
+}
@@ -3,8 +3,10 @@
 //! This module contains standardized error types that are used by various components
 //! of the fuzzy search implementation.
 
-use std::error::Error;
-use std::fmt;
+use alloc::format;
+use alloc::string::String;
+use ::core::error::Error;
+use ::core::fmt;
 
 /// Custom error enum for fuzzy search operations
 #[derive(Debug, Clone)]
@@ -20,6 +22,9 @@ pub enum FuseError {
     
     /// Invalid value for a specific key in a logical search query
     InvalidLogicalQueryForKey(String),
+
+    /// A logical search query is malformed
+    InvalidLogicalQuery(String),
     
     /// Pattern length exceeds the maximum allowed
     PatternLengthTooLarge(usize),
@@ -29,6 +34,36 @@ pub enum FuseError {
     
     /// A key's weight property has an invalid value
     InvalidKeyWeightValue(String),
+
+    /// A strict path lookup ran into an array without a selector
+    UnexpectedArray(String),
+
+    /// A snapshot could not be written or read
+    InvalidSnapshot(String),
+
+    /// A snapshot was written by an incompatible version of the library
+    UnsupportedSnapshotVersion(u32),
+
+    /// A compatibility fixture could not be read
+    InvalidFixture(String),
+
+    /// The document store does not support adding or removing documents
+    ReadOnlyStore,
+
+    /// An option has an invalid value
+    InvalidOption(String),
+
+    /// The search was cancelled before it finished
+    Cancelled,
+
+    /// A search was restricted to a key that is not searched
+    UnknownKey(String),
+
+    /// An index could not be parsed from its JSON form
+    InvalidIndex(String),
+
+    /// A search cursor was resumed after the collection changed
+    StaleCursor,
 }
 
 impl fmt::Display for FuseError {
@@ -38,9 +73,20 @@ impl fmt::Display for FuseError {
             Self::LogicalSearchUnavailable => write!(f, "Logical search is not available"),
             Self::IncorrectIndexType => write!(f, "Incorrect 'index' type"),
             Self::InvalidLogicalQueryForKey(key) => write!(f, "Invalid value for key {}", key),
+            Self::InvalidLogicalQuery(reason) => write!(f, "Invalid logical query: {}", reason),
             Self::PatternLengthTooLarge(max) => write!(f, "Pattern length exceeds max of {}.", max),
             Self::MissingKeyProperty(name) => write!(f, "Missing {} property in key", name),
             Self::InvalidKeyWeightValue(key) => write!(f, "Property 'weight' in key '{}' must be a positive integer", key),
+            Self::UnexpectedArray(path) => write!(f, "Unexpected array at '{}'; use [*] to traverse it", path),
+            Self::InvalidSnapshot(reason) => write!(f, "Invalid snapshot: {}", reason),
+            Self::UnsupportedSnapshotVersion(version) => write!(f, "Unsupported snapshot version {}", version),
+            Self::InvalidFixture(reason) => write!(f, "Invalid fixture: {}", reason),
+            Self::ReadOnlyStore => write!(f, "The document store is read-only"),
+            Self::InvalidOption(reason) => write!(f, "Invalid option: {}", reason),
+            Self::Cancelled => write!(f, "The search was cancelled"),
+            Self::UnknownKey(key) => write!(f, "Unknown search key '{}'", key),
+            Self::InvalidIndex(reason) => write!(f, "Invalid index: {}", reason),
+            Self::StaleCursor => write!(f, "The collection changed since the search cursor was created"),
         }
     }
 }
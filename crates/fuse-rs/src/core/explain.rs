@@ -0,0 +1,185 @@
+//! Explaining the score of a document
+//!
+//! [`Fuse::explain`] searches a single document and reports every value that
+//! matched together with the numbers its contribution to the score is made
+//! of, so it can be seen why one result ranks above another.
+
+use serde::Serialize;
+
+use crate::core::compute_score::match_score;
+use crate::core::error_messages::FuseError;
+use crate::core::fuse::Fuse;
+
+//----------------------------------------------------------------------
+// Types
+//----------------------------------------------------------------------
+
+/// The breakdown of the score of a document
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScoreExplanation {
+    /// The reference index of the document in the collection
+    pub ref_index: usize,
+
+    /// The combined score, the factors of the matches combined by the
+    /// scorer; by default their product (lower is better)
+    pub score: f64,
+
+    /// The values of the document that matched
+    pub matches: Vec<MatchExplanation>,
+}
+
+/// The contribution of a matched value to the score of its document
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MatchExplanation {
+    /// The key path the value was found in (`None` for string collections)
+    pub key: Option<String>,
+
+    /// Position of the value within an array-valued key
+    pub ref_index: Option<usize>,
+
+    /// The matched value
+    pub value: String,
+
+    /// The bitmap score of the value (`0.0` is an exact match)
+    pub raw_score: f64,
+
+    /// The field-length norm of the value (`1.0` when
+    /// `ignore_field_norm` is set)
+    pub norm: f64,
+
+    /// The weight of the key, normalized so that the weights of all keys
+    /// add up to 1 (`1.0` for string collections)
+    pub weight: f64,
+
+    /// The factor the value multiplies the score of the document by
+    pub factor: f64,
+}
+
+//----------------------------------------------------------------------
+// Implementation
+//----------------------------------------------------------------------
+
+impl<'a> Fuse<'a> {
+    /// Explains the score the document at `doc_idx` gets when searching for
+    /// `term`.
+    ///
+    /// # Returns
+    ///
+    /// The breakdown of the score, `None` if the document does not match or
+    /// is not in the index, or an error if the term cannot be searched with
+    /// the current options.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fuse_rs::{Fuse, FuseOptionKey, FuseOptions};
+    /// use serde_json::json;
+    ///
+    /// let docs = vec![json!({"title": "Old Man's War", "author": "John Scalzi"})];
+    /// let options = FuseOptions {
+    ///     keys: vec![FuseOptionKey::String("title".into()), FuseOptionKey::String("author".into())],
+    ///     ..Default::default()
+    /// };
+    /// let fuse = Fuse::new(&docs, &options, None);
+    ///
+    /// let explanation = fuse.explain("scalzi", 0).unwrap().unwrap();
+    /// assert_eq!(explanation.matches[0].key.as_deref(), Some("author"));
+    /// assert_eq!(explanation.score, explanation.matches[0].factor);
+    /// ```
+    pub fn explain(&self, term: &str, doc_idx: usize) -> Result<Option<ScoreExplanation>, FuseError> {
+        self.validate_term(term)?;
+
+        let Some(record) = self.get_index().record_at(doc_idx) else {
+            return Ok(None);
+        };
+
+        let Some(result) = self.search_record(&self.searcher(term), record)? else {
+            return Ok(None);
+        };
+
+        let keys = self.get_index().keys();
+        let weights = self.key_weights();
+        let ignore_field_norm = self.options().ignore_field_norm;
+        let scorer = self.options().scorer.as_ref();
+
+        let matches: Vec<MatchExplanation> = result
+            .matches
+            .iter()
+            .map(|m| {
+                let score = match_score(m, keys, weights, ignore_field_norm);
+                MatchExplanation {
+                    key: self.key_src(m),
+                    ref_index: m.idx,
+                    value: m.value.clone(),
+                    raw_score: m.score,
+                    norm: score.norm,
+                    weight: score.weight.unwrap_or(1.0),
+                    factor: scorer.match_factor(&score),
+                }
+            })
+            .collect();
+
+        let factors: Vec<f64> = matches.iter().map(|m| m.factor).collect();
+        Ok(Some(ScoreExplanation {
+            ref_index: result.idx,
+            score: scorer.combine(&factors),
+            matches,
+        }))
+    }
+}
+
+//----------------------------------------------------------------------
+// Tests
+//----------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::options::config::FuseOptions;
+    use crate::core::options::keys::FuseOptionKey;
+    use crate::core::results::search_result::FuseSearchOptions;
+    use crate::core::searchable::field_key;
+    use serde_json::json;
+
+    #[test]
+    fn test_explain_matches_search_score() {
+        let docs = vec![
+            json!({"title": "Old Man's War", "tags": ["war", "old"]}),
+            json!({"title": "The Lock Artist", "tags": ["thriller"]}),
+        ];
+        let options = FuseOptions {
+            keys: vec![field_key("title", 2.0), FuseOptionKey::String("tags".into())],
+            include_score: true,
+            ..Default::default()
+        };
+        let fuse = Fuse::new(&docs, &options, None);
+
+        let results = fuse.search_results("old", &FuseSearchOptions::default()).unwrap();
+        let explanation = fuse.explain("old", 0).unwrap().unwrap();
+
+        assert_eq!(Some(explanation.score), results[0].score);
+        assert_eq!(explanation.matches.len(), 2);
+
+        let title = &explanation.matches[0];
+        assert_eq!((title.key.as_deref(), title.ref_index, title.weight), (Some("title"), None, 2.0 / 3.0));
+
+        let tag = &explanation.matches[1];
+        assert_eq!((tag.key.as_deref(), tag.ref_index, tag.value.as_str()), (Some("tags"), Some(1), "old"));
+        assert_eq!((tag.raw_score, tag.factor), (0.0, f64::EPSILON.powf(tag.weight * tag.norm)));
+    }
+
+    #[test]
+    fn test_explain_without_match() {
+        let docs = vec![json!("apple"), json!("banana")];
+        let options = FuseOptions { ignore_field_norm: true, ..Default::default() };
+        let fuse = Fuse::new(&docs, &options, None);
+
+        let explanation = fuse.explain("banana", 1).unwrap().unwrap();
+        assert_eq!((explanation.matches[0].key.as_deref(), explanation.matches[0].norm), (None, 1.0));
+
+        assert_eq!(fuse.explain("banana", 0).unwrap(), None);
+        assert_eq!(fuse.explain("banana", 7).unwrap(), None);
+    }
+}
@@ -1,8 +1,45 @@
 use crate::{
-    core::{options::config::FuseOptions, error_messages::FuseError},
-    tools::{fuse_index::FuseIndex, key_store::KeyStore},
+    core::{
+        compute_score::compute_score,
+        error_messages::FuseError,
+        observer::SearchMetrics,
+        options::{config::FuseOptions, keys::FuseOptionKey},
+        result_cache::ResultCache,
+        snapshot::{self, SNAPSHOT_VERSION, SnapshotRef},
+        results::{
+            match_result::{
+                FuseSortFunctionArg, FuseSortFunctionItem, FuseSortFunctionMatch,
+                FuseSortFunctionMatchList, FuseSortFunctionMatchType, FuseSortItemField,
+                FuseSortItemValue,
+            },
+            search_result::{FuseResult, FuseResultMatch, FuseSearchOptions, ScoredMatch, ScoredRecord, SearchPage},
+            top_k::TopK,
+        },
+    },
+    helpers::char_offsets::char_len,
+    helpers::normalize::project_indices,
+    helpers::trace,
+    search::bitmap::constants::MAX_BITS,
+    search::edit_distance::MatchAlgorithm,
+    search::searcher::Searcher,
+    tools::{
+        doc_store::{DocStore, MemoryDocStore},
+        fuse_index::FuseIndex,
+        fuse_index_record::{FuseIndexRecord, IndexValue, RecordEntryValue},
+        key_store::{KeyStore, create_key},
+        norm::Norm,
+        token_trie::TokenTrie,
+    },
 };
 use serde_json::Value;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::io::{Read, Write};
+use std::sync::OnceLock;
+use std::time::Instant;
+
+#[cfg(feature = "grapheme-clusters")]
+use crate::helpers::graphemes::cluster_to_char_ranges;
 
 //----------------------------------------------------------------------
 // Main Fuse Implementation
@@ -13,6 +50,13 @@ use serde_json::Value;
 /// `Fuse` provides methods to perform fuzzy searches on a collection of JSON values
 /// using configurable options for matching and scoring.
 ///
+/// # Thread safety
+///
+/// `Fuse` is `Send + Sync`: searching only needs `&self`, and the caches it
+/// fills while searching (field norms, interned strings) are atomics or
+/// locks, so a constructed instance can be wrapped in an `Arc` and searched
+/// from several threads at once.
+///
 /// # Example
 ///
 pub struct Fuse<'a> {
@@ -20,14 +64,48 @@ pub struct Fuse<'a> {
     options: FuseOptions<'a>,
 
     /// The collection of documents to search through
-    docs: Vec<Value>,
+    docs: Box<dyn DocStore>,
 
     /// Index structure for searchable keys in documents
     key_store: KeyStore<'a>,
 
     index: FuseIndex<'a>,
+
+    /// Options of the keys of the index with their own analyzer, by key
+    /// position; empty when no key has one
+    key_options: Vec<Option<FuseOptions<'a>>>,
+
+    /// Keys matched by searches that don't name any, by key position;
+    /// `None` when every key is
+    searchable_keys: Option<Vec<bool>>,
+
+    /// Weights the matches of each key are scored with, by key position:
+    /// those of `key_store`, normalized to add up to 1 as in Fuse.js
+    key_weights: Vec<f64>,
+
+    /// Results of recent searches, emptied when the collection changes
+    result_cache: ResultCache,
+
+    /// Words of the indexed values for suggestions, built when first
+    /// needed and dropped when the collection changes
+    token_trie: OnceLock<TokenTrie>,
+
+    /// Number of times the collection has changed, so cursors can tell the
+    /// records they were scanning have moved
+    generation: u64,
 }
 
+// Instances are shared across threads; a non-thread-safe field (such as a
+// `RefCell` cache) should fail the build rather than the users' code
+const _: () = {
+    const fn assert_send_sync<T: Send + Sync>() {}
+
+    assert_send_sync::<Fuse<'static>>();
+    assert_send_sync::<FuseIndex<'static>>();
+    assert_send_sync::<Norm>();
+    assert_send_sync::<FuseSearchOptions>();
+};
+
 impl<'a> Fuse<'a> {
     /// Creates a new Fuse instance with the given data and search options.
     ///
@@ -40,27 +118,376 @@ impl<'a> Fuse<'a> {
     ///
     /// A new `Fuse` instance ready to perform searches
     pub fn new(docs: &[Value], options: &FuseOptions<'a>, index: Option<FuseIndex<'a>>) -> Self {
+        Self::from_docs(docs.to_vec(), options, index)
+    }
+
+    /// Creates a new Fuse instance like [`Fuse::new`], taking ownership of
+    /// the documents instead of copying them.
+    ///
+    /// The documents are indexed where they are and then moved into the
+    /// instance, so a large collection is never held twice.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fuse_rs::{Fuse, FuseOptions};
+    /// use serde_json::json;
+    ///
+    /// let docs = vec![json!("apple"), json!("banana")];
+    /// let fuse = Fuse::from_docs(docs, &FuseOptions::default(), None);
+    ///
+    /// assert_eq!(fuse.search("banana").unwrap()[0].item, json!("banana"));
+    /// ```
+    pub fn from_docs(docs: Vec<Value>, options: &FuseOptions<'a>, index: Option<FuseIndex<'a>>) -> Self {
         let cloned_options = options.clone();
         let key_store = KeyStore::new(&cloned_options.keys);
-        let fuse_index = if let Some(f_index) = index {
+        let fuse_index = if let Some(mut f_index) = index {
+            f_index.set_ngram_index(cloned_options.use_ngram_index);
             f_index
         } else {
-            FuseIndex::create_index(
-                &cloned_options.keys,
-                &docs,
-                Some(cloned_options.get_fn),
-                Some(cloned_options.field_norm_weight),
-            )
+            FuseIndex::create_index_with_options(&cloned_options.keys, &docs, &cloned_options)
         };
 
         Fuse {
+            result_cache: ResultCache::new(cloned_options.result_cache_size),
+            token_trie: OnceLock::new(),
+            generation: 0,
+            key_options: analyzed_key_options(&cloned_options, &fuse_index),
+            searchable_keys: searchable_keys(&key_store, &fuse_index),
+            key_weights: key_weights(&key_store, &fuse_index),
+            options: cloned_options,
+            docs: Box::new(MemoryDocStore::new(docs)),
+            key_store,
+            index: fuse_index,
+        }
+    }
+
+    /// Creates a new Fuse instance like [`Fuse::new`], returning an error
+    /// instead of panicking when a key is invalid, and checking that `index`
+    /// was built with the keys of `options`.
+    ///
+    /// # Returns
+    ///
+    /// The instance, the error of the first invalid key (e.g.
+    /// `FuseError::InvalidKeyWeightValue`), or `FuseError::IncorrectIndexType`
+    /// if the keys of `index` differ from those of `options`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fuse_rs::{Fuse, FuseError, FuseIndex, FuseOptionKey, FuseOptions};
+    /// use serde_json::json;
+    ///
+    /// let docs = vec![json!({"title": "Old Man's War", "author": "John Scalzi"})];
+    /// let title = vec![FuseOptionKey::String("title".into())];
+    /// let options = FuseOptions { keys: title.clone(), ..Default::default() };
+    ///
+    /// let index = FuseIndex::create_index(&title, &docs, None, None);
+    /// assert!(Fuse::try_new(&docs, &options, Some(index)).is_ok());
+    ///
+    /// let author = vec![FuseOptionKey::String("author".into())];
+    /// let index = FuseIndex::create_index(&author, &docs, None, None);
+    /// let result = Fuse::try_new(&docs, &options, Some(index));
+    /// assert!(matches!(result, Err(FuseError::IncorrectIndexType)));
+    /// ```
+    pub fn try_new(docs: &[Value], options: &FuseOptions<'a>, index: Option<FuseIndex<'a>>) -> Result<Self, FuseError> {
+        check_keys(options, index.as_ref())?;
+        Ok(Self::new(docs, options, index))
+    }
+
+    /// Creates a new Fuse instance searching the documents of `store`.
+    ///
+    /// The documents are read from the store to build the index (unless
+    /// `index` is given), to apply search filters and to return the matching
+    /// items; they are not copied into memory.
+    ///
+    /// # Arguments
+    ///
+    /// * `store` - The documents to search through
+    /// * `options` - Configuration options for search behavior
+    /// * `index` - A prebuilt index of the documents of `store`
+    pub fn with_store<S: DocStore + 'static>(store: S, options: &FuseOptions<'a>, index: Option<FuseIndex<'a>>) -> Self {
+        let cloned_options = options.clone();
+        let key_store = KeyStore::new(&cloned_options.keys);
+        let mut fuse_index = index.unwrap_or_else(|| {
+            let mut index = FuseIndex::create_index_with_options(&cloned_options.keys, &[], &cloned_options);
+            index.set_store(&store);
+            index
+        });
+        fuse_index.set_ngram_index(cloned_options.use_ngram_index);
+
+        Fuse {
+            result_cache: ResultCache::new(cloned_options.result_cache_size),
+            token_trie: OnceLock::new(),
+            generation: 0,
+            key_options: analyzed_key_options(&cloned_options, &fuse_index),
+            searchable_keys: searchable_keys(&key_store, &fuse_index),
+            key_weights: key_weights(&key_store, &fuse_index),
             options: cloned_options,
-            docs: docs.to_vec(),
+            docs: Box::new(store),
             key_store,
             index: fuse_index,
         }
     }
 
+    /// Returns the options of the instance.
+    pub fn options(&self) -> &FuseOptions<'a> {
+        &self.options
+    }
+
+    /// Returns the searched documents.
+    pub fn store(&self) -> &dyn DocStore {
+        self.docs.as_ref()
+    }
+
+    /// Returns the number of searched documents.
+    pub fn len(&self) -> usize {
+        self.docs.len()
+    }
+
+    /// Returns `true` if there is no document to search.
+    pub fn is_empty(&self) -> bool {
+        self.docs.is_empty()
+    }
+
+    /// Returns the document `idx`, e.g. the `ref_index` of a result, or
+    /// `None` if it does not exist.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fuse_rs::{Fuse, FuseOptions, FuseSearchOptions};
+    /// use serde_json::json;
+    ///
+    /// let fuse = Fuse::new(&[json!("apple"), json!("banana")], &FuseOptions::default(), None);
+    ///
+    /// let results = fuse.search_results("banana", &FuseSearchOptions::default()).unwrap();
+    /// assert_eq!(fuse.get(results[0].ref_index).as_deref(), Some(&json!("banana")));
+    /// assert_eq!(fuse.len(), 2);
+    /// ```
+    pub fn get(&self, idx: usize) -> Option<Cow<'_, Value>> {
+        self.docs.get(idx)
+    }
+
+    /// Iterates over the searched documents, in the order of their
+    /// `ref_index`.
+    ///
+    /// Documents held in memory are borrowed, not copied.
+    pub fn docs(&self) -> impl Iterator<Item = Cow<'_, Value>> + '_ {
+        self.docs.iter().map(|(_, doc)| doc)
+    }
+
+    /// Returns the index of the documents.
+    ///
+    /// Its keys and records can be stored and turned back into an index with
+    /// [`FuseIndex::parse_index`], which [`Fuse::try_new`] accepts without
+    /// indexing the documents again, as with Fuse.js' `createIndex` and
+    /// `parseIndex`.
+    pub fn get_index(&self) -> &FuseIndex<'a> {
+        &self.index
+    }
+
+    /// Returns the number of searches answered from the result cache since
+    /// the instance was created.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fuse_rs::{Fuse, FuseOptions};
+    /// use serde_json::json;
+    ///
+    /// let options = FuseOptions { result_cache_size: 8, ..Default::default() };
+    /// let fuse = Fuse::new(&[json!("apple")], &options, None);
+    ///
+    /// fuse.search("apple").unwrap();
+    /// fuse.search("apple").unwrap();
+    /// assert_eq!((fuse.result_cache_hits(), fuse.result_cache_misses()), (1, 1));
+    /// ```
+    pub fn result_cache_hits(&self) -> u64 {
+        self.result_cache.hits()
+    }
+
+    /// Returns the number of cacheable searches that were not found in the
+    /// result cache since the instance was created.
+    pub fn result_cache_misses(&self) -> u64 {
+        self.result_cache.misses()
+    }
+
+    /// Replaces the searched documents, keeping the options and keys.
+    ///
+    /// The documents are indexed unless `index` is given, like in
+    /// [`Fuse::new`]. Long-lived instances can be refreshed this way when
+    /// the dataset changes.
+    ///
+    /// # Returns
+    ///
+    /// `FuseError::IncorrectIndexType` if `index` was built with other keys
+    /// than those of the options, in which case the instance is unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fuse_rs::{Fuse, FuseOptions};
+    /// use serde_json::json;
+    ///
+    /// let mut fuse = Fuse::new(&[json!("apple")], &FuseOptions::default(), None);
+    /// fuse.set_collection(&[json!("banana"), json!("cherry")], None).unwrap();
+    ///
+    /// assert!(fuse.search("apple").unwrap().is_empty());
+    /// assert_eq!(fuse.search("cherry").unwrap()[0].item, json!("cherry"));
+    /// ```
+    pub fn set_collection(&mut self, docs: &[Value], index: Option<FuseIndex<'a>>) -> Result<(), FuseError> {
+        check_keys(&self.options, index.as_ref())?;
+
+        self.index = index
+            .unwrap_or_else(|| FuseIndex::create_index_with_options(&self.options.keys, docs, &self.options));
+        self.index.set_ngram_index(self.options.use_ngram_index);
+        self.key_options = analyzed_key_options(&self.options, &self.index);
+        self.searchable_keys = searchable_keys(&self.key_store, &self.index);
+        self.key_weights = key_weights(&self.key_store, &self.index);
+        self.docs = Box::new(MemoryDocStore::new(docs.to_vec()));
+        self.collection_changed();
+        Ok(())
+    }
+
+    /// Adds a document at the end of the collection and indexes it.
+    ///
+    /// `null` documents are ignored, as in Fuse.js.
+    ///
+    /// # Returns
+    ///
+    /// `FuseError::ReadOnlyStore` if the store does not support adding
+    /// documents.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fuse_rs::{Fuse, FuseOptions};
+    /// use serde_json::json;
+    ///
+    /// let mut fuse = Fuse::new(&[json!("apple")], &FuseOptions::default(), None);
+    /// fuse.add(json!("banana")).unwrap();
+    ///
+    /// assert_eq!(fuse.search("banana").unwrap()[0].item, json!("banana"));
+    /// ```
+    pub fn add(&mut self, doc: Value) -> Result<(), FuseError> {
+        if doc.is_null() {
+            return Ok(());
+        }
+
+        let idx = self.docs.len();
+        self.index.add_at(&doc, idx);
+        if let Err(e) = self.docs.push(doc) {
+            self.index.remove_at(idx);
+            return Err(e);
+        }
+
+        self.collection_changed();
+        Ok(())
+    }
+
+    /// Adds several documents at the end of the collection, like
+    /// [`Fuse::add`] for each of them but indexing them in one batch.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())`, or `FuseError::ReadOnlyStore` if the store does not support
+    /// adding documents; the documents it rejected are not indexed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fuse_rs::{Fuse, FuseOptions};
+    /// use serde_json::json;
+    ///
+    /// let mut fuse = Fuse::new(&[], &FuseOptions::default(), None);
+    /// fuse.add_all(vec![json!("apple"), json!("banana")]).unwrap();
+    ///
+    /// assert_eq!(fuse.search("banana").unwrap()[0].item, json!("banana"));
+    /// ```
+    pub fn add_all(&mut self, mut docs: Vec<Value>) -> Result<(), FuseError> {
+        docs.retain(|doc| !doc.is_null());
+        if docs.is_empty() {
+            return Ok(());
+        }
+
+        let first = self.docs.len();
+        self.index.add_all_at(&docs, first);
+        self.collection_changed();
+
+        let count = docs.len();
+        for (added, doc) in docs.into_iter().enumerate() {
+            if let Err(e) = self.docs.push(doc) {
+                for idx in (first + added..first + count).rev() {
+                    self.index.remove_at(idx);
+                }
+                return Err(e);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Removes the documents for which `predicate` returns `true`.
+    ///
+    /// The predicate receives each document with its position in the
+    /// collection before any removal.
+    ///
+    /// # Returns
+    ///
+    /// The removed documents, in collection order, or
+    /// `FuseError::ReadOnlyStore` if the store does not support removing
+    /// documents.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fuse_rs::{Fuse, FuseOptions};
+    /// use serde_json::json;
+    ///
+    /// let docs = vec![json!("apple"), json!("banana"), json!("apricot")];
+    /// let mut fuse = Fuse::new(&docs, &FuseOptions::default(), None);
+    ///
+    /// let removed = fuse.remove(|doc, _| doc.as_str().unwrap().starts_with('a')).unwrap();
+    /// assert_eq!(removed, vec![json!("apple"), json!("apricot")]);
+    /// assert_eq!(fuse.store().len(), 1);
+    /// ```
+    pub fn remove<P: FnMut(&Value, usize) -> bool>(&mut self, mut predicate: P) -> Result<Vec<Value>, FuseError> {
+        let ids: Vec<usize> = self
+            .docs
+            .iter()
+            .filter(|(id, doc)| predicate(doc, *id))
+            .map(|(id, _)| id)
+            .collect();
+
+        // Remove from the end so the ids of the remaining matches stay valid
+        let mut removed = Vec::with_capacity(ids.len());
+        for id in ids.into_iter().rev() {
+            removed.extend(self.remove_at(id)?);
+        }
+
+        removed.reverse();
+        Ok(removed)
+    }
+
+    /// Removes the document at position `idx` of the collection; the
+    /// following documents move down by one.
+    ///
+    /// # Returns
+    ///
+    /// The removed document, `None` if there is no document at `idx`, or
+    /// `FuseError::ReadOnlyStore` if the store does not support removing
+    /// documents.
+    pub fn remove_at(&mut self, idx: usize) -> Result<Option<Value>, FuseError> {
+        let removed = self.docs.remove(idx)?;
+        if removed.is_some() {
+            self.index.remove_at(idx);
+            self.collection_changed();
+        }
+
+        Ok(removed)
+    }
+
     /// Searches the data using the provided search term.
     ///
     /// # Arguments
@@ -69,57 +496,1866 @@ impl<'a> Fuse<'a> {
     ///
     /// # Returns
     ///
+    /// A `Result` containing a `FuseResult` per matching item, sorted by
+    /// relevance, with its index in the collection and, depending on the
+    /// options, its score and matches, or an error if the search cannot be
+    /// performed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fuse_rs::{Fuse, FuseOptions};
+    /// use serde_json::json;
+    ///
+    /// let docs = vec![json!("apple"), json!("banana")];
+    /// let options = FuseOptions { include_score: true, ..Default::default() };
+    /// let fuse = Fuse::new(&docs, &options, None);
+    ///
+    /// let results = fuse.search("banana").unwrap();
+    /// assert_eq!(results[0].item, json!("banana"));
+    /// assert_eq!(results[0].ref_index, 1);
+    /// assert!(results[0].score.is_some());
+    /// ```
+    pub fn search(&self, term: &str) -> Result<Vec<FuseResult<Value>>, FuseError> {
+        self.search_results(term, &FuseSearchOptions::default())
+    }
+
+    /// Searches the data like [`Fuse::search`], returning the matching items
+    /// alone, as `Fuse::search` did before it returned `FuseResult`s.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing a vector of matching JSON values sorted by relevance,
+    /// or an error if the search cannot be performed.
+    #[deprecated(since = "0.1.0", note = "Use Fuse::search and read the item of each result instead")]
+    pub fn search_items(&self, term: &str) -> Result<Vec<Value>, FuseError> {
+        self.search_with_options(term, &FuseSearchOptions::default())
+    }
+
+    /// Searches the data using the provided search term and per-call options.
+    ///
+    /// Documents rejected by `search_options.filter` are skipped before any
+    /// of their values are matched, results scoring above
+    /// `search_options.max_score` are dropped, and at most
+    /// `search_options.limit` results are returned, after skipping the first
+    /// `search_options.offset`.
+    ///
+    /// # Arguments
+    ///
+    /// * `term` - The search pattern to look for
+    /// * `search_options` - Options that only apply to this search
+    ///
+    /// # Returns
+    ///
     /// A `Result` containing a vector of matching JSON values sorted by relevance,
     /// or an error if the search cannot be performed.
-    pub fn search(&self, term: &str) -> Result<Vec<Value>, FuseError> {
-        // Check if extended search is requested but unavailable
-        if self.options.use_extended_search {
-            // Implementation of extended search is marked as unavailable in this example
-            return Err(FuseError::ExtendedSearchUnavailable);
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fuse_rs::{Fuse, FuseOptions, FuseSearchOptions};
+    /// use serde_json::{json, Value};
+    /// use std::sync::Arc;
+    ///
+    /// let docs = vec![json!("public note"), json!("private note")];
+    /// let fuse = Fuse::new(&docs, &FuseOptions::default(), None);
+    ///
+    /// let search_options = FuseSearchOptions {
+    ///     filter: Some(Arc::new(|doc: &Value| !doc.as_str().unwrap().starts_with("private"))),
+    ///     ..Default::default()
+    /// };
+    ///
+    /// let results = fuse.search_with_options("note", &search_options).unwrap();
+    /// assert_eq!(results, vec![json!("public note")]);
+    /// ```
+    pub fn search_with_options(
+        &self,
+        term: &str,
+        search_options: &FuseSearchOptions,
+    ) -> Result<Vec<Value>, FuseError> {
+        let results = self.search_results(term, search_options)?;
+        Ok(results.into_iter().map(|result| result.item).collect())
+    }
+
+    /// Searches the data like [`Fuse::search_with_options`], returning the
+    /// full results.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing a `FuseResult` per matching item, with its index
+    /// in the collection and, depending on the options, its score and matches.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fuse_rs::{Fuse, FuseOptions, FuseSearchOptions};
+    /// use serde_json::json;
+    ///
+    /// let docs = vec![json!("apple"), json!("banana")];
+    /// let fuse = Fuse::new(&docs, &FuseOptions::default(), None);
+    ///
+    /// let results = fuse.search_results("banana", &FuseSearchOptions::default()).unwrap();
+    /// assert_eq!(results[0].ref_index, 1);
+    /// ```
+    pub fn search_results(
+        &self,
+        term: &str,
+        search_options: &FuseSearchOptions,
+    ) -> Result<Vec<FuseResult<Value>>, FuseError> {
+        Ok(self.search_page(term, search_options)?.results)
+    }
+
+    /// Searches the data like [`Fuse::search_results`], only matching the
+    /// values of `keys`, by id (e.g. `"author.name"`).
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the results, `FuseError::UnknownKey` if one of
+    /// `keys` is not searched, or an error if the term cannot be searched.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fuse_rs::{Fuse, FuseOptions};
+    /// use serde_json::json;
+    ///
+    /// let docs = vec![
+    ///     json!({"title": "Old Man's War", "author": {"name": "John Scalzi"}}),
+    ///     json!({"title": "The Man in the High Castle", "author": {"name": "Philip K. Dick"}}),
+    /// ];
+    /// let options = FuseOptions { keys: vec!["title".into(), "author.name".into()], ..Default::default() };
+    /// let fuse = Fuse::new(&docs, &options, None);
+    ///
+    /// assert_eq!(fuse.search_in_keys("dick", &["title"]).unwrap().len(), 0);
+    /// assert_eq!(fuse.search_in_keys("dick", &["author.name"]).unwrap()[0].ref_index, 1);
+    /// ```
+    pub fn search_in_keys(&self, term: &str, keys: &[&str]) -> Result<Vec<FuseResult<Value>>, FuseError> {
+        let search_options = FuseSearchOptions {
+            keys: Some(keys.iter().map(|key| key.to_string()).collect()),
+            ..Default::default()
+        };
+
+        self.search_results(term, &search_options)
+    }
+
+    /// Searches the data like [`Fuse::search_results`], also counting every
+    /// matching item, so that results can be shown a page at a time with
+    /// `search_options.offset` and `search_options.limit`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the requested page of results and the total
+    /// number of matching items.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fuse_rs::{Fuse, FuseOptions, FuseSearchOptions};
+    /// use serde_json::json;
+    ///
+    /// let docs: Vec<_> = (1..=25).map(|i| json!(format!("chapter {}", i))).collect();
+    /// let fuse = Fuse::new(&docs, &FuseOptions::default(), None);
+    ///
+    /// let search_options = FuseSearchOptions { offset: 20, limit: 10, ..Default::default() };
+    /// let page = fuse.search_page("chapter", &search_options).unwrap();
+    /// assert_eq!((page.results.len(), page.total), (5, 25));
+    /// ```
+    pub fn search_page(&self, term: &str, search_options: &FuseSearchOptions) -> Result<SearchPage<Value>, FuseError> {
+        self.validate_term(term)?;
+
+        let started = self.options.observer.is_some().then(Instant::now);
+        let key = self.result_cache.key(term, search_options);
+        if let Some(page) = key.as_ref().and_then(|key| self.result_cache.get(key)) {
+            if let (Some(observer), Some(start)) = (&self.options.observer, started) {
+                observer.on_search(&SearchMetrics {
+                    results: page.results.len(),
+                    elapsed: start.elapsed(),
+                    cached: true,
+                    ..Default::default()
+                });
+            }
+            return Ok(page);
+        }
+
+        let page = self.search_uncached(term, search_options)?;
+        if let Some(key) = key {
+            self.result_cache.insert(key, page.clone());
         }
 
-        // Check pattern length against maximum allowed (if specified)
-        if let Some(max_length) = self.options.max_pattern_length {
-            if term.len() > max_length {
-                return Err(FuseError::PatternLengthTooLarge(max_length));
+        Ok(page)
+    }
+
+    /// Searches like [`Fuse::search_page`], without using the result cache.
+    fn search_uncached(&self, term: &str, search_options: &FuseSearchOptions) -> Result<SearchPage<Value>, FuseError> {
+        trace::enter_span!("fuse.search", pattern_chars = char_len(term), limit = search_options.limit);
+
+        let started = self.options.observer.is_some().then(|| (Instant::now(), self.interner_stats()));
+        let mut metrics = SearchMetrics::default();
+
+        let searcher = self.searcher(term);
+        let end = search_options.offset.saturating_add(search_options.limit);
+        let mut total = 0;
+
+        // When the page ends before the number of records, only the best
+        // `end` results are kept while scanning instead of sorting every match
+        let mut results = if self.options.should_sort && end < self.index.records().len() {
+            let mut top = TopK::new(end, self.options.sort_fn);
+            self.match_records(&searcher, search_options, &mut metrics, |result| {
+                if search_options.keeps_score(result.score) {
+                    total += 1;
+                    top.push(self.sort_arg(&result), result);
+                }
+            })?;
+            top.into_sorted_vec()
+        } else {
+            let mut results = Vec::new();
+            self.match_records(&searcher, search_options, &mut metrics, |result| results.push(result))?;
+
+            trace::enter_span!("fuse.rank", matches = results.len());
+            results.retain(|result| search_options.keeps_score(result.score));
+            total = results.len();
+
+            if self.options.should_sort {
+                self.sort_results(&mut results);
             }
+            results
+        };
+
+        trace::debug!(candidates = self.index.records().len(), matches = metrics.matches, "search matched");
+        results.truncate(end);
+        results.drain(..search_options.offset.min(results.len()));
+
+        trace::enter_span!("fuse.format", results = results.len());
+        let results = self.format(results);
+
+        if let (Some(observer), Some((start, (hits, misses)))) = (&self.options.observer, started) {
+            let (hits_after, misses_after) = self.interner_stats();
+            metrics.results = results.len();
+            metrics.elapsed = start.elapsed();
+            metrics.interner_hits = hits_after - hits;
+            metrics.interner_misses = misses_after - misses;
+            observer.on_search(&metrics);
         }
 
-        // TODO: Implement actual fuzzy search logic
-        // Currently returns an empty vector as a placeholder
-        Ok(vec![])
+        Ok(SearchPage { results, total })
     }
 
-    /// Performs a logical search with multiple conditions.
+    /// Saves the options, documents and index of this instance to `writer`.
     ///
-    /// # Arguments
+    /// The snapshot can be restored with [`Fuse::load`] without re-indexing
+    /// the documents. Options that are functions or trait objects (such as
+    /// `get_fn`, `sort_fn` and `tokenizer`) are not saved.
     ///
-    /// * `query` - A map of field names to query values
+    /// # Example
     ///
-    /// # Returns
+    /// ```
+    /// use fuse_rs::{Fuse, FuseOptions};
+    /// use serde_json::json;
+    ///
+    /// let docs = vec![json!("apple"), json!("banana")];
+    /// let fuse = Fuse::new(&docs, &FuseOptions::default(), None);
+    ///
+    /// let mut snapshot = Vec::new();
+    /// fuse.save(&mut snapshot).unwrap();
+    ///
+    /// let loaded = Fuse::load(snapshot.as_slice()).unwrap();
+    /// assert_eq!(loaded.search("banana").unwrap()[0].item, json!("banana"));
+    /// ```
+    pub fn save<W: Write>(&self, writer: W) -> Result<(), FuseError> {
+        let snapshot = SnapshotRef {
+            version: SNAPSHOT_VERSION,
+            options: &self.options,
+            docs: self.docs.iter().map(|(_, doc)| doc).collect(),
+            records: &self.index.compacted_records(),
+        };
+
+        snapshot::write(writer, &snapshot)
+    }
+
+    //------------------------------------------------------------------
+    // Search pipeline
+    //------------------------------------------------------------------
+
+    /// Checks that `term` can be searched with the current options.
+    pub(crate) fn validate_term(&self, term: &str) -> Result<(), FuseError> {
+        // Check pattern length (in characters) against maximum allowed (if specified)
+        if let Some(max_length) = self.options.max_pattern_length
+            && char_len(term) > max_length
+        {
+            return Err(FuseError::PatternLengthTooLarge(max_length));
+        }
+
+        Ok(())
+    }
+
+    /// Creates the searcher used to match `term` against indexed values.
+    pub(crate) fn searcher(&self, term: &str) -> Searcher<'_, 'a> {
+        let searcher = Searcher::new(term, &self.options);
+        if self.key_options.is_empty() {
+            return searcher;
+        }
+
+        let keys = self
+            .key_options
+            .iter()
+            .map(|options| options.as_ref().map(|options| Searcher::new(term, options)))
+            .collect();
+        Searcher::Keyed { default: Box::new(searcher), keys }
+    }
+
+    /// Returns the position of the key `key_id` in the index, or `None` if
+    /// it is not a search key.
+    pub(crate) fn key_index(&self, key_id: &str) -> Option<usize> {
+        let key = self.key_store.get(key_id)?;
+        self.index.keys().iter().position(|k| k.id == key.id)
+    }
+
+    /// Returns which keys `search_options` restricts the search to, by their
+    /// position in the index, or `None` if every key is searched.
+    pub(crate) fn searched_keys(&self, search_options: &FuseSearchOptions) -> Result<Option<Vec<bool>>, FuseError> {
+        let Some(keys) = &search_options.keys else {
+            return Ok(None);
+        };
+
+        let mut searched = vec![false; self.index.keys().len()];
+        for key_id in keys {
+            let key_index = self.key_index(key_id).ok_or_else(|| FuseError::UnknownKey(key_id.clone()))?;
+            searched[key_index] = true;
+        }
+
+        Ok(Some(searched))
+    }
+
+    /// Returns whether searches that don't name any key match the values of
+    /// the key at `key_index`.
+    pub(crate) fn is_searchable(&self, key_index: usize) -> bool {
+        self.searchable_keys.as_ref().is_none_or(|searchable| searchable[key_index])
+    }
+
+    /// Returns whether results should be sorted by relevance.
+    pub(crate) fn should_sort(&self) -> bool {
+        self.options.should_sort
+    }
+
+    /// Drops what was computed from the previous collection.
+    fn collection_changed(&mut self) {
+        self.result_cache.clear();
+        self.token_trie = OnceLock::new();
+        self.generation += 1;
+    }
+
+    /// Returns the number of times the collection has changed.
+    pub(crate) fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Returns the trie of the words of the collection, building it if
+    /// needed.
+    pub(crate) fn token_trie(&self) -> &TokenTrie {
+        self.token_trie.get_or_init(|| self.build_token_trie())
+    }
+
+    /// Returns the cache hits and misses of the interner so far.
+    fn interner_stats(&self) -> (u64, u64) {
+        self.options
+            .interner
+            .as_ref()
+            .map_or((0, 0), |interner| (interner.cache_hits(), interner.cache_misses()))
+    }
+
+    /// Matches every record that passes the filter of `search_options`,
+    /// passing each scored result to `on_match` and counting the records
+    /// scanned and matched in `metrics`.
+    ///
+    /// The progress hooks of `search_options` are called along the way; the
+    /// scan ends early if they ask to stop.
+    fn match_records(
+        &self,
+        searcher: &Searcher,
+        search_options: &FuseSearchOptions,
+        metrics: &mut SearchMetrics,
+        mut on_match: impl FnMut(ScoredRecord),
+    ) -> Result<(), FuseError> {
+        trace::enter_span!("fuse.match", records = self.index.records().len());
+        let records = self.index.records();
+        let progress = search_options.progress.as_deref();
+        let searched_keys = self.searched_keys(search_options)?;
+        let candidates = self.candidates(searcher);
+
+        for (position, record) in records.iter().enumerate() {
+            metrics.records_scanned += 1;
+
+            let is_candidate = candidates.as_ref().is_none_or(|candidates| candidates[position]);
+            if is_candidate && self.passes_filter(record, search_options) {
+                metrics.candidates += 1;
+
+                if let Some(mut result) = self.search_record_in(searcher, record, searched_keys.as_deref())? {
+                    self.score_results(std::slice::from_mut(&mut result));
+                    metrics.matches += 1;
+
+                    if let Some(progress) = progress {
+                        progress.on_match_found(result.idx, result.score);
+                    }
+                    on_match(result);
+                }
+            }
+
+            if let Some(progress) = progress
+                && progress.on_record_scored(position + 1, records.len()).is_break()
+            {
+                trace::debug!(scanned = position + 1, "search stopped");
+                break;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns which records can match `searcher`, by position in the
+    /// records, or `None` if every record has to be scored.
     ///
-    /// A `Result` containing matching JSON values or an error
-    pub fn logical_search(&self, query: &std::collections::HashMap<String, Value>) -> Result<Vec<Value>, FuseError> {
-        // Check if logical search is supported
-        // For this example, let's assume it's not implemented yet
-        if true {
-            return Err(FuseError::LogicalSearchUnavailable);
-        }
-        
-        // Validate query key values
-        for (key, value) in query {
-            // Check if the key exists in our key store
-            if !self.key_store.keys().iter().any(|k| k.id == *key) {
-                return Err(FuseError::InvalidLogicalQueryForKey(key.clone()));
-            }
-            
-            // Additional validation depending on the value type
-            if !value.is_string() && !value.is_array() && !value.is_object() {
-                return Err(FuseError::InvalidLogicalQueryForKey(key.clone()));
-            }
-        }
-        
-        // TODO: Implement actual logical search logic
-        Ok(vec![])
+    /// Records are pruned with the n-gram posting lists of the index, when
+    /// it has them and the searcher matches a single pattern normalized only
+    /// by case folding, as the lists are.
+    pub(crate) fn candidates(&self, searcher: &Searcher) -> Option<Vec<bool>> {
+        let Searcher::Bitmap(searcher) = searcher else {
+            return None;
+        };
+
+        let normalizer = self.options.normalizer();
+        if self.options.is_case_sensitive || !normalizer.is_case_folding_only() {
+            return None;
+        }
+
+        // The lists count characters rather than clusters
+        #[cfg(feature = "grapheme-clusters")]
+        if self.options.use_grapheme_clusters {
+            return None;
+        }
+
+        let pattern = searcher.pattern();
+        let len = char_len(pattern);
+        if len > MAX_BITS {
+            return None;
+        }
+
+        // Matches with more errors score above the threshold
+        let mut max_errors = (self.options.threshold * len as f64 + 1e-9).floor().max(0.0) as usize;
+
+        // A transposition is one edit, but changes as many n-grams as two
+        if self.options.algorithm == MatchAlgorithm::DamerauLevenshtein {
+            max_errors *= 2;
+        }
+        let candidates = self.index.ngram_candidates(pattern, max_errors);
+        trace::debug!(
+            pruned = candidates.as_ref().map_or(0, |c| c.iter().filter(|&&c| !c).count()),
+            "records pruned by n-grams"
+        );
+        candidates
+    }
+
+    /// Returns `true` if the document of `record` passes the filter of
+    /// `search_options`, or if there is no filter.
+    pub(crate) fn passes_filter(&self, record: &FuseIndexRecord, search_options: &FuseSearchOptions) -> bool {
+        search_options
+            .filter
+            .as_ref()
+            .is_none_or(|filter| {
+                self.index
+                    .position(record)
+                    .and_then(|position| self.docs.get(position))
+                    .is_some_and(|doc| filter(&doc))
+            })
+    }
+
+    /// Matches a single index record against the searcher.
+    ///
+    /// # Returns
+    ///
+    /// `Some(ScoredRecord)` with an unscored result if any value of the record
+    /// matched, `None` otherwise.
+    pub(crate) fn search_record(
+        &self,
+        searcher: &Searcher,
+        record: &FuseIndexRecord,
+    ) -> Result<Option<ScoredRecord>, FuseError> {
+        self.search_record_in(searcher, record, None)
+    }
+
+    /// Matches a single index record against the searcher, like
+    /// [`Fuse::search_record`], only in the keys flagged in `searched_keys`
+    /// (as returned by [`Fuse::searched_keys`]) if given, or else in the
+    /// searchable keys.
+    pub(crate) fn search_record_in(
+        &self,
+        searcher: &Searcher,
+        record: &FuseIndexRecord,
+        searched_keys: Option<&[bool]>,
+    ) -> Result<Option<ScoredRecord>, FuseError> {
+        // Records of removed documents stay in the index until it is compacted
+        let Some(position) = self.index.position(record) else {
+            return Ok(None);
+        };
+        let mut matches = Vec::new();
+
+        match record {
+            // Strings have no key, so no key subset can include them
+            FuseIndexRecord::String(_) if searched_keys.is_some() => {}
+            FuseIndexRecord::String(r) => {
+                let result = searcher.search_in(&r.v)?;
+                if result.is_match {
+                    matches.push(ScoredMatch {
+                        score: result.score,
+                        key: None,
+                        value: r.v.clone(),
+                        idx: None,
+                        norm: r.n,
+                        indices: result.indices,
+                    });
+                }
+            }
+            FuseIndexRecord::Object(r) => {
+                for key_index in 0..self.index.keys().len() {
+                    let searched = match searched_keys {
+                        Some(searched) => searched[key_index],
+                        None => self.is_searchable(key_index),
+                    };
+                    if !searched {
+                        continue;
+                    }
+
+                    if let Some(value) = r.entries.get(&key_index.to_string()) {
+                        self.find_matches(searcher, key_index, value, &mut matches)?;
+                    }
+                }
+            }
+        }
+
+        if matches.is_empty() {
+            return Ok(None);
+        }
+
+        Ok(Some(ScoredRecord {
+            idx: position,
+            score: 1.0,
+            matches,
+        }))
+    }
+
+    /// Collects the matches of a single record entry.
+    pub(crate) fn find_matches(
+        &self,
+        searcher: &Searcher,
+        key_index: usize,
+        value: &RecordEntryValue,
+        matches: &mut Vec<ScoredMatch>,
+    ) -> Result<(), FuseError> {
+        let values: &[IndexValue] = match value {
+            RecordEntryValue::Single(v) => std::slice::from_ref(v),
+            RecordEntryValue::Array(arr) => arr,
+        };
+
+        let searcher = searcher.for_key(key_index);
+        for v in values {
+            let result = searcher.search_in(&v.v)?;
+            if result.is_match {
+                matches.push(ScoredMatch {
+                    score: result.score,
+                    key: Some(key_index),
+                    value: v.v.clone(),
+                    idx: v.i,
+                    norm: v.n,
+                    indices: result.indices,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Scores results that were collected by `search_record`.
+    pub(crate) fn score_results(&self, results: &mut [ScoredRecord]) {
+        compute_score(
+            results,
+            self.index.keys(),
+            &self.key_weights,
+            self.options.ignore_field_norm,
+            self.options.scorer.as_ref(),
+        );
+    }
+
+    /// Returns the weights matches are scored with, by key position.
+    pub(crate) fn key_weights(&self) -> &[f64] {
+        &self.key_weights
+    }
+
+    /// Sorts results with the configured sort function.
+    pub(crate) fn sort_results(&self, results: &mut Vec<ScoredRecord>) {
+        let args: Vec<FuseSortFunctionArg> = results.iter().map(|r| self.sort_arg(r)).collect();
+        let sort_fn = self.options.sort_fn;
+
+        let mut order: Vec<usize> = (0..results.len()).collect();
+        order.sort_by(|&a, &b| sort_fn(&args[a], &args[b]).cmp(&0));
+
+        let mut slots: Vec<Option<ScoredRecord>> = results.drain(..).map(Some).collect();
+        results.extend(order.into_iter().filter_map(|i| slots[i].take()));
+    }
+
+    /// Builds the argument passed to the sort function for a result.
+    fn sort_arg(&self, result: &ScoredRecord) -> FuseSortFunctionArg {
+        let mut fields = HashMap::new();
+
+        if let Some(FuseIndexRecord::Object(record)) = self.index.record_at(result.idx) {
+            for (key, entry) in &record.entries {
+                let field = match entry {
+                    RecordEntryValue::Single(v) => FuseSortItemField::Single(sort_item_value(v)),
+                    RecordEntryValue::Array(arr) => {
+                        FuseSortItemField::Array(arr.iter().map(sort_item_value).collect())
+                    }
+                };
+                fields.insert(key.clone(), field);
+            }
+        }
+
+        let matches = result
+            .matches
+            .iter()
+            .map(|m| {
+                let key = self.key_src(m).unwrap_or_default();
+                let indices = m.indices.iter().map(|&(start, end)| vec![start, end]).collect();
+
+                match m.idx {
+                    Some(idx) => FuseSortFunctionMatchType::List(FuseSortFunctionMatchList {
+                        score: m.score,
+                        key,
+                        value: m.value.clone(),
+                        idx,
+                        indices,
+                    }),
+                    None => FuseSortFunctionMatchType::Simple(FuseSortFunctionMatch {
+                        score: m.score,
+                        key,
+                        value: m.value.clone(),
+                        indices,
+                    }),
+                }
+            })
+            .collect();
+
+        FuseSortFunctionArg {
+            idx: result.idx,
+            item: FuseSortFunctionItem { fields },
+            score: result.score,
+            matches: Some(matches),
+        }
+    }
+
+    /// Converts scored records into public results.
+    ///
+    /// Results whose document is missing from the store are dropped.
+    pub(crate) fn format(&self, results: Vec<ScoredRecord>) -> Vec<FuseResult<Value>> {
+        results
+            .into_iter()
+            .filter_map(|result| {
+                let item = self.docs.get(result.idx)?.into_owned();
+
+                let matches = self.options.include_matches.then(|| {
+                    result
+                        .matches
+                        .iter()
+                        .filter(|m| !m.indices.is_empty())
+                        .map(|m| self.format_match(m))
+                        .collect()
+                });
+
+                let scores_by_key = if self.options.include_score {
+                    self.scores_by_key(&result)
+                } else {
+                    None
+                };
+
+                Some(FuseResult {
+                    item,
+                    ref_index: result.idx,
+                    score: self.options.include_score.then_some(result.score),
+                    matches,
+                    scores_by_key,
+                })
+            })
+            .collect()
+    }
+
+    /// Converts a scored match into a public match, projecting its indices
+    /// onto the original value when normalization changed it.
+    fn format_match(&self, m: &ScoredMatch) -> FuseResultMatch {
+        let (normalized, offsets) = self.options.normalizer().normalize_with_offsets(&m.value);
+        let (normalized_value, original_indices) = if normalized == m.value {
+            (None, None)
+        } else {
+            let indices = Cow::Borrowed(m.indices.as_slice());
+            // Fuzzy matches count clusters, the offsets characters
+            #[cfg(feature = "grapheme-clusters")]
+            let indices = match self.options.use_grapheme_clusters && !self.options.use_extended_search {
+                true => Cow::Owned(cluster_to_char_ranges(&normalized, &indices)),
+                false => indices,
+            };
+            let original_indices = project_indices(&indices, &offsets);
+            (Some(normalized), Some(original_indices))
+        };
+
+        FuseResultMatch {
+            indices: m.indices.clone(),
+            key: self.key_src(m),
+            ref_index: m.idx,
+            value: Some(m.value.clone()),
+            normalized_value,
+            original_indices,
+        }
+    }
+
+    /// Collects the best match score of every key a result matched in.
+    fn scores_by_key(&self, result: &ScoredRecord) -> Option<HashMap<String, f64>> {
+        let mut scores: HashMap<String, f64> = HashMap::new();
+
+        for m in &result.matches {
+            if let Some(key) = self.key_src(m) {
+                scores
+                    .entry(key)
+                    .and_modify(|score| *score = score.min(m.score))
+                    .or_insert(m.score);
+            }
+        }
+
+        (!scores.is_empty()).then_some(scores)
+    }
+
+    /// Returns the source path of the key a match was found in.
+    pub(crate) fn key_src(&self, m: &ScoredMatch) -> Option<String> {
+        m.key
+            .and_then(|k| self.index.keys().get(k))
+            .map(|k| k.src.to_string())
+    }
+}
+
+impl Fuse<'static> {
+    /// Restores an instance saved with [`Fuse::save`].
+    ///
+    /// Options that were not saved take their default values.
+    ///
+    /// # Returns
+    ///
+    /// The restored instance, `FuseError::UnsupportedSnapshotVersion` if the
+    /// snapshot was written in another format, or `FuseError::InvalidSnapshot`
+    /// if it cannot be read.
+    pub fn load<R: Read>(reader: R) -> Result<Self, FuseError> {
+        let snapshot = snapshot::read(reader)?;
+        let options = FuseOptions::from_json(snapshot.options).map_err(|e| FuseError::InvalidSnapshot(e.to_string()))?;
+
+        let keys = options.keys.iter().map(create_key).collect::<Result<Vec<_>, _>>()?;
+        let mut index = FuseIndex::new(&options);
+        index.set_keys(keys);
+        index.set_index_records(snapshot.records);
+
+        let key_store = KeyStore::new(&options.keys);
+        Ok(Fuse {
+            result_cache: ResultCache::new(options.result_cache_size),
+            token_trie: OnceLock::new(),
+            generation: 0,
+            key_options: analyzed_key_options(&options, &index),
+            searchable_keys: searchable_keys(&key_store, &index),
+            key_weights: key_weights(&key_store, &index),
+            key_store,
+            options,
+            docs: Box::new(MemoryDocStore::new(snapshot.docs)),
+            index,
+        })
+    }
+}
+
+//----------------------------------------------------------------------
+// Free Functions
+//----------------------------------------------------------------------
+
+/// Indexes `docs` and searches them for `term`.
+///
+/// Invalid keys and patterns are reported as errors; no input makes this
+/// function panic, so it is the entry point for fuzzing the parsers and
+/// matchers (see the `arbitrary` feature). A panic is a bug.
+///
+/// # Example
+///
+/// ```
+/// use fuse_rs::{FuseError, FuseOptionKey, FuseOptionKeyName, FuseOptionKeyObject, FuseOptions, try_search};
+/// use serde_json::json;
+/// use std::borrow::Cow;
+///
+/// let key = FuseOptionKeyObject {
+///     name: Cow::Owned(FuseOptionKeyName::String("title".into())),
+///     weight: Some(f64::NAN),
+///     get_fn: None,
+///     fallback: None,
+///     analyzer: None,
+///     index: true,
+///     searchable: true,
+/// };
+/// let options = FuseOptions { keys: vec![FuseOptionKey::KeyObject(key)], ..Default::default() };
+///
+/// let result = try_search(&[json!({"title": "Dune"})], &options, "dune");
+/// assert!(matches!(result, Err(FuseError::InvalidKeyWeightValue(_))));
+/// ```
+pub fn try_search(docs: &[Value], options: &FuseOptions, term: &str) -> Result<Vec<FuseResult<Value>>, FuseError> {
+    Fuse::try_new(docs, options, None)?.search_results(term, &FuseSearchOptions::default())
+}
+
+//----------------------------------------------------------------------
+// Implementation Details
+//----------------------------------------------------------------------
+
+/// Returns the options matching the keys of `index` that have an analyzer,
+/// by key position, or nothing if no key has one.
+fn analyzed_key_options<'a>(options: &FuseOptions<'a>, index: &FuseIndex<'a>) -> Vec<Option<FuseOptions<'a>>> {
+    let analyzers: HashMap<String, _> = options
+        .keys
+        .iter()
+        .filter_map(|key| match key {
+            FuseOptionKey::KeyObject(obj) => Some((create_key(key).ok()?.id, obj.analyzer.as_ref()?)),
+            _ => None,
+        })
+        .collect();
+
+    if analyzers.is_empty() {
+        return Vec::new();
+    }
+
+    index
+        .keys()
+        .iter()
+        .map(|key| analyzers.get(&key.id).map(|analyzer| options.with_analyzer(analyzer)))
+        .collect()
+}
+
+/// Checks that the keys of `options` are valid and, if given, that `index`
+/// was built with them.
+fn check_keys(options: &FuseOptions, index: Option<&FuseIndex>) -> Result<(), FuseError> {
+    let mut ids = Vec::with_capacity(options.keys.len());
+    for key in &options.keys {
+        ids.push(create_key(key)?.id);
+    }
+
+    if let Some(index) = index
+        && !index.keys().iter().map(|key| &key.id).eq(ids.iter())
+    {
+        return Err(FuseError::IncorrectIndexType);
+    }
+
+    Ok(())
+}
+
+/// Returns which keys of `index` are searchable according to the keys of
+/// `key_store`, by key position, or `None` if all of them are.
+fn searchable_keys(key_store: &KeyStore, index: &FuseIndex) -> Option<Vec<bool>> {
+    let searchable: Vec<bool> = index
+        .keys()
+        .iter()
+        .map(|key| key_store.get(&key.id).is_none_or(|key| key.searchable))
+        .collect();
+
+    searchable.contains(&false).then_some(searchable)
+}
+
+/// Returns the weights of the keys of `index` as normalized by `key_store`,
+/// by key position. Keys missing from `key_store` keep their own weight.
+fn key_weights(key_store: &KeyStore, index: &FuseIndex) -> Vec<f64> {
+    index
+        .keys()
+        .iter()
+        .map(|key| key_store.get(&key.id).map_or(key.weight, |key| key.weight))
+        .collect()
+}
+
+/// Converts an indexed value to the representation used by sort functions.
+fn sort_item_value(value: &IndexValue) -> FuseSortItemValue {
+    FuseSortItemValue {
+        value: value.v.clone(),
+        idx: value.i,
+    }
+}
+
+//----------------------------------------------------------------------
+// Tests
+//----------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::options::keys::FuseOptionKey;
+    use serde_json::json;
+    use std::borrow::Cow;
+    use std::sync::Arc;
+
+    fn books() -> Vec<Value> {
+        vec![
+            json!({"title": "Old Man's War", "author": "John Scalzi"}),
+            json!({"title": "The Lock Artist", "author": "Steve Hamilton"}),
+            json!({"title": "HTML5", "author": "Remy Sharp"}),
+        ]
+    }
+
+    fn book_options() -> FuseOptions<'static> {
+        FuseOptions {
+            keys: vec![
+                FuseOptionKey::String("title".into()),
+                FuseOptionKey::String("author".into()),
+            ],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_search_object_list() {
+        let docs = books();
+        let fuse = Fuse::new(&docs, &book_options(), None);
+
+        let results = fuse.search("old man").unwrap();
+        assert_eq!(results[0].item, docs[0]);
+        assert_eq!(results[0].ref_index, 0);
+    }
+
+    #[test]
+    fn test_search_string_list() {
+        let docs = vec![json!("Apple"), json!("Orange"), json!("Banana")];
+        let fuse = Fuse::new(&docs, &FuseOptions::default(), None);
+
+        let results = fuse.search("ornge").unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!((&results[0].item, results[0].ref_index), (&json!("Orange"), 1));
+    }
+
+    #[test]
+    fn test_search_string_list_fuse_js_example() {
+        // The string list example of the Fuse.js documentation
+        let docs = vec![json!("Old Man's War"), json!("The Lock Artist")];
+        let options = FuseOptions { include_score: true, include_matches: true, ..Default::default() };
+        let fuse = Fuse::new(&docs, &options, None);
+
+        let results = fuse.search_results("od man", &FuseSearchOptions::default()).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!((results[0].ref_index, &results[0].item), (0, &docs[0]));
+
+        let matches = results[0].matches.as_ref().unwrap();
+        assert_eq!((matches[0].key.as_deref(), matches[0].ref_index), (None, None));
+        assert_eq!(matches[0].value.as_deref(), Some("Old Man's War"));
+    }
+
+    #[test]
+    fn test_search_no_results() {
+        let docs = books();
+        let options = FuseOptions {
+            threshold: 0.1,
+            ..book_options()
+        };
+        let fuse = Fuse::new(&docs, &options, None);
+
+        assert!(fuse.search("zzzzzz").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_search_with_filter() {
+        let docs = books();
+        let fuse = Fuse::new(&docs, &book_options(), None);
+
+        let search_options = FuseSearchOptions {
+            filter: Some(Arc::new(|doc: &Value| doc["author"] != "John Scalzi")),
+            ..Default::default()
+        };
+
+        let results = fuse.search_with_options("old man", &search_options).unwrap();
+        assert!(!results.contains(&docs[0]));
+    }
+
+    #[test]
+    fn test_search_with_limit() {
+        let docs = vec![json!("apple"), json!("apple pie"), json!("pineapple")];
+        let fuse = Fuse::new(&docs, &FuseOptions::default(), None);
+
+        let search_options = FuseSearchOptions {
+            limit: 1,
+            ..Default::default()
+        };
+
+        let results = fuse.search_with_options("apple", &search_options).unwrap();
+        assert_eq!(results, vec![json!("apple")]);
+    }
+
+    #[test]
+    fn test_limit_per_call() {
+        let docs = vec![json!("apple"), json!("apple pie"), json!("pineapple")];
+        let options = FuseOptions { result_cache_size: 4, ..Default::default() };
+        let fuse = Fuse::new(&docs, &options, None);
+        let limit = |limit| FuseSearchOptions { limit, ..Default::default() };
+
+        assert_eq!(fuse.search_with_options("apple", &limit(1)).unwrap(), vec![json!("apple")]);
+        assert_eq!(fuse.search_with_options("apple", &limit(2)).unwrap().len(), 2);
+
+        // The limit applies to its call only, not to the instance or the
+        // results it caches
+        assert_eq!(fuse.search("apple").unwrap().len(), 3);
+        assert_eq!(fuse.search_with_options("apple", &limit(1)).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_search_page() {
+        let docs: Vec<Value> = ["apple", "apple pie", "pineapple", "maple", "apples", "grape"].map(Value::from).to_vec();
+
+        for should_sort in [true, false] {
+            let fuse = Fuse::new(&docs, &FuseOptions { should_sort, ..Default::default() }, None);
+            let all = fuse.search_results("apple", &Default::default()).unwrap();
+            let all: Vec<usize> = all.iter().map(|r| r.ref_index).collect();
+
+            for offset in 0..=all.len() + 1 {
+                let search_options = FuseSearchOptions { offset, limit: 2, ..Default::default() };
+                let page = fuse.search_page("apple", &search_options).unwrap();
+
+                assert_eq!(page.total, all.len());
+                let expected = &all[offset.min(all.len())..(offset + 2).min(all.len())];
+                assert_eq!(page.results.iter().map(|r| r.ref_index).collect::<Vec<_>>(), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_search_in_keys() {
+        let docs = books();
+        let fuse = Fuse::new(&docs, &book_options(), None);
+
+        let by_title = fuse.search_in_keys("hamilton", &["title"]).unwrap();
+        assert!(by_title.is_empty());
+        let by_author = fuse.search_in_keys("hamilton", &["author"]).unwrap();
+        assert_eq!(by_author.iter().map(|result| result.ref_index).collect::<Vec<_>>(), vec![1]);
+
+        assert!(matches!(fuse.search_in_keys("hamilton", &["isbn"]), Err(FuseError::UnknownKey(key)) if key == "isbn"));
+    }
+
+    #[test]
+    fn test_search_with_max_score() {
+        let docs = vec![json!("apple"), json!("apple pie"), json!("pineapple"), json!("maple")];
+        let fuse = Fuse::new(&docs, &FuseOptions { include_score: true, ..Default::default() }, None);
+
+        let all = fuse.search_results("apple", &FuseSearchOptions::default()).unwrap();
+        let cutoff = all[1].score.unwrap();
+        assert!(all.len() > 2 && all[2].score.unwrap() > cutoff);
+
+        for limit in [usize::MAX, 3] {
+            let search_options = FuseSearchOptions { limit, max_score: Some(cutoff), ..Default::default() };
+            let results = fuse.search_results("apple", &search_options).unwrap();
+            assert_eq!(results.iter().map(|r| r.ref_index).collect::<Vec<_>>(), vec![all[0].ref_index, all[1].ref_index]);
+        }
+    }
+
+    #[test]
+    fn test_concurrent_searches() {
+        let docs: Vec<Value> = (0..100)
+            .map(|i| json!({"title": format!("Book {} of the series", i), "author": format!("Author {}", i % 7)}))
+            .collect();
+        let options = FuseOptions {
+            include_score: true,
+            interner: Some(std::sync::Arc::new(crate::Interner::new())),
+            ..book_options()
+        };
+        let fuse = std::sync::Arc::new(Fuse::new(&docs, &options, None));
+        let terms = ["book 4", "author 3", "series", "bok 12"];
+
+        let expected: Vec<_> = terms.iter().map(|term| fuse.search_results(term, &Default::default()).unwrap()).collect();
+
+        let handles: Vec<_> = (0..8)
+            .map(|thread| {
+                let fuse = std::sync::Arc::clone(&fuse);
+                std::thread::spawn(move || {
+                    let mut searched = Vec::new();
+                    for i in 0..20 {
+                        let term = terms[(thread + i) % terms.len()];
+                        let results = fuse.search_results(term, &Default::default()).unwrap();
+                        searched.push((term, results.iter().map(|r| (r.ref_index, r.score)).collect::<Vec<_>>()));
+                    }
+                    searched
+                })
+            })
+            .collect();
+
+        for handle in handles {
+            for (term, results) in handle.join().unwrap() {
+                let expected = &expected[terms.iter().position(|t| *t == term).unwrap()];
+                assert_eq!(results, expected.iter().map(|r| (r.ref_index, r.score)).collect::<Vec<_>>());
+            }
+        }
+    }
+
+    #[test]
+    fn test_limit_keeps_sorted_order() {
+        fn longest_first(a: &FuseSortFunctionArg, b: &FuseSortFunctionArg) -> i32 {
+            let len = |arg: &FuseSortFunctionArg| match arg.matches.as_deref() {
+                Some([FuseSortFunctionMatchType::Simple(m), ..]) => m.value.len(),
+                _ => 0,
+            };
+            len(b).cmp(&len(a)) as i32
+        }
+
+        let docs = vec![json!("apple pie"), json!("apple"), json!("crab apple"), json!("pineapple"), json!("apples")];
+
+        for sort_fn in [crate::core::options::sort::default_sort_fn, longest_first] {
+            let fuse = Fuse::new(&docs, &FuseOptions { sort_fn, ..Default::default() }, None);
+            let all = fuse.search_with_options("apple", &Default::default()).unwrap();
+
+            for limit in 0..=docs.len() {
+                let results = fuse.search_with_options("apple", &FuseSearchOptions { limit, ..Default::default() });
+                assert_eq!(results.unwrap(), all[..limit.min(all.len())]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_extended_search() {
+        let docs = books();
+        let options = FuseOptions {
+            use_extended_search: true,
+            ..book_options()
+        };
+        let fuse = Fuse::new(&docs, &options, None);
+
+        assert_eq!(fuse.search_with_options("^the !html", &Default::default()).unwrap(), vec![docs[1].clone()]);
+        assert_eq!(fuse.search_with_options("~~scalzy", &Default::default()).unwrap(), vec![docs[0].clone()]);
+        assert_eq!(fuse.search("=html5 | ~~hamiltun").unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_extended_search_with_synonyms() {
+        use crate::helpers::synonyms::SynonymMap;
+        use std::sync::Arc;
+
+        let docs = books();
+        let options = FuseOptions {
+            use_extended_search: true,
+            synonyms: Some(Arc::new(SynonymMap::new().with_synonyms("web", ["=html5"]))),
+            ..book_options()
+        };
+        let fuse = Fuse::new(&docs, &options, None);
+
+        assert_eq!(fuse.search_with_options("web", &Default::default()).unwrap(), vec![docs[2].clone()]);
+    }
+
+    #[test]
+    fn test_index_uses_tokenizer_options() {
+        use crate::helpers::stop_words::{StopWordLanguage, StopWords};
+
+        let docs = vec![json!("The Lord of the Rings"), json!("Lord Rings")];
+        let options = FuseOptions {
+            stop_words: Some(StopWords::for_language(StopWordLanguage::English)),
+            ..Default::default()
+        };
+        let fuse = Fuse::new(&docs, &options, None);
+
+        let norms: Vec<f64> = fuse.get_index().records().iter().map(|r| match r {
+            FuseIndexRecord::String(record) => record.n,
+            FuseIndexRecord::Object(_) => panic!("Expected string record"),
+        }).collect();
+        assert_eq!(norms[0], norms[1]);
+    }
+
+    #[test]
+    fn test_max_pattern_length_counts_chars() {
+        let docs = vec![json!("żółw"), json!("zolw")];
+        let options = FuseOptions {
+            max_pattern_length: Some(4),
+            ..Default::default()
+        };
+        let fuse = Fuse::new(&docs, &options, None);
+
+        assert_eq!(fuse.search_with_options("żółw", &Default::default()).unwrap().first(), Some(&docs[0]));
+        assert!(matches!(fuse.search("żółwi"), Err(FuseError::PatternLengthTooLarge(4))));
+    }
+
+    #[test]
+    fn test_format_includes_score_and_matches() {
+        let docs = books();
+        let options = FuseOptions {
+            include_score: true,
+            include_matches: true,
+            ..book_options()
+        };
+        let fuse = Fuse::new(&docs, &options, None);
+
+        let searcher = fuse.searcher("scalzi");
+        let mut results: Vec<ScoredRecord> = fuse
+            .get_index()
+            .records()
+            .iter()
+            .filter_map(|record| fuse.search_record(&searcher, record).unwrap())
+            .collect();
+        fuse.score_results(&mut results);
+
+        let formatted = fuse.format(results);
+        assert_eq!(formatted.len(), 1);
+        assert_eq!(formatted[0].ref_index, 0);
+        assert!(formatted[0].score.is_some());
+
+        let matches = formatted[0].matches.as_ref().unwrap();
+        assert_eq!(matches[0].key.as_deref(), Some("author"));
+        assert_eq!(matches[0].value.as_deref(), Some("John Scalzi"));
+        assert_eq!(matches[0].indices, vec![(5, 10)]);
+        assert_eq!(matches[0].normalized_value.as_deref(), Some("john scalzi"));
+        assert_eq!(matches[0].original_indices, Some(vec![(5, 10)]));
+    }
+
+    #[test]
+    fn test_matches_grouped_per_document() {
+        let docs = vec![
+            json!({"title": "War and Peace", "tags": ["war", "classic", "warfare"]}),
+            json!({"title": "Peace", "tags": ["calm"]}),
+        ];
+        let options = FuseOptions {
+            keys: vec![FuseOptionKey::String("title".into()), FuseOptionKey::String("tags".into())],
+            include_matches: true,
+            use_extended_search: true,
+            ..Default::default()
+        };
+        let fuse = Fuse::new(&docs, &options, None);
+
+        // Array entries come in index order, which lists them last to first
+        // like Fuse.js
+        let results = fuse.search_results("'war", &FuseSearchOptions::default()).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].ref_index, 0);
+
+        let matches: Vec<_> = results[0]
+            .matches
+            .as_ref()
+            .unwrap()
+            .iter()
+            .map(|m| (m.key.as_deref(), m.ref_index, m.value.as_deref()))
+            .collect();
+        assert_eq!(
+            matches,
+            vec![
+                (Some("title"), None, Some("War and Peace")),
+                (Some("tags"), Some(2), Some("warfare")),
+                (Some("tags"), Some(0), Some("war")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_format_projects_indices_onto_original() {
+        let docs = vec![json!("Straße Süd")];
+        let options = FuseOptions {
+            include_matches: true,
+            min_match_char_length: 3,
+            ..Default::default()
+        };
+        let fuse = Fuse::new(&docs, &options, None);
+
+        let searcher = fuse.searcher("süd");
+        let record = &fuse.get_index().records()[0];
+        let result = fuse.search_record(&searcher, record).unwrap().unwrap();
+
+        let formatted = fuse.format(vec![result]);
+        let m = &formatted[0].matches.as_ref().unwrap()[0];
+        assert_eq!(m.indices, vec![(8, 10)]);
+        assert_eq!(m.original_indices, Some(vec![(7, 9)]));
+    }
+
+    #[test]
+    fn test_format_unchanged_value_has_no_normalized_form() {
+        let docs = vec![json!("apple")];
+        let options = FuseOptions {
+            include_matches: true,
+            ..Default::default()
+        };
+        let fuse = Fuse::new(&docs, &options, None);
+
+        let searcher = fuse.searcher("apple");
+        let record = &fuse.get_index().records()[0];
+        let result = fuse.search_record(&searcher, record).unwrap().unwrap();
+
+        let formatted = fuse.format(vec![result]);
+        let m = &formatted[0].matches.as_ref().unwrap()[0];
+        assert!(m.normalized_value.is_none());
+        assert!(m.original_indices.is_none());
+    }
+
+    #[test]
+    fn test_format_scores_by_key() {
+        let docs = vec![json!({"title": "Rust in Action", "tags": ["rust", "systems", "rusty"]})];
+        let options = FuseOptions {
+            include_score: true,
+            keys: vec![
+                FuseOptionKey::String("title".into()),
+                FuseOptionKey::String("tags".into()),
+            ],
+            ..Default::default()
+        };
+        let fuse = Fuse::new(&docs, &options, None);
+
+        let searcher = fuse.searcher("rust");
+        let record = &fuse.get_index().records()[0];
+        let mut results = vec![fuse.search_record(&searcher, record).unwrap().unwrap()];
+        fuse.score_results(&mut results);
+
+        let scores = fuse.format(results).remove(0).scores_by_key.unwrap();
+        assert_eq!(scores.len(), 2);
+        assert_eq!(scores["tags"], 0.0);
+        assert!(scores["title"] > 0.0);
+    }
+
+    #[test]
+    fn test_with_store() {
+        /// Books kept serialized, read back on demand
+        #[derive(Debug)]
+        struct SerializedBooks(Vec<String>);
+
+        impl DocStore for SerializedBooks {
+            fn len(&self) -> usize {
+                self.0.len()
+            }
+
+            fn get(&self, id: usize) -> Option<Cow<'_, Value>> {
+                serde_json::from_str(self.0.get(id)?).ok().map(Cow::Owned)
+            }
+        }
+
+        let docs = books();
+        let store = SerializedBooks(docs.iter().map(Value::to_string).collect());
+        let fuse = Fuse::with_store(store, &book_options(), None);
+
+        assert_eq!(fuse.store().len(), 3);
+        assert_eq!(fuse.search_with_options("lock", &Default::default()).unwrap(), vec![docs[1].clone()]);
+
+        let search_options = FuseSearchOptions {
+            filter: Some(Arc::new(|doc: &Value| doc["author"] != "Remy Sharp")),
+            ..Default::default()
+        };
+        assert!(!fuse.search_with_options("html", &search_options).unwrap().contains(&docs[2]));
+    }
+
+    #[test]
+    fn test_observer_metrics() {
+        #[derive(Debug, Default)]
+        struct Recorder(std::sync::Mutex<Vec<SearchMetrics>>);
+
+        impl crate::SearchObserver for Recorder {
+            fn on_search(&self, metrics: &SearchMetrics) {
+                self.0.lock().unwrap().push(metrics.clone());
+            }
+        }
+
+        let recorder = std::sync::Arc::new(Recorder::default());
+        let docs = vec![json!("apple"), json!("apple pie"), json!("pineapple"), json!("banana")];
+        let options = FuseOptions {
+            observer: Some(recorder.clone()),
+            interner: Some(std::sync::Arc::new(crate::Interner::new())),
+            ..Default::default()
+        };
+        let fuse = Fuse::new(&docs, &options, None);
+
+        let search_options = FuseSearchOptions {
+            limit: 1,
+            filter: Some(Arc::new(|doc: &Value| doc != "apple pie")),
+            ..Default::default()
+        };
+        fuse.search_with_options("apple", &search_options).unwrap();
+        fuse.search("apple").unwrap();
+
+        let metrics = recorder.0.lock().unwrap();
+        assert_eq!(metrics.len(), 2);
+        assert_eq!((metrics[0].records_scanned, metrics[0].candidates), (4, 3));
+        assert_eq!((metrics[0].matches, metrics[0].results), (2, 1));
+        assert_eq!((metrics[0].interner_hits, metrics[0].interner_misses), (0, 3));
+        assert_eq!((metrics[1].candidates, metrics[1].matches, metrics[1].results), (4, 3, 3));
+        assert_eq!((metrics[1].interner_hits, metrics[1].interner_misses), (3, 1));
+    }
+
+    #[test]
+    fn test_observer_metrics_of_cached_search() {
+        #[derive(Debug, Default)]
+        struct Recorder(std::sync::Mutex<Vec<SearchMetrics>>);
+
+        impl crate::SearchObserver for Recorder {
+            fn on_search(&self, metrics: &SearchMetrics) {
+                self.0.lock().unwrap().push(metrics.clone());
+            }
+        }
+
+        let recorder = std::sync::Arc::new(Recorder::default());
+        let options = FuseOptions { observer: Some(recorder.clone()), result_cache_size: 4, ..Default::default() };
+        let fuse = Fuse::new(&[json!("apple"), json!("apple pie"), json!("banana")], &options, None);
+
+        fuse.search("apple").unwrap();
+        fuse.search("apple").unwrap();
+
+        let metrics = recorder.0.lock().unwrap();
+        assert_eq!(metrics.len(), 2);
+        assert_eq!((metrics[0].records_scanned, metrics[0].results, metrics[0].cached), (3, 2, false));
+        assert_eq!((metrics[1].records_scanned, metrics[1].results, metrics[1].cached), (0, 2, true));
+    }
+
+    #[test]
+    fn test_search_progress() {
+        #[derive(Debug, Default)]
+        struct Progress {
+            scanned: std::sync::Mutex<Vec<(usize, usize)>>,
+            found: std::sync::Mutex<Vec<usize>>,
+            stop_after: usize,
+        }
+
+        impl crate::SearchProgress for Progress {
+            fn on_record_scored(&self, scanned: usize, total: usize) -> std::ops::ControlFlow<()> {
+                self.scanned.lock().unwrap().push((scanned, total));
+                if scanned == self.stop_after {
+                    std::ops::ControlFlow::Break(())
+                } else {
+                    std::ops::ControlFlow::Continue(())
+                }
+            }
+
+            fn on_match_found(&self, ref_index: usize, score: f64) {
+                assert!(score < 1.0);
+                self.found.lock().unwrap().push(ref_index);
+            }
+        }
+
+        let docs = vec![json!("apple"), json!("banana"), json!("apple pie"), json!("pineapple")];
+        let fuse = Fuse::new(&docs, &FuseOptions::default(), None);
+
+        let progress = std::sync::Arc::new(Progress { stop_after: usize::MAX, ..Default::default() });
+        let search_options = FuseSearchOptions { progress: Some(progress.clone()), limit: 1, ..Default::default() };
+        fuse.search_with_options("apple", &search_options).unwrap();
+        assert_eq!(*progress.scanned.lock().unwrap(), vec![(1, 4), (2, 4), (3, 4), (4, 4)]);
+        assert_eq!(*progress.found.lock().unwrap(), vec![0, 2, 3]);
+
+        let progress = std::sync::Arc::new(Progress { stop_after: 2, ..Default::default() });
+        let search_options = FuseSearchOptions { progress: Some(progress.clone()), ..Default::default() };
+        assert_eq!(fuse.search_with_options("apple", &search_options).unwrap(), vec![json!("apple")]);
+        assert_eq!(progress.scanned.lock().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_result_cache() {
+        /// Counts the searches that scanned the records
+        #[derive(Debug, Default)]
+        struct Counter(std::sync::atomic::AtomicUsize);
+
+        impl crate::SearchObserver for Counter {
+            fn on_search(&self, metrics: &SearchMetrics) {
+                if !metrics.cached {
+                    self.0.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                }
+            }
+        }
+
+        let counter = std::sync::Arc::new(Counter::default());
+        let options = FuseOptions { observer: Some(counter.clone()), result_cache_size: 4, ..Default::default() };
+        let mut fuse = Fuse::new(&[json!("apple"), json!("banana")], &options, None);
+        let searches = || counter.0.load(std::sync::atomic::Ordering::Relaxed);
+
+        assert_eq!(fuse.search_with_options("apple", &Default::default()).unwrap(), vec![json!("apple")]);
+        assert_eq!((fuse.result_cache_hits(), fuse.result_cache_misses()), (0, 1));
+        assert_eq!(fuse.search_with_options("apple", &Default::default()).unwrap(), vec![json!("apple")]);
+        assert_eq!((fuse.result_cache_hits(), fuse.result_cache_misses()), (1, 1));
+        assert_eq!(searches(), 1);
+
+        fuse.search_with_options("apple", &FuseSearchOptions { limit: 1, ..Default::default() }).unwrap();
+        assert_eq!(searches(), 2);
+
+        fuse.add(json!("apple pie")).unwrap();
+        assert_eq!(fuse.search("apple").unwrap().len(), 2);
+        assert_eq!(searches(), 3);
+
+        fuse.remove_at(0).unwrap();
+        assert_eq!(fuse.search_with_options("apple", &Default::default()).unwrap(), vec![json!("apple pie")]);
+
+        fuse.set_collection(&[json!("cherry")], None).unwrap();
+        assert!(fuse.search("apple").unwrap().is_empty());
+        assert_eq!(searches(), 5);
+    }
+
+    #[test]
+    fn test_add_and_remove() {
+        let docs = books();
+        let mut fuse = Fuse::new(&docs, &book_options(), None);
+
+        fuse.add(json!({"title": "Dune", "author": "Frank Herbert"})).unwrap();
+        fuse.add(Value::Null).unwrap();
+        let results = fuse.search_results("dune", &FuseSearchOptions::default()).unwrap();
+        assert_eq!(results[0].ref_index, 3);
+
+        assert_eq!(fuse.remove_at(1).unwrap(), Some(docs[1].clone()));
+        assert_eq!(fuse.remove_at(9).unwrap(), None);
+        let results = fuse.search_results("dune", &FuseSearchOptions::default()).unwrap();
+        assert_eq!(results[0].ref_index, 2);
+
+        let removed = fuse.remove(|doc, _| doc["author"] == "Remy Sharp").unwrap();
+        assert_eq!(removed, vec![docs[2].clone()]);
+        assert_eq!(fuse.search_with_options("dune", &Default::default()).unwrap(), vec![json!({"title": "Dune", "author": "Frank Herbert"})]);
+        assert_eq!(fuse.store().len(), 2);
+    }
+
+    #[test]
+    fn test_add_all() {
+        let docs = books();
+        let mut fuse = Fuse::new(&docs[..1], &book_options(), None);
+
+        fuse.add_all(vec![docs[1].clone(), Value::Null, docs[2].clone()]).unwrap();
+        assert_eq!(fuse.len(), 3);
+        assert_eq!(fuse.search_results("sharp", &FuseSearchOptions::default()).unwrap()[0].ref_index, 2);
+
+        let indexed = Fuse::new(&docs, &book_options(), None);
+        assert_eq!(
+            serde_json::to_value(fuse.get_index().records()).unwrap(),
+            serde_json::to_value(indexed.get_index().records()).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_search_after_removals() {
+        let docs: Vec<Value> = (0..40).map(|i| json!({"title": format!("book {}", i), "author": "anon"})).collect();
+        let mut fuse = Fuse::new(&docs, &book_options(), None);
+
+        fuse.remove_at(10).unwrap();
+        fuse.remove_at(20).unwrap();
+        assert_eq!(fuse.get_index().records().len(), 40);
+
+        let results = fuse.search_results("book 25", &FuseSearchOptions::default()).unwrap();
+        assert_eq!(results[0].ref_index, 23);
+        assert_eq!(fuse.get(results[0].ref_index).as_deref(), Some(&docs[25]));
+        assert!(fuse.explain("book 25", 23).unwrap().is_some());
+
+        let search_options = FuseSearchOptions {
+            filter: Some(Arc::new(|doc: &Value| doc["title"] == "book 39")),
+            ..Default::default()
+        };
+        let results = fuse.search_results("book", &search_options).unwrap();
+        assert_eq!(results.iter().map(|r| r.ref_index).collect::<Vec<_>>(), vec![37]);
+
+        let mut snapshot = Vec::new();
+        fuse.save(&mut snapshot).unwrap();
+        let loaded = Fuse::load(snapshot.as_slice()).unwrap();
+        assert_eq!(loaded.get_index().records().len(), 38);
+        assert_eq!(loaded.search_results("book 25", &FuseSearchOptions::default()).unwrap()[0].ref_index, 23);
+    }
+
+    #[test]
+    fn test_remove_after_skipped_strings() {
+        let docs = vec![json!("apple"), json!(""), json!("banana"), json!("cherry")];
+        let mut fuse = Fuse::new(&docs, &FuseOptions::default(), None);
+
+        assert_eq!(fuse.search_with_options("banana", &Default::default()).unwrap(), vec![json!("banana")]);
+
+        fuse.remove_at(1).unwrap();
+        fuse.remove_at(1).unwrap();
+        assert_eq!(fuse.search_with_options("cherry", &Default::default()).unwrap(), vec![json!("cherry")]);
+        assert!(fuse.search("banana").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_set_collection() {
+        let docs = books();
+        let mut fuse = Fuse::new(&docs[..1], &book_options(), None);
+        assert!(fuse.search("hamilton").unwrap().is_empty());
+
+        fuse.set_collection(&docs, None).unwrap();
+        assert_eq!(fuse.search_with_options("hamilton", &Default::default()).unwrap(), vec![docs[1].clone()]);
+
+        let index = FuseIndex::create_index_with_options(&book_options().keys, &docs[2..], &book_options());
+        fuse.set_collection(&docs[2..], Some(index)).unwrap();
+        assert_eq!(fuse.search_with_options("sharp", &Default::default()).unwrap(), vec![docs[2].clone()]);
+        assert_eq!(fuse.store().len(), 1);
+
+        // An index of other keys is rejected, leaving the collection as is
+        let title = vec![FuseOptionKey::String("title".into())];
+        let index = FuseIndex::create_index(&title, &docs, None, None);
+        let result = fuse.set_collection(&docs, Some(index));
+        assert!(matches!(result, Err(FuseError::IncorrectIndexType)));
+        assert_eq!(fuse.search_with_options("sharp", &Default::default()).unwrap(), vec![docs[2].clone()]);
+    }
+
+    #[test]
+    fn test_reuse_parsed_index() {
+        let docs = books();
+        let fuse = Fuse::new(&docs, &book_options(), None);
+
+        let index = fuse.get_index();
+        let parsed = FuseIndex::parse_index((index.keys().to_vec(), index.records().clone()), None, None);
+        let reused = Fuse::try_new(&docs, &book_options(), Some(parsed)).unwrap();
+        assert_eq!(reused.search_with_options("hamilton", &Default::default()).unwrap(), fuse.search_with_options("hamilton", &Default::default()).unwrap());
+
+        let options = FuseOptions { keys: vec![FuseOptionKey::String("title".into())], ..Default::default() };
+        let parsed = FuseIndex::parse_index((index.keys().to_vec(), index.records().clone()), None, None);
+        assert!(matches!(Fuse::try_new(&docs, &options, Some(parsed)), Err(FuseError::IncorrectIndexType)));
+    }
+
+    #[test]
+    fn test_doc_accessors() {
+        let docs = books();
+        let mut fuse = Fuse::new(&docs, &book_options(), None);
+        assert_eq!(fuse.len(), docs.len());
+        assert!(!fuse.is_empty());
+        assert!(fuse.docs().map(Cow::into_owned).eq(docs.iter().cloned()));
+        assert_eq!(fuse.get(1).as_deref(), Some(&docs[1]));
+        assert!(fuse.get(docs.len()).is_none());
+
+        fuse.set_collection(&[], None).unwrap();
+        assert!(fuse.is_empty());
+        assert_eq!(fuse.docs().count(), 0);
+    }
+
+    #[test]
+    fn test_ngram_index_keeps_results() {
+        let words = ["the", "lord", "of", "rings", "old", "man", "war", "lock", "artist", "hobbit"];
+        let docs: Vec<Value> = (0..200)
+            .map(|i| json!({"title": format!("{} {} {}", words[i % 10], words[i * 7 % 10], i), "tags": [words[i * 3 % 10]]}))
+            .collect();
+
+        let algorithms = [MatchAlgorithm::Bitap, MatchAlgorithm::Levenshtein, MatchAlgorithm::DamerauLevenshtein];
+        for (threshold, algorithm) in [0.0, 0.2, 0.6].into_iter().flat_map(|t| algorithms.map(|a| (t, a))) {
+            let options = FuseOptions {
+                keys: vec!["title".into(), "tags".into()],
+                include_score: true,
+                threshold,
+                algorithm,
+                ..Default::default()
+            };
+            let plain = Fuse::new(&docs, &options, None);
+            let mut pruned = Fuse::new(&docs, &FuseOptions { use_ngram_index: true, ..options }, None);
+            pruned.add(json!({"title": "Lord of the Rings"})).unwrap();
+            pruned.remove_at(200).unwrap();
+
+            for pattern in ["lord of", "hobbit", "artist 42", "lokc artist", "Rings 19", "hbobit"] {
+                let expected = plain.search_results(pattern, &FuseSearchOptions::default()).unwrap();
+                let results = pruned.search_results(pattern, &FuseSearchOptions::default()).unwrap();
+                assert_eq!(
+                    results.iter().map(|r| (r.ref_index, r.score)).collect::<Vec<_>>(),
+                    expected.iter().map(|r| (r.ref_index, r.score)).collect::<Vec<_>>()
+                );
+            }
+        }
+
+        let options = FuseOptions { keys: vec!["title".into()], threshold: 0.1, use_ngram_index: true, ..Default::default() };
+        let fuse = Fuse::new(&docs, &options, None);
+        let candidates = fuse.candidates(&fuse.searcher("hobbit 19")).unwrap();
+        assert!(candidates.iter().filter(|&&c| c).count() < docs.len() / 2);
+    }
+
+    #[test]
+    fn test_match_algorithms() {
+        let docs = vec![json!("frist"), json!("the first"), json!("fist")];
+        let search = |algorithm| {
+            let options = FuseOptions {
+                algorithm,
+                include_score: true,
+                include_matches: true,
+                ignore_location: true,
+                ignore_field_norm: true,
+                ..Default::default()
+            };
+            let fuse = Fuse::new(&docs, &options, None);
+            fuse.search_results("first", &FuseSearchOptions::default())
+                .unwrap()
+                .into_iter()
+                .map(|r| (r.ref_index, (r.score.unwrap() * 1e6).round() / 1e6, r.matches.unwrap()[0].indices.clone()))
+                .collect::<Vec<_>>()
+        };
+
+        // The exact occurrence comes first; the transposition is two
+        // Levenshtein edits but one Damerau edit, and outranks the deletion
+        // only with the latter
+        let levenshtein = search(MatchAlgorithm::Levenshtein);
+        assert_eq!(levenshtein[0], (1, 0.001, vec![(4, 8)]));
+        assert_eq!(levenshtein[1..].iter().map(|r| (r.0, r.1)).collect::<Vec<_>>(), vec![(2, 0.2), (0, 0.4)]);
+
+        let damerau = search(MatchAlgorithm::DamerauLevenshtein);
+        assert_eq!(damerau[1..].iter().map(|r| (r.0, r.1, r.2.clone())).collect::<Vec<_>>(), vec![
+            (0, 0.2, vec![(0, 4)]),
+            (2, 0.2, vec![(0, 3)]),
+        ]);
+
+        // Bitap is still the default
+        assert_eq!(FuseOptions::default().algorithm, MatchAlgorithm::Bitap);
+        let options = FuseOptions::from_json(json!({"algorithm": "DamerauLevenshtein"})).unwrap();
+        assert_eq!(options.algorithm, MatchAlgorithm::DamerauLevenshtein);
+    }
+
+    #[test]
+    fn test_tokenize() {
+        let docs = vec![
+            json!("A safecracker who cannot speak is hired to open the lock of a mob boss, told by the artist himself"),
+            json!("Old Man's War"),
+        ];
+        let search = |pattern: &str, tokenize, match_all_tokens| {
+            let options = FuseOptions { tokenize, match_all_tokens, include_score: true, ..Default::default() };
+            let fuse = Fuse::new(&docs, &options, None);
+            fuse.search_results(pattern, &FuseSearchOptions::default())
+                .unwrap()
+                .into_iter()
+                .map(|r| (r.ref_index, r.score.unwrap()))
+                .collect::<Vec<_>>()
+        };
+
+        // The words are far apart and in another order in the description
+        assert!(search("artist lock", false, false).is_empty());
+        let results = search("artist lock", true, false);
+        assert_eq!(results.iter().map(|r| r.0).collect::<Vec<_>>(), vec![0]);
+
+        // Unmatched words no longer dilute the score, but every token must
+        // be found
+        let all = search("artist lock", true, true);
+        assert_eq!(all.len(), 1);
+        assert!(all[0].1 < results[0].1);
+        assert_eq!(search("artist zzzzz", true, false).len(), 1);
+        assert!(search("artist zzzzz", true, true).is_empty());
+
+        // A match of the whole pattern counts as much as all the tokens
+        let options = FuseOptions { tokenize: true, ignore_field_norm: true, include_score: true, ..Default::default() };
+        let fuse = Fuse::new(&docs, &options, None);
+        let results = fuse.search_results("old war", &FuseSearchOptions::default()).unwrap();
+        assert_eq!(results[0].ref_index, 1);
+        let plain = FuseOptions { tokenize: false, ..options.clone() };
+        let full = Fuse::new(&docs, &plain, None).search_results("old war", &FuseSearchOptions::default()).unwrap();
+        // "old" and "war" are each one of the three words, and miss the others
+        let tokens = (0.0 + 1.0 + 1.0 + 1.0 + 1.0 + 0.0) / 6.0;
+        assert!((results[0].score.unwrap() - (full[0].score.unwrap() + tokens) / 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_key_analyzers() {
+        use crate::core::options::keys::{FuseOptionKeyName, FuseOptionKeyObject, KeyAnalyzer};
+        use crate::helpers::phonetic::PhoneticAlgorithm;
+        use crate::helpers::stop_words::StopWords;
+
+        let key = |name: &str, analyzer| {
+            FuseOptionKey::KeyObject(FuseOptionKeyObject {
+                name: Cow::Owned(FuseOptionKeyName::String(name.to_string().into())),
+                weight: None,
+                get_fn: None,
+                fallback: None,
+                analyzer: Some(analyzer),
+                index: true,
+                searchable: true,
+            })
+        };
+        let docs = vec![
+            json!({"description": "Shoes for the trail", "sku": "TR-100", "maker": "John Smith"}),
+            json!({"description": "Boots", "sku": "tr-200", "maker": "Jane Doe"}),
+        ];
+        let options = FuseOptions {
+            keys: vec![
+                key("description", KeyAnalyzer { stop_words: Some(StopWords::new(["for", "the"])), ..Default::default() }),
+                key("sku", KeyAnalyzer { is_case_sensitive: true, ..Default::default() }),
+                key("maker", KeyAnalyzer { phonetic: Some(PhoneticAlgorithm::Soundex), ..Default::default() }),
+            ],
+            threshold: 0.0,
+            ..Default::default()
+        };
+        let fuse = Fuse::new(&docs, &options, None);
+        let plain_keys = vec!["description".into(), "sku".into(), "maker".into()];
+        let plain = Fuse::new(&docs, &FuseOptions { keys: plain_keys, ..options.clone() }, None);
+        let found = |fuse: &Fuse, pattern: &str| -> Vec<usize> {
+            let results = fuse.search_results(pattern, &FuseSearchOptions::default()).unwrap();
+            results.iter().map(|r| r.ref_index).collect()
+        };
+
+        // Stop words are removed from the descriptions and the patterns
+        // searched in them
+        assert_eq!(found(&fuse, "shoes trail"), vec![0]);
+        assert_eq!(found(&fuse, "the shoes for trail"), vec![0]);
+        assert!(found(&plain, "shoes trail").is_empty());
+
+        // SKUs keep their case, other keys don't
+        assert!(found(&fuse, "TR-2").is_empty());
+        assert_eq!(found(&plain, "TR-2"), vec![1]);
+        assert_eq!(found(&fuse, "BOOTS"), vec![1]);
+
+        // Makers are matched by how their names sound
+        assert_eq!(found(&fuse, "Jon Smyth"), vec![0]);
+        assert!(found(&plain, "Jon Smyth").is_empty());
+    }
+
+    #[cfg(feature = "grapheme-clusters")]
+    #[test]
+    fn test_grapheme_clusters() {
+        let family = "👨\u{200d}👩\u{200d}👧";
+        let docs = vec![json!(format!("A {} trip", family))];
+        let options = FuseOptions {
+            include_matches: true,
+            include_score: true,
+            use_grapheme_clusters: true,
+            ..Default::default()
+        };
+        let search = |options: &FuseOptions, pattern: &str| {
+            let fuse = Fuse::new(&docs, options, None);
+            fuse.search_results(pattern, &FuseSearchOptions::default()).unwrap()
+        };
+
+        // The emoji is a single unit, projected onto its characters
+        let results = search(&options, family);
+        let matches = results[0].matches.as_ref().unwrap();
+        assert_eq!(matches[0].indices, vec![(2, 2)]);
+        assert_eq!(matches[0].original_indices, Some(vec![(2, 6)]));
+
+        // and too short for a minimum of two units
+        let strict = FuseOptions { min_match_char_length: 2, ..options.clone() };
+        assert!(search(&strict, family).is_empty());
+        let chars = FuseOptions { use_grapheme_clusters: false, ..strict };
+        assert_eq!(search(&chars, family).len(), 1);
+
+        // A different emoji is a whole unit off, not just its last character
+        let other = "👨\u{200d}👩\u{200d}👦 t";
+        let by_cluster = search(&options, other)[0].score.unwrap();
+        let by_char = search(&FuseOptions { use_grapheme_clusters: false, ..options.clone() }, other)[0].score.unwrap();
+        assert!(by_cluster > by_char);
+    }
+
+    #[test]
+    fn test_key_flags() {
+        use crate::core::options::keys::{FuseOptionKeyName, FuseOptionKeyObject};
+        use crate::tools::fuse_index_record::FuseIndexRecord;
+
+        let key = |name: &str, index, searchable| {
+            FuseOptionKey::KeyObject(FuseOptionKeyObject {
+                name: Cow::Owned(FuseOptionKeyName::String(name.to_string().into())),
+                weight: None,
+                get_fn: None,
+                fallback: None,
+                analyzer: None,
+                index,
+                searchable,
+            })
+        };
+        let docs = vec![
+            json!({"title": "Dune", "internal": "draft", "notes": "draft"}),
+            json!({"title": "Draft Dodger", "internal": "final", "notes": "final"}),
+        ];
+        let options = FuseOptions {
+            keys: vec![key("title", true, true), key("internal", true, false), key("notes", false, true)],
+            threshold: 0.0,
+            ..Default::default()
+        };
+        let fuse = Fuse::new(&docs, &options, None);
+        let found = |search_options: &FuseSearchOptions| -> Vec<usize> {
+            let results = fuse.search_results("draft", search_options).unwrap();
+            results.iter().map(|r| r.ref_index).collect()
+        };
+
+        // Keys that aren't indexed have no values in the records
+        for record in fuse.get_index().records() {
+            let FuseIndexRecord::Object(record) = record else { panic!("expected an object record") };
+            assert_eq!(record.entries.len(), 2);
+        }
+
+        // Keys that aren't searchable are only matched when named
+        assert_eq!(found(&FuseSearchOptions::default()), vec![1]);
+        let named = FuseSearchOptions { keys: Some(vec!["internal".into()]), ..Default::default() };
+        assert_eq!(found(&named), vec![0]);
+        let query = json!({"internal": "final"});
+        let results = fuse.logical_search_results(&query, &FuseSearchOptions::default()).unwrap();
+        assert_eq!(results.iter().map(|r| r.ref_index).collect::<Vec<_>>(), vec![1]);
     }
 }
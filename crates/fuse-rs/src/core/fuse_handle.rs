@@ -0,0 +1,181 @@
+//! Sharing a mutable instance between threads
+//!
+//! A `Fuse` can be searched from several threads through an `Arc`, but
+//! updating its collection needs `&mut self`. [`FuseHandle`] puts the
+//! instance behind a read-write lock: any number of searches run at once,
+//! and `add`, `remove` and `set_collection` wait for them to finish, so a
+//! server can apply updates without rebuilding the instance.
+
+use std::sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+use serde_json::Value;
+
+use crate::core::error_messages::FuseError;
+use crate::core::fuse::Fuse;
+use crate::core::results::search_result::{FuseResult, FuseSearchOptions};
+use crate::tools::fuse_index::FuseIndex;
+
+//----------------------------------------------------------------------
+// Types
+//----------------------------------------------------------------------
+
+/// A cloneable handle to an instance shared between threads
+///
+/// Clones refer to the same instance. Searches take a read lock and updates
+/// a write lock; a search always sees the collection before or after an
+/// update, never in between.
+///
+/// # Example
+///
+/// ```
+/// use fuse_rs::{Fuse, FuseHandle, FuseOptions};
+/// use serde_json::json;
+///
+/// let handle = FuseHandle::new(Fuse::new(&[json!("apple")], &FuseOptions::default(), None));
+///
+/// let writer = handle.clone();
+/// std::thread::spawn(move || writer.add(json!("apple pie")).unwrap()).join().unwrap();
+///
+/// assert_eq!(handle.search("apple").unwrap().len(), 2);
+/// ```
+pub struct FuseHandle<'a> {
+    /// The shared instance
+    inner: Arc<RwLock<Fuse<'a>>>,
+}
+
+//----------------------------------------------------------------------
+// Implementation
+//----------------------------------------------------------------------
+
+impl<'a> FuseHandle<'a> {
+    /// Creates a handle sharing `fuse`.
+    pub fn new(fuse: Fuse<'a>) -> Self {
+        Self {
+            inner: Arc::new(RwLock::new(fuse)),
+        }
+    }
+
+    /// Locks the instance for reading, e.g. to run several searches on the
+    /// same version of the collection. Updates wait until the guard is
+    /// dropped.
+    pub fn read(&self) -> RwLockReadGuard<'_, Fuse<'a>> {
+        // A panic cannot leave the instance half-updated for searches, which
+        // only rely on the index and documents being consistent
+        self.inner.read().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Locks the instance for writing, e.g. to apply several updates at once.
+    pub fn write(&self) -> RwLockWriteGuard<'_, Fuse<'a>> {
+        self.inner.write().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Searches the shared instance, like [`Fuse::search`].
+    pub fn search(&self, term: &str) -> Result<Vec<FuseResult<Value>>, FuseError> {
+        self.read().search(term)
+    }
+
+    /// Searches the shared instance, like [`Fuse::search_results`].
+    pub fn search_results(
+        &self,
+        term: &str,
+        search_options: &FuseSearchOptions,
+    ) -> Result<Vec<FuseResult<Value>>, FuseError> {
+        self.read().search_results(term, search_options)
+    }
+
+    /// Adds a document to the shared instance, like [`Fuse::add`].
+    pub fn add(&self, doc: Value) -> Result<(), FuseError> {
+        self.write().add(doc)
+    }
+
+    /// Removes documents from the shared instance, like [`Fuse::remove`].
+    pub fn remove<P: FnMut(&Value, usize) -> bool>(&self, predicate: P) -> Result<Vec<Value>, FuseError> {
+        self.write().remove(predicate)
+    }
+
+    /// Removes a document from the shared instance, like [`Fuse::remove_at`].
+    pub fn remove_at(&self, idx: usize) -> Result<Option<Value>, FuseError> {
+        self.write().remove_at(idx)
+    }
+
+    /// Replaces the documents of the shared instance, like
+    /// [`Fuse::set_collection`].
+    ///
+    /// The new index is built before taking the write lock, so searches are
+    /// only blocked while the collections are swapped.
+    pub fn set_collection(&self, docs: &[Value], index: Option<FuseIndex<'a>>) -> Result<(), FuseError> {
+        let index = index.unwrap_or_else(|| {
+            let fuse = self.read();
+            let options = fuse.options();
+            FuseIndex::create_index_with_options(&options.keys, docs, options)
+        });
+
+        self.write().set_collection(docs, Some(index))
+    }
+}
+
+impl Clone for FuseHandle<'_> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: Arc::clone(&self.inner),
+        }
+    }
+}
+
+impl<'a> From<Fuse<'a>> for FuseHandle<'a> {
+    fn from(fuse: Fuse<'a>) -> Self {
+        Self::new(fuse)
+    }
+}
+
+//----------------------------------------------------------------------
+// Tests
+//----------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::options::config::FuseOptions;
+    use serde_json::json;
+
+    #[test]
+    fn test_search_during_updates() {
+        let handle = FuseHandle::new(Fuse::new(&[json!("apple 0")], &FuseOptions::default(), None));
+
+        std::thread::scope(|scope| {
+            let writer = handle.clone();
+            scope.spawn(move || {
+                for i in 1..50 {
+                    writer.add(json!(format!("apple {}", i))).unwrap();
+                }
+            });
+
+            for _ in 0..4 {
+                let reader = handle.clone();
+                scope.spawn(move || {
+                    let mut last = 0;
+                    for _ in 0..50 {
+                        // Documents are only added, so a search never finds fewer
+                        let found = reader.search("apple").unwrap().len();
+                        assert!(found >= last && found >= 1);
+                        last = found;
+                    }
+                });
+            }
+        });
+
+        assert_eq!(handle.search("apple").unwrap().len(), 50);
+    }
+
+    #[test]
+    fn test_set_collection() {
+        let handle = FuseHandle::from(Fuse::new(&[json!("apple")], &FuseOptions::default(), None));
+
+        handle.set_collection(&[json!("banana"), json!("cherry")], None).unwrap();
+        assert!(handle.search("apple").unwrap().is_empty());
+        assert_eq!(handle.read().store().len(), 2);
+
+        assert_eq!(handle.remove_at(0).unwrap(), Some(json!("banana")));
+        assert_eq!(handle.search("cherry").unwrap()[0].item, json!("cherry"));
+    }
+}
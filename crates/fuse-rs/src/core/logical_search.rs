@@ -0,0 +1,505 @@
+//! Logical search
+//!
+//! Evaluates the [`ParsedExpression`] of a logical query against the index.
+//! As in Fuse.js, a leaf matches a record when its pattern matches a value
+//! of its key; `$and` matches when every child matches and `$or` when any
+//! does. The matches of all matching leaves are kept, so results are scored
+//! and sorted like those of a plain search; comparisons only filter. Only
+//! collections of objects are searched.
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+use serde_json::Value;
+
+use crate::core::error_messages::FuseError;
+use crate::core::fuse::Fuse;
+use crate::core::query_parser::{Comparison, ParsedExpression, parse_query};
+use crate::core::results::search_result::{FuseResult, FuseSearchOptions, ScoredMatch, ScoredRecord};
+use crate::helpers::date::parse_rfc3339;
+use crate::helpers::trace;
+use crate::search::searcher::Searcher;
+use crate::tools::fuse_index_record::{FuseIndexRecord, IndexValue, RecordEntry, RecordEntryValue};
+
+//----------------------------------------------------------------------
+// Types
+//----------------------------------------------------------------------
+
+/// An expression whose leaves are ready to be searched
+enum CompiledExpression<'s, 'a> {
+    /// A searcher for the values of a key, which is `None` if the key is
+    /// not indexed
+    Leaf { key_index: Option<usize>, searcher: Searcher<'s, 'a> },
+
+    /// A comparison of the values of a key, which is `None` if the key is
+    /// not indexed
+    Compare { key_index: Option<usize>, op: Comparison, value: &'s Value },
+
+    /// Expressions that must all match
+    And(Vec<CompiledExpression<'s, 'a>>),
+
+    /// Expressions of which at least one must match
+    Or(Vec<CompiledExpression<'s, 'a>>),
+}
+
+//----------------------------------------------------------------------
+// Implementation
+//----------------------------------------------------------------------
+
+impl<'a> Fuse<'a> {
+    /// Performs a logical search with multiple conditions.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - A map of key ids to patterns, or of `$and` / `$or` to
+    ///   arrays of sub-queries
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing matching JSON values or an error
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use fuse_rs::{Fuse, FuseOptionKey, FuseOptions};
+    /// use serde_json::json;
+    ///
+    /// let docs = vec![
+    ///     json!({"title": "Old Man's War", "author": "John Scalzi"}),
+    ///     json!({"title": "The Old Man and the Sea", "author": "Ernest Hemingway"}),
+    /// ];
+    /// let options = FuseOptions {
+    ///     keys: vec![FuseOptionKey::String("title".into()), FuseOptionKey::String("author".into())],
+    ///     use_extended_search: true,
+    ///     ..Default::default()
+    /// };
+    /// let fuse = Fuse::new(&docs, &options, None);
+    ///
+    /// let query = HashMap::from([
+    ///     ("title".to_string(), json!("'old")),
+    ///     ("author".to_string(), json!("'hemingway")),
+    /// ]);
+    /// assert_eq!(fuse.logical_search(&query).unwrap(), vec![docs[1].clone()]);
+    /// ```
+    pub fn logical_search(&self, query: &HashMap<String, Value>) -> Result<Vec<Value>, FuseError> {
+        let query = Value::Object(query.clone().into_iter().collect());
+        let results = self.logical_search_results(&query, &FuseSearchOptions::default())?;
+
+        Ok(results.into_iter().map(|result| result.item).collect())
+    }
+
+    /// Searches the data with a logical query, returning the full results.
+    ///
+    /// The query is parsed with [`parse_query`](crate::parse_query); keys
+    /// that are not indexed match nothing.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing a `FuseResult` per matching item, or an error if
+    /// the query is invalid or a pattern is too long.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fuse_rs::{Fuse, FuseOptionKey, FuseOptions, FuseSearchOptions};
+    /// use serde_json::json;
+    ///
+    /// let docs = vec![
+    ///     json!({"title": "Old Man's War", "author": "John Scalzi"}),
+    ///     json!({"title": "The Lock Artist", "author": "Steve Hamilton"}),
+    ///     json!({"title": "HTML5", "author": "Remy Sharp"}),
+    /// ];
+    /// let options = FuseOptions {
+    ///     keys: vec![FuseOptionKey::String("title".into()), FuseOptionKey::String("author".into())],
+    ///     use_extended_search: true,
+    ///     ..Default::default()
+    /// };
+    /// let fuse = Fuse::new(&docs, &options, None);
+    ///
+    /// let query = json!({"$or": [{"author": "'scalzi"}, {"title": "^html"}]});
+    /// let results = fuse.logical_search_results(&query, &FuseSearchOptions::default()).unwrap();
+    ///
+    /// let mut found: Vec<usize> = results.iter().map(|r| r.ref_index).collect();
+    /// found.sort();
+    /// assert_eq!(found, vec![0, 2]);
+    /// ```
+    pub fn logical_search_results(
+        &self,
+        query: &Value,
+        search_options: &FuseSearchOptions,
+    ) -> Result<Vec<FuseResult<Value>>, FuseError> {
+        self.search_expression(&parse_query(query)?, search_options)
+    }
+
+    /// Searches the data with an expression built by the caller, without
+    /// going through the JSON form of logical queries.
+    ///
+    /// Servers can build expressions from request fields instead of
+    /// interpolating user input into a query. Leaf patterns are searched
+    /// like the term of a plain search, so they follow the extended syntax
+    /// when `use_extended_search` is set.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing a `FuseResult` per matching item, or an error if
+    /// a pattern is too long.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fuse_rs::{Fuse, FuseOptionKey, FuseOptions, FuseSearchOptions, ParsedExpression};
+    /// use serde_json::json;
+    ///
+    /// let docs = vec![
+    ///     json!({"title": "Old Man's War", "author": "John Scalzi"}),
+    ///     json!({"title": "The Lock Artist", "author": "Steve Hamilton"}),
+    /// ];
+    /// let options = FuseOptions {
+    ///     keys: vec![FuseOptionKey::String("title".into()), FuseOptionKey::String("author".into())],
+    ///     ..Default::default()
+    /// };
+    /// let fuse = Fuse::new(&docs, &options, None);
+    ///
+    /// let user_input = "hamilton";
+    /// let expression = ParsedExpression::Leaf { key_id: "author".into(), pattern: user_input.into() };
+    /// let results = fuse.search_expression(&expression, &FuseSearchOptions::default()).unwrap();
+    /// assert_eq!(results[0].ref_index, 1);
+    /// ```
+    pub fn search_expression(
+        &self,
+        expression: &ParsedExpression,
+        search_options: &FuseSearchOptions,
+    ) -> Result<Vec<FuseResult<Value>>, FuseError> {
+        let expression = self.compile(expression)?;
+        trace::enter_span!("fuse.logical_search", limit = search_options.limit);
+
+        let mut results = Vec::new();
+        for record in self.get_index().records() {
+            let FuseIndexRecord::Object(object) = record else {
+                continue;
+            };
+
+            // Records of removed documents stay in the index until it is compacted
+            let Some(position) = self.get_index().position(record) else {
+                continue;
+            };
+
+            if !self.passes_filter(record, search_options) {
+                continue;
+            }
+
+            let mut matches = Vec::new();
+            if self.evaluate(&expression, &object.entries, &mut matches)? {
+                results.push(ScoredRecord { idx: position, score: 1.0, matches });
+            }
+        }
+
+        self.score_results(&mut results);
+        results.retain(|result| search_options.keeps_score(result.score));
+
+        if self.should_sort() {
+            self.sort_results(&mut results);
+        }
+
+        results.truncate(search_options.offset.saturating_add(search_options.limit));
+        results.drain(..search_options.offset.min(results.len()));
+        Ok(self.format(results))
+    }
+
+    //------------------------------------------------------------------
+    // Evaluation
+    //------------------------------------------------------------------
+
+    /// Creates the searchers of the leaves of `expression`.
+    fn compile<'s>(&'s self, expression: &'s ParsedExpression) -> Result<CompiledExpression<'s, 'a>, FuseError> {
+        let compile_all = |children: &'s [ParsedExpression]| {
+            children.iter().map(|child| self.compile(child)).collect::<Result<Vec<_>, _>>()
+        };
+
+        Ok(match expression {
+            ParsedExpression::Leaf { key_id, pattern } => {
+                self.validate_term(pattern)?;
+                CompiledExpression::Leaf {
+                    key_index: self.key_index(key_id),
+                    searcher: self.searcher(pattern),
+                }
+            }
+            ParsedExpression::Compare { key_id, op, value } => CompiledExpression::Compare {
+                key_index: self.key_index(key_id),
+                op: *op,
+                value,
+            },
+            ParsedExpression::And(children) => CompiledExpression::And(compile_all(children)?),
+            ParsedExpression::Or(children) => CompiledExpression::Or(compile_all(children)?),
+        })
+    }
+
+    /// Evaluates `expression` against the entries of a record, appending the
+    /// matches of the leaves that contributed to `matches`.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the record matched.
+    fn evaluate(
+        &self,
+        expression: &CompiledExpression,
+        entries: &RecordEntry,
+        matches: &mut Vec<ScoredMatch>,
+    ) -> Result<bool, FuseError> {
+        match expression {
+            CompiledExpression::Leaf { key_index, searcher } => {
+                let found = matches.len();
+                if let Some(key_index) = *key_index
+                    && let Some(value) = entries.get(&key_index.to_string())
+                {
+                    self.find_matches(searcher, key_index, value, matches)?;
+                }
+
+                Ok(matches.len() > found)
+            }
+            CompiledExpression::Compare { key_index, op, value } => {
+                let values: &[IndexValue] = match key_index.and_then(|key_index| entries.get(&key_index.to_string())) {
+                    Some(RecordEntryValue::Single(v)) => std::slice::from_ref(v),
+                    Some(RecordEntryValue::Array(arr)) => arr,
+                    None => &[],
+                };
+
+                Ok(values.iter().any(|v| compare(v, *op, value)))
+            }
+            CompiledExpression::And(children) => {
+                // The matches of earlier children are dropped if a later one fails
+                let mut found = Vec::new();
+                for child in children {
+                    if !self.evaluate(child, entries, &mut found)? {
+                        return Ok(false);
+                    }
+                }
+
+                matches.extend(found);
+                Ok(!children.is_empty())
+            }
+            CompiledExpression::Or(children) => {
+                let mut matched = false;
+                for child in children {
+                    matched |= self.evaluate(child, entries, matches)?;
+                }
+
+                Ok(matched)
+            }
+        }
+    }
+}
+
+//----------------------------------------------------------------------
+// Implementation Details
+//----------------------------------------------------------------------
+
+/// Returns `true` if the indexed value `v` compares with `value` as `op`
+/// requires.
+fn compare(v: &IndexValue, op: Comparison, value: &Value) -> bool {
+    let ordering = match (value, &v.t) {
+        (Value::String(s), _) if matches!(op, Comparison::Before | Comparison::After) => {
+            // Values indexed from Fuse.js have no timestamp
+            let indexed = v.d.or_else(|| parse_rfc3339(&v.v));
+            indexed.zip(parse_rfc3339(s)).map(|(indexed, date)| indexed.cmp(&date))
+        }
+        (Value::Number(n), t) => {
+            // Values indexed from Fuse.js or from strings have no typed form
+            let indexed = match t {
+                Some(t) => t.as_f64(),
+                None => v.v.trim().parse::<f64>().ok(),
+            };
+            indexed.zip(n.as_f64()).and_then(|(indexed, n)| indexed.partial_cmp(&n))
+        }
+        (Value::Bool(b), Some(Value::Bool(t))) => Some(t.cmp(b)),
+        (Value::Bool(b), None) => (v.v == b.to_string()).then_some(Ordering::Equal),
+        (Value::String(s), None) => (v.v == *s).then_some(Ordering::Equal),
+        _ => None,
+    };
+
+    let Some(ordering) = ordering else {
+        return false;
+    };
+
+    match op {
+        Comparison::Gt => ordering == Ordering::Greater,
+        Comparison::Gte => ordering != Ordering::Less,
+        Comparison::Lt => ordering == Ordering::Less,
+        Comparison::Lte => ordering != Ordering::Greater,
+        Comparison::Eq => ordering == Ordering::Equal,
+        Comparison::Before => ordering == Ordering::Less,
+        Comparison::After => ordering == Ordering::Greater,
+    }
+}
+
+//----------------------------------------------------------------------
+// Tests
+//----------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::options::config::FuseOptions;
+    use crate::core::options::keys::FuseOptionKey;
+    use serde_json::json;
+
+    fn books() -> Vec<Value> {
+        vec![
+            json!({"title": "Old Man's War", "author": {"name": "John Scalzi"}, "tags": ["fiction"]}),
+            json!({"title": "The Lock Artist", "author": {"name": "Steve Hamilton"}, "tags": ["thriller"]}),
+            json!({"title": "Old Yeller", "author": {"name": "Fred Gipson"}, "tags": ["fiction", "dogs"]}),
+            json!({"title": "HTML5", "author": {"name": "Remy Sharp"}, "tags": ["web"]}),
+        ]
+    }
+
+    fn fuse(docs: &[Value]) -> Fuse<'static> {
+        let options = FuseOptions {
+            keys: vec![
+                FuseOptionKey::String("title".into()),
+                FuseOptionKey::String("author.name".into()),
+                FuseOptionKey::String("tags".into()),
+            ],
+            use_extended_search: true,
+            include_matches: true,
+            ..Default::default()
+        };
+        Fuse::new(docs, &options, None)
+    }
+
+    fn ref_indices(results: &[FuseResult<Value>]) -> Vec<usize> {
+        let mut indices: Vec<usize> = results.iter().map(|r| r.ref_index).collect();
+        indices.sort();
+        indices
+    }
+
+    #[test]
+    fn test_and_or() {
+        let docs = books();
+        let fuse = fuse(&docs);
+        let options = FuseSearchOptions::default();
+
+        let query = json!({"$and": [{"title": "^old"}, {"tags": "=dogs"}]});
+        assert_eq!(ref_indices(&fuse.logical_search_results(&query, &options).unwrap()), vec![2]);
+
+        let query = json!({"$or": [{"author.name": "'hamilton"}, {"tags": "=web"}]});
+        assert_eq!(ref_indices(&fuse.logical_search_results(&query, &options).unwrap()), vec![1, 3]);
+
+        let query = json!({
+            "$and": [
+                {"tags": "=fiction"},
+                {"$or": [{"$path": ["author", "name"], "$val": "'scalzi"}, {"title": "'yeller"}]}
+            ]
+        });
+        assert_eq!(ref_indices(&fuse.logical_search_results(&query, &options).unwrap()), vec![0, 2]);
+    }
+
+    #[test]
+    fn test_matches_of_matching_leaves() {
+        let docs = books();
+        let fuse = fuse(&docs);
+
+        let query = json!({"$or": [{"title": "^old"}, {"tags": "=dogs"}]});
+        let results = fuse.logical_search_results(&query, &FuseSearchOptions::default()).unwrap();
+
+        let yeller = results.iter().find(|r| r.ref_index == 2).unwrap();
+        let mut keys: Vec<String> = yeller.matches.iter().flatten().filter_map(|m| m.key.clone()).collect();
+        keys.sort();
+        assert_eq!(keys, vec!["tags", "title"]);
+
+        // Both leaves matched the second book, so it ranks first
+        assert_eq!(results[0].ref_index, 2);
+    }
+
+    #[test]
+    fn test_unknown_keys_and_string_lists() {
+        let docs = books();
+        let fuse = fuse(&docs);
+        let query = json!({"isbn": "978"});
+        assert!(fuse.logical_search_results(&query, &FuseSearchOptions::default()).unwrap().is_empty());
+
+        let docs = vec![json!("old man"), json!("old yeller")];
+        let fuse = Fuse::new(&docs, &FuseOptions::default(), None);
+        assert!(fuse.logical_search(&HashMap::from([("title".to_string(), json!("old"))])).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_search_expression() {
+        let docs = books();
+        let fuse = fuse(&docs);
+
+        let expression: ParsedExpression = serde_json::from_value(json!({
+            "or": [
+                {"leaf": {"key_id": "title", "pattern": "lock"}},
+                {"leaf": {"key_id": "author.name", "pattern": "'gipson"}}
+            ]
+        }))
+        .unwrap();
+
+        let results = fuse.search_expression(&expression, &FuseSearchOptions::default()).unwrap();
+        assert_eq!(ref_indices(&results), vec![1, 2]);
+    }
+
+    #[test]
+    fn test_comparisons() {
+        let docs = vec![
+            json!({"title": "Dune", "year": 1965, "in_print": true}),
+            json!({"title": "Dune Messiah", "year": 1969, "in_print": false}),
+            json!({"title": "Children of Dune", "year": "1976", "in_print": true}),
+            json!({"title": "Neuromancer", "year": 1984}),
+        ];
+        let options = FuseOptions {
+            keys: vec!["title".into(), "year".into(), "in_print".into()],
+            threshold: 0.3,
+            ..Default::default()
+        };
+        let fuse = Fuse::new(&docs, &options, None);
+        let search = |query: Value| ref_indices(&fuse.logical_search_results(&query, &FuseSearchOptions::default()).unwrap());
+
+        assert_eq!(search(json!({"title": "dune", "year": {"$gte": 1969}})), vec![1, 2]);
+        assert_eq!(search(json!({"year": {"$gt": 1965, "$lte": 1976}})), vec![1, 2]);
+        assert_eq!(search(json!({"year": {"$lt": 1965.5}})), vec![0]);
+        assert_eq!(search(json!({"in_print": {"$eq": true}})), vec![0, 2]);
+        assert_eq!(search(json!({"$or": [{"year": {"$eq": 1984}}, {"in_print": {"$eq": false}}]})), vec![1, 3]);
+
+        // Comparisons do not change the score of the fuzzy matches
+        let query = json!({"$and": [{"title": "messiah"}, {"year": {"$lt": 2000}}]});
+        let filtered = fuse.logical_search_results(&query, &FuseSearchOptions::default()).unwrap();
+        let plain = fuse.logical_search_results(&json!({"title": "messiah"}), &FuseSearchOptions::default()).unwrap();
+        assert_eq!(filtered[0].score, plain[0].score);
+    }
+
+    #[test]
+    fn test_date_comparisons() {
+        let docs = vec![
+            json!({"title": "Release notes", "published": "2021-06-01T12:30:00Z"}),
+            json!({"title": "Release plan", "published": "2021-06-01T14:00:00+02:00"}),
+            json!({"title": "Release party", "published": ["2019-12-31", "2022-01-01"]}),
+            json!({"title": "Release date", "published": "soon"}),
+        ];
+        let options = FuseOptions { keys: vec!["title".into(), "published".into()], ..Default::default() };
+        let fuse = Fuse::new(&docs, &options, None);
+        let search = |query: Value| ref_indices(&fuse.logical_search_results(&query, &FuseSearchOptions::default()).unwrap());
+
+        // The second date is at noon UTC, before the first one
+        assert_eq!(search(json!({"published": {"$before": "2021-06-01T12:15:00Z"}})), vec![1, 2]);
+        // Each comparison may be met by a different value of an array
+        assert_eq!(search(json!({"published": {"$after": "2021-06-01T12:00:00Z", "$before": "2021-06-02"}})), vec![0, 2]);
+        assert_eq!(search(json!({"title": "release", "published": {"$after": "2021-12-01"}})), vec![2]);
+        assert!(search(json!({"published": {"$after": "2030-01-01"}})).is_empty());
+
+        // Values indexed by Fuse.js have no timestamp
+        let value = IndexValue { v: "2021-06-01T12:30:00Z".into(), n: 1.0, i: None, t: None, d: None };
+        assert!(compare(&value, Comparison::After, &json!("2021-06-01")));
+        assert!(!compare(&value, Comparison::Before, &json!("2021-06-01")));
+    }
+
+    #[test]
+    fn test_invalid_query() {
+        let docs = books();
+        let fuse = fuse(&docs);
+
+        let query = HashMap::from([("title".to_string(), json!(["old"]))]);
+        assert!(matches!(fuse.logical_search(&query), Err(FuseError::InvalidLogicalQueryForKey(key)) if key == "title"));
+    }
+}
@@ -4,16 +4,87 @@
 //! including configuration options, result handling, and the primary search algorithm.
 
 // Configuration options
+#[cfg(feature = "std")]
 pub(crate) mod options;
 
 // Search result types and handlers
+#[cfg(feature = "std")]
 pub(crate) mod results;
 
 // Scoring functions
+#[cfg(feature = "std")]
 pub(crate) mod compute_score;
 
 // Error messages
 pub(crate) mod error_messages;
 
 // Main search implementation
-pub(crate) mod fuse;
\ No newline at end of file
+#[cfg(feature = "std")]
+pub(crate) mod fuse;
+
+// Fluent construction of instances
+#[cfg(feature = "std")]
+pub(crate) mod builder;
+
+// Per-search metrics
+#[cfg(feature = "std")]
+pub(crate) mod observer;
+
+// Logical query parsing and evaluation
+#[cfg(feature = "std")]
+pub(crate) mod query_parser;
+
+#[cfg(feature = "std")]
+pub(crate) mod logical_search;
+
+// Weighted multi-pattern search
+#[cfg(feature = "std")]
+pub(crate) mod weighted_search;
+
+// Autocomplete suggestions
+#[cfg(feature = "std")]
+pub(crate) mod suggest;
+
+// Resumable searches
+#[cfg(feature = "std")]
+pub(crate) mod search_cursor;
+
+// Lazily produced results
+#[cfg(feature = "std")]
+pub(crate) mod search_iter;
+
+// Breakdown of the score of a document
+#[cfg(feature = "std")]
+pub(crate) mod explain;
+
+// Sharing a mutable instance between threads
+#[cfg(feature = "std")]
+pub(crate) mod fuse_handle;
+
+// Caching the results of repeated searches
+#[cfg(feature = "std")]
+pub(crate) mod result_cache;
+
+// Cancelling stale searches
+#[cfg(feature = "std")]
+pub(crate) mod cancel;
+
+// Searching from async code
+#[cfg(feature = "tokio")]
+pub(crate) mod async_search;
+
+// Searching typed items
+#[cfg(feature = "std")]
+pub(crate) mod searchable;
+
+// Searching items of any serializable type
+#[cfg(feature = "std")]
+pub(crate) mod typed_fuse;
+
+// Saving and loading whole instances
+#[cfg(feature = "std")]
+pub(crate) mod snapshot;
+
+// Fuse.js compatibility fixtures
+#[cfg(feature = "std")]
+pub(crate) mod compat;
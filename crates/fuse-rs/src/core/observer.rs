@@ -0,0 +1,136 @@
+//! Per-search metrics and progress
+//!
+//! A [`SearchObserver`] set in the options is called after every search
+//! with its [`SearchMetrics`], so applications can export counters and
+//! timings (to Prometheus, StatsD, ...) without instrumenting the crate.
+//!
+//! A [`SearchProgress`] set in the options of a single search is called
+//! while the records are scanned, to report progress or stop long searches.
+
+use std::fmt;
+use std::ops::ControlFlow;
+use std::time::Duration;
+
+//----------------------------------------------------------------------
+// Types
+//----------------------------------------------------------------------
+
+/// Metrics of a single search
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SearchMetrics {
+    /// Number of index records considered
+    pub records_scanned: usize,
+
+    /// Number of records left after the search filter, which were matched
+    pub candidates: usize,
+
+    /// Number of records that matched the pattern
+    pub matches: usize,
+
+    /// Number of results returned, after the limit
+    pub results: usize,
+
+    /// Time spent in the search
+    pub elapsed: Duration,
+
+    /// Normalized texts found in the interner's cache. Lookups of concurrent
+    /// searches sharing the interner are counted too.
+    pub interner_hits: u64,
+
+    /// Normalized texts missing from the interner's cache
+    pub interner_misses: u64,
+
+    /// Whether the results came from the result cache, in which case no
+    /// record was scanned and only `results` and `elapsed` are set
+    pub cached: bool,
+}
+
+/// Receives the metrics of every search
+///
+/// Observers are called on the searching thread, after the results are
+/// ready; they should only record the metrics.
+///
+/// # Example
+///
+/// ```
+/// use std::sync::Arc;
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+/// use fuse_rs::{Fuse, FuseOptions, SearchMetrics, SearchObserver};
+/// use serde_json::json;
+///
+/// #[derive(Debug, Default)]
+/// struct Counters {
+///     searches: AtomicUsize,
+///     matches: AtomicUsize,
+/// }
+///
+/// impl SearchObserver for Counters {
+///     fn on_search(&self, metrics: &SearchMetrics) {
+///         self.searches.fetch_add(1, Ordering::Relaxed);
+///         self.matches.fetch_add(metrics.matches, Ordering::Relaxed);
+///     }
+/// }
+///
+/// let counters = Arc::new(Counters::default());
+/// let options = FuseOptions { observer: Some(counters.clone()), ..Default::default() };
+/// let fuse = Fuse::new(&[json!("apple"), json!("apple pie")], &options, None);
+///
+/// fuse.search("apple").unwrap();
+/// assert_eq!(counters.searches.load(Ordering::Relaxed), 1);
+/// assert_eq!(counters.matches.load(Ordering::Relaxed), 2);
+/// ```
+pub trait SearchObserver: fmt::Debug + Send + Sync {
+    /// Called once per successful search.
+    fn on_search(&self, metrics: &SearchMetrics);
+}
+
+/// Follows the progress of a single search
+///
+/// Hooks are called on the searching thread for every record, so they
+/// should be cheap (update a counter, check a flag). Both do nothing by
+/// default.
+///
+/// # Example
+///
+/// ```
+/// use std::ops::ControlFlow;
+/// use std::sync::Arc;
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+/// use fuse_rs::{Fuse, FuseOptions, FuseSearchOptions, SearchProgress};
+/// use serde_json::json;
+///
+/// /// Stops the search after the first match
+/// #[derive(Debug, Default)]
+/// struct FirstMatch(AtomicUsize);
+///
+/// impl SearchProgress for FirstMatch {
+///     fn on_record_scored(&self, _scanned: usize, _total: usize) -> ControlFlow<()> {
+///         if self.0.load(Ordering::Relaxed) > 0 { ControlFlow::Break(()) } else { ControlFlow::Continue(()) }
+///     }
+///
+///     fn on_match_found(&self, _ref_index: usize, _score: f64) {
+///         self.0.fetch_add(1, Ordering::Relaxed);
+///     }
+/// }
+///
+/// let docs = vec![json!("apple"), json!("banana"), json!("apple pie")];
+/// let fuse = Fuse::new(&docs, &FuseOptions::default(), None);
+///
+/// let search_options = FuseSearchOptions { progress: Some(Arc::new(FirstMatch::default())), ..Default::default() };
+/// assert_eq!(fuse.search_with_options("apple", &search_options).unwrap(), vec![json!("apple")]);
+/// ```
+pub trait SearchProgress: fmt::Debug + Send + Sync {
+    /// Called after each record is scanned, whether or not it matched, with
+    /// the number of records scanned so far and the number of records in the
+    /// index.
+    ///
+    /// Returning `ControlFlow::Break` stops the search, which then returns
+    /// the results found so far.
+    fn on_record_scored(&self, _scanned: usize, _total: usize) -> ControlFlow<()> {
+        ControlFlow::Continue(())
+    }
+
+    /// Called for each record that matches, with its reference index and
+    /// combined score, before results are sorted, cut off and limited.
+    fn on_match_found(&self, _ref_index: usize, _score: f64) {}
+}
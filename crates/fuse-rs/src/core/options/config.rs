@@ -1,13 +1,56 @@
-use crate::core::options::keys::FuseOptionKey;
+use crate::core::compute_score::{Scorer, default_scorer};
+use crate::core::observer::SearchObserver;
+use crate::core::options::keys::{FuseOptionKey, KeyAnalyzer};
 use crate::core::options::sort::{FuseSortFunction, default_sort_fn, default_sort_fn_wrapper};
-use crate::helpers::get::{self, GetFn, default_get_fn_wrapper};
+use crate::helpers::get::{GetFn, default_get_fn_wrapper};
+use crate::helpers::interner::Interner;
+use crate::helpers::normalize::Normalizer;
+use crate::helpers::phonetic::PhoneticAlgorithm;
+use crate::helpers::stemmer::Stemmer;
+use crate::helpers::stop_words::StopWords;
+#[cfg(feature = "stemming")]
+use rust_stemmers::Algorithm as StemmerLanguage;
+use crate::helpers::synonyms::SynonymProvider;
+use crate::helpers::tokenizer::{Tokenizer, default_tokenizer};
+use crate::helpers::transliterate::TransliterationTable;
+use crate::search::bitmap::compute_score::DistanceCurve;
+use crate::search::edit_distance::MatchAlgorithm;
+use crate::tools::norm::DEFAULT_CACHE_SIZE as DEFAULT_NORM_CACHE_SIZE;
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 use std::cmp::max;
+use std::sync::Arc;
 
 //----------------------------------------------------------------------
 // Helpers
 //----------------------------------------------------------------------
 
+/// Converts a camelCase option name to snake_case.
+fn snake_case(name: &str) -> String {
+    let mut snake = String::with_capacity(name.len() + 4);
+
+    for c in name.chars() {
+        if c.is_ascii_uppercase() {
+            snake.push('_');
+            snake.push(c.to_ascii_lowercase());
+        } else {
+            snake.push(c);
+        }
+    }
+
+    snake
+}
+
+/// Serde default for [`FuseOptions::norm_cache_size`].
+fn default_norm_cache_size() -> usize {
+    DEFAULT_NORM_CACHE_SIZE
+}
+
+/// Serde default for [`FuseOptions::index_threads`].
+fn default_index_threads() -> usize {
+    1
+}
+
 //----------------------------------------------------------------------
 // Configuration Options
 //----------------------------------------------------------------------
@@ -39,6 +82,32 @@ pub struct FuseOptions<'a> {
     #[serde(default)]
     pub ignore_diacritics: bool,
     
+    /// Characters to transliterate before matching (e.g. `ß` to `ss`, Cyrillic to Latin).
+    /// Applied to both the indexed text and the pattern, after case folding.
+    /// See [`default_transliteration_table`](crate::default_transliteration_table).
+    /// Default: `None` (no transliteration)
+    #[serde(default)]
+    pub transliteration: Option<TransliterationTable>,
+
+    /// Reduces words to their stem after the other normalization steps, for both
+    /// indexed values and patterns. Enable the `stemming` feature for Snowball stemmers.
+    /// Default: `None` (no stemming)
+    #[serde(skip)]
+    pub stemmer: Option<Arc<dyn Stemmer>>,
+
+    /// Language of the Snowball stemmer used when `stemmer` is not set, so
+    /// that stemming can be configured in JSON options (`"stemmingLanguage": "English"`).
+    /// Default: `None`
+    #[cfg(feature = "stemming")]
+    #[serde(default)]
+    pub stemming_language: Option<StemmerLanguage>,
+
+    /// Words left out of the field-length norm and removed from texts and patterns
+    /// before matching. See [`StopWords::for_language`](crate::StopWords::for_language).
+    /// Default: `None` (no stop words)
+    #[serde(default)]
+    pub stop_words: Option<StopWords>,
+
     /// When `true`, the score for each match is included in the result. Default: `false`
     #[serde(default)]
     pub include_score: bool,
@@ -56,7 +125,7 @@ pub struct FuseOptions<'a> {
     pub should_sort: bool,
 
     /// Function used to sort the results. Takes two search result arguments.
-    /// Default: sort by score ascending, then by index ascending
+    /// Default: [`default_sort_fn`](crate::default_sort_fn), sorting by score ascending, then by index ascending
     #[serde(skip, default = "default_sort_fn_wrapper")]
     pub sort_fn: FuseSortFunction,
 
@@ -87,10 +156,37 @@ pub struct FuseOptions<'a> {
     #[serde(default)]
     pub distance: usize,
 
+    /// How the score of a match grows with its distance from `location`.
+    /// Default: [`DistanceCurve::Linear`](crate::DistanceCurve::Linear)
+    #[serde(default)]
+    pub distance_curve: DistanceCurve,
+
+    /// Algorithm used for fuzzy matching. Edit-distance algorithms score
+    /// their edits like bitap errors, so thresholds carry over.
+    /// Default: [`MatchAlgorithm::Bitap`](crate::MatchAlgorithm::Bitap)
+    #[serde(default)]
+    pub algorithm: MatchAlgorithm,
+
     /// When `true`, enables the extended search mode which allows for more flexibility. Default: `false`
     #[serde(default)]
     pub use_extended_search: bool,
 
+    /// Algorithm used by the `~~` (sounds like) operator of extended search.
+    /// Default: [`PhoneticAlgorithm::Soundex`](crate::PhoneticAlgorithm::Soundex)
+    #[serde(default)]
+    pub phonetic_algorithm: PhoneticAlgorithm,
+
+    /// Supplies synonyms of the pattern, which are searched as alternatives of it.
+    /// See [`SynonymMap`](crate::SynonymMap).
+    /// Default: `None` (no synonyms)
+    #[serde(skip)]
+    pub synonyms: Option<Arc<dyn SynonymProvider>>,
+
+    /// Called with the metrics of every search. See [`SearchObserver`](crate::SearchObserver).
+    /// Default: `None`
+    #[serde(skip)]
+    pub observer: Option<Arc<dyn SearchObserver>>,
+
     /// Function used to retrieve a value from an item for comparison.
     /// Default: Basic property accessor function
     #[serde(skip, default = "default_get_fn_wrapper")]
@@ -99,19 +195,98 @@ pub struct FuseOptions<'a> {
     /// When `true`, search will ignore `location` and `distance`. Default: `false`
     #[serde(default)]
     pub ignore_location: bool,
+
+    /// With `ignore_location`, the bitap matcher only scans the text around
+    /// the occurrences of the pattern characters that a match within the
+    /// threshold must keep. Results are the same; long texts are searched
+    /// faster, the more so the lower the threshold. Default: `false`
+    #[serde(default)]
+    pub search_window: bool,
     
     /// When `true`, similarity scoring is disabled and field length normalization is ignored. Default: `false`
     #[serde(default)]
     pub ignore_field_norm: bool,
+
+    /// Folds the scores of the values a document matched in into its score.
+    /// Default: [`DefaultScorer`](crate::DefaultScorer), as in Fuse.js
+    #[serde(skip, default = "default_scorer")]
+    pub scorer: Arc<dyn Scorer>,
     
     /// Determines the importance of field length normalization. Default: `1`
     #[serde(default)]
     pub field_norm_weight: f64,
+
+    /// Splits field values into tokens when computing the field-length norm,
+    /// and patterns and values into tokens with `tokenize`.
+    /// Default: [`WhitespaceTokenizer`](crate::WhitespaceTokenizer)
+    #[serde(skip, default = "default_tokenizer")]
+    pub tokenizer: Arc<dyn Tokenizer>,
+
+    /// When `true`, each token of the pattern is also matched against each
+    /// token of a value, and the average of those scores is averaged with
+    /// the score of the whole pattern, like in Fuse.js 3. A value matching
+    /// only some of the tokens is then found too. Ignored by extended search.
+    /// Default: `false`
+    #[serde(default)]
+    pub tokenize: bool,
+
+    /// With `tokenize`, only values in which every token of the pattern
+    /// matches are found, and tokens of a value that no pattern token
+    /// matches don't count towards the score. Default: `false`
+    #[serde(default)]
+    pub match_all_tokens: bool,
+
+    /// Stores normalized field values and pattern chunks once, shared by all
+    /// searches using these options. See [`Interner`](crate::Interner).
+    /// Default: `None` (texts are normalized on every search)
+    #[serde(skip)]
+    pub interner: Option<Arc<Interner>>,
     
     /// Maximum pattern length. If the pattern exceeds this length, a PatternLengthTooLarge error is returned.
     /// Default: `None` (no limit)
     #[serde(default)]
     pub max_pattern_length: Option<usize>,
+
+    /// Number of recent searches whose results are kept and returned again
+    /// when the same search is repeated, e.g. by a debounced typeahead.
+    /// Searches with a `filter` or `progress` hooks are never cached.
+    /// Default: `0` (no caching)
+    #[serde(default)]
+    pub result_cache_size: usize,
+
+    /// When `true`, the index keeps posting lists of the trigrams of the indexed
+    /// values, and searches skip the records that cannot match the pattern
+    /// instead of scoring them. Results are unchanged; the lists take memory in
+    /// proportion to the indexed text. Pruning applies to plain patterns of up to
+    /// 64 characters (128 with `wide-bitmap`) without synonyms, when
+    /// normalization only folds case.
+    /// Default: `false`
+    #[serde(default)]
+    pub use_ngram_index: bool,
+
+    /// When `true`, fuzzy matching counts extended grapheme clusters instead
+    /// of characters, so that an emoji sequence or a letter with combining
+    /// marks is a single unit in scores, `min_match_char_length` and match
+    /// indices. Extended search queries still count characters, and n-gram
+    /// pruning is skipped.
+    /// Default: `false`
+    #[cfg(feature = "grapheme-clusters")]
+    #[serde(default)]
+    pub use_grapheme_clusters: bool,
+
+    /// Number of token counts whose field-length norm is cached. Threads
+    /// indexing concurrently share the cache without locking; values with
+    /// more tokens have their norm computed each time.
+    /// Default: `256`
+    #[serde(default = "default_norm_cache_size")]
+    pub norm_cache_size: usize,
+
+    /// Number of threads batches of 4096 documents or more are indexed on,
+    /// when the collection is set or with `add_all`. `0` uses one thread per
+    /// available CPU. Smaller batches are always indexed on the calling thread.
+    /// Default: `1`
+    #[serde(default = "default_index_threads")]
+    pub index_threads: usize,
 }
 
 impl<'a> Default for FuseOptions<'a> {
@@ -119,6 +294,11 @@ impl<'a> Default for FuseOptions<'a> {
         Self {
             is_case_sensitive: false,
             ignore_diacritics: false,
+            transliteration: None,
+            stemmer: None,
+            #[cfg(feature = "stemming")]
+            stemming_language: None,
+            stop_words: None,
             include_score: false,
             keys: Vec::new(),
             should_sort: true,
@@ -129,12 +309,29 @@ impl<'a> Default for FuseOptions<'a> {
             location: 0,
             threshold: 0.6,
             distance: 100,
+            distance_curve: DistanceCurve::Linear,
+            algorithm: MatchAlgorithm::Bitap,
             use_extended_search: false,
-            get_fn: get::get,
+            phonetic_algorithm: PhoneticAlgorithm::Soundex,
+            synonyms: None,
+            observer: None,
+            get_fn: GetFn::default(),
             ignore_location: false,
+            search_window: false,
             ignore_field_norm: false,
+            scorer: default_scorer(),
             field_norm_weight: 1.0,
+            tokenizer: default_tokenizer(),
+            tokenize: false,
+            match_all_tokens: false,
+            interner: None,
             max_pattern_length: None,
+            result_cache_size: 0,
+            use_ngram_index: false,
+            #[cfg(feature = "grapheme-clusters")]
+            use_grapheme_clusters: false,
+            norm_cache_size: DEFAULT_NORM_CACHE_SIZE,
+            index_threads: 1,
         }
     }
 }
@@ -145,6 +342,58 @@ impl<'a> FuseOptions<'a> {
         Default::default()
     }
 
+    /// Returns the text normalization pipeline configured by these options
+    pub fn normalizer(&self) -> Normalizer<'_> {
+        Normalizer::new(self)
+    }
+
+    /// Returns these options with the text processing of `analyzer`.
+    pub(crate) fn with_analyzer(&self, analyzer: &KeyAnalyzer) -> Self {
+        Self {
+            is_case_sensitive: analyzer.is_case_sensitive,
+            ignore_diacritics: analyzer.ignore_diacritics,
+            stemmer: match analyzer.phonetic {
+                Some(algorithm) => Some(Arc::new(algorithm)),
+                None => analyzer.stemmer.clone(),
+            },
+            #[cfg(feature = "stemming")]
+            stemming_language: None,
+            stop_words: analyzer.stop_words.clone(),
+            ..self.clone()
+        }
+    }
+
+    /// Builds options from a JSON object, e.g. options passed through bindings
+    ///
+    /// Option names may be written in snake_case (`include_score`) or in
+    /// camelCase like in Fuse.js (`includeScore`). Options that aren't given
+    /// keep the values of [`FuseOptions::default`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fuse_rs::FuseOptions;
+    /// use serde_json::json;
+    ///
+    /// let options = FuseOptions::from_json(json!({ "keys": ["title"], "includeScore": true })).unwrap();
+    /// assert!(options.include_score);
+    /// assert_eq!(options.threshold, 0.6);
+    /// ```
+    pub fn from_json(options: Value) -> Result<Self, serde_json::Error> {
+        let mut merged = match serde_json::to_value(Self::default())? {
+            Value::Object(defaults) => defaults,
+            _ => Map::new(),
+        };
+
+        if let Value::Object(options) = options {
+            for (name, value) in options {
+                merged.insert(snake_case(&name), value);
+            }
+        }
+
+        serde_json::from_value(Value::Object(merged))
+    }
+
     /// Validates and normalizes the options
     ///
     /// This ensures that options are within valid ranges and consistent with each other.
@@ -171,3 +420,92 @@ impl<'a> FuseOptions<'a> {
         opts
     }
 }
+
+//----------------------------------------------------------------------
+// Fuzzing Support
+//----------------------------------------------------------------------
+
+/// Options with arbitrary values, including out-of-range ones; functions
+/// and trait objects keep their defaults
+#[cfg(feature = "arbitrary")]
+impl<'a, 'u> arbitrary::Arbitrary<'u> for FuseOptions<'a> {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'u>) -> arbitrary::Result<Self> {
+        Ok(Self {
+            is_case_sensitive: u.arbitrary()?,
+            ignore_diacritics: u.arbitrary()?,
+            transliteration: u.arbitrary()?,
+            stop_words: u.arbitrary::<Option<Vec<String>>>()?.map(StopWords::new),
+            include_score: u.arbitrary()?,
+            keys: u.arbitrary()?,
+            should_sort: u.arbitrary()?,
+            include_matches: u.arbitrary()?,
+            find_all_matches: u.arbitrary()?,
+            min_match_char_length: u.arbitrary()?,
+            location: u.arbitrary()?,
+            threshold: u.arbitrary()?,
+            distance: u.arbitrary()?,
+            distance_curve: u.arbitrary()?,
+            algorithm: u.arbitrary()?,
+            use_extended_search: u.arbitrary()?,
+            phonetic_algorithm: u.arbitrary()?,
+            ignore_location: u.arbitrary()?,
+            search_window: u.arbitrary()?,
+            ignore_field_norm: u.arbitrary()?,
+            field_norm_weight: u.arbitrary()?,
+            tokenize: u.arbitrary()?,
+            match_all_tokens: u.arbitrary()?,
+            max_pattern_length: u.arbitrary()?,
+            ..Default::default()
+        })
+    }
+}
+
+//----------------------------------------------------------------------
+// Tests
+//----------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_snake_case() {
+        assert_eq!(snake_case("includeScore"), "include_score");
+        assert_eq!(snake_case("minMatchCharLength"), "min_match_char_length");
+        assert_eq!(snake_case("keys"), "keys");
+    }
+
+    #[test]
+    fn test_from_json_keeps_defaults() {
+        let options = FuseOptions::from_json(json!({
+            "keys": ["title"],
+            "includeScore": true,
+            "ignore_location": true
+        }))
+        .unwrap();
+
+        assert!(options.include_score);
+        assert!(options.ignore_location);
+        assert!(options.should_sort);
+        assert_eq!(options.threshold, FuseOptions::default().threshold);
+        assert_eq!(options.keys.len(), 1);
+    }
+
+    #[test]
+    fn test_deserialize_without_norm_cache_size() {
+        let options: FuseOptions = serde_json::from_value(json!({})).unwrap();
+        assert_eq!(options.norm_cache_size, DEFAULT_NORM_CACHE_SIZE);
+
+        let mut value = serde_json::to_value(FuseOptions {
+            include_score: true,
+            ..Default::default()
+        })
+        .unwrap();
+        value.as_object_mut().unwrap().remove("norm_cache_size");
+
+        let options: FuseOptions = serde_json::from_value(value).unwrap();
+        assert!(options.include_score);
+        assert_eq!(options.norm_cache_size, DEFAULT_NORM_CACHE_SIZE);
+    }
+}
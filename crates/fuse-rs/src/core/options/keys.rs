@@ -7,6 +7,14 @@
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::borrow::Cow;
+use std::fmt;
+use std::ops::Deref;
+use std::sync::Arc;
+
+use crate::helpers::get::GetValue;
+use crate::helpers::phonetic::PhoneticAlgorithm;
+use crate::helpers::stemmer::Stemmer;
+use crate::helpers::stop_words::StopWords;
 
 //----------------------------------------------------------------------
 // Key Definition Types
@@ -15,8 +23,10 @@ use std::borrow::Cow;
 /// Represents the name of a key to be used for searching
 /// 
 /// Can be either a single string or an array of strings to represent
-/// nested paths within a document.
+/// nested paths within a document, written as such in JSON options
+/// (`{"name": "title"}` or `{"name": ["author", "name"]}`).
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
 pub enum FuseOptionKeyName<'a> {
     /// A single string representing a key name (e.g., "title")
     String(Cow<'a, str>),
@@ -35,10 +45,52 @@ impl<'a> FuseOptionKeyName<'a> {
     }
 }
 
-/// Function type for retrieving values from a JSON structure
+/// Optional custom accessor of a key, extracting its value from complex
+/// structures
+pub type FuseKeyValueGetter = Option<KeyGetFn>;
+
+/// Function extracting the value of a key from a document
+///
+/// Wraps a closure, so it can capture state. Clones share the closure, and
+/// it is called like a function: `get_fn(doc)`. Returning a
+/// `GetValue::Array` indexes every value, like an array found by a path;
+/// returning `None` leaves the key to its fallback.
+///
+/// # Example
+///
+/// ```
+/// use fuse_rs::{GetValue, KeyGetFn};
+/// use serde_json::json;
+///
+/// // Tags computed from a comma-separated field
+/// let get_fn = KeyGetFn::new(|doc| {
+///     let tags = doc["tags"].as_str()?.split(',').map(|tag| GetValue::String(tag.trim().into()));
+///     Some(GetValue::Array(tags.collect()))
+/// });
 ///
-/// This allows custom accessor functions to extract values from complex structures.
-pub type FuseKeyValueGetter = Option<fn(&Value) -> &str>;
+/// let tags = get_fn(&json!({"tags": "sci-fi, space"}));
+/// assert_eq!(tags, Some(GetValue::Array(vec![GetValue::String("sci-fi".into()), GetValue::String("space".into())])));
+/// assert_eq!(get_fn(&json!({})), None);
+/// ```
+#[derive(Clone)]
+pub struct KeyGetFn(Arc<DynKeyGetFn>);
+
+/// The closure wrapped by a `KeyGetFn`
+type DynKeyGetFn = dyn Fn(&Value) -> Option<GetValue> + Send + Sync;
+
+/// Fallback used when a key finds no value in a document
+///
+/// Without a fallback, documents missing the key's path are invisible for
+/// that key.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum FuseKeyFallback<'a> {
+    /// A fixed value indexed in place of the missing one (strings, numbers,
+    /// booleans or arrays of them)
+    Value(Value),
+
+    /// Alternative paths tried in order; the first one that finds a value is used
+    Paths(Vec<FuseOptionKeyName<'a>>),
+}
 
 /// A complex key configuration with name and optional weight
 /// 
@@ -56,6 +108,94 @@ pub struct FuseOptionKeyObject<'a> {
     /// Custom function to extract values for this key
     #[serde(skip)]
     pub get_fn: FuseKeyValueGetter,
+
+    /// Value or paths used when the key's path finds nothing (default: none)
+    #[serde(default)]
+    pub fallback: Option<FuseKeyFallback<'a>>,
+
+    /// Text processing of this key's values, in place of the options' own
+    /// (default: none)
+    #[serde(default)]
+    pub analyzer: Option<KeyAnalyzer>,
+
+    /// Whether the key's values are indexed (default: `true`). The values of
+    /// a key that is not indexed are never read, so it never matches.
+    #[serde(default = "enabled")]
+    pub index: bool,
+
+    /// Whether searches match the key's values (default: `true`). The values
+    /// of a key that is not searchable are still indexed, for logical queries
+    /// and for searches naming the key in
+    /// [`FuseSearchOptions::keys`](crate::FuseSearchOptions::keys), but
+    /// other searches leave them out of matching and scoring.
+    #[serde(default = "enabled")]
+    pub searchable: bool,
+}
+
+/// Text processing of the values of one key
+///
+/// A key with an analyzer matches its values, and the patterns searched in
+/// them, with the case sensitivity, diacritics removal, stemmer (or phonetic
+/// codes) and stop words of the analyzer instead of those of
+/// [`FuseOptions`](crate::FuseOptions). The default analyzer folds case and
+/// does nothing else. Other keys, the field-length norm and transliteration
+/// follow the options.
+///
+/// # Example
+///
+/// ```
+/// use std::borrow::Cow;
+/// use fuse_rs::{
+///     FuseOptionKey,
+///     FuseOptionKeyName,
+///     FuseOptionKeyObject,
+///     KeyAnalyzer,
+///     PhoneticAlgorithm,
+///     StopWordLanguage,
+///     StopWords
+/// };
+///
+/// let key = |name: &str, analyzer| FuseOptionKey::KeyObject(FuseOptionKeyObject {
+///     name: Cow::Owned(FuseOptionKeyName::String(name.to_string().into())),
+///     weight: None,
+///     get_fn: None,
+///     fallback: None,
+///     analyzer: Some(analyzer),
+///     index: true,
+///     searchable: true,
+/// });
+///
+/// let keys = vec![
+///     key("description", KeyAnalyzer {
+///         stop_words: Some(StopWords::for_language(StopWordLanguage::English)),
+///         ..Default::default()
+///     }),
+///     // SKUs are matched as written
+///     key("sku", KeyAnalyzer { is_case_sensitive: true, ..Default::default() }),
+///     // Names are matched by how they sound
+///     key("author", KeyAnalyzer { phonetic: Some(PhoneticAlgorithm::Soundex), ..Default::default() }),
+/// ];
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct KeyAnalyzer {
+    /// Whether case is preserved instead of folded (default: `false`)
+    pub is_case_sensitive: bool,
+
+    /// Whether diacritics are removed (default: `false`)
+    pub ignore_diacritics: bool,
+
+    /// Reduces words to their stem (default: none)
+    #[serde(skip)]
+    pub stemmer: Option<Arc<dyn Stemmer>>,
+
+    /// Words removed before matching (default: none)
+    pub stop_words: Option<StopWords>,
+
+    /// Replaces every word with its phonetic code, in place of the stemmer,
+    /// so that names spelled differently but pronounced alike ("Smyth",
+    /// "Smith") match exactly (default: none)
+    pub phonetic: Option<PhoneticAlgorithm>,
 }
 
 /// Defines which keys in the data to search
@@ -94,3 +234,78 @@ impl<'a> Default for FuseOptionKey<'a> {
         Self::StringArray(Vec::new())
     }
 }
+
+impl<'a> From<&'a str> for FuseOptionKey<'a> {
+    fn from(name: &'a str) -> Self {
+        Self::String(Cow::Borrowed(name))
+    }
+}
+
+impl KeyGetFn {
+    /// Wraps a key getter closure.
+    pub fn new<F>(get_fn: F) -> Self
+    where
+        F: Fn(&Value) -> Option<GetValue> + Send + Sync + 'static,
+    {
+        Self(Arc::new(get_fn))
+    }
+}
+
+impl Deref for KeyGetFn {
+    type Target = DynKeyGetFn;
+
+    fn deref(&self) -> &Self::Target {
+        &*self.0
+    }
+}
+
+impl fmt::Debug for KeyGetFn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("KeyGetFn")
+    }
+}
+
+//----------------------------------------------------------------------
+// Implementation Details
+//----------------------------------------------------------------------
+
+/// Default of the flags of keys that are on unless turned off.
+fn enabled() -> bool {
+    true
+}
+
+//----------------------------------------------------------------------
+// Fuzzing Support
+//----------------------------------------------------------------------
+
+#[cfg(feature = "arbitrary")]
+impl<'a, 'u> arbitrary::Arbitrary<'u> for FuseOptionKeyName<'a> {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'u>) -> arbitrary::Result<Self> {
+        Ok(if u.arbitrary()? {
+            Self::String(Cow::Owned(u.arbitrary()?))
+        } else {
+            Self::StringArray(u.arbitrary::<Vec<String>>()?.into_iter().map(Cow::Owned).collect())
+        })
+    }
+}
+
+/// Keys with any name, weight and fallback paths; fallback values,
+/// getters and analyzers are left out
+#[cfg(feature = "arbitrary")]
+impl<'a, 'u> arbitrary::Arbitrary<'u> for FuseOptionKey<'a> {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'u>) -> arbitrary::Result<Self> {
+        Ok(match u.int_in_range(0..=2)? {
+            0 => Self::KeyObject(FuseOptionKeyObject {
+                name: Cow::Owned(u.arbitrary()?),
+                weight: u.arbitrary()?,
+                get_fn: None,
+                fallback: u.arbitrary::<Option<Vec<FuseOptionKeyName>>>()?.map(FuseKeyFallback::Paths),
+                analyzer: None,
+                index: u.arbitrary()?,
+                searchable: u.arbitrary()?,
+            }),
+            1 => Self::String(Cow::Owned(u.arbitrary()?)),
+            _ => Self::StringArray(u.arbitrary::<Vec<String>>()?.into_iter().map(Cow::Owned).collect()),
+        })
+    }
+}
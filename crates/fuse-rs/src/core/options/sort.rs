@@ -3,6 +3,8 @@
 //! This module provides types and functions for sorting search results
 //! according to relevance scores and other criteria.
 
+use std::cmp::Ordering;
+
 use crate::core::results::match_result::FuseSortFunctionArg;
 
 //----------------------------------------------------------------------
@@ -29,6 +31,10 @@ pub type FuseSortFunction = fn(&FuseSortFunctionArg, &FuseSortFunctionArg) -> i3
 /// This function sorts results primarily by score (ascending),
 /// and then by original index (ascending) when scores are equal.
 ///
+/// Scores are compared with a total order, so the result is deterministic on
+/// every platform: scores are only tied when they are exactly equal, and `NaN`
+/// scores always sort after every other score.
+///
 /// # Arguments
 ///
 /// * `a` - First search result to compare
@@ -36,15 +42,24 @@ pub type FuseSortFunction = fn(&FuseSortFunctionArg, &FuseSortFunctionArg) -> i3
 ///
 /// # Returns
 ///
-/// An integer indicating the relative order: -1 for a before b, 1 for a after b
+/// An integer indicating the relative order: -1 for a before b, 1 for a after b,
+/// and 0 only when both the scores and the indices are equal
 #[inline]
 pub fn default_sort_fn(a: &FuseSortFunctionArg, b: &FuseSortFunctionArg) -> i32 {
-    if (a.score - b.score).abs() < f64::EPSILON {
-        // When scores are equal, sort by index
-        if a.idx < b.idx { -1 } else { 1 }
-    } else {
-        // Primary sort by score (lower is better)
-        if a.score < b.score { -1 } else { 1 }
+    let ordering = compare_scores(a.score, b.score).then_with(|| a.idx.cmp(&b.idx));
+
+    match ordering {
+        Ordering::Less => -1,
+        Ordering::Equal => 0,
+        Ordering::Greater => 1,
+    }
+}
+
+/// Compares two scores (lower is better), placing `NaN` last.
+fn compare_scores(a: f64, b: f64) -> Ordering {
+    match (a.is_nan(), b.is_nan()) {
+        (false, false) => a.total_cmp(&b),
+        (a_nan, b_nan) => a_nan.cmp(&b_nan),
     }
 }
 
@@ -56,3 +71,51 @@ pub fn default_sort_fn(a: &FuseSortFunctionArg, b: &FuseSortFunctionArg) -> i32
 pub fn default_sort_fn_wrapper() -> FuseSortFunction {
     default_sort_fn
 }
+
+//----------------------------------------------------------------------
+// Tests
+//----------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::results::match_result::FuseSortFunctionItem;
+    use std::collections::HashMap;
+
+    fn arg(idx: usize, score: f64) -> FuseSortFunctionArg {
+        FuseSortFunctionArg {
+            idx,
+            item: FuseSortFunctionItem { fields: HashMap::new() },
+            score,
+            matches: None,
+        }
+    }
+
+    #[test]
+    fn test_default_sort_by_score_then_index() {
+        assert_eq!(default_sort_fn(&arg(1, 0.1), &arg(0, 0.2)), -1);
+        assert_eq!(default_sort_fn(&arg(0, 0.2), &arg(1, 0.1)), 1);
+        assert_eq!(default_sort_fn(&arg(0, 0.1), &arg(1, 0.1)), -1);
+        assert_eq!(default_sort_fn(&arg(1, 0.1), &arg(0, 0.1)), 1);
+        assert_eq!(default_sort_fn(&arg(0, 0.1), &arg(0, 0.1)), 0);
+    }
+
+    #[test]
+    fn test_default_sort_nearly_equal_scores_are_ordered() {
+        let a = arg(1, 0.1);
+        let b = arg(0, 0.1 + f64::EPSILON / 2.0);
+        assert_eq!(default_sort_fn(&a, &b), -1);
+    }
+
+    #[test]
+    fn test_default_sort_nan_last() {
+        assert_eq!(default_sort_fn(&arg(0, f64::NAN), &arg(1, 1.0)), 1);
+        assert_eq!(default_sort_fn(&arg(0, 1.0), &arg(1, -f64::NAN)), -1);
+        assert_eq!(default_sort_fn(&arg(0, f64::NAN), &arg(1, -f64::NAN)), -1);
+
+        let mut args = [arg(0, f64::NAN), arg(1, 0.5), arg(2, 0.1), arg(3, f64::NAN)];
+        args.sort_by(|a, b| default_sort_fn(a, b).cmp(&0));
+        let order: Vec<usize> = args.iter().map(|a| a.idx).collect();
+        assert_eq!(order, vec![2, 1, 0, 3]);
+    }
+}
@@ -0,0 +1,372 @@
+//! Logical query parsing
+//!
+//! Logical queries combine patterns searched in single keys with `$and` and
+//! `$or`, as in Fuse.js:
+//!
+//! ```json
+//! { "$and": [{ "author": "tolkien" }, { "$or": [{ "title": "^lord" }, { "title": "^hob" }] }] }
+//! ```
+//!
+//! An object with several keys and no operator is an implicit `$and` of its
+//! entries, and `{ "$path": [...], "$val": "..." }` names a key whose path
+//! segments contain dots.
+//!
+//! Instead of a pattern, a key can be given comparisons with `$gt`, `$gte`,
+//! `$lt`, `$lte` and `$eq`, to filter on numeric or boolean fields alongside
+//! the fuzzy conditions: `{ "$and": [{ "title": "dune" }, { "year": { "$gte": 2015 } }] }`.
+//! Fields holding RFC 3339 dates are filtered with `$before` and `$after`:
+//! `{ "published": { "$after": "2020-01-01T00:00:00Z" } }`.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::core::error_messages::FuseError;
+use crate::helpers::date::parse_rfc3339;
+use crate::tools::key_store::{create_key_id, create_key_path};
+
+//----------------------------------------------------------------------
+// Constants
+//----------------------------------------------------------------------
+
+/// Operator whose children must all match
+const AND: &str = "$and";
+
+/// Operator of which at least one child must match
+const OR: &str = "$or";
+
+/// Path of a leaf given explicitly
+const PATH: &str = "$path";
+
+/// Pattern of a leaf given with `$path`
+const VAL: &str = "$val";
+
+/// Operators of the comparisons, in the order of [`Comparison`]
+const COMPARISONS: [(&str, Comparison); 7] = [
+    ("$gt", Comparison::Gt),
+    ("$gte", Comparison::Gte),
+    ("$lt", Comparison::Lt),
+    ("$lte", Comparison::Lte),
+    ("$eq", Comparison::Eq),
+    ("$before", Comparison::Before),
+    ("$after", Comparison::After),
+];
+
+//----------------------------------------------------------------------
+// Types
+//----------------------------------------------------------------------
+
+/// A parsed logical query
+///
+/// Expressions can also be built directly, or deserialized from their own
+/// JSON form (`{"and": [{"leaf": {"key_id": "title", "pattern": "old"}}]}`),
+/// and searched with [`Fuse::search_expression`](crate::Fuse::search_expression).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ParsedExpression {
+    /// A pattern searched in the values of one key
+    Leaf {
+        /// Id of the key, its path segments joined with dots (dots within
+        /// segments are escaped)
+        key_id: String,
+
+        /// The searched pattern
+        pattern: String,
+    },
+
+    /// A comparison of the values of one key with a constant
+    ///
+    /// Comparisons filter records without contributing to their score.
+    Compare {
+        /// Id of the key, as for leaves
+        key_id: String,
+
+        /// How the values are compared
+        op: Comparison,
+
+        /// The constant: a number, or for `Eq` also a boolean or a string,
+        /// or for `Before` and `After` an RFC 3339 date string
+        value: Value,
+    },
+
+    /// Expressions that must all match
+    And(Vec<ParsedExpression>),
+
+    /// Expressions of which at least one must match
+    Or(Vec<ParsedExpression>),
+}
+
+/// The comparison of a [`ParsedExpression::Compare`]
+///
+/// A record matches when one of the values of the key compares as required.
+/// Numbers are compared numerically, including values indexed from numeric
+/// strings; `Eq` also compares booleans and strings exactly. `Before` and
+/// `After` compare the instants of RFC 3339 dates, whatever their offsets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Comparison {
+    /// Greater than (`$gt`)
+    Gt,
+
+    /// Greater than or equal (`$gte`)
+    Gte,
+
+    /// Less than (`$lt`)
+    Lt,
+
+    /// Less than or equal (`$lte`)
+    Lte,
+
+    /// Equal (`$eq`)
+    Eq,
+
+    /// Strictly earlier than a date (`$before`)
+    Before,
+
+    /// Strictly later than a date (`$after`)
+    After,
+}
+
+//----------------------------------------------------------------------
+// Free Functions
+//----------------------------------------------------------------------
+
+/// Parses a logical query.
+///
+/// Operators whose value is not an array have no children; an `$and` without
+/// children matches nothing.
+///
+/// # Returns
+///
+/// The expression, `FuseError::InvalidLogicalQueryForKey` if a pattern is
+/// neither a string nor an object of comparisons with valid constants, or
+/// `FuseError::InvalidLogicalQuery` if a node is not an object or is empty.
+///
+/// # Example
+///
+/// ```
+/// use fuse_rs::{ParsedExpression, parse_query};
+/// use serde_json::json;
+///
+/// let expression = parse_query(&json!({"title": "old", "author.name": "scalzi"})).unwrap();
+///
+/// assert_eq!(
+///     expression,
+///     ParsedExpression::And(vec![
+///         ParsedExpression::Leaf { key_id: "author.name".into(), pattern: "scalzi".into() },
+///         ParsedExpression::Leaf { key_id: "title".into(), pattern: "old".into() },
+///     ])
+/// );
+/// ```
+pub fn parse_query(query: &Value) -> Result<ParsedExpression, FuseError> {
+    let node = as_node(query)?;
+
+    if is_expression(node) {
+        parse_node(node)
+    } else {
+        implicit_and(node)
+    }
+}
+
+//----------------------------------------------------------------------
+// Implementation Details
+//----------------------------------------------------------------------
+
+/// Parses an object of the query.
+fn parse_node(node: &Map<String, Value>) -> Result<ParsedExpression, FuseError> {
+    let is_path = node.contains_key(PATH);
+
+    if !is_path && node.len() > 1 && !is_expression(node) {
+        return implicit_and(node);
+    }
+
+    if !is_expression(node) {
+        return parse_leaf(node, is_path);
+    }
+
+    let mut children = Vec::new();
+    for child in node.values().filter_map(Value::as_array).flatten() {
+        children.push(parse_node(as_node(child)?)?);
+    }
+
+    // Keys are sorted, so `$and` wins when both operators are given
+    if node.contains_key(AND) {
+        Ok(ParsedExpression::And(children))
+    } else {
+        Ok(ParsedExpression::Or(children))
+    }
+}
+
+/// Parses a leaf, `{ key: pattern }` or `{ $path, $val }`.
+fn parse_leaf(node: &Map<String, Value>, is_path: bool) -> Result<ParsedExpression, FuseError> {
+    let (path, pattern) = if is_path {
+        let path = match &node[PATH] {
+            Value::String(key) => create_key_path(key),
+            Value::Array(segments) => segments
+                .iter()
+                .map(|segment| segment.as_str().map(String::from))
+                .collect::<Option<Vec<_>>>()
+                .ok_or_else(|| FuseError::InvalidLogicalQuery(format!("{} must hold strings", PATH)))?,
+            _ => return Err(FuseError::InvalidLogicalQuery(format!("{} must be a string or an array", PATH))),
+        };
+        (path, node.get(VAL))
+    } else {
+        let (key, pattern) = node.iter().next().expect("leaves are not empty");
+        (create_key_path(key), Some(pattern))
+    };
+
+    let key_id = create_key_id(&path);
+    match pattern {
+        Some(Value::String(pattern)) => Ok(ParsedExpression::Leaf { key_id, pattern: pattern.clone() }),
+        Some(Value::Object(comparisons)) if !comparisons.is_empty() => parse_comparisons(key_id, comparisons),
+        _ => Err(FuseError::InvalidLogicalQueryForKey(key_id)),
+    }
+}
+
+/// Parses the comparisons of a key, `{ "$gte": 2000, "$lt": 2010 }`; several
+/// comparisons must all hold.
+fn parse_comparisons(key_id: String, comparisons: &Map<String, Value>) -> Result<ParsedExpression, FuseError> {
+    let mut children = Vec::with_capacity(comparisons.len());
+    for (operator, value) in comparisons {
+        let op = COMPARISONS
+            .iter()
+            .find(|(name, _)| name == operator)
+            .map(|&(_, op)| op)
+            .ok_or_else(|| FuseError::InvalidLogicalQueryForKey(key_id.clone()))?;
+
+        let valid = match (op, value) {
+            (Comparison::Before | Comparison::After, Value::String(s)) => parse_rfc3339(s).is_some(),
+            (Comparison::Before | Comparison::After, _) => false,
+            (_, Value::Number(_)) => true,
+            (_, Value::Bool(_) | Value::String(_)) => op == Comparison::Eq,
+            _ => false,
+        };
+        if !valid {
+            return Err(FuseError::InvalidLogicalQueryForKey(key_id));
+        }
+
+        children.push(ParsedExpression::Compare { key_id: key_id.clone(), op, value: value.clone() });
+    }
+
+    Ok(match children.len() {
+        1 => children.remove(0),
+        _ => ParsedExpression::And(children),
+    })
+}
+
+/// Turns the entries of `node` into the children of an `$and`.
+fn implicit_and(node: &Map<String, Value>) -> Result<ParsedExpression, FuseError> {
+    let mut children = Vec::with_capacity(node.len());
+    for (key, value) in node {
+        let mut child = Map::new();
+        child.insert(key.clone(), value.clone());
+        children.push(parse_node(&child)?);
+    }
+
+    Ok(ParsedExpression::And(children))
+}
+
+/// Returns the object of a query node.
+fn as_node(query: &Value) -> Result<&Map<String, Value>, FuseError> {
+    match query {
+        Value::Object(node) if !node.is_empty() => Ok(node),
+        Value::Object(_) => Err(FuseError::InvalidLogicalQuery("empty expression".into())),
+        _ => Err(FuseError::InvalidLogicalQuery(format!("expected an object, got {}", query))),
+    }
+}
+
+/// Returns `true` if `node` holds an operator.
+fn is_expression(node: &Map<String, Value>) -> bool {
+    node.contains_key(AND) || node.contains_key(OR)
+}
+
+//----------------------------------------------------------------------
+// Tests
+//----------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn leaf(key_id: &str, pattern: &str) -> ParsedExpression {
+        ParsedExpression::Leaf { key_id: key_id.into(), pattern: pattern.into() }
+    }
+
+    #[test]
+    fn test_parse_operators() {
+        let query = json!({
+            "$and": [
+                {"author": "tolkien"},
+                {"$or": [{"title": "^lord"}, {"$path": ["info.v2", "isbn"], "$val": "978"}]}
+            ]
+        });
+
+        assert_eq!(
+            parse_query(&query).unwrap(),
+            ParsedExpression::And(vec![
+                leaf("author", "tolkien"),
+                ParsedExpression::Or(vec![leaf("title", "^lord"), leaf(r"info\.v2.isbn", "978")]),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_parse_single_leaf() {
+        assert_eq!(parse_query(&json!({"title": "old"})).unwrap(), ParsedExpression::And(vec![leaf("title", "old")]));
+        assert_eq!(parse_query(&json!({"$and": "title"})).unwrap(), ParsedExpression::And(vec![]));
+    }
+
+    #[test]
+    fn test_parse_comparisons() {
+        let compare = |op, value| ParsedExpression::Compare { key_id: "year".into(), op, value };
+
+        assert_eq!(
+            parse_query(&json!({"title": "dune", "year": {"$gte": 2015}})).unwrap(),
+            ParsedExpression::And(vec![leaf("title", "dune"), compare(Comparison::Gte, json!(2015))])
+        );
+        assert_eq!(
+            parse_query(&json!({"year": {"$lt": 2010, "$gt": 2000}})).unwrap(),
+            ParsedExpression::And(vec![ParsedExpression::And(vec![
+                compare(Comparison::Gt, json!(2000)),
+                compare(Comparison::Lt, json!(2010)),
+            ])])
+        );
+
+        assert_eq!(
+            parse_query(&json!({"year": {"$after": "2000-01-01", "$before": "2010-01-01T00:00:00Z"}})).unwrap(),
+            ParsedExpression::And(vec![ParsedExpression::And(vec![
+                compare(Comparison::After, json!("2000-01-01")),
+                compare(Comparison::Before, json!("2010-01-01T00:00:00Z")),
+            ])])
+        );
+
+        for invalid in [
+            json!({"$gt": "2000"}),
+            json!({"$lte": true}),
+            json!({"$near": 1}),
+            json!({}),
+            json!({"$before": 2000}),
+            json!({"$after": "last year"}),
+        ] {
+            assert!(matches!(
+                parse_query(&json!({ "year": invalid })),
+                Err(FuseError::InvalidLogicalQueryForKey(key)) if key == "year"
+            ));
+        }
+    }
+
+    #[test]
+    fn test_parse_invalid_queries() {
+        assert!(matches!(
+            parse_query(&json!({"title": 1})),
+            Err(FuseError::InvalidLogicalQueryForKey(key)) if key == "title"
+        ));
+        assert!(matches!(
+            parse_query(&json!({"$path": "title"})),
+            Err(FuseError::InvalidLogicalQueryForKey(_))
+        ));
+        assert!(matches!(parse_query(&json!({})), Err(FuseError::InvalidLogicalQuery(_))));
+        assert!(matches!(parse_query(&json!({"$or": ["title"]})), Err(FuseError::InvalidLogicalQuery(_))));
+        assert!(matches!(parse_query(&json!("title")), Err(FuseError::InvalidLogicalQuery(_))));
+    }
+}
@@ -0,0 +1,207 @@
+//! Caching the results of repeated searches
+//!
+//! Typeahead inputs often search the same term several times in a row (a
+//! debounced keystroke, a deleted and retyped letter). With
+//! [`FuseOptions::result_cache_size`](crate::FuseOptions::result_cache_size)
+//! set, an instance keeps the results of its most recent searches and
+//! returns them again instead of rescoring the collection. The cache is
+//! emptied whenever the collection changes.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use serde_json::Value;
+
+use crate::core::results::search_result::{FuseSearchOptions, SearchPage};
+use crate::helpers::trace;
+
+//----------------------------------------------------------------------
+// Types
+//----------------------------------------------------------------------
+
+/// The results of the most recently used searches
+#[derive(Debug, Default)]
+pub(crate) struct ResultCache {
+    /// Maximum number of searches kept, `0` disables the cache
+    capacity: usize,
+
+    /// Cached pages and when they were last used
+    entries: Mutex<Entries>,
+
+    /// Number of searches found in the cache
+    hits: AtomicU64,
+
+    /// Number of cacheable searches missing from the cache
+    misses: AtomicU64,
+}
+
+/// The cached searches
+#[derive(Debug, Default)]
+struct Entries {
+    /// Cached pages by search, with the tick of their last use
+    pages: HashMap<CacheKey, (u64, SearchPage<Value>)>,
+
+    /// Incremented on every use of the cache
+    tick: u64,
+}
+
+/// Everything a cached page depends on, besides the instance itself
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct CacheKey {
+    /// The searched term
+    term: String,
+
+    /// `FuseSearchOptions::limit`
+    limit: usize,
+
+    /// `FuseSearchOptions::offset`
+    offset: usize,
+
+    /// `FuseSearchOptions::max_score`, as bits so it can be hashed
+    max_score: Option<u64>,
+
+    /// `FuseSearchOptions::keys`
+    keys: Option<Vec<String>>,
+}
+
+//----------------------------------------------------------------------
+// Implementation
+//----------------------------------------------------------------------
+
+impl ResultCache {
+    /// Creates a cache of at most `capacity` searches.
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            ..Default::default()
+        }
+    }
+
+    /// Returns the key of a search, or `None` if it cannot be cached: the
+    /// cache is disabled, or the search has a filter or progress hooks,
+    /// whose behavior is unknown.
+    pub(crate) fn key(&self, term: &str, search_options: &FuseSearchOptions) -> Option<CacheKey> {
+        if self.capacity == 0 || search_options.filter.is_some() || search_options.progress.is_some() {
+            return None;
+        }
+
+        Some(CacheKey {
+            term: term.to_string(),
+            limit: search_options.limit,
+            offset: search_options.offset,
+            max_score: search_options.max_score.map(f64::to_bits),
+            keys: search_options.keys.clone(),
+        })
+    }
+
+    /// Returns the cached page of a search, marking it as recently used.
+    pub(crate) fn get(&self, key: &CacheKey) -> Option<SearchPage<Value>> {
+        let mut entries = self.lock();
+        let tick = entries.next_tick();
+
+        let Some((used, page)) = entries.pages.get_mut(key) else {
+            self.misses.fetch_add(1, Ordering::Relaxed);
+            return None;
+        };
+        *used = tick;
+        trace::trace!(term = key.term.as_str(), "result cache hit");
+        self.hits.fetch_add(1, Ordering::Relaxed);
+        Some(page.clone())
+    }
+
+    /// Caches the page of a search, evicting the least recently used one if
+    /// the cache is full.
+    pub(crate) fn insert(&self, key: CacheKey, page: SearchPage<Value>) {
+        let mut entries = self.lock();
+        let tick = entries.next_tick();
+
+        if entries.pages.len() >= self.capacity && !entries.pages.contains_key(&key) {
+            let oldest = entries.pages.iter().min_by_key(|(_, (used, _))| *used).map(|(key, _)| key.clone());
+            if let Some(oldest) = oldest {
+                entries.pages.remove(&oldest);
+            }
+        }
+
+        entries.pages.insert(key, (tick, page));
+    }
+
+    /// Forgets every cached search.
+    pub(crate) fn clear(&self) {
+        self.lock().pages.clear();
+    }
+
+    /// Returns the number of searches found in the cache since it was
+    /// created.
+    pub(crate) fn hits(&self) -> u64 {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of cacheable searches that were not cached, since
+    /// the cache was created.
+    pub(crate) fn misses(&self) -> u64 {
+        self.misses.load(Ordering::Relaxed)
+    }
+
+    /// Locks the entries, recovering them if a thread panicked while
+    /// holding the lock (they are never left half-updated).
+    fn lock(&self) -> std::sync::MutexGuard<'_, Entries> {
+        self.entries.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl Entries {
+    /// Returns the tick of a new use of the cache.
+    fn next_tick(&mut self) -> u64 {
+        self.tick += 1;
+        self.tick
+    }
+}
+
+//----------------------------------------------------------------------
+// Tests
+//----------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    fn page(total: usize) -> SearchPage<Value> {
+        SearchPage { results: Vec::new(), total }
+    }
+
+    #[test]
+    fn test_evicts_least_recently_used() {
+        let cache = ResultCache::new(2);
+        let key = |term: &str| cache.key(term, &FuseSearchOptions::default()).unwrap();
+
+        cache.insert(key("a"), page(1));
+        cache.insert(key("b"), page(2));
+        assert_eq!(cache.get(&key("a")).map(|p| p.total), Some(1));
+
+        cache.insert(key("c"), page(3));
+        assert!(cache.get(&key("b")).is_none());
+        assert_eq!(cache.get(&key("a")).map(|p| p.total), Some(1));
+        assert_eq!(cache.get(&key("c")).map(|p| p.total), Some(3));
+
+        cache.clear();
+        assert!(cache.get(&key("a")).is_none());
+        assert_eq!((cache.hits(), cache.misses()), (3, 2));
+    }
+
+    #[test]
+    fn test_uncacheable_searches() {
+        assert!(ResultCache::new(0).key("a", &FuseSearchOptions::default()).is_none());
+
+        let cache = ResultCache::new(4);
+        let filtered = FuseSearchOptions { filter: Some(Arc::new(|_: &Value| true)), ..Default::default() };
+        assert!(cache.key("a", &filtered).is_none());
+
+        let limited = FuseSearchOptions { limit: 5, ..Default::default() };
+        assert_ne!(cache.key("a", &limited), cache.key("a", &FuseSearchOptions::default()));
+
+        let keyed = FuseSearchOptions { keys: Some(vec!["title".to_string()]), ..Default::default() };
+        assert_ne!(cache.key("a", &keyed), cache.key("a", &FuseSearchOptions::default()));
+    }
+}
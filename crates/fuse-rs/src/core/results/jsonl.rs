@@ -0,0 +1,167 @@
+//! JSON Lines output for search results
+//!
+//! This module streams search results as newline-delimited JSON, one result
+//! per line, so they can be piped into other tools.
+
+use std::io::{self, Write};
+
+use serde::Serialize;
+use serde_json::Value;
+
+use super::search_result::FuseResult;
+
+//----------------------------------------------------------------------
+// Writer
+//----------------------------------------------------------------------
+
+/// Writes `FuseResult`s as JSON Lines to any `io::Write`
+///
+/// Each result is serialized as a single JSON object (`item`, `refIndex`,
+/// `score`, `matches`, `scoresByKey`) followed by a newline. Fields that are
+/// not present on a result are omitted.
+///
+/// # Example
+///
+/// ```
+/// use fuse_rs::{FuseResult, JsonLinesWriter};
+/// use serde_json::json;
+///
+/// let results = vec![FuseResult {
+///     item: json!({"title": "Old Man's War"}),
+///     ref_index: 0,
+///     score: Some(0.1),
+///     matches: None,
+///     scores_by_key: None,
+/// }];
+///
+/// let mut writer = JsonLinesWriter::new(Vec::new()).with_fields(["item", "refIndex"]);
+/// writer.write_all(&results).unwrap();
+///
+/// let output = String::from_utf8(writer.into_inner()).unwrap();
+/// assert_eq!(output, "{\"item\":{\"title\":\"Old Man's War\"},\"refIndex\":0}\n");
+/// ```
+#[derive(Debug)]
+pub struct JsonLinesWriter<W: Write> {
+    /// The underlying output
+    writer: W,
+
+    /// Top-level result fields to keep (all fields when `None`)
+    fields: Option<Vec<String>>,
+}
+
+impl<W: Write> JsonLinesWriter<W> {
+    /// Creates a writer that outputs every field of each result.
+    pub fn new(writer: W) -> Self {
+        Self { writer, fields: None }
+    }
+
+    /// Restricts the output to the given top-level result fields.
+    ///
+    /// Field names use the serialized form, e.g. `"item"`, `"refIndex"`,
+    /// `"score"`, `"matches"` and `"scoresByKey"`.
+    pub fn with_fields<I, S>(mut self, fields: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.fields = Some(fields.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Writes a single result as one line.
+    pub fn write<T: Serialize>(&mut self, result: &FuseResult<T>) -> io::Result<()> {
+        let mut value = serde_json::to_value(result)?;
+
+        if let (Some(fields), Value::Object(map)) = (&self.fields, &mut value) {
+            map.retain(|name, _| fields.iter().any(|f| f == name));
+        }
+
+        serde_json::to_writer(&mut self.writer, &value)?;
+        self.writer.write_all(b"\n")
+    }
+
+    /// Writes every result, one per line, and flushes the output.
+    pub fn write_all<'r, T, I>(&mut self, results: I) -> io::Result<()>
+    where
+        T: Serialize + 'r,
+        I: IntoIterator<Item = &'r FuseResult<T>>,
+    {
+        for result in results {
+            self.write(result)?;
+        }
+
+        self.writer.flush()
+    }
+
+    /// Consumes the writer, returning the underlying output.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+//----------------------------------------------------------------------
+// Tests
+//----------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::results::search_result::FuseResultMatch;
+    use serde_json::json;
+
+    fn results() -> Vec<FuseResult<Value>> {
+        vec![
+            FuseResult {
+                item: json!("apple"),
+                ref_index: 0,
+                score: Some(0.0),
+                matches: Some(vec![FuseResultMatch {
+                    indices: vec![(0, 4)],
+                    key: None,
+                    ref_index: None,
+                    value: Some("apple".to_string()),
+                    normalized_value: None,
+                    original_indices: None,
+                }]),
+                scores_by_key: None,
+            },
+            FuseResult {
+                item: json!("pineapple"),
+                ref_index: 1,
+                score: None,
+                matches: None,
+                scores_by_key: None,
+            },
+        ]
+    }
+
+    #[test]
+    fn test_write_all_lines() {
+        let mut writer = JsonLinesWriter::new(Vec::new());
+        writer.write_all(&results()).unwrap();
+
+        let output = String::from_utf8(writer.into_inner()).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+
+        assert_eq!(lines.len(), 2);
+        assert_eq!(
+            serde_json::from_str::<Value>(lines[0]).unwrap(),
+            json!({
+                "item": "apple",
+                "refIndex": 0,
+                "score": 0.0,
+                "matches": [{"indices": [[0, 4]], "value": "apple"}]
+            })
+        );
+        assert_eq!(lines[1], r#"{"item":"pineapple","refIndex":1}"#);
+    }
+
+    #[test]
+    fn test_write_selected_fields() {
+        let mut writer = JsonLinesWriter::new(Vec::new()).with_fields(["refIndex"]);
+        writer.write_all(&results()).unwrap();
+
+        let output = String::from_utf8(writer.into_inner()).unwrap();
+        assert_eq!(output, "{\"refIndex\":0}\n{\"refIndex\":1}\n");
+    }
+}
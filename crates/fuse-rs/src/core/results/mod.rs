@@ -8,3 +8,9 @@ pub mod search_result;
 
 // Types for representing individual matches within documents
 pub mod match_result;
+
+// JSON Lines output for search results
+pub mod jsonl;
+
+// Bounded selection of the best results
+pub(crate) mod top_k;
@@ -3,6 +3,14 @@
 //! This module contains the primary data structures for representing
 //! results returned by the fuzzy search engine.
 
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+use crate::core::observer::SearchProgress;
+
 //----------------------------------------------------------------------
 // Search Result Types
 //----------------------------------------------------------------------
@@ -22,33 +30,111 @@ pub type RangeTuple = (usize, usize);
 /// Represents a match within a search result
 ///
 /// Contains information about where the match occurred, including character
-/// positions and which key contained the match.
-#[derive(Debug, Clone)]
+/// positions and which key contained the match. Positions are character
+/// indices into the normalized value.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct FuseResultMatch {
     /// Array of index ranges showing where matches occurred
     pub indices: Vec<RangeTuple>,
     
     /// The key in the document where the match was found
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub key: Option<String>,
     
-    /// The reference index of the document in the original collection
+    /// Position of the matched value within an array-valued key, `None`
+    /// for single values
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub ref_index: Option<usize>,
     
     /// The matched value as a string
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub value: Option<String>,
+
+    /// The normalized (case-folded / diacritics-stripped) form of `value` that
+    /// `indices` refer to. `None` when normalization left the value unchanged.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub normalized_value: Option<String>,
+
+    /// `indices` projected back onto the characters of the original `value`.
+    /// `None` when normalization left the value unchanged, in which case
+    /// `indices` already apply to `value`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub original_indices: Option<Vec<RangeTuple>>,
 }
 
+/// Predicate deciding whether a document takes part in a search
+pub type FuseSearchFilter = Arc<dyn Fn(&Value) -> bool + Send + Sync>;
+
 /// Options for controlling search behavior
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct FuseSearchOptions {
-    /// Maximum number of results to return
+    /// Maximum number of results to return. Default: `usize::MAX` (no limit)
     pub limit: usize,
+
+    /// Number of best results skipped before the returned ones, to return
+    /// later pages of results. Default: `0`
+    pub offset: usize,
+
+    /// Optional predicate applied to each document before it is matched.
+    /// Documents for which it returns `false` are skipped entirely, e.g. to
+    /// restrict a search to items the current user may see. Default: `None`
+    pub filter: Option<FuseSearchFilter>,
+
+    /// Highest combined score a result may have (scores go from `0.0`, an
+    /// exact match, to `1.0`). Weaker results are dropped, whatever the
+    /// `threshold` their values were matched with. Default: `None`
+    pub max_score: Option<f64>,
+
+    /// Hooks called while the records are scanned, to report progress or
+    /// stop the search early. Default: `None`
+    pub progress: Option<Arc<dyn SearchProgress>>,
+
+    /// Keys to search, by id (e.g. `"author.name"`); values of the other
+    /// keys are not matched. Keys that are not searched fail the search with
+    /// `FuseError::UnknownKey`. Logical searches, whose queries name their
+    /// keys, ignore it. Default: `None` (every key)
+    pub keys: Option<Vec<String>>,
+}
+
+impl FuseSearchOptions {
+    /// Returns `true` if a result with the combined score `score` is kept.
+    pub(crate) fn keeps_score(&self, score: f64) -> bool {
+        self.max_score.is_none_or(|max_score| score <= max_score)
+    }
+}
+
+impl Default for FuseSearchOptions {
+    fn default() -> Self {
+        Self {
+            limit: usize::MAX,
+            offset: 0,
+            filter: None,
+            max_score: None,
+            progress: None,
+            keys: None,
+        }
+    }
+}
+
+impl fmt::Debug for FuseSearchOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FuseSearchOptions")
+            .field("limit", &self.limit)
+            .field("offset", &self.offset)
+            .field("filter", &self.filter.as_ref().map(|_| "Fn(&Value) -> bool"))
+            .field("max_score", &self.max_score)
+            .field("progress", &self.progress)
+            .field("keys", &self.keys)
+            .finish()
+    }
 }
 
 /// A complete search result including the matched item and scoring details
 ///
 /// Generic over the item type to allow for different data types in search collections.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct FuseResult<T> {
     /// The original item that matched the search
     pub item: T,
@@ -57,8 +143,67 @@ pub struct FuseResult<T> {
     pub ref_index: usize,
     
     /// The relevance score of this match (lower is better)
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub score: Option<f64>,
     
-    /// Details about which parts of the item matched and where
+    /// Details about which parts of the item matched and where: one entry
+    /// per matched value, for every key and array entry of the item. An item
+    /// is returned once however many of its values matched.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub matches: Option<Vec<FuseResultMatch>>,
+
+    /// The best match score found in each key, keyed by the key path.
+    /// Only present when scores are included and the item matched in at least one key.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scores_by_key: Option<HashMap<String, f64>>,
+}
+
+/// A page of search results, as returned by `Fuse::search_page`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchPage<T> {
+    /// The results of the page, best first
+    pub results: Vec<FuseResult<T>>,
+
+    /// The number of matching items over all pages
+    pub total: usize,
+}
+
+//----------------------------------------------------------------------
+// Internal Result Types
+//----------------------------------------------------------------------
+
+/// A single matched value found while scanning an index record
+#[derive(Debug, Clone)]
+pub(crate) struct ScoredMatch {
+    /// The bitmap score of the value (lower is better)
+    pub score: f64,
+
+    /// Position of the matched key in the index keys (`None` for string records)
+    pub key: Option<usize>,
+
+    /// The matched value
+    pub value: String,
+
+    /// Position of the value within an array-valued key
+    pub idx: Option<usize>,
+
+    /// The field-length norm of the value
+    pub norm: f64,
+
+    /// Ranges of matched characters within the value
+    pub indices: Vec<RangeTuple>,
+}
+
+/// An index record that matched the search pattern, before formatting
+#[derive(Debug, Clone)]
+pub(crate) struct ScoredRecord {
+    /// The reference index of the record in the collection
+    pub idx: usize,
+
+    /// The combined score of all matches (computed after scanning)
+    pub score: f64,
+
+    /// All values of the record that matched
+    pub matches: Vec<ScoredMatch>,
 }
@@ -0,0 +1,163 @@
+//! Bounded selection of the best results
+//!
+//! When results are sorted and only the first `limit` are returned, keeping
+//! every match until the end of the scan is wasteful. [`TopK`] keeps the best
+//! `limit` results seen so far in a binary heap whose top is the worst of
+//! them, so each new result either replaces it or is dropped right away.
+
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+use crate::core::options::sort::FuseSortFunction;
+use crate::core::results::match_result::FuseSortFunctionArg;
+use crate::core::results::search_result::ScoredRecord;
+
+//----------------------------------------------------------------------
+// Types
+//----------------------------------------------------------------------
+
+/// The best results pushed so far, at most `limit` of them
+pub(crate) struct TopK {
+    /// Maximum number of results kept
+    limit: usize,
+
+    /// Function ordering the results, best first
+    sort_fn: FuseSortFunction,
+
+    /// Number of results pushed so far, used to break ties in push order
+    pushed: usize,
+
+    /// Kept results, the worst on top
+    heap: BinaryHeap<Ranked>,
+}
+
+/// A result with the argument it is compared by
+struct Ranked {
+    /// Argument passed to the sort function
+    arg: FuseSortFunctionArg,
+
+    /// Position of the result in push order
+    seq: usize,
+
+    /// Function ordering the results, best first
+    sort_fn: FuseSortFunction,
+
+    /// The result
+    result: ScoredRecord,
+}
+
+//----------------------------------------------------------------------
+// Implementation
+//----------------------------------------------------------------------
+
+impl TopK {
+    /// Creates an empty selection of at most `limit` results.
+    pub(crate) fn new(limit: usize, sort_fn: FuseSortFunction) -> Self {
+        Self {
+            limit,
+            sort_fn,
+            pushed: 0,
+            heap: BinaryHeap::with_capacity(limit.saturating_add(1).min(1024)),
+        }
+    }
+
+    /// Offers a result, keeping it only if it is among the best so far.
+    pub(crate) fn push(&mut self, arg: FuseSortFunctionArg, result: ScoredRecord) {
+        let ranked = Ranked {
+            arg,
+            seq: self.pushed,
+            sort_fn: self.sort_fn,
+            result,
+        };
+        self.pushed += 1;
+
+        if self.heap.len() < self.limit {
+            self.heap.push(ranked);
+        } else if let Some(mut worst) = self.heap.peek_mut()
+            && ranked < *worst
+        {
+            *worst = ranked;
+        }
+    }
+
+    /// Returns the kept results, best first.
+    ///
+    /// Results the sort function considers equal keep their push order, as
+    /// with a stable sort of every result.
+    pub(crate) fn into_sorted_vec(self) -> Vec<ScoredRecord> {
+        self.heap.into_sorted_vec().into_iter().map(|ranked| ranked.result).collect()
+    }
+}
+
+//----------------------------------------------------------------------
+// Implementation Details
+//----------------------------------------------------------------------
+
+impl Ord for Ranked {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.sort_fn)(&self.arg, &other.arg).cmp(&0).then(self.seq.cmp(&other.seq))
+    }
+}
+
+impl PartialOrd for Ranked {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Ranked {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Ranked {}
+
+//----------------------------------------------------------------------
+// Tests
+//----------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::options::sort::default_sort_fn;
+    use crate::core::results::match_result::FuseSortFunctionItem;
+    use std::collections::HashMap;
+
+    fn result(idx: usize, score: f64) -> (FuseSortFunctionArg, ScoredRecord) {
+        let arg = FuseSortFunctionArg {
+            idx,
+            item: FuseSortFunctionItem { fields: HashMap::new() },
+            score,
+            matches: None,
+        };
+        (arg, ScoredRecord { idx, score, matches: Vec::new() })
+    }
+
+    fn select(limit: usize, sort_fn: FuseSortFunction, scores: &[f64]) -> Vec<usize> {
+        let mut top = TopK::new(limit, sort_fn);
+        for (idx, &score) in scores.iter().enumerate() {
+            let (arg, record) = result(idx, score);
+            top.push(arg, record);
+        }
+        top.into_sorted_vec().into_iter().map(|r| r.idx).collect()
+    }
+
+    #[test]
+    fn test_keeps_best_results() {
+        let scores = [0.5, 0.1, 0.9, 0.3, 0.1, 0.7];
+
+        assert_eq!(select(3, default_sort_fn, &scores), vec![1, 4, 3]);
+        assert_eq!(select(10, default_sort_fn, &scores), vec![1, 4, 3, 0, 5, 2]);
+        assert!(select(0, default_sort_fn, &scores).is_empty());
+    }
+
+    #[test]
+    fn test_ties_keep_push_order() {
+        fn by_score(a: &FuseSortFunctionArg, b: &FuseSortFunctionArg) -> i32 {
+            a.score.total_cmp(&b.score) as i32
+        }
+
+        assert_eq!(select(2, by_score, &[0.2, 0.1, 0.1, 0.1]), vec![1, 2]);
+    }
+}
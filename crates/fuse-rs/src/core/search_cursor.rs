@@ -0,0 +1,281 @@
+//! Resumable search cursors
+//!
+//! A `SearchCursor` remembers how far a search has scanned the index and the
+//! results collected so far, so a long search can be paused (for example when
+//! a deadline is reached) and resumed later without restarting from the first
+//! record.
+
+use std::time::Instant;
+
+use serde_json::Value;
+
+use crate::core::error_messages::FuseError;
+use crate::core::fuse::Fuse;
+use crate::core::results::search_result::ScoredRecord;
+use crate::helpers::trace;
+
+//----------------------------------------------------------------------
+// Types
+//----------------------------------------------------------------------
+
+/// The state of a paused or in-progress search
+///
+/// A cursor is created with [`Fuse::search_cursor`] and driven with
+/// [`SearchCursor::next_page`]. It must be resumed with the same `Fuse`
+/// instance it was created from. Adding or removing documents moves the
+/// records the cursor is scanning, so resuming it afterwards fails with
+/// `FuseError::StaleCursor`.
+///
+/// # Example
+///
+/// ```
+/// use fuse_rs::{Fuse, FuseOptions};
+/// use serde_json::json;
+///
+/// let docs = vec![json!("apple"), json!("apricot"), json!("banana")];
+/// let fuse = Fuse::new(&docs, &FuseOptions::default(), None);
+///
+/// let mut cursor = fuse.search_cursor("apple").unwrap();
+/// let mut items = Vec::new();
+/// while !cursor.is_exhausted() {
+///     items.extend(cursor.next_page(&fuse, 1, None).unwrap());
+/// }
+///
+/// assert_eq!(items, fuse.search_with_options("apple", &Default::default()).unwrap());
+/// ```
+#[derive(Debug, Clone)]
+pub struct SearchCursor {
+    /// The search pattern
+    pattern: String,
+
+    /// Position of the next record to scan
+    position: usize,
+
+    /// Number of records in the index when the cursor was created
+    total: usize,
+
+    /// Generation of the collection the cursor scans
+    generation: u64,
+
+    /// Scored results that have not been returned yet
+    pending: Vec<ScoredRecord>,
+
+    /// Whether `pending` has been sorted (only done once the scan completes)
+    is_sorted: bool,
+}
+
+//----------------------------------------------------------------------
+// Public API
+//----------------------------------------------------------------------
+
+impl<'a> Fuse<'a> {
+    /// Starts a resumable search for `term`.
+    ///
+    /// No records are scanned until [`SearchCursor::next_page`] is called.
+    ///
+    /// # Returns
+    ///
+    /// A `SearchCursor` positioned at the first record, or an error if the
+    /// term cannot be searched with the current options.
+    pub fn search_cursor(&self, term: &str) -> Result<SearchCursor, FuseError> {
+        self.validate_term(term)?;
+
+        Ok(SearchCursor {
+            pattern: term.to_string(),
+            position: 0,
+            total: self.get_index().records().len(),
+            generation: self.generation(),
+            pending: Vec::new(),
+            is_sorted: false,
+        })
+    }
+}
+
+impl SearchCursor {
+    /// Scans records until a page is available or `deadline` passes.
+    ///
+    /// When results are sorted, the whole index has to be scanned before the
+    /// first page can be returned; until then an empty page is returned each
+    /// time the deadline is hit. When sorting is disabled, results are returned
+    /// in collection order as soon as `page_size` of them have been found.
+    ///
+    /// # Arguments
+    ///
+    /// * `fuse` - The `Fuse` instance the cursor was created from
+    /// * `page_size` - Maximum number of items to return
+    /// * `deadline` - Optional point in time after which scanning pauses
+    ///
+    /// # Returns
+    ///
+    /// The next page of matching items, which may be empty if the scan was
+    /// paused or the cursor is exhausted. Fails with `FuseError::InvalidOption`
+    /// if `page_size` is 0, and with `FuseError::StaleCursor` if documents
+    /// were added or removed since the cursor was created.
+    pub fn next_page(
+        &mut self,
+        fuse: &Fuse,
+        page_size: usize,
+        deadline: Option<Instant>,
+    ) -> Result<Vec<Value>, FuseError> {
+        if page_size == 0 {
+            return Err(FuseError::InvalidOption(String::from("page size must be at least 1")));
+        }
+
+        if fuse.generation() != self.generation {
+            return Err(FuseError::StaleCursor);
+        }
+
+        trace::enter_span!("fuse.search_page", position = self.position, page_size);
+        let should_sort = fuse.should_sort();
+        let searcher = fuse.searcher(&self.pattern);
+        let records = fuse.get_index().records();
+
+        while self.position < self.total {
+            if !should_sort && self.pending.len() >= page_size {
+                break;
+            }
+
+            if deadline.is_some_and(|d| Instant::now() >= d) {
+                break;
+            }
+
+            if let Some(record) = records.get(self.position)
+                && let Some(mut result) = fuse.search_record(&searcher, record)?
+            {
+                fuse.score_results(std::slice::from_mut(&mut result));
+                self.pending.push(result);
+            }
+
+            self.position += 1;
+        }
+
+        if should_sort {
+            if !self.is_scan_complete() {
+                return Ok(Vec::new());
+            }
+
+            if !self.is_sorted {
+                fuse.sort_results(&mut self.pending);
+                self.is_sorted = true;
+            }
+        }
+
+        let count = page_size.min(self.pending.len());
+        let page: Vec<ScoredRecord> = self.pending.drain(..count).collect();
+
+        Ok(fuse.format(page).into_iter().map(|result| result.item).collect())
+    }
+
+    /// Returns the position of the next record to scan.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Returns `true` once every record has been scanned.
+    pub fn is_scan_complete(&self) -> bool {
+        self.position >= self.total
+    }
+
+    /// Returns `true` once every record has been scanned and returned.
+    pub fn is_exhausted(&self) -> bool {
+        self.is_scan_complete() && self.pending.is_empty()
+    }
+}
+
+//----------------------------------------------------------------------
+// Tests
+//----------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::options::config::FuseOptions;
+    use serde_json::json;
+
+    fn fruits() -> Vec<Value> {
+        vec![
+            json!("apple"),
+            json!("pineapple"),
+            json!("banana"),
+            json!("apple pie"),
+        ]
+    }
+
+    #[test]
+    fn test_cursor_pages_match_search() {
+        let docs = fruits();
+        let fuse = Fuse::new(&docs, &FuseOptions::default(), None);
+        let expected = fuse.search_with_options("apple", &Default::default()).unwrap();
+
+        let mut cursor = fuse.search_cursor("apple").unwrap();
+        let mut pages = Vec::new();
+        while !cursor.is_exhausted() {
+            pages.extend(cursor.next_page(&fuse, 2, None).unwrap());
+        }
+
+        assert_eq!(pages, expected);
+    }
+
+    #[test]
+    fn test_cursor_pauses_at_deadline() {
+        let docs = fruits();
+        let fuse = Fuse::new(&docs, &FuseOptions::default(), None);
+
+        let mut cursor = fuse.search_cursor("apple").unwrap();
+        let page = cursor.next_page(&fuse, 10, Some(Instant::now())).unwrap();
+
+        assert!(page.is_empty());
+        assert_eq!(cursor.position(), 0);
+        assert!(!cursor.is_scan_complete());
+
+        let page = cursor.next_page(&fuse, 10, None).unwrap();
+        assert_eq!(page, fuse.search_with_options("apple", &Default::default()).unwrap());
+        assert!(cursor.is_exhausted());
+    }
+
+    #[test]
+    fn test_cursor_unsorted_stops_early() {
+        let docs = fruits();
+        let options = FuseOptions {
+            should_sort: false,
+            ..Default::default()
+        };
+        let fuse = Fuse::new(&docs, &options, None);
+
+        let mut cursor = fuse.search_cursor("apple").unwrap();
+        let page = cursor.next_page(&fuse, 1, None).unwrap();
+
+        assert_eq!(page, vec![json!("apple")]);
+        assert_eq!(cursor.position(), 1);
+    }
+
+    #[test]
+    fn test_cursor_rejects_empty_pages() {
+        let docs = fruits();
+        let options = FuseOptions {
+            should_sort: false,
+            ..Default::default()
+        };
+        let fuse = Fuse::new(&docs, &options, None);
+
+        let mut cursor = fuse.search_cursor("apple").unwrap();
+        assert!(matches!(cursor.next_page(&fuse, 0, None), Err(FuseError::InvalidOption(_))));
+        assert_eq!(cursor.position(), 0);
+    }
+
+    #[test]
+    fn test_cursor_fails_after_collection_changes() {
+        let docs = fruits();
+        let mut fuse = Fuse::new(&docs, &FuseOptions::default(), None);
+
+        let mut cursor = fuse.search_cursor("apple").unwrap();
+        cursor.next_page(&fuse, 1, Some(Instant::now())).unwrap();
+
+        fuse.remove_at(0).unwrap();
+        assert!(matches!(cursor.next_page(&fuse, 1, None), Err(FuseError::StaleCursor)));
+
+        // A new cursor sees the current collection
+        let mut cursor = fuse.search_cursor("apple").unwrap();
+        assert!(!cursor.next_page(&fuse, 10, None).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,203 @@
+//! Lazily produced search results
+//!
+//! A `SearchIter` scores records as results are requested. Without sorting,
+//! results come in collection order and records after the last requested
+//! result are never scanned, so `.take(n)` stops the search early. With
+//! sorting, the whole index is scanned on the first call to `next`, but only
+//! the results actually consumed are formatted.
+
+use std::vec::IntoIter;
+
+use serde_json::Value;
+
+use crate::core::error_messages::FuseError;
+use crate::core::fuse::Fuse;
+use crate::core::results::search_result::{FuseResult, ScoredRecord};
+use crate::helpers::trace;
+use crate::search::searcher::Searcher;
+
+//----------------------------------------------------------------------
+// Types
+//----------------------------------------------------------------------
+
+/// An iterator over the results of a search
+///
+/// Created with [`Fuse::search_iter`]. A failed search yields its error once
+/// and then ends.
+///
+/// # Example
+///
+/// ```
+/// use fuse_rs::{Fuse, FuseOptions};
+/// use serde_json::json;
+///
+/// let docs = vec![json!("apple"), json!("banana"), json!("apple pie"), json!("cherry")];
+/// let options = FuseOptions { should_sort: false, ..Default::default() };
+/// let fuse = Fuse::new(&docs, &options, None);
+///
+/// // Stops scanning after the second match
+/// let first: Vec<usize> = fuse
+///     .search_iter("apple")
+///     .unwrap()
+///     .take(2)
+///     .map(|result| result.unwrap().ref_index)
+///     .collect();
+/// assert_eq!(first, vec![0, 2]);
+/// ```
+pub struct SearchIter<'f, 'a> {
+    /// The searched instance
+    fuse: &'f Fuse<'a>,
+
+    /// Searcher of the term
+    searcher: Searcher<'f, 'a>,
+
+    /// Position of the next record to scan
+    position: usize,
+
+    /// Sorted results left to return, once the index has been scanned
+    sorted: Option<IntoIter<ScoredRecord>>,
+
+    /// Whether an error has been returned
+    failed: bool,
+}
+
+//----------------------------------------------------------------------
+// Implementation
+//----------------------------------------------------------------------
+
+impl<'a> Fuse<'a> {
+    /// Searches the data for `term`, producing the results lazily.
+    ///
+    /// # Returns
+    ///
+    /// An iterator over the results, or an error if the term cannot be
+    /// searched with the current options.
+    pub fn search_iter(&self, term: &str) -> Result<SearchIter<'_, 'a>, FuseError> {
+        self.validate_term(term)?;
+
+        Ok(SearchIter {
+            fuse: self,
+            searcher: self.searcher(term),
+            position: 0,
+            sorted: None,
+            failed: false,
+        })
+    }
+}
+
+impl<'f, 'a> SearchIter<'f, 'a> {
+    /// Scans the next records until one matches.
+    fn next_match(&mut self) -> Result<Option<ScoredRecord>, FuseError> {
+        let records = self.fuse.get_index().records();
+
+        while let Some(record) = records.get(self.position) {
+            self.position += 1;
+
+            if let Some(mut result) = self.fuse.search_record(&self.searcher, record)? {
+                self.fuse.score_results(std::slice::from_mut(&mut result));
+                return Ok(Some(result));
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Scans the whole index and sorts the results.
+    fn sort_all(&mut self) -> Result<IntoIter<ScoredRecord>, FuseError> {
+        trace::enter_span!("fuse.search_iter.sort", records = self.fuse.get_index().records().len());
+        let mut results = Vec::new();
+        while let Some(result) = self.next_match()? {
+            results.push(result);
+        }
+
+        self.fuse.sort_results(&mut results);
+        Ok(results.into_iter())
+    }
+
+    /// Returns the next scored result, in the order they are returned.
+    fn next_scored(&mut self) -> Result<Option<ScoredRecord>, FuseError> {
+        if !self.fuse.should_sort() {
+            return self.next_match();
+        }
+
+        if self.sorted.is_none() {
+            self.sorted = Some(self.sort_all()?);
+        }
+
+        Ok(self.sorted.as_mut().and_then(Iterator::next))
+    }
+}
+
+impl Iterator for SearchIter<'_, '_> {
+    type Item = Result<FuseResult<Value>, FuseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
+
+        loop {
+            match self.next_scored() {
+                // Results whose document is missing from the store are skipped
+                Ok(Some(result)) => {
+                    if let Some(result) = self.fuse.format(vec![result]).pop() {
+                        return Some(Ok(result));
+                    }
+                }
+                Ok(None) => return None,
+                Err(e) => {
+                    self.failed = true;
+                    return Some(Err(e));
+                }
+            }
+        }
+    }
+}
+
+//----------------------------------------------------------------------
+// Tests
+//----------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::options::config::FuseOptions;
+    use crate::core::results::search_result::FuseSearchOptions;
+    use serde_json::json;
+
+    fn fruits() -> Vec<Value> {
+        vec![json!("apple"), json!("pineapple"), json!("banana"), json!("apple pie")]
+    }
+
+    #[test]
+    fn test_iter_matches_search() {
+        let docs = fruits();
+        let fuse = Fuse::new(&docs, &FuseOptions { include_score: true, ..Default::default() }, None);
+
+        let expected = fuse.search_results("apple", &FuseSearchOptions::default()).unwrap();
+        let results: Vec<FuseResult<Value>> = fuse.search_iter("apple").unwrap().map(Result::unwrap).collect();
+
+        assert_eq!(results.len(), expected.len());
+        for (result, expected) in results.iter().zip(&expected) {
+            assert_eq!((result.ref_index, result.score), (expected.ref_index, expected.score));
+        }
+    }
+
+    #[test]
+    fn test_unsorted_iter_is_lazy() {
+        let docs = fruits();
+        let fuse = Fuse::new(&docs, &FuseOptions { should_sort: false, ..Default::default() }, None);
+
+        let mut iter = fuse.search_iter("apple").unwrap();
+        assert_eq!(iter.next().unwrap().unwrap().ref_index, 0);
+        assert_eq!(iter.position, 1);
+    }
+
+    #[test]
+    fn test_invalid_term() {
+        let docs = fruits();
+        let fuse = Fuse::new(&docs, &FuseOptions { max_pattern_length: Some(2), ..Default::default() }, None);
+
+        assert!(matches!(fuse.search_iter("apple"), Err(FuseError::PatternLengthTooLarge(2))));
+    }
+}
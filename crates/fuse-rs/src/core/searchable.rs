@@ -0,0 +1,168 @@
+//! Searching collections of typed items
+//!
+//! A type implementing `Searchable` declares its searchable fields, so a
+//! slice of it can be searched with [`Fuse::from_items`] without building
+//! JSON documents and keys by hand. With the `derive` feature the trait can
+//! be derived:
+//!
+//! ```ignore
+//! #[derive(Searchable)]
+//! struct Book {
+//!     #[fuse(weight = 2.0)]
+//!     title: String,
+//!     #[fuse]
+//!     author: String,
+//!     isbn: String,
+//! }
+//! ```
+
+use std::borrow::Cow;
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::core::fuse::Fuse;
+use crate::core::options::config::FuseOptions;
+use crate::core::options::keys::{FuseOptionKey, FuseOptionKeyName, FuseOptionKeyObject};
+
+//----------------------------------------------------------------------
+// Types
+//----------------------------------------------------------------------
+
+/// A type whose values can be searched by their fields
+///
+/// # Example
+///
+/// ```
+/// use fuse_rs::{Fuse, FuseOptionKey, FuseOptions, Searchable};
+/// use serde_json::{Value, json};
+///
+/// struct Book {
+///     title: String,
+///     author: String,
+/// }
+///
+/// impl Searchable for Book {
+///     fn search_keys() -> Vec<FuseOptionKey<'static>> {
+///         vec![FuseOptionKey::String("title".into()), FuseOptionKey::String("author".into())]
+///     }
+///
+///     fn to_document(&self) -> Value {
+///         json!({ "title": self.title, "author": self.author })
+///     }
+/// }
+///
+/// let books = [
+///     Book { title: "Old Man's War".into(), author: "John Scalzi".into() },
+///     Book { title: "The Lock Artist".into(), author: "Steve Hamilton".into() },
+/// ];
+///
+/// let fuse = Fuse::from_items(&books, &FuseOptions::default());
+/// let results = fuse.search_results("hamilton", &Default::default()).unwrap();
+/// assert_eq!(results[0].ref_index, 1);
+/// ```
+pub trait Searchable {
+    /// Returns the keys of the searchable fields, with their weights.
+    fn search_keys() -> Vec<FuseOptionKey<'static>>;
+
+    /// Returns the document indexed for this value, holding (at least) the
+    /// fields named by `search_keys`.
+    fn to_document(&self) -> Value;
+}
+
+//----------------------------------------------------------------------
+// Implementation
+//----------------------------------------------------------------------
+
+impl Fuse<'static> {
+    /// Creates a Fuse instance searching `items` by the fields they declare
+    /// as searchable.
+    ///
+    /// The keys of `options` are replaced by [`Searchable::search_keys`].
+    /// Results refer to the items by their index in `items`.
+    pub fn from_items<T: Searchable>(items: &[T], options: &FuseOptions<'static>) -> Self {
+        let docs: Vec<Value> = items.iter().map(Searchable::to_document).collect();
+        let options = FuseOptions {
+            keys: T::search_keys(),
+            ..options.clone()
+        };
+
+        Fuse::new(&docs, &options, None)
+    }
+}
+
+//----------------------------------------------------------------------
+// Free Functions
+//----------------------------------------------------------------------
+
+/// Builds the key of the field `name`, used by the derive macro.
+#[doc(hidden)]
+pub fn field_key(name: &'static str, weight: f64) -> FuseOptionKey<'static> {
+    FuseOptionKey::KeyObject(FuseOptionKeyObject {
+        name: Cow::Owned(FuseOptionKeyName::String(Cow::Borrowed(name))),
+        weight: Some(weight),
+        get_fn: None,
+        fallback: None,
+        analyzer: None,
+        index: true,
+        searchable: true,
+    })
+}
+
+/// Converts the value of a field, used by the derive macro.
+///
+/// Values that cannot be represented as JSON are indexed as `null`, which is
+/// never matched.
+#[doc(hidden)]
+pub fn field_value<T: Serialize + ?Sized>(value: &T) -> Value {
+    serde_json::to_value(value).unwrap_or(Value::Null)
+}
+
+//----------------------------------------------------------------------
+// Tests
+//----------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{Map, json};
+
+    struct Book {
+        title: &'static str,
+        tags: Vec<&'static str>,
+    }
+
+    impl Searchable for Book {
+        fn search_keys() -> Vec<FuseOptionKey<'static>> {
+            vec![field_key("title", 2.0), field_key("tags", 1.0)]
+        }
+
+        fn to_document(&self) -> Value {
+            let mut document = Map::new();
+            document.insert("title".into(), field_value(self.title));
+            document.insert("tags".into(), field_value(&self.tags));
+            Value::Object(document)
+        }
+    }
+
+    #[test]
+    fn test_from_items() {
+        let books = [
+            Book { title: "Dune", tags: vec!["desert", "spicy"] },
+            Book { title: "Spice World", tags: vec!["music"] },
+        ];
+        let options = FuseOptions {
+            keys: vec![FuseOptionKey::String("ignored".into())],
+            include_score: true,
+            ..Default::default()
+        };
+        let fuse = Fuse::from_items(&books, &options);
+
+        let results = fuse.search_results("spice", &Default::default()).unwrap();
+        let indices: Vec<usize> = results.iter().map(|result| result.ref_index).collect();
+
+        // The title key weighs more than the tags key
+        assert_eq!(indices, vec![1, 0]);
+        assert_eq!(results[0].item, json!({"title": "Spice World", "tags": ["music"]}));
+    }
+}
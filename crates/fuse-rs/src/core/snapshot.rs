@@ -0,0 +1,164 @@
+//! Snapshots of whole `Fuse` instances
+//!
+//! A snapshot is a JSON object holding the options, the documents and the
+//! index records of an instance, so it can be restored without re-indexing.
+//! Snapshots carry a format version and are rejected by versions of the
+//! library that cannot read them.
+//!
+//! Options that are functions or trait objects (`get_fn`, `sort_fn`,
+//! `tokenizer`, `synonyms`, `interner`, ...) are not saved; a loaded
+//! instance uses their defaults.
+
+use std::borrow::Cow;
+use std::io::{BufReader, BufWriter, Read, Write};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::core::error_messages::FuseError;
+use crate::core::options::config::FuseOptions;
+use crate::tools::fuse_index_record::{FuseIndexRecord, FuseIndexRecords};
+
+//----------------------------------------------------------------------
+// Constants
+//----------------------------------------------------------------------
+
+/// Version of the snapshot format written by `write`
+pub(crate) const SNAPSHOT_VERSION: u32 = 1;
+
+//----------------------------------------------------------------------
+// Types
+//----------------------------------------------------------------------
+
+/// The state of an instance, borrowed for writing
+#[derive(Serialize)]
+pub(crate) struct SnapshotRef<'s, 'a> {
+    /// Version of the snapshot format
+    pub version: u32,
+
+    /// Search options
+    pub options: &'s FuseOptions<'a>,
+
+    /// The searched documents
+    pub docs: Vec<Cow<'s, Value>>,
+
+    /// The index of the documents
+    pub records: &'s FuseIndexRecords,
+}
+
+/// The state of an instance, as read back
+#[derive(Deserialize)]
+pub(crate) struct Snapshot {
+    /// Version of the snapshot format
+    pub version: u32,
+
+    /// Search options, as written by `FuseOptions`' `Serialize`
+    pub options: Value,
+
+    /// The searched documents
+    pub docs: Vec<Value>,
+
+    /// The index of the documents
+    pub records: FuseIndexRecords,
+}
+
+//----------------------------------------------------------------------
+// Free Functions
+//----------------------------------------------------------------------
+
+/// Writes `snapshot` to `writer` as JSON.
+pub(crate) fn write<W: Write>(writer: W, snapshot: &SnapshotRef) -> Result<(), FuseError> {
+    let mut writer = BufWriter::new(writer);
+
+    serde_json::to_writer(&mut writer, snapshot).map_err(|e| FuseError::InvalidSnapshot(e.to_string()))?;
+    writer.flush().map_err(|e| FuseError::InvalidSnapshot(e.to_string()))
+}
+
+/// Reads a snapshot written by `write` from `reader`.
+///
+/// # Returns
+///
+/// The snapshot, or an error if it is not valid JSON, was written in another
+/// format version, or has records pointing past the documents.
+pub(crate) fn read<R: Read>(reader: R) -> Result<Snapshot, FuseError> {
+    let snapshot: Snapshot =
+        serde_json::from_reader(BufReader::new(reader)).map_err(|e| FuseError::InvalidSnapshot(e.to_string()))?;
+
+    if snapshot.version != SNAPSHOT_VERSION {
+        return Err(FuseError::UnsupportedSnapshotVersion(snapshot.version));
+    }
+
+    let docs = snapshot.docs.len();
+    for record in &snapshot.records {
+        let index = match record {
+            FuseIndexRecord::String(r) => r.i,
+            FuseIndexRecord::Object(r) => r.i,
+        };
+
+        if index >= docs {
+            return Err(FuseError::InvalidSnapshot(format!("record of document {} out of {}", index, docs)));
+        }
+    }
+
+    Ok(snapshot)
+}
+
+//----------------------------------------------------------------------
+// Tests
+//----------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    use crate::{Fuse, FuseOptionKey};
+
+    fn books() -> Vec<Value> {
+        vec![
+            json!({"title": "Old Man's War", "author": {"name": "John Scalzi"}}),
+            json!({"title": "The Lock Artist", "author": {"name": "Steve Hamilton"}}),
+        ]
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let options = FuseOptions {
+            keys: vec![FuseOptionKey::String("title".into()), FuseOptionKey::String("author.name".into())],
+            include_score: true,
+            threshold: 0.3,
+            ..Default::default()
+        };
+        let fuse = Fuse::new(&books(), &options, None);
+
+        let mut snapshot = Vec::new();
+        fuse.save(&mut snapshot).unwrap();
+        let loaded = Fuse::load(snapshot.as_slice()).unwrap();
+
+        let search = |fuse: &Fuse, term| serde_json::to_value(fuse.search_results(term, &Default::default()).unwrap()).unwrap();
+        assert_eq!(search(&loaded, "hamilton"), search(&fuse, "hamilton"));
+        assert_eq!(search(&loaded, "old war"), search(&fuse, "old war"));
+        assert_eq!(search(&loaded, "scalzy"), search(&fuse, "scalzy"));
+    }
+
+    #[test]
+    fn test_load_rejects_other_versions() {
+        let snapshot = json!({"version": 99, "options": {}, "docs": [], "records": []}).to_string();
+
+        assert!(matches!(Fuse::load(snapshot.as_bytes()), Err(FuseError::UnsupportedSnapshotVersion(99))));
+    }
+
+    #[test]
+    fn test_load_rejects_invalid_snapshots() {
+        assert!(matches!(Fuse::load(&b"{"[..]), Err(FuseError::InvalidSnapshot(_))));
+
+        let snapshot = json!({
+            "version": SNAPSHOT_VERSION,
+            "options": {},
+            "docs": [],
+            "records": [{"v": "apple", "i": 0, "n": 1.0}],
+        });
+        let result = read(snapshot.to_string().as_bytes());
+        assert!(matches!(result, Err(FuseError::InvalidSnapshot(reason)) if reason.contains("out of 0")));
+    }
+}
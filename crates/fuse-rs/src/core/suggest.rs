@@ -0,0 +1,174 @@
+//! Autocomplete suggestions
+//!
+//! [`Fuse::suggest`] completes the word being typed with the words of the
+//! indexed values. The words are kept in a prefix trie, built on the first
+//! call and again after the collection changes, so suggestions do not scan
+//! the records.
+
+use serde::Serialize;
+
+use crate::core::fuse::Fuse;
+use crate::helpers::caseless::fold;
+use crate::helpers::tokenizer::{Tokenizer, UnicodeWordTokenizer};
+use crate::helpers::trace;
+use crate::tools::fuse_index_record::{FuseIndexRecord, IndexValue, RecordEntryValue};
+use crate::tools::token_trie::TokenTrie;
+
+//----------------------------------------------------------------------
+// Types
+//----------------------------------------------------------------------
+
+/// A word completing a prefix
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Suggestion {
+    /// The completed word, case-folded
+    pub text: String,
+
+    /// How far the prefix is from the beginning of the word, from `0.0`
+    /// (it begins with the prefix) to `1.0`
+    pub score: f64,
+
+    /// Number of times the word occurs in the indexed values
+    pub count: usize,
+}
+
+//----------------------------------------------------------------------
+// Implementation
+//----------------------------------------------------------------------
+
+impl<'a> Fuse<'a> {
+    /// Returns up to `limit` words of the indexed values completing
+    /// `prefix`, for autocomplete.
+    ///
+    /// Words begin with the prefix up to a few typos: the score of a word is
+    /// the number of edits between the prefix and its beginning divided by
+    /// the length of the prefix, and words scoring above
+    /// [`FuseOptions::threshold`](crate::FuseOptions::threshold) are left
+    /// out. Suggestions are ordered by score, then by decreasing number of
+    /// occurrences. Words are compared case-insensitively, without the
+    /// other normalization options.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fuse_rs::{Fuse, FuseOptions};
+    /// use serde_json::json;
+    ///
+    /// let docs = vec![json!("Old Man's War"), json!("The Old Guard"), json!("Olive Kitteridge")];
+    /// let fuse = Fuse::new(&docs, &FuseOptions::default(), None);
+    ///
+    /// let words: Vec<String> = fuse.suggest("ol", 2).into_iter().map(|s| s.text).collect();
+    /// assert_eq!(words, vec!["old", "olive"]);
+    /// ```
+    pub fn suggest(&self, prefix: &str, limit: usize) -> Vec<Suggestion> {
+        trace::enter_span!("fuse.suggest", limit = limit);
+        let prefix: String = fold(prefix).collect();
+        let len = prefix.chars().count();
+
+        // Beginnings with more edits score above the threshold
+        let max_errors = (self.options().threshold * len as f64 + 1e-9).floor().max(0.0) as usize;
+        let mut suggestions: Vec<Suggestion> = self
+            .token_trie()
+            .completions(&prefix, max_errors)
+            .into_iter()
+            .map(|completion| Suggestion {
+                text: completion.token,
+                score: if len == 0 { 0.0 } else { completion.errors as f64 / len as f64 },
+                count: completion.count,
+            })
+            .collect();
+
+        // Completions come in word order, which the stable sort keeps for ties
+        suggestions.sort_by(|a, b| a.score.total_cmp(&b.score).then(b.count.cmp(&a.count)));
+        suggestions.truncate(limit);
+        suggestions
+    }
+
+    /// Builds the trie of the words of the records of the documents in the
+    /// collection.
+    pub(crate) fn build_token_trie(&self) -> TokenTrie {
+        trace::enter_span!("fuse.token_trie", records = self.get_index().records().len());
+        let mut trie = TokenTrie::new();
+        let mut insert = |text: &str| {
+            let folded: String = fold(text).collect();
+            for token in UnicodeWordTokenizer.tokenize(&folded) {
+                trie.insert(token.text);
+            }
+        };
+
+        for record in self.get_index().records() {
+            // Records of removed documents stay in the index until it is compacted
+            if self.get_index().position(record).is_none() {
+                continue;
+            }
+
+            match record {
+                FuseIndexRecord::String(r) => insert(&r.v),
+                FuseIndexRecord::Object(r) => {
+                    let searchable = r
+                        .entries
+                        .iter()
+                        .filter(|(key_index, _)| key_index.parse().is_ok_and(|k| self.is_searchable(k)));
+                    for (_, value) in searchable {
+                        let values: &[IndexValue] = match value {
+                            RecordEntryValue::Single(v) => std::slice::from_ref(v),
+                            RecordEntryValue::Array(arr) => arr,
+                        };
+                        values.iter().for_each(|v| insert(&v.v));
+                    }
+                }
+            }
+        }
+
+        trie
+    }
+}
+
+//----------------------------------------------------------------------
+// Tests
+//----------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::options::config::FuseOptions;
+    use serde_json::json;
+
+    fn words(suggestions: &[Suggestion]) -> Vec<&str> {
+        suggestions.iter().map(|s| s.text.as_str()).collect()
+    }
+
+    #[test]
+    fn test_suggest_ranks_by_score_and_count() {
+        let docs = vec![
+            json!({"title": "The Hobbit", "tags": ["fantasy", "hobbits"]}),
+            json!({"title": "Hobbit Tales", "tags": ["fantasy"]}),
+            json!({"title": "Habits of Mind", "tags": ["self-help"]}),
+        ];
+        let options = FuseOptions { keys: vec!["title".into(), "tags".into()], threshold: 0.4, ..Default::default() };
+        let fuse = Fuse::new(&docs, &options, None);
+
+        let suggestions = fuse.suggest("Hob", 10);
+        assert_eq!(words(&suggestions), vec!["hobbit", "hobbits", "habits"]);
+        assert_eq!(suggestions[0].count, 2);
+        assert!(suggestions[2].score > 0.0);
+
+        assert_eq!(words(&fuse.suggest("fan", 10)), vec!["fantasy"]);
+        assert_eq!(fuse.suggest("fan", 10)[0].count, 2);
+        assert_eq!(fuse.suggest("", 1).len(), 1);
+        assert!(fuse.suggest("zzz", 10).is_empty());
+    }
+
+    #[test]
+    fn test_suggest_follows_collection_changes() {
+        let options = FuseOptions { threshold: 0.0, ..Default::default() };
+        let mut fuse = Fuse::new(&[json!("apple pie")], &options, None);
+        assert_eq!(words(&fuse.suggest("ap", 5)), vec!["apple"]);
+
+        fuse.add(json!("apricot jam")).unwrap();
+        assert_eq!(words(&fuse.suggest("ap", 5)), vec!["apple", "apricot"]);
+
+        fuse.remove_at(0).unwrap();
+        assert_eq!(words(&fuse.suggest("ap", 5)), vec!["apricot"]);
+    }
+}
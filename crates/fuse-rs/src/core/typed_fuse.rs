@@ -0,0 +1,222 @@
+//! Searching collections of any serializable type
+//!
+//! [`TypedFuse`] indexes the JSON form of the items of a slice and returns
+//! references to the items themselves in its results, so collections of
+//! structs can be searched without converting results back from JSON.
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::core::error_messages::FuseError;
+use crate::core::fuse::Fuse;
+use crate::core::options::config::FuseOptions;
+use crate::core::results::search_result::{FuseResult, FuseSearchOptions};
+use crate::core::searchable::{Searchable, field_value};
+
+//----------------------------------------------------------------------
+// Types
+//----------------------------------------------------------------------
+
+/// A Fuse instance searching a slice of typed items
+///
+/// Items are serialized once, when the instance is created; search filters
+/// receive this JSON form.
+///
+/// # Example
+///
+/// ```
+/// use fuse_rs::{FuseOptionKey, FuseOptions, TypedFuse};
+/// use serde::Serialize;
+///
+/// #[derive(Debug, PartialEq, Serialize)]
+/// struct Book {
+///     title: String,
+///     author: String,
+/// }
+///
+/// let books = vec![
+///     Book { title: "Old Man's War".into(), author: "John Scalzi".into() },
+///     Book { title: "The Lock Artist".into(), author: "Steve Hamilton".into() },
+/// ];
+/// let options = FuseOptions {
+///     keys: vec![FuseOptionKey::String("title".into()), FuseOptionKey::String("author".into())],
+///     ..Default::default()
+/// };
+///
+/// let fuse = TypedFuse::new(&books, &options);
+/// let results: Vec<&Book> = fuse.search("hamilton").unwrap();
+/// assert_eq!(results, vec![&books[1]]);
+/// ```
+pub struct TypedFuse<'a, T> {
+    /// The searched items, in the order of the index
+    items: &'a [T],
+
+    /// The instance searching the serialized items
+    fuse: Fuse<'static>,
+}
+
+//----------------------------------------------------------------------
+// Implementation
+//----------------------------------------------------------------------
+
+impl<'a, T: Serialize> TypedFuse<'a, T> {
+    /// Creates an instance searching `items` by the keys of `options`.
+    ///
+    /// Items that cannot be represented as JSON are indexed as `null`, which
+    /// is never matched.
+    pub fn new(items: &'a [T], options: &FuseOptions<'static>) -> Self {
+        let docs: Vec<Value> = items.iter().map(field_value).collect();
+
+        Self {
+            items,
+            fuse: Fuse::new(&docs, options, None),
+        }
+    }
+}
+
+impl<'a, T: Searchable> TypedFuse<'a, T> {
+    /// Creates an instance searching `items` by the fields they declare as
+    /// searchable.
+    ///
+    /// The keys of `options` are replaced by [`Searchable::search_keys`].
+    pub fn from_searchable(items: &'a [T], options: &FuseOptions<'static>) -> Self {
+        let docs: Vec<Value> = items.iter().map(Searchable::to_document).collect();
+        let options = FuseOptions {
+            keys: T::search_keys(),
+            ..options.clone()
+        };
+
+        Self {
+            items,
+            fuse: Fuse::new(&docs, &options, None),
+        }
+    }
+}
+
+impl<'a, T> TypedFuse<'a, T> {
+    /// Searches the items using the provided search term.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the matching items, best first, or an error
+    pub fn search(&self, term: &str) -> Result<Vec<&'a T>, FuseError> {
+        let results = self.search_results(term, &FuseSearchOptions::default())?;
+        Ok(results.into_iter().map(|result| result.item).collect())
+    }
+
+    /// Searches the items like [`Fuse::search_results`], returning the full
+    /// results.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing a `FuseResult` per matching item, whose `item`
+    /// is a reference into the searched slice.
+    pub fn search_results(
+        &self,
+        term: &str,
+        search_options: &FuseSearchOptions,
+    ) -> Result<Vec<FuseResult<&'a T>>, FuseError> {
+        let results = self.fuse.search_results(term, search_options)?;
+        Ok(results.into_iter().map(|result| self.typed(result)).collect())
+    }
+
+    /// Searches the items with a logical query, like
+    /// [`Fuse::logical_search_results`].
+    pub fn logical_search_results(
+        &self,
+        query: &Value,
+        search_options: &FuseSearchOptions,
+    ) -> Result<Vec<FuseResult<&'a T>>, FuseError> {
+        let results = self.fuse.logical_search_results(query, search_options)?;
+        Ok(results.into_iter().map(|result| self.typed(result)).collect())
+    }
+
+    /// Returns the searched items.
+    pub fn items(&self) -> &'a [T] {
+        self.items
+    }
+
+    /// Returns the instance searching the serialized items.
+    pub fn fuse(&self) -> &Fuse<'static> {
+        &self.fuse
+    }
+
+    /// Replaces the JSON item of a result with the item it was built from.
+    fn typed(&self, result: FuseResult<Value>) -> FuseResult<&'a T> {
+        FuseResult {
+            item: &self.items[result.ref_index],
+            ref_index: result.ref_index,
+            score: result.score,
+            matches: result.matches,
+            scores_by_key: result.scores_by_key,
+        }
+    }
+}
+
+//----------------------------------------------------------------------
+// Tests
+//----------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::options::keys::FuseOptionKey;
+    use crate::core::searchable::field_key;
+    use serde_json::json;
+    use std::sync::Arc;
+
+    #[derive(Debug, PartialEq, Serialize)]
+    struct Movie {
+        title: String,
+        year: u16,
+    }
+
+    impl Searchable for Movie {
+        fn search_keys() -> Vec<FuseOptionKey<'static>> {
+            vec![field_key("title", 1.0)]
+        }
+
+        fn to_document(&self) -> Value {
+            json!({ "title": self.title })
+        }
+    }
+
+    fn movies() -> Vec<Movie> {
+        vec![
+            Movie { title: "Alien".into(), year: 1979 },
+            Movie { title: "Aliens".into(), year: 1986 },
+            Movie { title: "Heat".into(), year: 1995 },
+        ]
+    }
+
+    #[test]
+    fn test_search_returns_items() {
+        let movies = movies();
+        let options = FuseOptions {
+            keys: vec![FuseOptionKey::String("title".into())],
+            include_score: true,
+            ..Default::default()
+        };
+        let fuse = TypedFuse::new(&movies, &options);
+
+        let results = fuse.search_results("aliens", &FuseSearchOptions::default()).unwrap();
+        assert_eq!(results[0].item, &movies[1]);
+        assert!(results[0].score < results[1].score);
+
+        let search_options = FuseSearchOptions {
+            filter: Some(Arc::new(|doc: &Value| doc["year"].as_u64() < Some(1980))),
+            ..Default::default()
+        };
+        let results = fuse.search_results("aliens", &search_options).unwrap();
+        assert_eq!(results.iter().map(|r| r.item).collect::<Vec<_>>(), vec![&movies[0]]);
+    }
+
+    #[test]
+    fn test_from_searchable() {
+        let movies = movies();
+        let fuse = TypedFuse::from_searchable(&movies, &FuseOptions::default());
+
+        assert_eq!(fuse.search("heat").unwrap(), vec![&movies[2]]);
+        assert!(fuse.search("1995").unwrap().is_empty());
+    }
+}
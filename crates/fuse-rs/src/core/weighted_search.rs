@@ -0,0 +1,185 @@
+//! Weighted multi-pattern search
+//!
+//! Searches several patterns at once, each with its own weight, e.g. to
+//! favor an exact brand name while still accepting loose matches in a
+//! description. Every pattern is matched and scored on its own like a plain
+//! search; the score of an item then combines the scores of the patterns it
+//! matched, the same way the scores of weighted keys are combined.
+
+use std::collections::BTreeMap;
+
+use serde_json::Value;
+
+use crate::core::error_messages::FuseError;
+use crate::core::fuse::Fuse;
+use crate::core::results::search_result::{FuseResult, FuseSearchOptions, ScoredRecord};
+use crate::helpers::trace;
+
+//----------------------------------------------------------------------
+// Implementation
+//----------------------------------------------------------------------
+
+impl<'a> Fuse<'a> {
+    /// Searches the data for several patterns, combining their scores with
+    /// the given weights.
+    ///
+    /// An item is returned if it matches at least one pattern. Its score is
+    /// the product of the scores of the patterns it matched, each raised to
+    /// the power of its weight divided by the sum of the weights; patterns it
+    /// did not match count as `1.0`, the worst score. Heavier patterns thus
+    /// weigh more in the ranking, and items matching more patterns rank
+    /// higher. The matches of every pattern are kept.
+    ///
+    /// `search_options` applies as in [`Fuse::search_results`], except for
+    /// the progress hooks, which are not called.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the results, `FuseError::InvalidOption` if a
+    /// weight is not a positive number, or an error if a pattern cannot be
+    /// searched.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fuse_rs::{Fuse, FuseOptions, FuseSearchOptions};
+    /// use serde_json::json;
+    ///
+    /// let docs = vec![
+    ///     json!({"brand": "Acme", "description": "A sturdy anvil"}),
+    ///     json!({"brand": "Anvil Co", "description": "Acme compatible parts"}),
+    /// ];
+    /// let options = FuseOptions { keys: vec!["brand".into(), "description".into()], ..Default::default() };
+    /// let fuse = Fuse::new(&docs, &options, None);
+    ///
+    /// let terms = vec![("acme", 3.0), ("anvil", 1.0)];
+    /// let results = fuse.search_weighted(&terms, &FuseSearchOptions::default()).unwrap();
+    /// assert_eq!(results[0].ref_index, 0);
+    /// ```
+    pub fn search_weighted(
+        &self,
+        terms: &[(&str, f64)],
+        search_options: &FuseSearchOptions,
+    ) -> Result<Vec<FuseResult<Value>>, FuseError> {
+        for &(term, weight) in terms {
+            self.validate_term(term)?;
+            if !(weight.is_finite() && weight > 0.0) {
+                return Err(FuseError::InvalidOption(format!(
+                    "weight of pattern '{}' must be a positive number",
+                    term
+                )));
+            }
+        }
+
+        trace::enter_span!("fuse.weighted_search", patterns = terms.len(), limit = search_options.limit);
+        let total_weight: f64 = terms.iter().map(|&(_, weight)| weight).sum();
+        let searched_keys = self.searched_keys(search_options)?;
+
+        // Combined results by record position, so that ties keep their order
+        let mut combined: BTreeMap<usize, ScoredRecord> = BTreeMap::new();
+        for &(term, weight) in terms {
+            let searcher = self.searcher(term);
+            let candidates = self.candidates(&searcher);
+
+            for (position, record) in self.get_index().records().iter().enumerate() {
+                if candidates.as_ref().is_some_and(|candidates| !candidates[position])
+                    || !self.passes_filter(record, search_options)
+                {
+                    continue;
+                }
+
+                let Some(mut result) = self.search_record_in(&searcher, record, searched_keys.as_deref())? else {
+                    continue;
+                };
+                self.score_results(std::slice::from_mut(&mut result));
+
+                // As with weighted keys, exact matches count as epsilon so
+                // that the weights still order items matching exactly
+                let factor = result.score.max(f64::EPSILON).powf(weight / total_weight);
+                let entry = combined.entry(position).or_insert_with(|| ScoredRecord {
+                    idx: result.idx,
+                    score: 1.0,
+                    matches: Vec::new(),
+                });
+                entry.score *= factor;
+                entry.matches.append(&mut result.matches);
+            }
+        }
+
+        let mut results: Vec<ScoredRecord> = combined.into_values().collect();
+        results.retain(|result| search_options.keeps_score(result.score));
+
+        if self.should_sort() {
+            self.sort_results(&mut results);
+        }
+
+        results.truncate(search_options.offset.saturating_add(search_options.limit));
+        results.drain(..search_options.offset.min(results.len()));
+        Ok(self.format(results))
+    }
+}
+
+//----------------------------------------------------------------------
+// Tests
+//----------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::options::config::FuseOptions;
+    use serde_json::json;
+
+    fn products() -> Vec<Value> {
+        vec![
+            json!({"brand": "Acme", "description": "Anvils and rockets"}),
+            json!({"brand": "Rocket Inc", "description": "Acme-grade rockets"}),
+            json!({"brand": "Bolt", "description": "Screws"}),
+        ]
+    }
+
+    fn product_options() -> FuseOptions<'static> {
+        FuseOptions {
+            keys: vec!["brand".into(), "description".into()],
+            include_score: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_weights_order_results() {
+        let docs = products();
+        let fuse = Fuse::new(&docs, &product_options(), None);
+        let order = |terms: &[(&str, f64)]| -> Vec<usize> {
+            let results = fuse.search_weighted(terms, &FuseSearchOptions::default()).unwrap();
+            results.iter().map(|result| result.ref_index).collect()
+        };
+
+        assert_eq!(order(&[("acme", 5.0), ("rocket", 1.0)])[..2], [0, 1]);
+        assert_eq!(order(&[("acme", 1.0), ("rocket inc", 5.0)])[0], 1);
+        assert_eq!(order(&[("screws", 1.0)])[0], 2);
+    }
+
+    #[test]
+    fn test_single_pattern_matches_plain_search() {
+        let docs = products();
+        let fuse = Fuse::new(&docs, &product_options(), None);
+
+        let weighted = fuse.search_weighted(&[("rocket", 2.0)], &FuseSearchOptions::default()).unwrap();
+        let plain = fuse.search_results("rocket", &FuseSearchOptions::default()).unwrap();
+        assert_eq!(
+            weighted.iter().map(|r| (r.ref_index, r.score)).collect::<Vec<_>>(),
+            plain.iter().map(|r| (r.ref_index, r.score)).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_invalid_weight() {
+        let fuse = Fuse::new(&products(), &product_options(), None);
+
+        for weight in [0.0, -1.0, f64::NAN] {
+            let result = fuse.search_weighted(&[("acme", weight)], &FuseSearchOptions::default());
+            assert!(matches!(result, Err(FuseError::InvalidOption(_))));
+        }
+        assert!(fuse.search_weighted(&[], &FuseSearchOptions::default()).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,242 @@
+//! Allocation-free case-insensitive comparison
+//!
+//! The functions in this module compare texts through lazily case-folded
+//! character iterators, so case-insensitive exact, prefix, suffix and
+//! substring checks don't need a lowercased copy of every text. Positions
+//! are character positions in the case-folded text, matching what
+//! [`Normalizer::normalize`](crate::Normalizer::normalize) would produce.
+//!
+//! Case folding is lowercasing plus the Unicode full case folding mappings
+//! lowercasing misses: final sigma folds to `σ`, `ß` to `ss`, ligatures like
+//! `ﬁ` to their letters, and Greek symbol variants like `ϐ` to the plain
+//! letter. Dotted capital `İ` and dotless `ı` both fold to a plain `i`, so
+//! Turkish text matches whichever `i` a pattern is typed with.
+
+//----------------------------------------------------------------------
+// Types
+//----------------------------------------------------------------------
+
+/// Iterator over the case folding of a character, returned by [`fold_char`]
+#[derive(Debug, Clone)]
+pub struct FoldChar {
+    /// The folded characters
+    chars: [char; 3],
+
+    /// Position of the next character from the front
+    front: usize,
+
+    /// Position after the next character from the back
+    back: usize,
+}
+
+//----------------------------------------------------------------------
+// Constants
+//----------------------------------------------------------------------
+
+/// Characters whose case folding differs from their lowercase, sorted by
+/// character
+const FOLDINGS: &[(char, &str)] = &[
+    ('ß', "ss"), ('İ', "i"), ('ı', "i"), ('ŉ', "ʼn"), ('ſ', "s"),
+    ('\u{345}', "ι"), ('ς', "σ"), ('ϐ', "β"), ('ϑ', "θ"), ('ϕ', "φ"),
+    ('ϖ', "π"), ('ϰ', "κ"), ('ϱ', "ρ"), ('ϵ', "ε"), ('ẛ', "ṡ"),
+    ('ẞ', "ss"), ('\u{1fbe}', "ι"), ('ﬀ', "ff"), ('ﬁ', "fi"), ('ﬂ', "fl"),
+    ('ﬃ', "ffi"), ('ﬄ', "ffl"), ('ﬅ', "st"), ('ﬆ', "st"),
+];
+
+//----------------------------------------------------------------------
+// Implementation
+//----------------------------------------------------------------------
+
+impl Iterator for FoldChar {
+    type Item = char;
+
+    fn next(&mut self) -> Option<char> {
+        if self.front == self.back {
+            return None;
+        }
+
+        self.front += 1;
+        Some(self.chars[self.front - 1])
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.back - self.front;
+        (len, Some(len))
+    }
+}
+
+impl DoubleEndedIterator for FoldChar {
+    fn next_back(&mut self) -> Option<char> {
+        if self.front == self.back {
+            return None;
+        }
+
+        self.back -= 1;
+        Some(self.chars[self.back])
+    }
+}
+
+impl ExactSizeIterator for FoldChar {}
+
+//----------------------------------------------------------------------
+// Public API
+//----------------------------------------------------------------------
+
+/// Returns the case folding of `c`.
+pub fn fold_char(c: char) -> FoldChar {
+    let mut chars = ['\0'; 3];
+    let mut back = 0;
+
+    match FOLDINGS.binary_search_by_key(&c, |&(from, _)| from) {
+        Ok(i) => {
+            for folded in FOLDINGS[i].1.chars() {
+                chars[back] = folded;
+                back += 1;
+            }
+        }
+        Err(_) => {
+            for lower in c.to_lowercase() {
+                chars[back] = lower;
+                back += 1;
+            }
+        }
+    }
+
+    FoldChar { chars, front: 0, back }
+}
+
+/// Returns the case-folded characters of `text`.
+pub fn fold(text: &str) -> impl DoubleEndedIterator<Item = char> + Clone + '_ {
+    text.chars().flat_map(fold_char)
+}
+
+/// Returns the number of characters of the case-folded `text`.
+pub fn folded_len(text: &str) -> usize {
+    fold(text).count()
+}
+
+/// Returns `true` if `a` and `b` are equal ignoring case.
+pub fn caseless_eq(a: &str, b: &str) -> bool {
+    fold(a).eq(fold(b))
+}
+
+/// Returns `true` if `text` starts with `prefix` ignoring case.
+pub fn caseless_starts_with(text: &str, prefix: &str) -> bool {
+    is_prefix(fold(prefix), fold(text))
+}
+
+/// Returns `true` if `text` ends with `suffix` ignoring case.
+pub fn caseless_ends_with(text: &str, suffix: &str) -> bool {
+    is_prefix(fold(suffix).rev(), fold(text).rev())
+}
+
+/// Returns `true` if `text` contains `pattern` ignoring case.
+pub fn caseless_contains(text: &str, pattern: &str) -> bool {
+    let mut text = fold(text);
+
+    loop {
+        if is_prefix(fold(pattern), text.clone()) {
+            return true;
+        }
+
+        if text.next().is_none() {
+            return false;
+        }
+    }
+}
+
+/// Returns the inclusive character ranges of all non-overlapping occurrences
+/// of `pattern` in `text`, ignoring case.
+pub fn caseless_match_indices(text: &str, pattern: &str) -> Vec<(usize, usize)> {
+    let len = folded_len(pattern);
+    if len == 0 {
+        return Vec::new();
+    }
+
+    let mut indices = Vec::new();
+    let mut text = fold(text);
+    let mut position = 0;
+
+    loop {
+        if is_prefix(fold(pattern), text.clone()) {
+            indices.push((position, position + len - 1));
+            text.nth(len - 1);
+            position += len;
+        } else if text.next().is_some() {
+            position += 1;
+        } else {
+            return indices;
+        }
+    }
+}
+
+//----------------------------------------------------------------------
+// Implementation Details
+//----------------------------------------------------------------------
+
+/// Returns `true` if `text` starts with all the characters of `prefix`.
+fn is_prefix<P, T>(mut prefix: P, mut text: T) -> bool
+where
+    P: Iterator<Item = char>,
+    T: Iterator<Item = char>,
+{
+    prefix.all(|p| text.next() == Some(p))
+}
+
+//----------------------------------------------------------------------
+// Tests
+//----------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_caseless_comparisons() {
+        assert!(caseless_eq("Scheme", "sCHEME"));
+        assert!(!caseless_eq("Scheme", "Schemes"));
+        assert!(caseless_starts_with("JavaScript", "java"));
+        assert!(!caseless_starts_with("Java", "javascript"));
+        assert!(caseless_ends_with("INDEX.JS", ".js"));
+        assert!(caseless_contains("Old Man's War", "MAN'S"));
+        assert!(!caseless_contains("Old Man's War", "boy"));
+        assert!(caseless_contains("anything", ""));
+    }
+
+    #[test]
+    fn test_caseless_match_indices() {
+        assert_eq!(caseless_match_indices("BaNaNa", "an"), vec![(1, 2), (3, 4)]);
+        assert_eq!(caseless_match_indices("aaaa", "AA"), vec![(0, 1), (2, 3)]);
+        assert!(caseless_match_indices("abc", "").is_empty());
+    }
+
+    #[test]
+    fn test_positions_follow_folded_text() {
+        // 'ß' folds to two characters
+        assert_eq!(folded_len("Straße"), 7);
+        assert_eq!(caseless_match_indices("Straße Süd", "süd"), vec![(8, 10)]);
+        assert!(caseless_eq("STRASSE", "straße"));
+    }
+
+    #[test]
+    fn test_foldings_are_sorted() {
+        assert!(FOLDINGS.windows(2).all(|w| w[0].0 < w[1].0));
+    }
+
+    #[test]
+    fn test_case_folding_edge_cases() {
+        // Final sigma
+        assert!(caseless_eq("ΟΔΟΣ", "οδος"));
+        assert!(caseless_eq("οδός", "ΟΔΌΣ"));
+
+        // Dotted and dotless i
+        assert!(caseless_eq("İSTANBUL", "istanbul"));
+        assert!(caseless_eq("ıspanak", "ISPANAK"));
+        assert_eq!(folded_len("İstanbul"), 8);
+
+        // Ligatures and symbol variants
+        assert!(caseless_eq("ﬁle", "FILE"));
+        assert!(caseless_eq("ϐ", "Β"));
+        assert_eq!(fold("ẞ").rev().collect::<String>(), "ss");
+    }
+}
@@ -0,0 +1,174 @@
+//! Character-based slicing of UTF-8 text
+//!
+//! Matchers work on character positions while Rust strings are indexed by
+//! bytes, so slicing a `&str` with a character position panics (or silently
+//! cuts a code point) on multi-byte text. The helpers in this module convert
+//! between the two and never split a character.
+
+use std::ops::Range;
+
+use crate::core::results::search_result::RangeTuple;
+use crate::helpers::diacritics::is_diacritic;
+
+//----------------------------------------------------------------------
+// Types
+//----------------------------------------------------------------------
+
+/// Map from character positions to byte offsets for a text
+///
+/// Building the map is `O(n)`; lookups are `O(1)` (or `O(log n)` for byte to
+/// character conversion), which makes it suitable when many ranges of the
+/// same text are converted, e.g. when highlighting match indices.
+///
+/// # Example
+///
+/// ```
+/// use fuse_rs::CharOffsets;
+///
+/// let text = "Ünïcödé text";
+/// let offsets = CharOffsets::new(text);
+///
+/// // Match indices are inclusive character ranges
+/// assert_eq!(offsets.slice(text, (0, 6)), Some("Ünïcödé"));
+/// assert_eq!(offsets.slice(text, (8, 11)), Some("text"));
+/// assert_eq!(offsets.slice(text, (8, 20)), None);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct CharOffsets {
+    /// Byte offset of every character, followed by the length of the text
+    bytes: Vec<usize>,
+}
+
+//----------------------------------------------------------------------
+// Implementation
+//----------------------------------------------------------------------
+
+impl CharOffsets {
+    /// Builds the offset map for `text`.
+    pub fn new(text: &str) -> Self {
+        let bytes = text
+            .char_indices()
+            .map(|(byte, _)| byte)
+            .chain(std::iter::once(text.len()))
+            .collect();
+
+        Self { bytes }
+    }
+
+    /// Returns the number of characters in the text.
+    pub fn char_count(&self) -> usize {
+        self.bytes.len() - 1
+    }
+
+    /// Returns the byte offset of character `index`.
+    ///
+    /// `index` may be equal to the character count, which yields the length
+    /// of the text.
+    pub fn byte_offset(&self, index: usize) -> Option<usize> {
+        self.bytes.get(index).copied()
+    }
+
+    /// Returns the index of the character starting at byte `offset`.
+    ///
+    /// Returns `None` if `offset` is not on a character boundary.
+    pub fn char_index(&self, offset: usize) -> Option<usize> {
+        self.bytes.binary_search(&offset).ok()
+    }
+
+    /// Converts an inclusive character range into a byte range.
+    ///
+    /// Returns `None` if the range is reversed or extends past the text.
+    pub fn byte_range(&self, (start, end): RangeTuple) -> Option<Range<usize>> {
+        if start > end {
+            return None;
+        }
+
+        Some(self.byte_offset(start)?..self.byte_offset(end + 1)?)
+    }
+
+    /// Slices `text` with an inclusive character range.
+    ///
+    /// `text` must be the text the map was built from.
+    pub fn slice<'t>(&self, text: &'t str, range: RangeTuple) -> Option<&'t str> {
+        text.get(self.byte_range(range)?)
+    }
+
+    /// Extends an inclusive character range over trailing combining marks.
+    ///
+    /// A range ending on a base letter (e.g. the `e` of `"e\u{301}"`) is
+    /// widened so it doesn't separate the letter from its accents.
+    pub fn extend_to_cluster(&self, text: &str, (start, end): RangeTuple) -> RangeTuple {
+        let Some(rest) = self.byte_offset(end + 1).and_then(|b| text.get(b..)) else {
+            return (start, end);
+        };
+
+        let marks = rest.chars().take_while(|c| is_diacritic(*c)).count();
+        (start, end + marks)
+    }
+}
+
+//----------------------------------------------------------------------
+// Free Functions
+//----------------------------------------------------------------------
+
+/// Returns the number of characters in `text`.
+pub fn char_len(text: &str) -> usize {
+    text.chars().count()
+}
+
+/// Slices `text` from character `start` (inclusive) to `end` (exclusive).
+///
+/// Positions past the end of the text are clamped, so this never panics.
+pub fn char_slice(text: &str, start: usize, end: usize) -> &str {
+    let byte_at = |index: usize| {
+        text.char_indices()
+            .nth(index)
+            .map_or(text.len(), |(byte, _)| byte)
+    };
+
+    let start = byte_at(start);
+    let end = byte_at(end).max(start);
+
+    &text[start..end]
+}
+
+//----------------------------------------------------------------------
+// Tests
+//----------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_offsets_multibyte() {
+        let text = "aé😀b";
+        let offsets = CharOffsets::new(text);
+
+        assert_eq!(offsets.char_count(), 4);
+        assert_eq!(offsets.byte_offset(2), Some(3));
+        assert_eq!(offsets.byte_offset(4), Some(text.len()));
+        assert_eq!(offsets.char_index(3), Some(2));
+        assert_eq!(offsets.char_index(4), None);
+        assert_eq!(offsets.byte_range((1, 2)), Some(1..7));
+        assert_eq!(offsets.slice(text, (2, 3)), Some("😀b"));
+        assert_eq!(offsets.byte_range((3, 2)), None);
+    }
+
+    #[test]
+    fn test_extend_to_cluster() {
+        let text = "cafe\u{301} au lait";
+        let offsets = CharOffsets::new(text);
+
+        assert_eq!(offsets.extend_to_cluster(text, (0, 3)), (0, 4));
+        assert_eq!(offsets.extend_to_cluster(text, (6, 7)), (6, 7));
+    }
+
+    #[test]
+    fn test_char_slice_clamps() {
+        assert_eq!(char_slice("héllo", 1, 3), "él");
+        assert_eq!(char_slice("héllo", 3, 10), "lo");
+        assert_eq!(char_slice("héllo", 4, 2), "");
+        assert_eq!(char_len("héllo"), 5);
+    }
+}
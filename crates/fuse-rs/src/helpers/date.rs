@@ -0,0 +1,159 @@
+//! RFC 3339 date parsing
+//!
+//! Dates in JSON documents are strings. Values written as an RFC 3339
+//! date-time (`2021-06-01T12:30:00Z`, `2021-06-01 12:30:00.250+02:00`) or
+//! full-date (`2021-06-01`, midnight UTC) are recognized when indexing, and
+//! their instant kept so that logical queries can filter on it.
+
+//----------------------------------------------------------------------
+// Implementation
+//----------------------------------------------------------------------
+
+/// Parses an RFC 3339 date-time or full-date.
+///
+/// # Returns
+///
+/// The number of milliseconds between the Unix epoch and the instant, or
+/// `None` if `text` is not a valid RFC 3339 date. Fractions of a
+/// millisecond are dropped.
+pub(crate) fn parse_rfc3339(text: &str) -> Option<i64> {
+    let bytes = text.as_bytes();
+    // Most strings are rejected without looking further
+    if bytes.len() < 10 || bytes[4] != b'-' || bytes[7] != b'-' {
+        return None;
+    }
+
+    let year = digits(bytes, 0, 4)?;
+    let month = digits(bytes, 5, 2)?;
+    let day = digits(bytes, 8, 2)?;
+    if !(1..=12).contains(&month) || day == 0 || day > days_in_month(year, month) {
+        return None;
+    }
+
+    let days = days_from_civil(year, month, day);
+    if bytes.len() == 10 {
+        return Some(days * MILLIS_PER_DAY);
+    }
+
+    if !matches!(bytes[10], b'T' | b't' | b' ') || bytes.len() < 20 || bytes[13] != b':' || bytes[16] != b':' {
+        return None;
+    }
+
+    let hour = digits(bytes, 11, 2)?;
+    let minute = digits(bytes, 14, 2)?;
+    // 60 is a leap second
+    let second = digits(bytes, 17, 2)?;
+    if hour > 23 || minute > 59 || second > 60 {
+        return None;
+    }
+
+    let mut pos = 19;
+    let mut millis = 0;
+    if bytes[pos] == b'.' {
+        let start = pos + 1;
+        pos = start;
+        while pos < bytes.len() && bytes[pos].is_ascii_digit() {
+            pos += 1;
+        }
+        if pos == start {
+            return None;
+        }
+        // Only the first three digits are kept
+        let kept = (pos - start).min(3);
+        millis = digits(bytes, start, kept)? * 10_i64.pow(3 - kept as u32);
+    }
+
+    let offset = match bytes.get(pos) {
+        Some(b'Z' | b'z') if pos + 1 == bytes.len() => 0,
+        Some(&sign @ (b'+' | b'-')) if pos + 6 == bytes.len() && bytes[pos + 3] == b':' => {
+            let hours = digits(bytes, pos + 1, 2)?;
+            let minutes = digits(bytes, pos + 4, 2)?;
+            if hours > 23 || minutes > 59 {
+                return None;
+            }
+            let offset = (hours * 60 + minutes) * 60_000;
+            if sign == b'-' { -offset } else { offset }
+        }
+        _ => return None,
+    };
+
+    let seconds = ((days * 24 + hour) * 60 + minute) * 60 + second;
+    Some(seconds * 1000 + millis - offset)
+}
+
+//----------------------------------------------------------------------
+// Implementation Details
+//----------------------------------------------------------------------
+
+/// Milliseconds in a day
+const MILLIS_PER_DAY: i64 = 86_400_000;
+
+/// Reads the `len` ASCII digits at `start` as a number.
+fn digits(bytes: &[u8], start: usize, len: usize) -> Option<i64> {
+    bytes.get(start..start + len)?.iter().try_fold(0, |n, &b| {
+        b.is_ascii_digit().then(|| n * 10 + i64::from(b - b'0'))
+    })
+}
+
+/// Returns the number of days in `month` of `year`.
+fn days_in_month(year: i64, month: i64) -> i64 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Returns the number of days between the Unix epoch and a date of the
+/// proleptic Gregorian calendar.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    // Years start in March, so that leap days end them
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+//----------------------------------------------------------------------
+// Tests
+//----------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_rfc3339() {
+        assert_eq!(parse_rfc3339("1970-01-01"), Some(0));
+        assert_eq!(parse_rfc3339("1970-01-01T00:00:01Z"), Some(1000));
+        assert_eq!(parse_rfc3339("2000-02-29T12:00:00Z"), Some(951_825_600_000));
+        assert_eq!(parse_rfc3339("1969-12-31T23:59:59.5z"), Some(-500));
+        assert_eq!(parse_rfc3339("2021-06-01 14:30:00.250123+02:00"), Some(1_622_550_600_250));
+        assert_eq!(parse_rfc3339("2021-06-01T12:30:00.25Z"), parse_rfc3339("2021-06-01T14:30:00.250+02:00"));
+        assert_eq!(parse_rfc3339("2021-06-01T07:30:00-05:00"), parse_rfc3339("2021-06-01T12:30:00Z"));
+    }
+
+    #[test]
+    fn test_parse_rfc3339_rejects_invalid_dates() {
+        for text in [
+            "",
+            "2021",
+            "June 1, 2021",
+            "2021-13-01",
+            "2021-02-29",
+            "2021-04-31",
+            "2021-06-01T",
+            "2021-06-01T12:30:00",
+            "2021-06-01T24:00:00Z",
+            "2021-06-01T12:30:00.Z",
+            "2021-06-01T12:30:00+0200",
+            "2021-06-01T12:30:00Z trailing",
+            "2021-06-0１",
+        ] {
+            assert_eq!(parse_rfc3339(text), None, "{}", text);
+        }
+    }
+}
@@ -2,22 +2,92 @@
 //!
 //! This module provides functionality to strip diacritical marks
 //! (accents, umlauts, etc.) from text to improve search accuracy.
+//!
+//! With the `unicode-normalization` feature (enabled by default), text is
+//! decomposed to NFD and every combining mark is removed, which covers
+//! precomposed letters of any script (Vietnamese, Polish, ...). Without it,
+//! only Latin-1 accented letters and standalone combining marks are handled.
+
+#[cfg(not(feature = "unicode-normalization"))]
+use std::cmp::Ordering;
 
-use regex::Regex;
-use unicode_normalization::UnicodeNormalization;
-use lazy_static::lazy_static;
+#[cfg(feature = "unicode-normalization")]
+use unicode_normalization::{UnicodeNormalization, char::is_combining_mark};
 
 //----------------------------------------------------------------------
-// Constants & Statics
+// Constants
 //----------------------------------------------------------------------
 
-lazy_static! {
-    /// Regular expression matching all Unicode diacritical marks
-    ///
-    /// This comprehensive pattern covers combining marks from various 
-    /// Unicode blocks, including Latin, Greek, Cyrillic, Arabic, and many others.
-    static ref DIACRITICS_REGEX: Regex = Regex::new(r"[\u0300-\u036F\u0483-\u0489\u0591-\u05BD\u05BF\u05C1\u05C2\u05C4\u05C5\u05C7\u0610-\u061A\u064B-\u065F\u0670\u06D6-\u06DC\u06DF-\u06E4\u06E7\u06E8\u06EA-\u06ED\u0711\u0730-\u074A\u07A6-\u07B0\u07EB-\u07F3\u07FD\u0816-\u0819\u081B-\u0823\u0825-\u0827\u0829-\u082D\u0859-\u085B\u08D3-\u08E1\u08E3-\u0903\u093A-\u093C\u093E-\u094F\u0951-\u0957\u0962\u0963\u0981-\u0983\u09BC\u09BE-\u09C4\u09C7\u09C8\u09CB-\u09CD\u09D7\u09E2\u09E3\u09FE\u0A01-\u0A03\u0A3C\u0A3E-\u0A42\u0A47\u0A48\u0A4B-\u0A4D\u0A51\u0A70\u0A71\u0A75\u0A81-\u0A83\u0ABC\u0ABE-\u0AC5\u0AC7-\u0AC9\u0ACB-\u0ACD\u0AE2\u0AE3\u0AFA-\u0AFF\u0B01-\u0B03\u0B3C\u0B3E-\u0B44\u0B47\u0B48\u0B4B-\u0B4D\u0B56\u0B57\u0B62\u0B63\u0B82\u0BBE-\u0BC2\u0BC6-\u0BC8\u0BCA-\u0BCD\u0BD7\u0C00-\u0C04\u0C3E-\u0C44\u0C46-\u0C48\u0C4A-\u0C4D\u0C55\u0C56\u0C62\u0C63\u0C81-\u0C83\u0CBC\u0CBE-\u0CC4\u0CC6-\u0CC8\u0CCA-\u0CCD\u0CD5\u0CD6\u0CE2\u0CE3\u0D00-\u0D03\u0D3B\u0D3C\u0D3E-\u0D44\u0D46-\u0D48\u0D4A-\u0D4D\u0D57\u0D62\u0D63\u0D82\u0D83\u0DCA\u0DCF-\u0DD4\u0DD6\u0DD8-\u0DDF\u0DF2\u0DF3\u0E31\u0E34-\u0E3A\u0E47-\u0E4E\u0EB1\u0EB4-\u0EB9\u0EBB\u0EBC\u0EC8-\u0ECD\u0F18\u0F19\u0F35\u0F37\u0F39\u0F3E\u0F3F\u0F71-\u0F84\u0F86\u0F87\u0F8D-\u0F97\u0F99-\u0FBC\u0FC6\u102B-\u103E\u1056-\u1059\u105E-\u1060\u1062-\u1064\u1067-\u106D\u1071-\u1074\u1082-\u108D\u108F\u109A-\u109D\u135D-\u135F\u1712-\u1714\u1732-\u1734\u1752\u1753\u1772\u1773\u17B4-\u17D3\u17DD\u180B-\u180D\u1885\u1886\u18A9\u1920-\u192B\u1930-\u193B\u1A17-\u1A1B\u1A55-\u1A5E\u1A60-\u1A7C\u1A7F\u1AB0-\u1ABE\u1B00-\u1B04\u1B34-\u1B44\u1B6B-\u1B73\u1B80-\u1B82\u1BA1-\u1BAD\u1BE6-\u1BF3\u1C24-\u1C37\u1CD0-\u1CD2\u1CD4-\u1CE8\u1CED\u1CF2-\u1CF4\u1CF7-\u1CF9\u1DC0-\u1DF9\u1DFB-\u1DFF\u20D0-\u20F0\u2CEF-\u2CF1\u2D7F\u2DE0-\u2DFF\u302A-\u302F\u3099\u309A\uA66F-\uA672\uA674-\uA67D\uA69E\uA69F\uA6F0\uA6F1\uA802\uA806\uA80B\uA823-\uA827\uA880\uA881\uA8B4-\uA8C5\uA8E0-\uA8F1\uA8FF\uA926-\uA92D\uA947-\uA953\uA980-\uA983\uA9B3-\uA9C0\uA9E5\uAA29-\uAA36\uAA43\uAA4C\uAA4D\uAA7B-\uAA7D\uAAB0\uAAB2-\uAAB4\uAAB7\uAAB8\uAABE\uAABF\uAAC1\uAAEB-\uAAEF\uAAF5\uAAF6\uABE3-\uABEA\uABEC\uABED\uFB1E\uFE00-\uFE0F\uFE20-\uFE2F]").unwrap();
-}
+/// Ranges of Unicode diacritical (combining) marks, sorted and disjoint
+///
+/// Covers combining marks from various Unicode blocks, including Latin,
+/// Greek, Cyrillic, Arabic, and many others.
+#[cfg(not(feature = "unicode-normalization"))]
+const DIACRITIC_RANGES: &[(char, char)] = &[
+    ('\u{0300}', '\u{036F}'), ('\u{0483}', '\u{0489}'), ('\u{0591}', '\u{05BD}'),
+    ('\u{05BF}', '\u{05BF}'), ('\u{05C1}', '\u{05C2}'), ('\u{05C4}', '\u{05C5}'),
+    ('\u{05C7}', '\u{05C7}'), ('\u{0610}', '\u{061A}'), ('\u{064B}', '\u{065F}'),
+    ('\u{0670}', '\u{0670}'), ('\u{06D6}', '\u{06DC}'), ('\u{06DF}', '\u{06E4}'),
+    ('\u{06E7}', '\u{06E8}'), ('\u{06EA}', '\u{06ED}'), ('\u{0711}', '\u{0711}'),
+    ('\u{0730}', '\u{074A}'), ('\u{07A6}', '\u{07B0}'), ('\u{07EB}', '\u{07F3}'),
+    ('\u{07FD}', '\u{07FD}'), ('\u{0816}', '\u{0819}'), ('\u{081B}', '\u{0823}'),
+    ('\u{0825}', '\u{0827}'), ('\u{0829}', '\u{082D}'), ('\u{0859}', '\u{085B}'),
+    ('\u{08D3}', '\u{08E1}'), ('\u{08E3}', '\u{0903}'), ('\u{093A}', '\u{093C}'),
+    ('\u{093E}', '\u{094F}'), ('\u{0951}', '\u{0957}'), ('\u{0962}', '\u{0963}'),
+    ('\u{0981}', '\u{0983}'), ('\u{09BC}', '\u{09BC}'), ('\u{09BE}', '\u{09C4}'),
+    ('\u{09C7}', '\u{09C8}'), ('\u{09CB}', '\u{09CD}'), ('\u{09D7}', '\u{09D7}'),
+    ('\u{09E2}', '\u{09E3}'), ('\u{09FE}', '\u{09FE}'), ('\u{0A01}', '\u{0A03}'),
+    ('\u{0A3C}', '\u{0A3C}'), ('\u{0A3E}', '\u{0A42}'), ('\u{0A47}', '\u{0A48}'),
+    ('\u{0A4B}', '\u{0A4D}'), ('\u{0A51}', '\u{0A51}'), ('\u{0A70}', '\u{0A71}'),
+    ('\u{0A75}', '\u{0A75}'), ('\u{0A81}', '\u{0A83}'), ('\u{0ABC}', '\u{0ABC}'),
+    ('\u{0ABE}', '\u{0AC5}'), ('\u{0AC7}', '\u{0AC9}'), ('\u{0ACB}', '\u{0ACD}'),
+    ('\u{0AE2}', '\u{0AE3}'), ('\u{0AFA}', '\u{0AFF}'), ('\u{0B01}', '\u{0B03}'),
+    ('\u{0B3C}', '\u{0B3C}'), ('\u{0B3E}', '\u{0B44}'), ('\u{0B47}', '\u{0B48}'),
+    ('\u{0B4B}', '\u{0B4D}'), ('\u{0B56}', '\u{0B57}'), ('\u{0B62}', '\u{0B63}'),
+    ('\u{0B82}', '\u{0B82}'), ('\u{0BBE}', '\u{0BC2}'), ('\u{0BC6}', '\u{0BC8}'),
+    ('\u{0BCA}', '\u{0BCD}'), ('\u{0BD7}', '\u{0BD7}'), ('\u{0C00}', '\u{0C04}'),
+    ('\u{0C3E}', '\u{0C44}'), ('\u{0C46}', '\u{0C48}'), ('\u{0C4A}', '\u{0C4D}'),
+    ('\u{0C55}', '\u{0C56}'), ('\u{0C62}', '\u{0C63}'), ('\u{0C81}', '\u{0C83}'),
+    ('\u{0CBC}', '\u{0CBC}'), ('\u{0CBE}', '\u{0CC4}'), ('\u{0CC6}', '\u{0CC8}'),
+    ('\u{0CCA}', '\u{0CCD}'), ('\u{0CD5}', '\u{0CD6}'), ('\u{0CE2}', '\u{0CE3}'),
+    ('\u{0D00}', '\u{0D03}'), ('\u{0D3B}', '\u{0D3C}'), ('\u{0D3E}', '\u{0D44}'),
+    ('\u{0D46}', '\u{0D48}'), ('\u{0D4A}', '\u{0D4D}'), ('\u{0D57}', '\u{0D57}'),
+    ('\u{0D62}', '\u{0D63}'), ('\u{0D82}', '\u{0D83}'), ('\u{0DCA}', '\u{0DCA}'),
+    ('\u{0DCF}', '\u{0DD4}'), ('\u{0DD6}', '\u{0DD6}'), ('\u{0DD8}', '\u{0DDF}'),
+    ('\u{0DF2}', '\u{0DF3}'), ('\u{0E31}', '\u{0E31}'), ('\u{0E34}', '\u{0E3A}'),
+    ('\u{0E47}', '\u{0E4E}'), ('\u{0EB1}', '\u{0EB1}'), ('\u{0EB4}', '\u{0EB9}'),
+    ('\u{0EBB}', '\u{0EBC}'), ('\u{0EC8}', '\u{0ECD}'), ('\u{0F18}', '\u{0F19}'),
+    ('\u{0F35}', '\u{0F35}'), ('\u{0F37}', '\u{0F37}'), ('\u{0F39}', '\u{0F39}'),
+    ('\u{0F3E}', '\u{0F3F}'), ('\u{0F71}', '\u{0F84}'), ('\u{0F86}', '\u{0F87}'),
+    ('\u{0F8D}', '\u{0F97}'), ('\u{0F99}', '\u{0FBC}'), ('\u{0FC6}', '\u{0FC6}'),
+    ('\u{102B}', '\u{103E}'), ('\u{1056}', '\u{1059}'), ('\u{105E}', '\u{1060}'),
+    ('\u{1062}', '\u{1064}'), ('\u{1067}', '\u{106D}'), ('\u{1071}', '\u{1074}'),
+    ('\u{1082}', '\u{108D}'), ('\u{108F}', '\u{108F}'), ('\u{109A}', '\u{109D}'),
+    ('\u{135D}', '\u{135F}'), ('\u{1712}', '\u{1714}'), ('\u{1732}', '\u{1734}'),
+    ('\u{1752}', '\u{1753}'), ('\u{1772}', '\u{1773}'), ('\u{17B4}', '\u{17D3}'),
+    ('\u{17DD}', '\u{17DD}'), ('\u{180B}', '\u{180D}'), ('\u{1885}', '\u{1886}'),
+    ('\u{18A9}', '\u{18A9}'), ('\u{1920}', '\u{192B}'), ('\u{1930}', '\u{193B}'),
+    ('\u{1A17}', '\u{1A1B}'), ('\u{1A55}', '\u{1A5E}'), ('\u{1A60}', '\u{1A7C}'),
+    ('\u{1A7F}', '\u{1A7F}'), ('\u{1AB0}', '\u{1ABE}'), ('\u{1B00}', '\u{1B04}'),
+    ('\u{1B34}', '\u{1B44}'), ('\u{1B6B}', '\u{1B73}'), ('\u{1B80}', '\u{1B82}'),
+    ('\u{1BA1}', '\u{1BAD}'), ('\u{1BE6}', '\u{1BF3}'), ('\u{1C24}', '\u{1C37}'),
+    ('\u{1CD0}', '\u{1CD2}'), ('\u{1CD4}', '\u{1CE8}'), ('\u{1CED}', '\u{1CED}'),
+    ('\u{1CF2}', '\u{1CF4}'), ('\u{1CF7}', '\u{1CF9}'), ('\u{1DC0}', '\u{1DF9}'),
+    ('\u{1DFB}', '\u{1DFF}'), ('\u{20D0}', '\u{20F0}'), ('\u{2CEF}', '\u{2CF1}'),
+    ('\u{2D7F}', '\u{2D7F}'), ('\u{2DE0}', '\u{2DFF}'), ('\u{302A}', '\u{302F}'),
+    ('\u{3099}', '\u{309A}'), ('\u{A66F}', '\u{A672}'), ('\u{A674}', '\u{A67D}'),
+    ('\u{A69E}', '\u{A69F}'), ('\u{A6F0}', '\u{A6F1}'), ('\u{A802}', '\u{A802}'),
+    ('\u{A806}', '\u{A806}'), ('\u{A80B}', '\u{A80B}'), ('\u{A823}', '\u{A827}'),
+    ('\u{A880}', '\u{A881}'), ('\u{A8B4}', '\u{A8C5}'), ('\u{A8E0}', '\u{A8F1}'),
+    ('\u{A8FF}', '\u{A8FF}'), ('\u{A926}', '\u{A92D}'), ('\u{A947}', '\u{A953}'),
+    ('\u{A980}', '\u{A983}'), ('\u{A9B3}', '\u{A9C0}'), ('\u{A9E5}', '\u{A9E5}'),
+    ('\u{AA29}', '\u{AA36}'), ('\u{AA43}', '\u{AA43}'), ('\u{AA4C}', '\u{AA4D}'),
+    ('\u{AA7B}', '\u{AA7D}'), ('\u{AAB0}', '\u{AAB0}'), ('\u{AAB2}', '\u{AAB4}'),
+    ('\u{AAB7}', '\u{AAB8}'), ('\u{AABE}', '\u{AABF}'), ('\u{AAC1}', '\u{AAC1}'),
+    ('\u{AAEB}', '\u{AAEF}'), ('\u{AAF5}', '\u{AAF6}'), ('\u{ABE3}', '\u{ABEA}'),
+    ('\u{ABEC}', '\u{ABED}'), ('\u{FB1E}', '\u{FB1E}'), ('\u{FE00}', '\u{FE0F}'),
+    ('\u{FE20}', '\u{FE2F}'),
+];
 
 //----------------------------------------------------------------------
 // Public API
@@ -39,11 +109,74 @@ pub trait Diacritics {
 /// Implementation for string references
 impl Diacritics for &str {
     fn strip_diacritics(self) -> String {
-        // First decompose the string into base characters and combining marks
-        let result = self.nfd().collect::<String>();
-        
-        // Then remove all the combining marks
-        DIACRITICS_REGEX.replace_all(&result, "").to_string()
+        self.chars().flat_map(strip_char_diacritics).collect()
+    }
+}
+
+/// Returns `true` if `c` is a diacritical (combining) mark
+///
+/// This uses the same character set as [`Diacritics::strip_diacritics`], so
+/// it can be used to strip marks from a decomposed string one character at a time.
+#[cfg(feature = "unicode-normalization")]
+pub fn is_diacritic(c: char) -> bool {
+    is_combining_mark(c)
+}
+
+/// Returns `true` if `c` is a diacritical (combining) mark
+///
+/// This uses the same character set as [`Diacritics::strip_diacritics`], so
+/// it can be used to strip marks from a decomposed string one character at a time.
+#[cfg(not(feature = "unicode-normalization"))]
+pub fn is_diacritic(c: char) -> bool {
+    DIACRITIC_RANGES
+        .binary_search_by(|&(start, end)| {
+            if end < c {
+                Ordering::Less
+            } else if start > c {
+                Ordering::Greater
+            } else {
+                Ordering::Equal
+            }
+        })
+        .is_ok()
+}
+
+/// Decomposes `c` and yields it without its diacritical marks
+///
+/// A precomposed character such as `'é'` yields its base letter `'e'`; a
+/// combining mark yields nothing.
+#[cfg(feature = "unicode-normalization")]
+pub fn strip_char_diacritics(c: char) -> impl Iterator<Item = char> {
+    std::iter::once(c).nfd().filter(|d| !is_diacritic(*d))
+}
+
+/// Yields `c` without its diacritical marks
+///
+/// Without the `unicode-normalization` feature only the precomposed letters
+/// of the Latin-1 Supplement block are folded to their base letter; any other
+/// precomposed character is kept as is and standalone combining marks are
+/// removed.
+#[cfg(not(feature = "unicode-normalization"))]
+pub fn strip_char_diacritics(c: char) -> impl Iterator<Item = char> {
+    std::iter::once(fold_latin1(c)).filter(|d| !is_diacritic(*d))
+}
+
+/// Base letters for U+00C0..=U+00FF, matching what NFD decomposition yields
+///
+/// Letters without a canonical decomposition (`Æ`, `Ð`, `Ø`, `ß`, ...) map to
+/// themselves.
+#[cfg(not(feature = "unicode-normalization"))]
+const LATIN1_BASE: &str = "AAAAAAÆCEEEEIIIIÐNOOOOO×ØUUUUYÞßaaaaaaæceeeeiiiiðnooooo÷øuuuuyþy";
+
+/// Folds a precomposed Latin-1 letter to its base letter
+#[cfg(not(feature = "unicode-normalization"))]
+fn fold_latin1(c: char) -> char {
+    match c {
+        '\u{00C0}'..='\u{00FF}' => LATIN1_BASE
+            .chars()
+            .nth(c as usize - 0xC0)
+            .unwrap_or(c),
+        _ => c,
     }
 }
 
@@ -72,11 +205,49 @@ mod tests {
         assert_eq!(input.strip_diacritics(), expected);
     }
 
+    #[test]
+    fn test_is_diacritic() {
+        assert!(is_diacritic('\u{0301}'));
+        assert!(!is_diacritic('e'));
+        assert!(!is_diacritic('é'));
+    }
+
     #[test]
     fn test_strip_diacritics_empty() {
         let input = "";
         let expected = "";
         assert_eq!(input.strip_diacritics(), expected);
     }
-}
 
+    #[test]
+    fn test_strip_diacritics_combining_input() {
+        // Already decomposed text is handled with or without decomposition
+        let input = "Cafe\u{0301}";
+        assert_eq!(input.strip_diacritics(), "Cafe");
+    }
+
+    #[cfg(feature = "unicode-normalization")]
+    #[test]
+    fn test_strip_diacritics_beyond_latin1() {
+        assert_eq!("Tiếng Việt có dấu".strip_diacritics(), "Tieng Viet co dau");
+        assert_eq!("Zażółć gęślą jaźń".strip_diacritics(), "Zazołc gesla jazn");
+        assert_eq!("Ελληνικά".strip_diacritics(), "Ελληνικα");
+    }
+
+    #[cfg(feature = "unicode-normalization")]
+    #[test]
+    fn test_is_diacritic_any_combining_mark() {
+        // Combining marks added in recent Unicode versions
+        assert!(is_diacritic('\u{1AC0}'));
+        assert!(is_diacritic('\u{0903}'));
+    }
+
+    #[test]
+    #[cfg(not(feature = "unicode-normalization"))]
+    fn test_diacritic_ranges_are_sorted() {
+        assert!(DIACRITIC_RANGES.iter().all(|(start, end)| start <= end));
+        assert!(DIACRITIC_RANGES.windows(2).all(|pair| pair[0].1 < pair[1].0));
+        assert!(is_diacritic('\u{FE2F}'));
+        assert!(!is_diacritic('\u{FE30}'));
+    }
+}
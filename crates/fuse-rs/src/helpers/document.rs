@@ -0,0 +1,123 @@
+//! Abstraction over document representations
+//!
+//! Path lookup and indexing only need a handful of operations on a document:
+//! read a property, read an array element and read a scalar. The `Document`
+//! trait captures those so documents don't have to be converted into
+//! `serde_json::Value` before they can be indexed.
+
+use serde_json::Value;
+
+use crate::helpers::get::GetValue;
+
+//----------------------------------------------------------------------
+// Public API
+//----------------------------------------------------------------------
+
+/// Read-only access to a tree of JSON-like values
+///
+/// Implemented for `serde_json::Value`. Other representations (for example
+/// the value types of other JSON or CBOR crates) can implement it to be
+/// indexed with [`FuseIndex::add_document`](crate::FuseIndex::add_document).
+///
+/// # Example
+///
+/// ```
+/// use fuse_rs::{Document, GetValue};
+///
+/// /// A flat record with string fields only
+/// enum Flat {
+///     Record(Vec<(&'static str, Flat)>),
+///     Text(&'static str),
+/// }
+///
+/// impl Document for Flat {
+///     fn field(&self, name: &str) -> Option<&Self> {
+///         match self {
+///             Flat::Record(fields) => fields.iter().find(|(k, _)| *k == name).map(|(_, v)| v),
+///             Flat::Text(_) => None,
+///         }
+///     }
+///
+///     fn array_len(&self) -> Option<usize> {
+///         None
+///     }
+///
+///     fn element(&self, _index: usize) -> Option<&Self> {
+///         None
+///     }
+///
+///     fn scalar(&self) -> Option<GetValue> {
+///         match self {
+///             Flat::Text(s) => Some(GetValue::String(s.to_string())),
+///             Flat::Record(_) => None,
+///         }
+///     }
+/// }
+///
+/// let doc = Flat::Record(vec![("title", Flat::Text("Dune"))]);
+/// assert_eq!(doc.field("title").and_then(Document::scalar), Some(GetValue::String("Dune".into())));
+/// ```
+pub trait Document: Sized {
+    /// Returns the property `name` of an object.
+    fn field(&self, name: &str) -> Option<&Self>;
+
+    /// Returns the number of elements if this is an array.
+    fn array_len(&self) -> Option<usize>;
+
+    /// Returns the element at `index` of an array.
+    fn element(&self, index: usize) -> Option<&Self>;
+
+    /// Returns the value if this is a string, number or boolean.
+    fn scalar(&self) -> Option<GetValue>;
+
+    /// Returns the values of the properties if this is an object, for `*`
+    /// path segments.
+    fn field_values(&self) -> Vec<&Self> {
+        Vec::new()
+    }
+
+    /// Returns the value if this is a string.
+    fn as_text(&self) -> Option<&str> {
+        None
+    }
+
+    /// Returns `true` if this is an array.
+    fn is_array(&self) -> bool {
+        self.array_len().is_some()
+    }
+}
+
+//----------------------------------------------------------------------
+// Implementations
+//----------------------------------------------------------------------
+
+impl Document for Value {
+    fn field(&self, name: &str) -> Option<&Self> {
+        self.as_object()?.get(name)
+    }
+
+    fn array_len(&self) -> Option<usize> {
+        self.as_array().map(Vec::len)
+    }
+
+    fn element(&self, index: usize) -> Option<&Self> {
+        self.as_array()?.get(index)
+    }
+
+    fn field_values(&self) -> Vec<&Self> {
+        self.as_object().map(|object| object.values().collect()).unwrap_or_default()
+    }
+
+    fn scalar(&self) -> Option<GetValue> {
+        match self {
+            Value::String(s) => Some(GetValue::from_text(s.clone())),
+            Value::Number(n) => Some(GetValue::Number(n.clone())),
+            Value::Bool(b) => Some(GetValue::Bool(*b)),
+            Value::Null | Value::Array(_) | Value::Object(_) => None,
+        }
+    }
+
+    fn as_text(&self) -> Option<&str> {
+        self.as_str()
+    }
+}
@@ -1,18 +1,115 @@
-use serde_json::Value;
+use serde_json::{Number, Value};
 use serde::{Deserialize, Serialize};
 use std::borrow::Cow;
+use std::fmt;
+use std::ops::Deref;
+use std::sync::Arc;
+
+use crate::helpers::date::parse_rfc3339;
+use crate::helpers::document::Document;
+use crate::FuseError;
+use crate::helpers::path::{Path, Segment, Selector, join_path, resolve_position};
 
 //----------------------------------------------------------------------
 // Public API
 //----------------------------------------------------------------------
 
 /// Result value returned by path-based JSON object lookup
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum GetValue {
     /// A single string value extracted from a JSON object
     String(String),
-    /// Multiple string values collected from a JSON array
-    Array(Vec<String>),
+    /// A single number extracted from a JSON object
+    Number(Number),
+    /// A single boolean extracted from a JSON object
+    Bool(bool),
+    /// A single string holding an RFC 3339 date, e.g. `2021-06-01T12:30:00Z`
+    Date {
+        /// The string as written in the document
+        text: String,
+        /// Milliseconds between the Unix epoch and the date
+        timestamp: i64,
+    },
+    /// Multiple scalar values collected from a JSON array
+    Array(Vec<GetValue>),
+}
+
+impl GetValue {
+    /// Returns the searchable text form of a scalar value.
+    ///
+    /// Numbers and booleans are stringified the same way JSON renders them.
+    /// Returns `None` for `GetValue::Array`.
+    pub fn as_text(&self) -> Option<String> {
+        match self {
+            GetValue::String(s) | GetValue::Date { text: s, .. } => Some(s.clone()),
+            GetValue::Number(n) => Some(n.to_string()),
+            GetValue::Bool(b) => Some(b.to_string()),
+            GetValue::Array(_) => None,
+        }
+    }
+
+    /// Converts a JSON value into a `GetValue`.
+    ///
+    /// Arrays keep their scalar items; objects and nulls are ignored.
+    /// Strings holding an RFC 3339 date become `GetValue::Date`.
+    pub fn from_json(value: &Value) -> Option<GetValue> {
+        match value {
+            Value::Array(items) => {
+                let list: Vec<GetValue> = items
+                    .iter()
+                    .filter(|item| !item.is_array())
+                    .filter_map(GetValue::from_json)
+                    .collect();
+                (!list.is_empty()).then_some(GetValue::Array(list))
+            },
+            Value::String(s) => Some(GetValue::from_text(s.clone())),
+            Value::Number(n) => Some(GetValue::Number(n.clone())),
+            Value::Bool(b) => Some(GetValue::Bool(*b)),
+            Value::Null | Value::Object(_) => None,
+        }
+    }
+
+    /// Converts a string into a `GetValue::Date` if it holds an RFC 3339
+    /// date, or a `GetValue::String` otherwise.
+    pub fn from_text(text: String) -> GetValue {
+        match parse_rfc3339(&text) {
+            Some(timestamp) => GetValue::Date { text, timestamp },
+            None => GetValue::String(text),
+        }
+    }
+
+    /// Returns the original JSON value of a non-string scalar.
+    ///
+    /// Strings and dates return `None` since their searchable form already
+    /// is the original value.
+    pub fn typed_value(&self) -> Option<Value> {
+        match self {
+            GetValue::Number(n) => Some(Value::Number(n.clone())),
+            GetValue::Bool(b) => Some(Value::Bool(*b)),
+            GetValue::String(_) | GetValue::Date { .. } | GetValue::Array(_) => None,
+        }
+    }
+
+    /// Returns the milliseconds between the Unix epoch and a date, or `None`
+    /// for other values.
+    pub fn timestamp(&self) -> Option<i64> {
+        match self {
+            GetValue::Date { timestamp, .. } => Some(*timestamp),
+            _ => None,
+        }
+    }
+}
+
+impl PartialEq<String> for GetValue {
+    fn eq(&self, other: &String) -> bool {
+        matches!(self, GetValue::String(s) | GetValue::Date { text: s, .. } if s == other)
+    }
+}
+
+impl PartialEq<&str> for GetValue {
+    fn eq(&self, other: &&str) -> bool {
+        matches!(self, GetValue::String(s) | GetValue::Date { text: s, .. } if s == other)
+    }
 }
 
 /// Path specification for the get function
@@ -22,10 +119,44 @@ pub enum GetFnPath<'a> {
     String(Cow<'a, str>),
     /// An array of strings representing nested path components (e.g. ["user", "address", "city"])
     StringArray(Vec<Cow<'a, str>>),
+    /// A path parsed ahead of time, e.g. the path of an index key
+    Compiled(Cow<'a, Path>),
 }
 
-/// Function type for retrieving values from a JSON object using a path
-pub type GetFn = fn(&Value, &GetFnPath) -> Option<GetValue>;
+/// Function for retrieving values from a JSON object using a path
+///
+/// Wraps a closure, so getters can capture state such as lookup tables or
+/// caches. Clones share the closure, and it is called like a function:
+/// `(options.get_fn)(doc, path)`. The default is [`get`].
+///
+/// # Example
+///
+/// ```
+/// use std::collections::HashMap;
+/// use fuse_rs::{Fuse, FuseOptions, GetFn, GetValue};
+/// use serde_json::json;
+///
+/// // Index languages by name rather than by code
+/// let names = HashMap::from([("fr".to_string(), "French".to_string())]);
+/// let get = GetFn::default();
+/// let options = FuseOptions {
+///     keys: vec!["lang".into()],
+///     get_fn: GetFn::new(move |doc, path| match get(doc, path)? {
+///         GetValue::String(code) => Some(GetValue::String(names.get(&code).cloned().unwrap_or(code))),
+///         value => Some(value),
+///     }),
+///     ..Default::default()
+/// };
+///
+/// let docs = vec![json!({"lang": "fr"})];
+/// let fuse = Fuse::new(&docs, &options, None);
+/// assert_eq!(fuse.search("french").unwrap().len(), 1);
+/// ```
+#[derive(Clone)]
+pub struct GetFn(Arc<DynGetFn>);
+
+/// The closure wrapped by a `GetFn`
+type DynGetFn = dyn Fn(&Value, &GetFnPath) -> Option<GetValue> + Send + Sync;
 
 /// Extract values from a JSON object using a path specification
 ///
@@ -34,19 +165,14 @@ pub type GetFn = fn(&Value, &GetFnPath) -> Option<GetValue>;
 /// * `path` - Path specification (either dot notation string or array of path components)
 ///
 /// # Returns
-/// * `Some(GetValue::String)` - If a single value was found
+/// * `Some(GetValue::String)`, `Some(GetValue::Date)`, `Some(GetValue::Number)` or `Some(GetValue::Bool)` - If a single value was found
 /// * `Some(GetValue::Array)` - If multiple values were found (from traversing arrays)
 /// * `None` - If the path doesn't exist in the object
 pub fn get(obj: &Value, path: &GetFnPath) -> Option<GetValue> {
     match path {
-        GetFnPath::String(s) => {
-            let path_str: &str = s.as_ref();
-            <&str as Get>::get(&path_str, obj)
-        }
-        GetFnPath::StringArray(arr) => {
-            let path_vec: Vec<String> = arr.iter().map(|s| s.to_string()).collect();
-            <Vec<String> as Get>::get(&path_vec, obj)
-        }
+        GetFnPath::String(s) => Path::parse(s).get(obj),
+        GetFnPath::StringArray(arr) => Path::from_segments(arr).get(obj),
+        GetFnPath::Compiled(path) => path.get(obj),
     }
 }
 
@@ -54,8 +180,38 @@ pub fn get(obj: &Value, path: &GetFnPath) -> Option<GetValue> {
 ///
 /// This returns the default getter function from the `get` module
 /// which can access properties by path from a JSON value.
-pub fn default_get_fn_wrapper() -> fn(&Value, &GetFnPath) -> Option<GetValue> {
-    get
+pub fn default_get_fn_wrapper() -> GetFn {
+    GetFn::default()
+}
+
+impl GetFn {
+    /// Wraps a getter closure.
+    pub fn new<F>(get_fn: F) -> Self
+    where
+        F: Fn(&Value, &GetFnPath) -> Option<GetValue> + Send + Sync + 'static,
+    {
+        Self(Arc::new(get_fn))
+    }
+}
+
+impl Default for GetFn {
+    fn default() -> Self {
+        Self::new(get)
+    }
+}
+
+impl Deref for GetFn {
+    type Target = DynGetFn;
+
+    fn deref(&self) -> &Self::Target {
+        &*self.0
+    }
+}
+
+impl fmt::Debug for GetFn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("GetFn")
+    }
 }
 
 //----------------------------------------------------------------------
@@ -69,68 +225,196 @@ pub trait Get {
 }
 
 /// Implementation for string paths using dot notation (e.g. "user.name")
+///
+/// See [`split_path`](crate::helpers::path::split_path) for the syntax.
 impl Get for &str {
     fn get(&self, obj: &Value) -> Option<GetValue> {
-        let path = self
-            .split('.')
-            .map(|s| s.to_string())
-            .collect::<Vec<String>>();
-        path.get(obj)
+        Path::parse(self).get(obj)
     }
 }
 
 /// Implementation for array paths (e.g. ["user", "name"])
 impl Get for Vec<String> {
     fn get(&self, obj: &Value) -> Option<GetValue> {
-        let mut list: Vec<String> = vec![];
-        let mut is_array = false;
-
-        get_value(self, obj, &mut list, 0, &mut is_array);
+        Path::from_segments(self).get(obj)
+    }
+}
 
-        if list.is_empty() {
-            None
-        } else if is_array {
-            Some(GetValue::Array(list))
-        } else {
-            Some(GetValue::String(list[0].clone()))
-        }
+/// Implementation for pre-compiled paths
+impl Get for Path {
+    fn get(&self, obj: &Value) -> Option<GetValue> {
+        get_document(obj, self)
     }
 }
 
-/// Helper function to recursively extract values from a JSON object using a path
+/// Extracts values from any [`Document`] using a pre-compiled path
 ///
-/// This function handles array traversal and value collection.
-fn get_value(path: &Vec<String>, obj: &Value, list: &mut Vec<String>, index: usize, is_array: &mut bool) {
-    if index >= path.len() {
-        match obj {
-            Value::String(s) => list.push(s.clone()),
-            Value::Bool(b) => list.push(b.to_string()),
-            Value::Number(n) => list.push(n.to_string()),
-            _ => return,
-        }
+/// This is the lookup behind [`get`]; it follows the same rules for array
+/// fan-out, numeric indices and array selectors.
+pub fn get_document<D: Document>(doc: &D, path: &Path) -> Option<GetValue> {
+    lookup(doc, path, false).unwrap_or(None)
+}
+
+/// Extracts values from a JSON object, traversing arrays only where asked to
+///
+/// Unlike [`get`], arrays along the path don't fan out implicitly: they must
+/// be traversed with a selector (`tags[*]`, `tags[0]`, `tags[1..]`) or a
+/// numeric segment. Arrays reached by the last segment are still collected.
+///
+/// # Errors
+///
+/// Returns `FuseError::UnexpectedArray` when the path runs into an array it
+/// doesn't select from.
+///
+/// # Example
+///
+/// ```
+/// use fuse_rs::{GetFnPath, GetValue, get_strict};
+/// use serde_json::json;
+///
+/// let book = json!({ "tags": [{ "name": "sci-fi" }, { "name": "space" }] });
+///
+/// assert!(get_strict(&book, &GetFnPath::String("tags.name".into())).is_err());
+/// match get_strict(&book, &GetFnPath::String("tags[*].name".into())) {
+///     Ok(Some(GetValue::Array(names))) => assert_eq!(names, ["sci-fi", "space"]),
+///     other => panic!("unexpected {:?}", other),
+/// }
+/// ```
+pub fn get_strict(obj: &Value, path: &GetFnPath) -> Result<Option<GetValue>, FuseError> {
+    match path {
+        GetFnPath::String(s) => try_get_document(obj, &Path::parse(s)),
+        GetFnPath::StringArray(arr) => try_get_document(obj, &Path::from_segments(arr)),
+        GetFnPath::Compiled(path) => try_get_document(obj, path),
+    }
+}
+
+/// Extracts values from any [`Document`] using a pre-compiled path, with the
+/// strict array rules of [`get_strict`]
+pub fn try_get_document<D: Document>(doc: &D, path: &Path) -> Result<Option<GetValue>, FuseError> {
+    lookup(doc, path, true)
+}
+
+/// Runs a lookup and collects its values into a single `GetValue`.
+fn lookup<D: Document>(doc: &D, path: &Path, strict: bool) -> Result<Option<GetValue>, FuseError> {
+    let mut list: Vec<GetValue> = vec![];
+    let mut is_array = false;
+
+    get_value(path.segments(), doc, &mut list, 0, &mut is_array, strict)?;
+
+    Ok(if list.is_empty() {
+        None
+    } else if is_array {
+        Some(GetValue::Array(list))
     } else {
-        let key = &path[index];
+        Some(list.swap_remove(0))
+    })
+}
 
-        // Check if key is a numeric index (for array access)
-        let value = if let Ok(num) = key.parse::<usize>() {
-            obj.get(num)
-        } else {
-            obj.get(key)
+/// Helper function to recursively extract values from a document using a path
+///
+/// This function handles array traversal and value collection. In `strict`
+/// mode, arrays only fan out through selectors.
+fn get_value<D: Document>(
+    path: &[Segment],
+    obj: &D,
+    list: &mut Vec<GetValue>,
+    index: usize,
+    is_array: &mut bool,
+    strict: bool,
+) -> Result<(), FuseError> {
+    let Some(segment) = path.get(index) else {
+        list.extend(obj.scalar());
+        return Ok(());
+    };
+
+    // `*` expands to every element or property value, unless a property is
+    // literally named `*`
+    if segment.is_wildcard() && obj.field(&segment.raw).is_none() {
+        *is_array = true;
+        let children = match obj.array_len() {
+            Some(len) => (0..len).filter_map(|i| obj.element(i)).collect(),
+            None => obj.field_values(),
         };
+        for child in children {
+            descend(path, child, list, index, is_array, strict)?;
+        }
+        return Ok(());
+    }
 
-        match value {
-            Some(v) => {
-                if v.is_array() {
-                    *is_array = true;
-                    for item in v.as_array().unwrap() {
-                        get_value(path, item, list, index + 1, is_array);
-                    }
-                } else {
-                    get_value(path, v, list, index + 1, is_array);
-                }
-            },
-            None => return,
+    // A property literally named like the segment wins over selector syntax
+    let (name, numeric, selector) = match segment.selector {
+        Some(_) if obj.field(&segment.raw).is_some() => (segment.raw.as_str(), None, None),
+        selector => (segment.name.as_str(), segment.index, selector),
+    };
+
+    // Numeric keys index into arrays; everything else is a literal property name
+    let value = match numeric {
+        _ if name.is_empty() && selector.is_some() => Some(obj),
+        Some(num) if obj.is_array() => obj.element(num),
+        _ => obj.field(name),
+    };
+
+    let Some(value) = value else {
+        return Ok(());
+    };
+
+    let elements = |from: usize, to: usize, list: &mut Vec<GetValue>, is_array: &mut bool| -> Result<(), FuseError> {
+        *is_array = true;
+        for i in from..to.max(from) {
+            if let Some(item) = value.element(i) {
+                get_value(path, item, list, index + 1, is_array, strict)?;
+            }
         }
+        Ok(())
+    };
+
+    match (selector, value.array_len()) {
+        (None, _) => descend(path, value, list, index, is_array, strict),
+        (Some(Selector::Index(position)), Some(len)) => match value.element(resolve_position(position, len)) {
+            Some(v) => descend(path, v, list, index, is_array, strict),
+            None => Ok(()),
+        },
+        (Some(Selector::Range(from, to)), Some(len)) => {
+            let from = from.map_or(0, |from| resolve_position(from, len));
+            let to = to.map_or(len, |to| resolve_position(to, len));
+            elements(from, to, list, is_array)
+        },
+        (Some(Selector::All), Some(len)) => elements(0, len, list, is_array),
+        (Some(_), None) => Ok(()),
+    }
+}
+
+/// Continues the lookup below `value`, fanning out over arrays.
+///
+/// In `strict` mode, only arrays reached by the last segment fan out; others
+/// must be followed by a numeric segment.
+fn descend<D: Document>(
+    path: &[Segment],
+    value: &D,
+    list: &mut Vec<GetValue>,
+    index: usize,
+    is_array: &mut bool,
+    strict: bool,
+) -> Result<(), FuseError> {
+    let next_is_position = path.get(index + 1).is_some_and(|next| next.index.is_some());
+    let next_is_wildcard = path.get(index + 1).is_some_and(Segment::is_wildcard);
+
+    match value.array_len() {
+        // The wildcard expands the array itself
+        Some(_) if next_is_wildcard => get_value(path, value, list, index + 1, is_array, strict),
+        Some(_) if strict && next_is_position => get_value(path, value, list, index + 1, is_array, strict),
+        Some(_) if strict && index + 1 < path.len() => {
+            let raw: Vec<String> = path[..=index].iter().map(|s| s.raw.clone()).collect();
+            Err(FuseError::UnexpectedArray(join_path(&raw)))
+        },
+        Some(len) => {
+            *is_array = true;
+            for item in (0..len).filter_map(|i| value.element(i)) {
+                get_value(path, item, list, index + 1, is_array, strict)?;
+            }
+            Ok(())
+        },
+        None => get_value(path, value, list, index + 1, is_array, strict),
     }
 }
 
@@ -141,7 +425,7 @@ fn get_value(path: &Vec<String>, obj: &Value, list: &mut Vec<String>, index: usi
 #[cfg(test)]
 mod tests {
     use serde_json::json;
-    use super::{get, GetFnPath, GetValue};
+    use super::{get, get_strict, GetFnPath, GetValue};
 
     /// Sample JSON object for testing
     fn test_json() -> serde_json::Value {
@@ -185,15 +469,61 @@ mod tests {
     fn test_get_number_as_string() {
         let obj = test_json();
         
-        // Test number conversion to string
+        // Numbers keep their type; the searchable form is the JSON rendering
         let path = GetFnPath::StringArray(vec!["author".into(), "age".into()]);
         let result = get(&obj, &path);
         match result {
-            Some(GetValue::String(s)) => assert_eq!(s, "18".to_string()),
-            _ => panic!("Expected a string"),
+            Some(value @ GetValue::Number(_)) => {
+                assert_eq!(value.as_text(), Some("18".to_string()));
+                assert_eq!(value.typed_value(), Some(json!(18)));
+            },
+            _ => panic!("Expected a number"),
+        }
+    }
+
+    #[test]
+    fn test_get_typed_values() {
+        let obj = json!({
+            "price": 9.5,
+            "inStock": true,
+            "ratings": [4, "n/a", false]
+        });
+
+        let price = get(&obj, &GetFnPath::String("price".into())).unwrap();
+        assert_eq!(price.typed_value().and_then(|v| v.as_f64()), Some(9.5));
+
+        let in_stock = get(&obj, &GetFnPath::String("inStock".into())).unwrap();
+        assert_eq!(in_stock, GetValue::Bool(true));
+        assert_eq!(in_stock.as_text(), Some("true".to_string()));
+
+        match get(&obj, &GetFnPath::String("ratings".into())) {
+            Some(GetValue::Array(arr)) => assert_eq!(
+                arr,
+                vec![
+                    GetValue::Number(4.into()),
+                    GetValue::String("n/a".into()),
+                    GetValue::Bool(false),
+                ]
+            ),
+            _ => panic!("Expected an array"),
         }
     }
 
+    #[test]
+    fn test_get_dates() {
+        let obj = json!({"published": "2021-06-01T12:30:00Z", "edition": "2nd, 2021-06"});
+
+        let published = get(&obj, &GetFnPath::String("published".into())).unwrap();
+        assert_eq!(published.timestamp(), Some(1_622_550_600_000));
+        assert_eq!(published.as_text(), Some("2021-06-01T12:30:00Z".to_string()));
+        assert_eq!(published, "2021-06-01T12:30:00Z");
+        assert_eq!(published.typed_value(), None);
+
+        let edition = get(&obj, &GetFnPath::String("edition".into())).unwrap();
+        assert_eq!(edition, GetValue::String("2nd, 2021-06".into()));
+        assert_eq!(edition.timestamp(), None);
+    }
+
     #[test]
     fn test_get_dot_notation() {
         let obj = test_json();
@@ -232,4 +562,134 @@ mod tests {
             _ => panic!("Expected an array"),
         }
     }
+
+    #[test]
+    fn test_get_literal_dot_keys() {
+        let obj = json!({
+            "foo.bar": "literal",
+            "foo": { "bar": "nested" },
+            "counts": { "2024": "by year" }
+        });
+
+        let escaped = GetFnPath::String("foo\\.bar".into());
+        match get(&obj, &escaped) {
+            Some(GetValue::String(s)) => assert_eq!(s, "literal"),
+            _ => panic!("Expected a string"),
+        }
+
+        let literal = GetFnPath::StringArray(vec!["foo.bar".into()]);
+        match get(&obj, &literal) {
+            Some(GetValue::String(s)) => assert_eq!(s, "literal"),
+            _ => panic!("Expected a string"),
+        }
+
+        let nested = GetFnPath::String("foo.bar".into());
+        match get(&obj, &nested) {
+            Some(GetValue::String(s)) => assert_eq!(s, "nested"),
+            _ => panic!("Expected a string"),
+        }
+
+        // Numeric components are property names on objects
+        let numeric = GetFnPath::String("counts.2024".into());
+        match get(&obj, &numeric) {
+            Some(GetValue::String(s)) => assert_eq!(s, "by year"),
+            _ => panic!("Expected a string"),
+        }
+    }
+
+    #[test]
+    fn test_get_array_index_and_range() {
+        let obj = json!({
+            "tags": ["a", "b", "c", "d"],
+            "items": [{ "name": "first" }, { "name": "second" }, { "name": "third" }],
+            "odd[0]": "literal"
+        });
+
+        let cases = [
+            ("tags[0]", Some(GetValue::String("a".into()))),
+            ("tags[-1]", Some(GetValue::String("d".into()))),
+            ("tags[10]", None),
+            ("items[-2].name", Some(GetValue::String("second".into()))),
+            ("odd[0]", Some(GetValue::String("literal".into()))),
+        ];
+
+        for (path, expected) in cases {
+            let result = get(&obj, &GetFnPath::String(path.into()));
+            assert_eq!(format!("{:?}", result), format!("{:?}", expected), "path {}", path);
+        }
+
+        let range = |path: &str| match get(&obj, &GetFnPath::String(path.into())) {
+            Some(GetValue::Array(arr)) => arr,
+            other => panic!("Expected an array for {}, got {:?}", path, other),
+        };
+
+        assert_eq!(range("tags[0..3]"), vec!["a", "b", "c"]);
+        assert_eq!(range("tags[-2..]"), vec!["c", "d"]);
+        assert_eq!(range("tags[..1]"), vec!["a"]);
+        assert_eq!(range("items[1..].name"), vec!["second", "third"]);
+        assert!(get(&obj, &GetFnPath::String("tags[3..1]".into())).is_none());
+    }
+
+    /// Unwraps the strings of a multi-value lookup
+    fn strings(value: Option<GetValue>) -> Vec<GetValue> {
+        match value {
+            Some(GetValue::Array(values)) => values,
+            other => panic!("Expected an array, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_get_wildcard_selector() {
+        let obj = test_json();
+
+        let explicit = get(&obj, &GetFnPath::String("author.tags[*].nested.value".into()));
+        let implicit = get(&obj, &GetFnPath::String("author.tags.nested.value".into()));
+        assert_eq!(explicit, implicit);
+        assert_eq!(strings(explicit), ["nested test 1", "nested test 2"]);
+
+        assert!(get(&obj, &GetFnPath::String("author.name[*]".into())).is_none());
+    }
+
+    #[test]
+    fn test_get_wildcard_segments() {
+        let obj = json!({
+            "chapters": [
+                { "heading": "Arrival", "sections": { "a": { "heading": "Dock" }, "b": { "heading": "Gate" } } },
+                { "heading": "Departure", "sections": [{ "heading": "Launch" }] }
+            ],
+            "appendices": { "one": { "heading": "Maps" }, "two": [{ "heading": "Glossary" }] },
+            "odd": { "*": "literal", "x": "other" }
+        });
+        let path = |path: &str| get(&obj, &GetFnPath::String(path.into()));
+
+        assert_eq!(strings(path("chapters.*.heading")), ["Arrival", "Departure"]);
+        assert_eq!(strings(path("chapters.*.sections.*.heading")), ["Dock", "Gate", "Launch"]);
+        assert_eq!(strings(path("appendices.*.heading")), ["Maps", "Glossary"]);
+        assert_eq!(path("odd.*"), Some(GetValue::String("literal".into())));
+        assert!(path("chapters.*.heading.*").is_none());
+
+        // Strict lookups accept wildcards as explicit fan-out
+        let strict = get_strict(&obj, &GetFnPath::String("chapters.*.heading".into())).unwrap();
+        assert_eq!(strings(strict), ["Arrival", "Departure"]);
+    }
+
+    #[test]
+    fn test_get_strict() {
+        let obj = test_json();
+        let strict = |path: &str| get_strict(&obj, &GetFnPath::String(path.into()));
+
+        match strict("author.tags.nested.value") {
+            Err(crate::FuseError::UnexpectedArray(at)) => assert_eq!(at, "author.tags"),
+            other => panic!("Expected an error, got {:?}", other),
+        }
+
+        assert_eq!(strings(strict("author.tags[*].nested.value").unwrap()), ["nested test 1", "nested test 2"]);
+        assert_eq!(strict("author.tags[0].value").unwrap(), Some(GetValue::String("American".into())));
+        assert_eq!(strict("author.tags.1.nested.value").unwrap(), Some(GetValue::String("nested test 2".into())));
+
+        // Arrays reached by the last segment are still collected
+        assert_eq!(strings(strict("author.tags[1].value").unwrap()), ["sci-fi", "space"]);
+        assert_eq!(strings(strict("author.tags[*].value").unwrap()), ["American", "sci-fi", "space"]);
+        assert_eq!(strict("author.missing.value").unwrap(), None);
+    }
 }
@@ -0,0 +1,134 @@
+//! Grapheme clusters as matching units
+//!
+//! The bitap matcher compares characters, so an emoji sequence or a letter
+//! followed by combining marks counts as several. To count every extended
+//! grapheme cluster as one unit, the pattern and the texts are encoded with
+//! one character per cluster before matching: single-character clusters stay
+//! as they are, longer clusters of the pattern get a private-use character
+//! each, and the other long clusters share one that matches nothing.
+
+use std::collections::HashMap;
+
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::core::results::search_result::RangeTuple;
+
+//----------------------------------------------------------------------
+// Types
+//----------------------------------------------------------------------
+
+/// Encodes texts with one character per grapheme cluster
+#[derive(Debug, Clone, Default)]
+pub(crate) struct GraphemeEncoder {
+    /// The characters standing for the clusters of the pattern that need one
+    codes: HashMap<String, char>,
+}
+
+//----------------------------------------------------------------------
+// Constants
+//----------------------------------------------------------------------
+
+/// First character of Supplementary Private Use Area-B, from which the
+/// clusters of the pattern get their characters
+const FIRST_CODE: u32 = 0x10_0000;
+
+/// Last character of Supplementary Private Use Area-B, standing for the
+/// clusters that are not in the pattern
+const UNKNOWN: char = '\u{10FFFD}';
+
+//----------------------------------------------------------------------
+// Implementation
+//----------------------------------------------------------------------
+
+impl GraphemeEncoder {
+    /// Creates an encoder for the clusters of `pattern`.
+    pub(crate) fn new(pattern: &str) -> Self {
+        let mut codes = HashMap::new();
+        for cluster in pattern.graphemes(true).filter(|cluster| needs_code(cluster)) {
+            let next = char::from_u32(FIRST_CODE + codes.len() as u32).filter(|&code| code != UNKNOWN);
+            if let (false, Some(code)) = (codes.contains_key(cluster), next) {
+                codes.insert(cluster.to_string(), code);
+            }
+        }
+
+        Self { codes }
+    }
+
+    /// Encodes `text` with one character per grapheme cluster.
+    pub(crate) fn encode(&self, text: &str) -> String {
+        text.graphemes(true)
+            .map(|cluster| match cluster.chars().next() {
+                Some(c) if !needs_code(cluster) => c,
+                _ => self.codes.get(cluster).copied().unwrap_or(UNKNOWN),
+            })
+            .collect()
+    }
+}
+
+//----------------------------------------------------------------------
+// Free Functions
+//----------------------------------------------------------------------
+
+/// Converts ranges of grapheme clusters of `text` into ranges of characters.
+pub(crate) fn cluster_to_char_ranges(text: &str, ranges: &[RangeTuple]) -> Vec<RangeTuple> {
+    // Character position of the start of every cluster, and of the end
+    let mut starts = Vec::new();
+    let mut position = 0;
+    for cluster in text.graphemes(true) {
+        starts.push(position);
+        position += cluster.chars().count();
+    }
+    starts.push(position);
+
+    ranges
+        .iter()
+        .filter_map(|&(start, end)| Some((*starts.get(start)?, starts.get(end + 1)? - 1)))
+        .collect()
+}
+
+//----------------------------------------------------------------------
+// Implementation Details
+//----------------------------------------------------------------------
+
+/// Returns `true` for the clusters that can't stand for themselves: those
+/// of several characters, and private-use characters that could be taken
+/// for a code.
+fn needs_code(cluster: &str) -> bool {
+    let mut chars = cluster.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => u32::from(c) >= FIRST_CODE,
+        _ => true,
+    }
+}
+
+//----------------------------------------------------------------------
+// Tests
+//----------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_clusters() {
+        // A family emoji (5 characters) and "é" as "e" and a combining accent
+        let pattern = "👨‍👩‍👧 cafe\u{301}";
+        let encoder = GraphemeEncoder::new(pattern);
+
+        assert_eq!(encoder.encode(pattern), "\u{100000} caf\u{100001}");
+        assert_eq!(encoder.encode("cafe\u{301} 👨‍👩‍👧"), "caf\u{100001} \u{100000}");
+
+        // Clusters missing from the pattern match nothing in it
+        let other = encoder.encode("👩‍👩‍👦 cafe\u{300}");
+        assert!(other.starts_with(UNKNOWN));
+        assert!(other.ends_with(UNKNOWN));
+        assert_eq!(encoder.encode("\u{10FFFD}\u{100000}"), format!("{}{}", UNKNOWN, UNKNOWN));
+    }
+
+    #[test]
+    fn test_cluster_to_char_ranges() {
+        let text = "a👨‍👩‍👧b";
+        assert_eq!(cluster_to_char_ranges(text, &[(1, 1), (0, 2)]), vec![(1, 5), (0, 6)]);
+        assert!(cluster_to_char_ranges(text, &[(2, 3)]).is_empty());
+    }
+}
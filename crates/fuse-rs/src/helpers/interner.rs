@@ -0,0 +1,270 @@
+//! Shared storage for normalized text
+//!
+//! Searching normalizes every field value it looks at, once per search. An
+//! `Interner` set in the options remembers the normalized form of each
+//! distinct value instead, so repeated searches reuse it and duplicate values
+//! (and their normalized forms) are stored only once. Normalized forms are
+//! keyed by the [fingerprint](crate::Normalizer::fingerprint) of the
+//! normalization, so keys with their own analyzer and instances with
+//! different options can share an interner. Pattern chunks built by the
+//! searchers are interned in the same storage.
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::ops::Deref;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+
+use crate::helpers::normalize::Normalizer;
+use crate::helpers::trace;
+
+//----------------------------------------------------------------------
+// Types
+//----------------------------------------------------------------------
+
+/// Deduplicated storage for field values, their normalized forms and pattern
+/// chunks
+///
+/// An interner is shared through [`FuseOptions::interner`](crate::FuseOptions::interner)
+/// and can be used by several searches, including concurrent ones, and by
+/// instances that normalize text differently: normalized forms are cached
+/// by text and normalizer fingerprint.
+///
+/// # Example
+///
+/// ```
+/// use std::sync::Arc;
+/// use fuse_rs::{Fuse, FuseOptions, Interner};
+/// use serde_json::json;
+///
+/// let interner = Arc::new(Interner::new());
+/// let options = FuseOptions {
+///     interner: Some(interner.clone()),
+///     ..Default::default()
+/// };
+///
+/// let docs = vec![json!("Old Man's War"), json!("Old Man's War"), json!("The Lock Artist")];
+/// let fuse = Fuse::new(&docs, &options, None);
+/// fuse.search("old man").unwrap();
+///
+/// // Both copies of the duplicate title share one normalized entry
+/// assert_eq!(interner.normalized_len(), 2);
+/// ```
+#[derive(Default)]
+pub struct Interner {
+    /// Every distinct string stored
+    strings: RwLock<HashSet<Arc<str>>>,
+
+    /// Normalized form of every value seen, by normalizer fingerprint and
+    /// value
+    normalized: RwLock<HashMap<u64, NormalizedForms>>,
+
+    /// Lookups of normalized forms that were cached
+    hits: AtomicU64,
+
+    /// Lookups of normalized forms that were not cached
+    misses: AtomicU64,
+}
+
+/// Normalized forms of values, by value
+type NormalizedForms = HashMap<Arc<str>, Arc<str>>;
+
+/// Text normalized for matching, either owned or shared with an interner
+pub(crate) enum NormalizedText {
+    Owned(String),
+    Shared(Arc<str>),
+}
+
+//----------------------------------------------------------------------
+// Implementation
+//----------------------------------------------------------------------
+
+impl Interner {
+    /// Creates an empty interner.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the stored copy of `text`, storing it first if needed.
+    pub fn intern(&self, text: &str) -> Arc<str> {
+        if let Some(stored) = self.strings.read().unwrap().get(text) {
+            return stored.clone();
+        }
+
+        let mut strings = self.strings.write().unwrap();
+        match strings.get(text) {
+            Some(stored) => stored.clone(),
+            None => {
+                let stored: Arc<str> = Arc::from(text);
+                strings.insert(stored.clone());
+                stored
+            },
+        }
+    }
+
+    /// Returns the normalized form of `text`, normalizing and storing it the
+    /// first time `text` is seen with the fingerprint of `normalizer`.
+    pub fn normalize(&self, text: &str, normalizer: &Normalizer) -> Arc<str> {
+        let fingerprint = normalizer.fingerprint();
+        let cached = self
+            .normalized
+            .read()
+            .unwrap()
+            .get(&fingerprint)
+            .and_then(|forms| forms.get(text).cloned());
+        if let Some(normalized) = cached {
+            trace::trace!("normalized text cache hit");
+            self.hits.fetch_add(1, Ordering::Relaxed);
+            return normalized;
+        }
+
+        trace::trace!("normalized text cache miss");
+        self.misses.fetch_add(1, Ordering::Relaxed);
+
+        let normalized = self.intern(&normalizer.normalize(text));
+        self.normalized
+            .write()
+            .unwrap()
+            .entry(fingerprint)
+            .or_default()
+            .entry(self.intern(text))
+            .or_insert(normalized)
+            .clone()
+    }
+
+    /// Returns the number of distinct strings stored.
+    pub fn len(&self) -> usize {
+        self.strings.read().unwrap().len()
+    }
+
+    /// Returns `true` if nothing is stored.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of normalized forms cached, counting a value once
+    /// per fingerprint it was normalized with.
+    pub fn normalized_len(&self) -> usize {
+        self.normalized.read().unwrap().values().map(HashMap::len).sum()
+    }
+
+    /// Returns the number of normalized forms found in the cache since the
+    /// interner was created.
+    pub fn cache_hits(&self) -> u64 {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of normalized forms computed because they were not
+    /// cached, since the interner was created.
+    pub fn cache_misses(&self) -> u64 {
+        self.misses.load(Ordering::Relaxed)
+    }
+
+    /// Removes everything stored.
+    pub fn clear(&self) {
+        self.normalized.write().unwrap().clear();
+        self.strings.write().unwrap().clear();
+    }
+}
+
+impl fmt::Debug for Interner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Interner")
+            .field("strings", &self.len())
+            .field("normalized", &self.normalized_len())
+            .field("cache_hits", &self.cache_hits())
+            .field("cache_misses", &self.cache_misses())
+            .finish()
+    }
+}
+
+impl NormalizedText {
+    /// Normalizes `text`, through `interner` when there is one.
+    pub(crate) fn new(text: &str, normalizer: &Normalizer, interner: Option<&Interner>) -> Self {
+        match interner {
+            Some(interner) => Self::Shared(interner.normalize(text, normalizer)),
+            None => Self::Owned(normalizer.normalize(text)),
+        }
+    }
+}
+
+impl Deref for NormalizedText {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        match self {
+            Self::Owned(text) => text,
+            Self::Shared(text) => text,
+        }
+    }
+}
+
+//----------------------------------------------------------------------
+// Free Functions
+//----------------------------------------------------------------------
+
+/// Moves `text` into shared storage, reusing the interned copy if any.
+pub(crate) fn share(text: String, interner: Option<&Interner>) -> Arc<str> {
+    match interner {
+        Some(interner) => interner.intern(&text),
+        None => Arc::from(text),
+    }
+}
+
+//----------------------------------------------------------------------
+// Tests
+//----------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::FuseOptions;
+
+    #[test]
+    fn test_intern_deduplicates() {
+        let interner = Interner::new();
+        let first = interner.intern("war");
+        let second = interner.intern("war");
+
+        assert!(Arc::ptr_eq(&first, &second));
+        assert_eq!(interner.len(), 1);
+    }
+
+    #[test]
+    fn test_normalize_is_cached() {
+        let options = FuseOptions::default();
+        let interner = Interner::new();
+
+        let first = interner.normalize("Old Man's War", &options.normalizer());
+        let second = interner.normalize("Old Man's War", &options.normalizer());
+
+        assert_eq!(&*first, "old man's war");
+        assert!(Arc::ptr_eq(&first, &second));
+        assert_eq!(interner.normalized_len(), 1);
+        assert_eq!((interner.cache_hits(), interner.cache_misses()), (1, 1));
+
+        // Values already in normal form share their entry with it
+        interner.normalize("war", &options.normalizer());
+        assert_eq!(interner.len(), 3);
+
+        interner.clear();
+        assert!(interner.is_empty());
+    }
+
+    #[test]
+    fn test_normalize_is_cached_by_fingerprint() {
+        let interner = Interner::new();
+        let folding = FuseOptions::default();
+        let case_sensitive = FuseOptions { is_case_sensitive: true, ..Default::default() };
+
+        assert_eq!(&*interner.normalize("War", &folding.normalizer()), "war");
+        assert_eq!(&*interner.normalize("War", &case_sensitive.normalizer()), "War");
+        assert_eq!(interner.normalized_len(), 2);
+
+        // Options that normalize the same way share entries
+        let same = FuseOptions { include_score: true, ..Default::default() };
+        assert_eq!(same.normalizer().fingerprint(), folding.normalizer().fingerprint());
+        interner.normalize("War", &same.normalizer());
+        assert_eq!((interner.cache_hits(), interner.normalized_len()), (1, 2));
+    }
+}
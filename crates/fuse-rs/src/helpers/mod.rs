@@ -6,5 +6,51 @@
 // Path and property access utilities
 pub(crate) mod get;
 
+// RFC 3339 date parsing
+pub(crate) mod date;
+
+// Pre-compiled key paths
+pub(crate) mod path;
+
+// Document representation abstraction
+pub(crate) mod document;
+
 // Text normalization utilities
-pub(crate) mod diacritics;
\ No newline at end of file
+pub(crate) mod diacritics;
+
+// Text normalization for matching
+pub(crate) mod normalize;
+
+// Shared storage for normalized text
+pub(crate) mod interner;
+
+// Allocation-free case-insensitive comparison
+pub(crate) mod caseless;
+
+// Character transliteration tables
+pub(crate) mod transliterate;
+
+// Word stemming
+pub(crate) mod stemmer;
+
+// Stop-word lists
+pub(crate) mod stop_words;
+
+// Query-time synonym expansion
+pub(crate) mod synonyms;
+
+// Character-based slicing of UTF-8 text
+pub(crate) mod char_offsets;
+
+// Grapheme clusters as matching units
+#[cfg(feature = "grapheme-clusters")]
+pub(crate) mod graphemes;
+
+// Splitting text into tokens
+pub(crate) mod tokenizer;
+
+// Phonetic codes for name matching
+pub(crate) mod phonetic;
+
+// Optional `tracing` instrumentation
+pub(crate) mod trace;
@@ -0,0 +1,407 @@
+//! Text normalization shared by indexing, searching and result formatting
+//!
+//! Patterns and texts are normalized the same way before matching (case
+//! folding and, optionally, transliteration and diacritics removal). Normalization works one
+//! character at a time so that every normalized character can be traced back
+//! to the original character it came from. Stop-word removal and stemming,
+//! when configured, run last on whole words; stem characters map to the
+//! characters of the word.
+
+use std::hash::{DefaultHasher, Hash, Hasher};
+
+use crate::core::options::config::FuseOptions;
+use crate::core::results::search_result::RangeTuple;
+use crate::helpers::caseless::fold_char;
+use crate::helpers::char_offsets::char_len;
+use crate::helpers::diacritics::strip_char_diacritics;
+use crate::helpers::stemmer::Stemmer;
+#[cfg(feature = "stemming")]
+use crate::helpers::stemmer::SnowballStemmer;
+use crate::helpers::stop_words::StopWords;
+use crate::helpers::tokenizer::{Tokenizer, UnicodeWordTokenizer};
+use crate::helpers::transliterate::{TransliterationTable, transliterate_char};
+
+//----------------------------------------------------------------------
+// Types
+//----------------------------------------------------------------------
+
+/// The normalization pipeline configured by a set of options
+///
+/// Obtained with [`FuseOptions::normalizer`]. It normalizes text exactly like
+/// the searchers do, e.g. to highlight matches in a normalized copy of a
+/// value or to pre-normalize data stored elsewhere.
+///
+/// # Example
+///
+/// ```
+/// use fuse_rs::{FuseOptions, default_transliteration_table, project_indices};
+///
+/// let options = FuseOptions {
+///     ignore_diacritics: true,
+///     transliteration: Some(default_transliteration_table()),
+///     ..Default::default()
+/// };
+/// let normalizer = options.normalizer();
+///
+/// assert_eq!(normalizer.normalize("Crème Brûlée"), "creme brulee");
+///
+/// // Map a match on the normalized text back onto the original
+/// let (normalized, offsets) = normalizer.normalize_with_offsets("Straße Süd");
+/// assert_eq!(normalized, "strasse sud");
+/// assert_eq!(project_indices(&[(8, 10)], &offsets), vec![(7, 9)]);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Normalizer<'o> {
+    /// Whether case is preserved
+    is_case_sensitive: bool,
+
+    /// Whether diacritics are removed
+    ignore_diacritics: bool,
+
+    /// Characters to transliterate
+    transliteration: Option<&'o TransliterationTable>,
+
+    /// Words removed before matching
+    stop_words: Option<&'o StopWords>,
+
+    /// Stemmer applied to every word
+    stemmer: Option<&'o dyn Stemmer>,
+
+    /// The fingerprint, computed upfront when the options have an interner
+    fingerprint: Option<u64>,
+}
+
+//----------------------------------------------------------------------
+// Implementation
+//----------------------------------------------------------------------
+
+impl<'o> Normalizer<'o> {
+    /// Creates the normalizer configured by `options`.
+    pub fn new(options: &'o FuseOptions) -> Self {
+        let stemmer = options.stemmer.as_deref();
+        #[cfg(feature = "stemming")]
+        let stemmer = stemmer.or_else(|| {
+            let language = options.stemming_language?;
+            Some(SnowballStemmer::for_language(language) as &dyn Stemmer)
+        });
+
+        let mut normalizer = Self {
+            is_case_sensitive: options.is_case_sensitive,
+            ignore_diacritics: options.ignore_diacritics,
+            transliteration: options.transliteration.as_ref(),
+            stop_words: options.stop_words.as_ref(),
+            stemmer,
+            fingerprint: None,
+        };
+
+        if options.interner.is_some() {
+            normalizer.fingerprint = Some(normalizer.fingerprint());
+        }
+
+        normalizer
+    }
+
+    /// Returns a hash of the normalization steps and their settings.
+    ///
+    /// Normalizers with equal fingerprints normalize text the same way, so
+    /// an [`Interner`](crate::Interner) can share normalized forms between
+    /// them. Custom stemmers are told apart by their `Debug` output.
+    pub fn fingerprint(&self) -> u64 {
+        if let Some(fingerprint) = self.fingerprint {
+            return fingerprint;
+        }
+
+        let mut hasher = DefaultHasher::new();
+        self.is_case_sensitive.hash(&mut hasher);
+        self.ignore_diacritics.hash(&mut hasher);
+        self.transliteration.map(|table| unordered_hash(table.iter())).hash(&mut hasher);
+        self.stop_words.map(|words| unordered_hash(words.iter())).hash(&mut hasher);
+        self.stemmer.map(|stemmer| format!("{stemmer:?}")).hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// Normalizes `text` according to the case, transliteration, diacritics,
+    /// stop-word and stemming options.
+    pub fn normalize(&self, text: &str) -> String {
+        if self.has_word_steps() {
+            return self.normalize_with_offsets(text).0;
+        }
+
+        if self.is_identity() {
+            return text.to_string();
+        }
+
+        let mut normalized = String::with_capacity(text.len());
+        self.for_each_normalized(text, |_, c| normalized.push(c));
+        normalized
+    }
+
+    /// Normalizes `text` and records where each normalized character came from.
+    ///
+    /// # Returns
+    ///
+    /// The normalized text and, for every character of it, the character index of
+    /// the original character it was produced from.
+    pub fn normalize_with_offsets(&self, text: &str) -> (String, Vec<usize>) {
+        let mut normalized = String::with_capacity(text.len());
+        let mut offsets = Vec::with_capacity(text.len());
+
+        self.for_each_normalized(text, |i, c| {
+            normalized.push(c);
+            offsets.push(i);
+        });
+
+        if !self.has_word_steps() {
+            return (normalized, offsets);
+        }
+
+        self.transform_words(&normalized, &offsets)
+    }
+
+    /// Returns `true` if normalization does nothing beyond case folding.
+    ///
+    /// Texts can then be compared with the [`caseless`](crate::helpers::caseless)
+    /// functions instead of being normalized.
+    pub fn is_case_folding_only(&self) -> bool {
+        !self.ignore_diacritics && self.transliteration.is_none() && !self.has_word_steps()
+    }
+
+    /// Returns `true` if normalization leaves text unchanged.
+    pub fn is_identity(&self) -> bool {
+        self.is_case_sensitive && self.is_case_folding_only()
+    }
+
+    /// Returns `true` if stop words or stemming are configured.
+    fn has_word_steps(&self) -> bool {
+        self.stop_words.is_some() || self.stemmer.is_some()
+    }
+
+    /// Calls `f` with the original character index and each normalized character.
+    fn for_each_normalized<F: FnMut(usize, char)>(&self, text: &str, mut f: F) {
+        for (i, c) in text.chars().enumerate() {
+            if self.is_case_sensitive {
+                self.transliterate(i, c, &mut f);
+            } else {
+                for folded in fold_char(c) {
+                    self.transliterate(i, folded, &mut f);
+                }
+            }
+        }
+    }
+
+    /// Emits `c`, transliterated when a table is configured.
+    fn transliterate<F: FnMut(usize, char)>(&self, i: usize, c: char, f: &mut F) {
+        match self.transliteration {
+            Some(table) => transliterate_char(c, table, |t| self.emit(i, t, f)),
+            None => self.emit(i, c, f),
+        }
+    }
+
+    /// Emits `c`, decomposed and stripped of diacritics when requested.
+    fn emit<F: FnMut(usize, char)>(&self, i: usize, c: char, f: &mut F) {
+        if !self.ignore_diacritics {
+            f(i, c);
+            return;
+        }
+
+        for d in strip_char_diacritics(c) {
+            f(i, d);
+        }
+    }
+
+    /// Removes stop words from a normalized text and replaces the remaining
+    /// words with their stem.
+    ///
+    /// A removed word takes the whitespace that follows it along. A text made
+    /// only of stop words keeps them, so a pattern like "the" can still match.
+    /// Characters of a stem map to the characters of the original word at the
+    /// same position; characters past the end of the word map to its last one.
+    fn transform_words(&self, text: &str, offsets: &[usize]) -> (String, Vec<usize>) {
+        let chars: Vec<char> = text.chars().collect();
+        let words = UnicodeWordTokenizer.tokenize(text);
+        let stop_words = self
+            .stop_words
+            .filter(|stop_words| !words.iter().all(|w| stop_words.contains(w.text)));
+
+        let mut stemmed = String::with_capacity(text.len());
+        let mut stemmed_offsets = Vec::with_capacity(offsets.len());
+        let mut next = 0;
+
+        for word in words {
+            stemmed.extend(&chars[next..word.position]);
+            stemmed_offsets.extend_from_slice(&offsets[next..word.position]);
+
+            let len = char_len(word.text);
+            next = word.position + len;
+
+            if stop_words.is_some_and(|stop_words| stop_words.contains(word.text)) {
+                while chars.get(next).is_some_and(|c| c.is_whitespace()) {
+                    next += 1;
+                }
+                continue;
+            }
+
+            match self.stemmer {
+                Some(stemmer) => {
+                    for (k, c) in stemmer.stem(word.text).chars().enumerate() {
+                        stemmed.push(c);
+                        stemmed_offsets.push(offsets[word.position + k.min(len - 1)]);
+                    }
+                },
+                None => {
+                    stemmed.push_str(word.text);
+                    stemmed_offsets.extend_from_slice(&offsets[word.position..next]);
+                },
+            }
+        }
+
+        stemmed.extend(&chars[next..]);
+        stemmed_offsets.extend_from_slice(&offsets[next..]);
+
+        (stemmed, stemmed_offsets)
+    }
+}
+
+//----------------------------------------------------------------------
+// Free Functions
+//----------------------------------------------------------------------
+
+/// Projects character ranges of a normalized text back onto the original text.
+///
+/// # Arguments
+///
+/// * `indices` - Inclusive character ranges within the normalized text
+/// * `offsets` - Offsets returned by [`Normalizer::normalize_with_offsets`]
+pub fn project_indices(indices: &[RangeTuple], offsets: &[usize]) -> Vec<RangeTuple> {
+    indices
+        .iter()
+        .filter_map(|&(start, end)| Some((*offsets.get(start)?, *offsets.get(end)?)))
+        .collect()
+}
+
+/// Returns a hash of `items` that doesn't depend on their order.
+fn unordered_hash<T: Hash>(items: impl Iterator<Item = T>) -> u64 {
+    items.fold(0, |sum, item| {
+        let mut hasher = DefaultHasher::new();
+        item.hash(&mut hasher);
+        sum.wrapping_add(hasher.finish())
+    })
+}
+
+//----------------------------------------------------------------------
+// Tests
+//----------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::transliterate::default_transliteration_table;
+
+    #[test]
+    fn test_normalize_lowercase() {
+        let options = FuseOptions::default();
+        assert_eq!(options.normalizer().normalize("Hello World"), "hello world");
+    }
+
+    #[test]
+    fn test_normalize_case_sensitive() {
+        let options = FuseOptions {
+            is_case_sensitive: true,
+            ..Default::default()
+        };
+        assert_eq!(options.normalizer().normalize("Hello World"), "Hello World");
+    }
+
+    #[test]
+    fn test_normalize_ignore_diacritics() {
+        let options = FuseOptions {
+            ignore_diacritics: true,
+            ..Default::default()
+        };
+        assert_eq!(options.normalizer().normalize("Crème Brûlée"), "creme brulee");
+    }
+
+    #[test]
+    fn test_normalize_with_offsets_expanding_char() {
+        // 'ß' folds to two characters
+        let options = FuseOptions::default();
+        let (normalized, offsets) = options.normalizer().normalize_with_offsets("Straße");
+
+        assert_eq!(normalized, "strasse");
+        assert_eq!(offsets[3..], [3, 4, 4, 5]);
+        assert_eq!(project_indices(&[(4, 6)], &offsets), vec![(4, 5)]);
+    }
+
+    #[test]
+    fn test_normalize_case_folding() {
+        let options = FuseOptions { transliteration: None, ..Default::default() };
+        let normalizer = options.normalizer();
+
+        assert_eq!(normalizer.normalize("ΟΔΟΣ"), normalizer.normalize("οδος"));
+        assert_eq!(normalizer.normalize("İSTANBUL"), "istanbul");
+        assert_eq!(normalizer.normalize("ISPANAK"), normalizer.normalize("ıspanak"));
+    }
+
+    #[test]
+    fn test_normalize_transliteration() {
+        let options = FuseOptions {
+            transliteration: Some(default_transliteration_table()),
+            ignore_diacritics: true,
+            ..Default::default()
+        };
+
+        assert_eq!(options.normalizer().normalize("Straße in Ærøskøbing"), "strasse in aeroskobing");
+        assert_eq!(options.normalizer().normalize("ＴＯＫＹＯ\u{3000}２０２０"), "tokyo 2020");
+        #[cfg(feature = "unicode-normalization")]
+        assert_eq!(options.normalizer().normalize("Straße in Łódź"), "strasse in lodz");
+
+        let (normalized, offsets) = options.normalizer().normalize_with_offsets("Þór");
+        assert_eq!(normalized, "thor");
+        assert_eq!(offsets, vec![0, 0, 1, 2]);
+    }
+
+    #[test]
+    fn test_normalize_stop_words() {
+        use crate::helpers::stop_words::{StopWordLanguage, StopWords};
+
+        let options = FuseOptions {
+            stop_words: Some(StopWords::for_language(StopWordLanguage::English)),
+            ..Default::default()
+        };
+
+        let (normalized, offsets) = options.normalizer().normalize_with_offsets("The Lord of the Rings");
+        assert_eq!(normalized, "lord rings");
+        assert_eq!(project_indices(&[(5, 9)], &offsets), vec![(16, 20)]);
+        assert_eq!(options.normalizer().normalize("To be, or not"), "to be, or not");
+    }
+
+    #[test]
+    fn test_normalize_stemming() {
+        use std::borrow::Cow;
+        use std::sync::Arc;
+
+        #[derive(Debug)]
+        struct Ing;
+
+        impl Stemmer for Ing {
+            fn stem<'w>(&self, word: &'w str) -> Cow<'w, str> {
+                match word.strip_suffix("ing") {
+                    Some(stem) => Cow::Owned(format!("{stem}e")),
+                    None => Cow::Borrowed(word),
+                }
+            }
+        }
+
+        let options = FuseOptions {
+            stemmer: Some(Arc::new(Ing)),
+            ..Default::default()
+        };
+
+        assert_eq!(options.normalizer().normalize("Writing, Coding!"), "write, code!");
+
+        let (normalized, offsets) = options.normalizer().normalize_with_offsets("a Making");
+        assert_eq!(normalized, "a make");
+        assert_eq!(offsets, vec![0, 1, 2, 3, 4, 5]);
+        assert_eq!(project_indices(&[(2, 5)], &offsets), vec![(2, 5)]);
+    }
+}
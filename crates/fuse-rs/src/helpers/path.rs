@@ -0,0 +1,282 @@
+//! Pre-compiled key paths
+//!
+//! Key paths are written in dot notation (`"author.tags[0].name"`) or as
+//! lists of components. A `Path` parses them once into segments, so that
+//! looking up the same key in many documents doesn't re-split the path,
+//! re-parse array selectors or re-detect numeric indices for every access.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+//----------------------------------------------------------------------
+// Types
+//----------------------------------------------------------------------
+
+/// A parsed key path
+///
+/// Serializes as the list of its components, as written.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(from = "Vec<String>", into = "Vec<String>")]
+pub struct Path {
+    segments: Vec<Segment>,
+}
+
+/// A single parsed component of a `Path`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Segment {
+    /// The component as written
+    pub raw: String,
+
+    /// The property name, without the array selector
+    pub name: String,
+
+    /// The property name parsed as an array position, if it is numeric
+    pub index: Option<usize>,
+
+    /// The trailing array selector (`[-1]`, `[0..3]`, `[*]`), if any
+    pub selector: Option<Selector>,
+}
+
+/// Array selector attached to a path segment (e.g. `tags[-1]`, `tags[0..3]` or `tags[*]`)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Selector {
+    /// A single position; negative values count from the end
+    Index(isize),
+    /// A half-open range of positions; missing bounds mean the start/end
+    Range(Option<isize>, Option<isize>),
+    /// Every element (`[*]`), fanning out explicitly
+    All,
+}
+
+/// Splits a path segment into its property name and optional array selector.
+///
+/// Segments without a well-formed trailing `[...]` are returned unchanged.
+fn parse_segment(segment: &str) -> (&str, Option<Selector>) {
+    let Some(inner) = segment.strip_suffix(']') else {
+        return (segment, None);
+    };
+    let Some(open) = inner.rfind('[') else {
+        return (segment, None);
+    };
+
+    let (name, selector) = (&inner[..open], &inner[open + 1..]);
+
+    let parse_bound = |bound: &str| -> Option<Option<isize>> {
+        if bound.is_empty() {
+            Some(None)
+        } else {
+            bound.parse::<isize>().ok().map(Some)
+        }
+    };
+
+    let selector = match selector.split_once("..") {
+        _ if selector == "*" => Some(Selector::All),
+        Some((from, to)) => match (parse_bound(from), parse_bound(to)) {
+            (Some(from), Some(to)) => Some(Selector::Range(from, to)),
+            _ => None,
+        },
+        None => selector.parse::<isize>().ok().map(Selector::Index),
+    };
+
+    match selector {
+        Some(selector) => (name, Some(selector)),
+        None => (segment, None),
+    }
+}
+
+/// Resolves a possibly negative position against an array of length `len`.
+pub fn resolve_position(position: isize, len: usize) -> usize {
+    if position < 0 {
+        len.saturating_sub(position.unsigned_abs())
+    } else {
+        (position as usize).min(len)
+    }
+}
+
+//----------------------------------------------------------------------
+// Implementation
+//----------------------------------------------------------------------
+
+impl Path {
+    /// Parses a dot-notation path (see [`split_path`] for the syntax).
+    pub fn parse(path: &str) -> Self {
+        Self::from_segments(split_path(path))
+    }
+
+    /// Builds a path from components, each taken as a single segment.
+    pub fn from_segments<I, S>(segments: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let segments = segments
+            .into_iter()
+            .map(|raw| Segment::parse(raw.as_ref()))
+            .collect();
+
+        Self { segments }
+    }
+
+    /// Returns the parsed segments.
+    pub fn segments(&self) -> &[Segment] {
+        &self.segments
+    }
+}
+
+impl Segment {
+    /// Parses a single path component.
+    fn parse(raw: &str) -> Self {
+        let (name, selector) = parse_segment(raw);
+
+        Self {
+            raw: raw.to_string(),
+            name: name.to_string(),
+            index: name.parse::<usize>().ok(),
+            selector,
+        }
+    }
+
+    /// Returns `true` for a `*` segment, which stands for every element of
+    /// an array or value of an object.
+    pub fn is_wildcard(&self) -> bool {
+        self.raw == "*"
+    }
+}
+
+impl From<Vec<String>> for Path {
+    fn from(segments: Vec<String>) -> Self {
+        Self::from_segments(segments)
+    }
+}
+
+impl From<Path> for Vec<String> {
+    fn from(path: Path) -> Self {
+        path.segments.into_iter().map(|segment| segment.raw).collect()
+    }
+}
+
+impl fmt::Display for Path {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let raw: Vec<String> = self.segments.iter().map(|s| s.raw.clone()).collect();
+        f.write_str(&join_path(&raw))
+    }
+}
+
+//----------------------------------------------------------------------
+// Path Syntax
+//----------------------------------------------------------------------
+
+/// Splits a dot-notation path into its components.
+///
+/// A `*` component matches every element of an array or value of an object.
+/// `\.` produces a literal dot and `\\` a literal backslash; any other
+/// backslash is kept as is. Dots inside an array selector (`tags[0..3]`) do
+/// not split the path.
+///
+/// # Example
+///
+/// ```ignore
+/// assert_eq!(split_path("a.b\\.c"), vec!["a", "b.c"]);
+/// ```
+pub fn split_path(path: &str) -> Vec<String> {
+    let mut segments = Vec::new();
+    let mut current = String::new();
+    let mut chars = path.chars().peekable();
+    let mut in_selector = false;
+
+    while let Some(c) = chars.next() {
+        match c {
+            '[' => {
+                in_selector = true;
+                current.push(c);
+            },
+            ']' => {
+                in_selector = false;
+                current.push(c);
+            },
+            '\\' => match chars.peek() {
+                Some(&next) if next == '.' || next == '\\' => {
+                    current.push(next);
+                    chars.next();
+                }
+                _ => current.push(c),
+            },
+            '.' if !in_selector => segments.push(std::mem::take(&mut current)),
+            _ => current.push(c),
+        }
+    }
+
+    segments.push(current);
+    segments
+}
+
+/// Joins path components into dot notation, escaping literal dots.
+///
+/// This is the inverse of [`split_path`].
+pub fn join_path(path: &[String]) -> String {
+    path.iter()
+        .map(|segment| segment.replace('\\', "\\\\").replace('.', "\\."))
+        .collect::<Vec<String>>()
+        .join(".")
+}
+
+//----------------------------------------------------------------------
+// Tests
+//----------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_path_escaped_dots() {
+        assert_eq!(split_path("author.name"), vec!["author", "name"]);
+        assert_eq!(split_path("meta.first\\.name"), vec!["meta", "first.name"]);
+        assert_eq!(split_path("a\\\\.b"), vec!["a\\", "b"]);
+        assert_eq!(split_path("a\\b"), vec!["a\\b"]);
+        assert_eq!(split_path("tags[0..3].name"), vec!["tags[0..3]", "name"]);
+    }
+
+    #[test]
+    fn test_join_path_round_trip() {
+        let path = vec!["meta".to_string(), "first.name".to_string(), "a\\b".to_string()];
+        assert_eq!(split_path(&join_path(&path)), path);
+    }
+
+    #[test]
+    fn test_parse_segment_selectors() {
+        assert_eq!(parse_segment("tags"), ("tags", None));
+        assert_eq!(parse_segment("tags[-1]"), ("tags", Some(Selector::Index(-1))));
+        assert_eq!(parse_segment("tags[0..3]"), ("tags", Some(Selector::Range(Some(0), Some(3)))));
+        assert_eq!(parse_segment("tags[1..]"), ("tags", Some(Selector::Range(Some(1), None))));
+        assert_eq!(parse_segment("tags[*]"), ("tags", Some(Selector::All)));
+        assert_eq!(parse_segment("[*]"), ("", Some(Selector::All)));
+        assert_eq!(parse_segment("tags[x]"), ("tags[x]", None));
+    }
+
+    #[test]
+    fn test_path_precompiles_segments() {
+        let path = Path::parse("items.2.tags[-1]");
+        let segments = path.segments();
+
+        assert_eq!(segments.len(), 3);
+        assert_eq!(segments[1].index, Some(2));
+        assert_eq!(segments[2].name, "tags");
+        assert_eq!(segments[2].selector, Some(Selector::Index(-1)));
+        assert_eq!(path.to_string(), "items.2.tags[-1]");
+
+        let path = Path::parse("chapters.*.heading");
+        assert!(path.segments()[1].is_wildcard());
+        assert!(!Path::parse("tags[*]").segments()[0].is_wildcard());
+    }
+
+    #[test]
+    fn test_path_serializes_as_components() {
+        let path = Path::from_segments(["first.name", "tags[0]"]);
+        let json = serde_json::to_string(&path).unwrap();
+
+        assert_eq!(json, r#"["first.name","tags[0]"]"#);
+        assert_eq!(serde_json::from_str::<Path>(&json).unwrap(), path);
+    }
+}
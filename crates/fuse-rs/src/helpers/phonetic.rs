@@ -0,0 +1,295 @@
+//! Phonetic codes for name matching
+//!
+//! Names are often misspelled in ways that sound alike but have a large edit
+//! distance ("Smith" / "Smyth", "Catherine" / "Kathryn"). Phonetic algorithms
+//! map words to a code based on their pronunciation so such spellings can be
+//! compared directly.
+//!
+//! Codes are compared by the `~~` operator of extended search, and replace
+//! the words of the keys whose [`KeyAnalyzer`](crate::KeyAnalyzer) sets
+//! `phonetic`.
+
+use std::borrow::Cow;
+
+use serde::{Deserialize, Serialize};
+
+use crate::helpers::diacritics::strip_char_diacritics;
+use crate::helpers::stemmer::Stemmer;
+
+//----------------------------------------------------------------------
+// Types
+//----------------------------------------------------------------------
+
+/// Algorithm used to compute phonetic codes
+///
+/// # Example
+///
+/// ```
+/// use fuse_rs::PhoneticAlgorithm;
+///
+/// assert_eq!(PhoneticAlgorithm::Soundex.encode("Robert"), "R163");
+/// assert_eq!(PhoneticAlgorithm::Metaphone.encode("Catherine"), "K0RN");
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum PhoneticAlgorithm {
+    /// American Soundex: a letter followed by three digits
+    #[default]
+    Soundex,
+
+    /// Original Metaphone: a variable-length consonant skeleton
+    Metaphone,
+}
+
+//----------------------------------------------------------------------
+// Implementation
+//----------------------------------------------------------------------
+
+impl PhoneticAlgorithm {
+    /// Returns the phonetic code of `word`.
+    ///
+    /// Characters other than ASCII letters (after removing accents) are
+    /// ignored; a word without letters has an empty code.
+    pub fn encode(&self, word: &str) -> String {
+        match self {
+            Self::Soundex => soundex(word),
+            Self::Metaphone => metaphone(word),
+        }
+    }
+}
+
+/// Replaces words with their phonetic code in the normalization pipeline.
+/// Words without letters (numbers) are kept as they are.
+impl Stemmer for PhoneticAlgorithm {
+    fn stem<'w>(&self, word: &'w str) -> Cow<'w, str> {
+        match self.encode(word) {
+            code if code.is_empty() => Cow::Borrowed(word),
+            code => Cow::Owned(code),
+        }
+    }
+}
+
+/// Returns the American Soundex code of `word`.
+pub fn soundex(word: &str) -> String {
+    let letters = ascii_letters(word);
+    let Some(&first) = letters.first() else {
+        return String::new();
+    };
+
+    let mut code = String::from(first);
+    let mut previous = soundex_digit(first);
+
+    for &c in &letters[1..] {
+        if code.len() == 4 {
+            break;
+        }
+
+        let digit = soundex_digit(c);
+        if digit != '0' && digit != previous {
+            code.push(digit);
+        }
+
+        // H and W don't separate letters with the same code, vowels do
+        if c != 'H' && c != 'W' {
+            previous = digit;
+        }
+    }
+
+    while code.len() < 4 {
+        code.push('0');
+    }
+
+    code
+}
+
+/// Returns the original Metaphone code of `word`.
+pub fn metaphone(word: &str) -> String {
+    let mut w = ascii_letters(word);
+
+    // Initial letter exceptions
+    match w.as_slice() {
+        ['A', 'E', ..] | ['G', 'N', ..] | ['K', 'N', ..] | ['P', 'N', ..] | ['W', 'R', ..] => {
+            w.remove(0);
+        },
+        ['X', ..] => w[0] = 'S',
+        ['W', 'H', ..] => {
+            w.remove(1);
+        },
+        _ => {},
+    }
+
+    let at = |i: usize| w.get(i).copied().unwrap_or_default();
+    let before = |i: usize| if i == 0 { '\0' } else { at(i - 1) };
+    let last = w.len().saturating_sub(1);
+
+    let mut code = String::new();
+
+    for (i, &c) in w.iter().enumerate() {
+        // Double letters are coded once, except for C
+        if c != 'C' && before(i) == c {
+            continue;
+        }
+
+        match c {
+            'A' | 'E' | 'I' | 'O' | 'U' => {
+                if i == 0 {
+                    code.push(c);
+                }
+            },
+            'B' => {
+                if !(i == last && before(i) == 'M') {
+                    code.push('B');
+                }
+            },
+            'C' => {
+                if at(i + 1) == 'I' && at(i + 2) == 'A' {
+                    code.push('X');
+                } else if at(i + 1) == 'H' {
+                    code.push(if before(i) == 'S' { 'K' } else { 'X' });
+                } else if matches!(at(i + 1), 'I' | 'E' | 'Y') {
+                    if before(i) != 'S' {
+                        code.push('S');
+                    }
+                } else {
+                    code.push('K');
+                }
+            },
+            'D' => {
+                if at(i + 1) == 'G' && matches!(at(i + 2), 'E' | 'Y' | 'I') {
+                    code.push('J');
+                } else {
+                    code.push('T');
+                }
+            },
+            'G' => {
+                let silent_gh = at(i + 1) == 'H' && i + 1 != last && !is_vowel(at(i + 2));
+                let silent_gn = at(i + 1) == 'N'
+                    && (i + 1 == last || (at(i + 2) == 'E' && at(i + 3) == 'D' && i + 3 == last));
+
+                if silent_gh || silent_gn {
+                    continue;
+                }
+
+                if matches!(at(i + 1), 'I' | 'E' | 'Y') && before(i) != 'G' {
+                    code.push('J');
+                } else {
+                    code.push('K');
+                }
+            },
+            'H' => {
+                let after_vowel = is_vowel(before(i)) && !is_vowel(at(i + 1));
+                if !after_vowel && !matches!(before(i), 'C' | 'S' | 'P' | 'T' | 'G') {
+                    code.push('H');
+                }
+            },
+            'K' => {
+                if before(i) != 'C' {
+                    code.push('K');
+                }
+            },
+            'P' => code.push(if at(i + 1) == 'H' { 'F' } else { 'P' }),
+            'Q' => code.push('K'),
+            'S' => {
+                if at(i + 1) == 'H' || (at(i + 1) == 'I' && matches!(at(i + 2), 'O' | 'A')) {
+                    code.push('X');
+                } else {
+                    code.push('S');
+                }
+            },
+            'T' => {
+                if at(i + 1) == 'I' && matches!(at(i + 2), 'O' | 'A') {
+                    code.push('X');
+                } else if at(i + 1) == 'H' {
+                    code.push('0');
+                } else if !(at(i + 1) == 'C' && at(i + 2) == 'H') {
+                    code.push('T');
+                }
+            },
+            'V' => code.push('F'),
+            'W' | 'Y' => {
+                if is_vowel(at(i + 1)) {
+                    code.push(c);
+                }
+            },
+            'X' => code.push_str("KS"),
+            'Z' => code.push('S'),
+            _ => code.push(c),
+        }
+    }
+
+    code
+}
+
+//----------------------------------------------------------------------
+// Implementation Details
+//----------------------------------------------------------------------
+
+/// Returns the ASCII letters of `word` in upper case, with accents removed.
+fn ascii_letters(word: &str) -> Vec<char> {
+    word.chars()
+        .flat_map(strip_char_diacritics)
+        .filter(char::is_ascii_alphabetic)
+        .map(|c| c.to_ascii_uppercase())
+        .collect()
+}
+
+/// Returns the Soundex digit of an upper case letter (`'0'` for letters that
+/// are not coded).
+fn soundex_digit(c: char) -> char {
+    match c {
+        'B' | 'F' | 'P' | 'V' => '1',
+        'C' | 'G' | 'J' | 'K' | 'Q' | 'S' | 'X' | 'Z' => '2',
+        'D' | 'T' => '3',
+        'L' => '4',
+        'M' | 'N' => '5',
+        'R' => '6',
+        _ => '0',
+    }
+}
+
+fn is_vowel(c: char) -> bool {
+    matches!(c, 'A' | 'E' | 'I' | 'O' | 'U')
+}
+
+//----------------------------------------------------------------------
+// Tests
+//----------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_soundex() {
+        assert_eq!(soundex("Robert"), "R163");
+        assert_eq!(soundex("Rupert"), "R163");
+        assert_eq!(soundex("Rubin"), "R150");
+        assert_eq!(soundex("Ashcraft"), "A261");
+        assert_eq!(soundex("Tymczak"), "T522");
+        assert_eq!(soundex("Pfister"), "P236");
+        assert_eq!(soundex("Lee"), "L000");
+        assert_eq!(soundex("Müller"), soundex("Muller"));
+        assert_eq!(soundex("42"), "");
+    }
+
+    #[test]
+    fn test_metaphone() {
+        assert_eq!(metaphone("Smith"), "SM0");
+        assert_eq!(metaphone("Smyth"), "SM0");
+        assert_eq!(metaphone("Knight"), "NT");
+        assert_eq!(metaphone("Philip"), "FLP");
+        assert_eq!(metaphone("Catherine"), "K0RN");
+        assert_eq!(metaphone("Kathryn"), "K0RN");
+        assert_eq!(metaphone("Thumb"), "0M");
+        assert_eq!(metaphone("Xavier"), "SFR");
+        assert_eq!(metaphone(""), "");
+    }
+
+    #[test]
+    fn test_stem_to_code() {
+        assert_eq!(PhoneticAlgorithm::Soundex.stem("smyth"), "S530");
+        assert_eq!(PhoneticAlgorithm::Metaphone.stem("smyth"), "SM0");
+        assert_eq!(PhoneticAlgorithm::Soundex.stem("221b"), "B000");
+        assert_eq!(PhoneticAlgorithm::Soundex.stem("1984"), "1984");
+    }
+}
@@ -0,0 +1,160 @@
+//! Word stemming for normalization
+//!
+//! A stemmer reduces inflected words to a common stem ("searching",
+//! "searches" → "search") so different forms of a word match each other.
+//! Stemming is applied by the normalization pipeline, i.e. to both indexed
+//! values and patterns.
+//!
+//! Any type implementing [`Stemmer`] can be used. With the `stemming`
+//! feature, [`SnowballStemmer`] provides the Snowball algorithms of the
+//! `rust-stemmers` crate.
+
+use std::borrow::Cow;
+use std::fmt;
+#[cfg(feature = "stemming")]
+use std::sync::OnceLock;
+
+//----------------------------------------------------------------------
+// Constants
+//----------------------------------------------------------------------
+
+/// Number of languages with a Snowball stemmer
+#[cfg(feature = "stemming")]
+const SNOWBALL_LANGUAGES: usize = rust_stemmers::Algorithm::Turkish as usize + 1;
+
+//----------------------------------------------------------------------
+// Types
+//----------------------------------------------------------------------
+
+/// Reduces a word to its stem
+///
+/// Words are passed after case folding, transliteration and diacritics
+/// removal, so a stemmer only sees normalized text.
+///
+/// # Example
+///
+/// ```
+/// use std::borrow::Cow;
+/// use std::sync::Arc;
+/// use fuse_rs::{Fuse, FuseOptions, Stemmer};
+/// use serde_json::json;
+///
+/// /// Strips a plural `s`
+/// #[derive(Debug)]
+/// struct Plural;
+///
+/// impl Stemmer for Plural {
+///     fn stem<'w>(&self, word: &'w str) -> Cow<'w, str> {
+///         Cow::Borrowed(word.strip_suffix('s').unwrap_or(word))
+///     }
+/// }
+///
+/// let docs = vec![json!("apples and pears")];
+/// let options = FuseOptions {
+///     stemmer: Some(Arc::new(Plural)),
+///     threshold: 0.0,
+///     ..Default::default()
+/// };
+/// let fuse = Fuse::new(&docs, &options, None);
+///
+/// assert_eq!(fuse.search("apple and pear").unwrap().len(), 1);
+/// ```
+pub trait Stemmer: fmt::Debug + Send + Sync {
+    /// Returns the stem of `word`.
+    fn stem<'w>(&self, word: &'w str) -> Cow<'w, str>;
+}
+
+/// Snowball stemmer for a single language
+///
+/// Options can also name the language with
+/// [`FuseOptions::stemming_language`](crate::FuseOptions::stemming_language)
+/// instead of holding a stemmer.
+#[cfg(feature = "stemming")]
+pub struct SnowballStemmer {
+    /// The language the stemmer was created for
+    language: rust_stemmers::Algorithm,
+
+    /// The underlying stemmer
+    inner: rust_stemmers::Stemmer,
+}
+
+//----------------------------------------------------------------------
+// Implementation
+//----------------------------------------------------------------------
+
+#[cfg(feature = "stemming")]
+impl SnowballStemmer {
+    /// Creates a stemmer for `language`.
+    pub fn new(language: rust_stemmers::Algorithm) -> Self {
+        Self {
+            language,
+            inner: rust_stemmers::Stemmer::create(language),
+        }
+    }
+
+    /// Returns the language of the stemmer.
+    pub fn language(&self) -> rust_stemmers::Algorithm {
+        self.language
+    }
+
+    /// Returns the stemmer of `language` shared by the whole program,
+    /// creating it on first use.
+    pub fn for_language(language: rust_stemmers::Algorithm) -> &'static SnowballStemmer {
+        static STEMMERS: [OnceLock<SnowballStemmer>; SNOWBALL_LANGUAGES] =
+            [const { OnceLock::new() }; SNOWBALL_LANGUAGES];
+
+        STEMMERS[language as usize].get_or_init(|| Self::new(language))
+    }
+}
+
+#[cfg(feature = "stemming")]
+impl fmt::Debug for SnowballStemmer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SnowballStemmer")
+            .field("language", &self.language)
+            .finish()
+    }
+}
+
+#[cfg(feature = "stemming")]
+impl Stemmer for SnowballStemmer {
+    fn stem<'w>(&self, word: &'w str) -> Cow<'w, str> {
+        self.inner.stem(word)
+    }
+}
+
+//----------------------------------------------------------------------
+// Tests
+//----------------------------------------------------------------------
+
+#[cfg(all(test, feature = "stemming"))]
+mod tests {
+    use super::*;
+    use crate::{Fuse, FuseOptions};
+    use serde_json::json;
+
+    #[test]
+    fn test_stemming_language() {
+        let stemmer = SnowballStemmer::for_language(rust_stemmers::Algorithm::English);
+        assert_eq!(stemmer.stem("running"), "run");
+        assert!(std::ptr::eq(stemmer, SnowballStemmer::for_language(rust_stemmers::Algorithm::English)));
+
+        let options = FuseOptions::from_json(json!({"stemmingLanguage": "English", "threshold": 0.0})).unwrap();
+        let fuse = Fuse::new(&[json!("Running shoes"), json!("Rune stones")], &options, None);
+        assert_eq!(fuse.search_with_options("run shoe", &Default::default()).unwrap(), vec![json!("Running shoes")]);
+
+        // An explicit stemmer takes precedence
+        #[derive(Debug)]
+        struct Verbatim;
+
+        impl Stemmer for Verbatim {
+            fn stem<'w>(&self, word: &'w str) -> Cow<'w, str> {
+                Cow::Borrowed(word)
+            }
+        }
+
+        assert_eq!(options.normalizer().normalize("running"), "run");
+        let options = FuseOptions { stemmer: Some(std::sync::Arc::new(Verbatim)), ..options };
+        assert_eq!(options.normalizer().normalize("running"), "running");
+    }
+}
@@ -0,0 +1,225 @@
+//! Stop-word lists
+//!
+//! Stop words ("the", "of", "and", ...) carry little meaning but make up a
+//! large share of most texts. When configured through
+//! [`FuseOptions::stop_words`](crate::FuseOptions::stop_words) they are left
+//! out of the token count used for the field-length norm and removed from
+//! texts and patterns before matching.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::helpers::caseless::fold;
+use crate::helpers::tokenizer::{Token, Tokenizer};
+
+//----------------------------------------------------------------------
+// Constants & Statics
+//----------------------------------------------------------------------
+
+const ENGLISH: &[&str] = &[
+    "a", "an", "and", "are", "as", "at", "be", "but", "by", "for", "from", "has", "have",
+    "he", "her", "his", "i", "if", "in", "into", "is", "it", "its", "my", "no", "not",
+    "of", "on", "or", "our", "she", "so", "such", "than", "that", "the", "their", "them",
+    "then", "there", "these", "they", "this", "to", "was", "we", "were", "will", "with",
+    "you", "your",
+];
+
+const FRENCH: &[&str] = &[
+    "au", "aux", "avec", "ce", "ces", "dans", "de", "des", "du", "elle", "en", "et", "eux",
+    "il", "je", "la", "le", "les", "leur", "lui", "ma", "mais", "me", "mes", "moi", "mon",
+    "ne", "nos", "notre", "nous", "on", "ou", "par", "pas", "pour", "qu", "que", "qui",
+    "sa", "se", "ses", "son", "sur", "ta", "te", "tes", "toi", "ton", "tu", "un", "une",
+    "vos", "votre", "vous", "l", "d", "j", "m", "n", "s", "t", "c", "y", "à", "est",
+];
+
+const GERMAN: &[&str] = &[
+    "aber", "als", "am", "an", "auch", "auf", "aus", "bei", "bin", "bis", "das", "dass",
+    "dem", "den", "der", "des", "die", "du", "ein", "eine", "einem", "einen", "einer",
+    "eines", "er", "es", "für", "hat", "ich", "ihr", "im", "in", "ist", "ja", "mit",
+    "nach", "nicht", "noch", "nur", "oder", "sie", "sind", "so", "um", "und", "uns",
+    "von", "vor", "war", "was", "wie", "wir", "zu", "zum", "zur",
+];
+
+const SPANISH: &[&str] = &[
+    "a", "al", "con", "como", "de", "del", "el", "en", "es", "esta", "este", "ella",
+    "ellos", "la", "las", "le", "les", "lo", "los", "más", "me", "mi", "no", "nos", "o",
+    "para", "pero", "por", "que", "se", "si", "sin", "su", "sus", "te", "tu", "un", "una",
+    "uno", "unos", "y", "ya", "yo",
+];
+
+const ITALIAN: &[&str] = &[
+    "a", "ai", "al", "alla", "alle", "che", "chi", "con", "da", "dal", "dei", "del",
+    "della", "delle", "di", "e", "è", "gli", "i", "il", "in", "io", "la", "le", "lo",
+    "ma", "mi", "ne", "nei", "nel", "nella", "non", "o", "per", "se", "si", "su", "sua",
+    "suo", "ti", "tra", "tu", "un", "una", "uno",
+];
+
+const PORTUGUESE: &[&str] = &[
+    "a", "ao", "aos", "as", "com", "como", "da", "das", "de", "do", "dos", "e", "é",
+    "ela", "ele", "em", "entre", "eu", "mais", "mas", "na", "nas", "no", "nos", "não",
+    "o", "os", "ou", "para", "pela", "pelo", "por", "que", "se", "seu", "sua", "um",
+    "uma", "à",
+];
+
+//----------------------------------------------------------------------
+// Types
+//----------------------------------------------------------------------
+
+/// Languages with a built-in stop-word list
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StopWordLanguage {
+    English,
+    French,
+    German,
+    Spanish,
+    Italian,
+    Portuguese,
+}
+
+/// A set of stop words
+///
+/// Words are compared case-insensitively, ignoring punctuation around them.
+///
+/// # Example
+///
+/// ```
+/// use fuse_rs::{StopWordLanguage, StopWords};
+///
+/// let stop_words = StopWords::for_language(StopWordLanguage::English).with_words(["vol"]);
+///
+/// assert!(stop_words.contains("The"));
+/// assert!(stop_words.contains("vol."));
+/// assert!(!stop_words.contains("hobbit"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct StopWords {
+    /// The lowercased stop words
+    words: HashSet<String>,
+}
+
+/// Tokenizer that leaves stop words out of the tokens of another tokenizer
+///
+/// A text made only of stop words keeps all of its tokens, so it is never
+/// counted as empty.
+#[derive(Debug, Clone)]
+pub struct StopWordTokenizer {
+    /// The tokenizer whose tokens are filtered
+    inner: Arc<dyn Tokenizer>,
+
+    /// The words to leave out
+    stop_words: StopWords,
+}
+
+//----------------------------------------------------------------------
+// Implementation
+//----------------------------------------------------------------------
+
+impl StopWords {
+    /// Creates a set from a user-supplied list of words.
+    pub fn new<I, S>(words: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        Self::default().with_words(words)
+    }
+
+    /// Creates the built-in set for `language`.
+    pub fn for_language(language: StopWordLanguage) -> Self {
+        let words = match language {
+            StopWordLanguage::English => ENGLISH,
+            StopWordLanguage::French => FRENCH,
+            StopWordLanguage::German => GERMAN,
+            StopWordLanguage::Spanish => SPANISH,
+            StopWordLanguage::Italian => ITALIAN,
+            StopWordLanguage::Portuguese => PORTUGUESE,
+        };
+
+        Self::new(words)
+    }
+
+    /// Adds `words` to the set.
+    pub fn with_words<I, S>(mut self, words: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.words.extend(words.into_iter().map(|w| fold(w.as_ref()).collect()));
+        self
+    }
+
+    /// Returns `true` if `word` is a stop word.
+    pub fn contains(&self, word: &str) -> bool {
+        let word = word.trim_matches(|c: char| !c.is_alphanumeric());
+        !word.is_empty() && self.words.contains(&fold(word).collect::<String>())
+    }
+
+    /// Returns the stop words, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.words.iter().map(String::as_str)
+    }
+
+    /// Returns the number of stop words in the set.
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    /// Returns `true` if the set is empty.
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+}
+
+impl StopWordTokenizer {
+    /// Creates a tokenizer that removes `stop_words` from the tokens of `inner`.
+    pub fn new(inner: Arc<dyn Tokenizer>, stop_words: StopWords) -> Self {
+        Self { inner, stop_words }
+    }
+}
+
+impl Tokenizer for StopWordTokenizer {
+    fn tokenize<'t>(&self, text: &'t str) -> Vec<Token<'t>> {
+        let tokens = self.inner.tokenize(text);
+
+        if tokens.iter().all(|t| self.stop_words.contains(t.text)) {
+            return tokens;
+        }
+
+        tokens.into_iter().filter(|t| !self.stop_words.contains(t.text)).collect()
+    }
+}
+
+//----------------------------------------------------------------------
+// Tests
+//----------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::tokenizer::WhitespaceTokenizer;
+
+    #[test]
+    fn test_stop_words_contains() {
+        let stop_words = StopWords::for_language(StopWordLanguage::German);
+
+        assert!(stop_words.contains("Der"));
+        assert!(stop_words.contains("für"));
+        assert!(!stop_words.contains("Zauberberg"));
+        assert!(!StopWords::new(["the"]).contains("..."));
+    }
+
+    #[test]
+    fn test_stop_word_tokenizer() {
+        let tokenizer = StopWordTokenizer::new(
+            Arc::new(WhitespaceTokenizer),
+            StopWords::for_language(StopWordLanguage::English),
+        );
+
+        let tokens: Vec<&str> = tokenizer.tokenize("The Lord of the Rings").iter().map(|t| t.text).collect();
+        assert_eq!(tokens, vec!["Lord", "Rings"]);
+        assert_eq!(tokenizer.count("To be or not to be"), 6);
+    }
+}
@@ -0,0 +1,163 @@
+//! Query-time synonym expansion
+//!
+//! A [`SynonymProvider`] is consulted when a pattern is compiled into a
+//! searcher. The synonyms it returns are searched as alternatives of the
+//! pattern, so a search for "nyc" also finds "New York" without callers
+//! rewriting their queries.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::helpers::caseless::fold;
+
+//----------------------------------------------------------------------
+// Types
+//----------------------------------------------------------------------
+
+/// Supplies synonyms for search terms
+pub trait SynonymProvider: fmt::Debug + Send + Sync {
+    /// Returns the synonyms of `term`, not including `term` itself.
+    ///
+    /// Called with the whole pattern and with each of its words.
+    fn synonyms(&self, term: &str) -> Vec<String>;
+}
+
+/// Synonym lookup table
+///
+/// Terms are matched case-insensitively.
+///
+/// # Example
+///
+/// ```
+/// use std::sync::Arc;
+/// use fuse_rs::{Fuse, FuseOptions, SynonymMap};
+/// use serde_json::json;
+///
+/// let synonyms = SynonymMap::new().with_group(["nyc", "new york"]);
+///
+/// let docs = vec![json!("New York pizza"), json!("Chicago pizza")];
+/// let options = FuseOptions {
+///     synonyms: Some(Arc::new(synonyms)),
+///     threshold: 0.2,
+///     ..Default::default()
+/// };
+/// let fuse = Fuse::new(&docs, &options, None);
+///
+/// assert_eq!(fuse.search("nyc pizza").unwrap()[0].item, json!("New York pizza"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SynonymMap {
+    /// Synonyms by lowercased term
+    map: HashMap<String, Vec<String>>,
+}
+
+//----------------------------------------------------------------------
+// Implementation
+//----------------------------------------------------------------------
+
+impl SynonymMap {
+    /// Creates an empty table.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds one-way synonyms: `term` expands to `synonyms`, not the reverse.
+    pub fn with_synonyms<I, S>(mut self, term: &str, synonyms: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let entry = self.map.entry(fold(term).collect()).or_default();
+        for synonym in synonyms {
+            let synonym = synonym.into();
+            if !entry.contains(&synonym) {
+                entry.push(synonym);
+            }
+        }
+        self
+    }
+
+    /// Adds a group of terms that are all synonyms of each other.
+    pub fn with_group<I, S>(mut self, group: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let group: Vec<String> = group.into_iter().map(Into::into).collect();
+
+        for term in &group {
+            let others = group.iter().filter(|other| *other != term).cloned();
+            self = self.with_synonyms(term, others);
+        }
+        self
+    }
+}
+
+impl SynonymProvider for SynonymMap {
+    fn synonyms(&self, term: &str) -> Vec<String> {
+        self.map.get(&fold(term).collect::<String>()).cloned().unwrap_or_default()
+    }
+}
+
+/// Returns `pattern` followed by its variants with synonyms substituted.
+///
+/// Synonyms of the whole pattern come first, then variants where a single
+/// word is replaced by one of its synonyms. Duplicates are removed.
+pub fn expand_synonyms(pattern: &str, provider: &dyn SynonymProvider) -> Vec<String> {
+    let mut variants = vec![pattern.to_string()];
+    let mut push = |variant: String| {
+        if !variants.contains(&variant) {
+            variants.push(variant);
+        }
+    };
+
+    for synonym in provider.synonyms(pattern) {
+        push(synonym);
+    }
+
+    let words: Vec<&str> = pattern.split_whitespace().collect();
+    if words.len() > 1 {
+        for (i, word) in words.iter().enumerate() {
+            for synonym in provider.synonyms(word) {
+                let mut variant = words.clone();
+                variant[i] = &synonym;
+                push(variant.join(" "));
+            }
+        }
+    }
+
+    variants
+}
+
+//----------------------------------------------------------------------
+// Tests
+//----------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_synonym_map() {
+        let synonyms = SynonymMap::new()
+            .with_group(["NYC", "New York", "Big Apple"])
+            .with_synonyms("tv", ["television"]);
+
+        assert_eq!(synonyms.synonyms("nyc"), vec!["New York", "Big Apple"]);
+        assert_eq!(synonyms.synonyms("TV"), vec!["television"]);
+        assert!(synonyms.synonyms("television").is_empty());
+    }
+
+    #[test]
+    fn test_expand_synonyms() {
+        let synonyms = SynonymMap::new()
+            .with_group(["nyc", "new york"])
+            .with_synonyms("nyc pizza", ["new york slice"]);
+
+        assert_eq!(
+            expand_synonyms("nyc pizza", &synonyms),
+            vec!["nyc pizza", "new york slice", "new york pizza"]
+        );
+        assert_eq!(expand_synonyms("boston", &synonyms), vec!["boston"]);
+    }
+}
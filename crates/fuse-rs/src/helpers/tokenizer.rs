@@ -0,0 +1,357 @@
+//! Splitting text into tokens
+//!
+//! Tokens are used to compute the field-length norm (the more tokens a
+//! field has, the less a match in it is worth). Splitting on whitespace
+//! works for most Latin-script text but not for languages written without
+//! spaces, so the tokenizer is pluggable through
+//! [`FuseOptions::tokenizer`](crate::FuseOptions::tokenizer).
+
+use std::fmt;
+use std::sync::Arc;
+
+//----------------------------------------------------------------------
+// Types
+//----------------------------------------------------------------------
+
+/// A token produced by a `Tokenizer`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Token<'t> {
+    /// The token text, borrowed from the tokenized text
+    pub text: &'t str,
+
+    /// Character index of the first character of the token
+    pub position: usize,
+}
+
+/// Splits text into tokens
+///
+/// # Example
+///
+/// ```
+/// use std::sync::Arc;
+/// use fuse_rs::{CjkBigramTokenizer, FuseOptions, Tokenizer};
+///
+/// let tokenizer = CjkBigramTokenizer;
+/// let tokens: Vec<&str> = tokenizer.tokenize("東京タワー tour").iter().map(|t| t.text).collect();
+/// assert_eq!(tokens, vec!["東京", "京タ", "タワ", "ワー", "tour"]);
+///
+/// let options = FuseOptions {
+///     tokenizer: Arc::new(CjkBigramTokenizer),
+///     ..Default::default()
+/// };
+/// ```
+pub trait Tokenizer: fmt::Debug + Send + Sync {
+    /// Splits `text` into tokens, in order of appearance.
+    fn tokenize<'t>(&self, text: &'t str) -> Vec<Token<'t>>;
+
+    /// Returns the number of tokens in `text`.
+    fn count(&self, text: &str) -> usize {
+        self.tokenize(text).len()
+    }
+}
+
+/// Splits on runs of whitespace (the default)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct WhitespaceTokenizer;
+
+/// Splits on word boundaries
+///
+/// A word is a run of letters, digits, combining marks and connector
+/// punctuation (`_`); an apostrophe between two letters (`don't`) stays in
+/// the word. Everything else separates words and is not part of any token.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct UnicodeWordTokenizer;
+
+/// Splits like `UnicodeWordTokenizer`, with CJK text split into bigrams
+///
+/// Chinese, Japanese and Korean are written without spaces, so each run of
+/// CJK characters is turned into overlapping pairs of characters (a single
+/// character run yields that character).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CjkBigramTokenizer;
+
+/// Splits with a closure returning the tokens as slices of the text
+///
+/// Slices that are not part of the text are ignored; token positions are
+/// derived from where the slices start.
+///
+/// # Example
+///
+/// ```
+/// use std::sync::Arc;
+/// use fuse_rs::{FnTokenizer, FuseOptions, Tokenizer};
+///
+/// // Product codes such as "AB-1234-X" count as one token per segment
+/// let tokenizer = FnTokenizer::new(|text| text.split(['-', ' ']).filter(|s| !s.is_empty()).collect());
+/// assert_eq!(tokenizer.count("AB-1234-X"), 3);
+///
+/// let options = FuseOptions {
+///     tokenizer: Arc::new(tokenizer),
+///     ..Default::default()
+/// };
+/// ```
+pub struct FnTokenizer<F> {
+    /// Splits a text into slices of it
+    split: F,
+}
+
+//----------------------------------------------------------------------
+// Implementations
+//----------------------------------------------------------------------
+
+impl<F> FnTokenizer<F>
+where
+    F: for<'t> Fn(&'t str) -> Vec<&'t str> + Send + Sync,
+{
+    /// Creates a tokenizer splitting texts with `split`.
+    pub fn new(split: F) -> Self {
+        Self { split }
+    }
+}
+
+impl<F> fmt::Debug for FnTokenizer<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FnTokenizer").finish_non_exhaustive()
+    }
+}
+
+impl<F> Tokenizer for FnTokenizer<F>
+where
+    F: for<'t> Fn(&'t str) -> Vec<&'t str> + Send + Sync,
+{
+    fn tokenize<'t>(&self, text: &'t str) -> Vec<Token<'t>> {
+        let start = text.as_ptr() as usize;
+
+        (self.split)(text)
+            .into_iter()
+            .filter_map(|token| {
+                let byte = (token.as_ptr() as usize).checked_sub(start)?;
+                let within = byte + token.len() <= text.len() && text.is_char_boundary(byte);
+                within.then(|| Token { text: token, position: text[..byte].chars().count() })
+            })
+            .collect()
+    }
+}
+
+impl Tokenizer for WhitespaceTokenizer {
+    fn tokenize<'t>(&self, text: &'t str) -> Vec<Token<'t>> {
+        split_runs(text, |c| !c.is_whitespace())
+    }
+
+    fn count(&self, text: &str) -> usize {
+        text.split_whitespace().count()
+    }
+}
+
+impl Tokenizer for UnicodeWordTokenizer {
+    fn tokenize<'t>(&self, text: &'t str) -> Vec<Token<'t>> {
+        words(text)
+    }
+}
+
+impl Tokenizer for CjkBigramTokenizer {
+    fn tokenize<'t>(&self, text: &'t str) -> Vec<Token<'t>> {
+        let mut tokens = Vec::new();
+
+        for word in words(text) {
+            for run in split_script_runs(word) {
+                if !is_cjk(run.text.chars().next().unwrap_or_default()) {
+                    tokens.push(run);
+                    continue;
+                }
+
+                let chars: Vec<(usize, char)> = run.text.char_indices().collect();
+                if chars.len() == 1 {
+                    tokens.push(run);
+                    continue;
+                }
+
+                for (i, pair) in chars.windows(2).enumerate() {
+                    let end = pair[1].0 + pair[1].1.len_utf8();
+                    tokens.push(Token {
+                        text: &run.text[pair[0].0..end],
+                        position: run.position + i,
+                    });
+                }
+            }
+        }
+
+        tokens
+    }
+}
+
+/// Returns the default tokenizer used by `FuseOptions`
+pub fn default_tokenizer() -> Arc<dyn Tokenizer> {
+    Arc::new(WhitespaceTokenizer)
+}
+
+//----------------------------------------------------------------------
+// Implementation Details
+//----------------------------------------------------------------------
+
+/// Splits `text` into maximal runs of characters accepted by `keep`.
+fn split_runs<F: Fn(char) -> bool>(text: &str, keep: F) -> Vec<Token<'_>> {
+    let mut tokens = Vec::new();
+    let mut start: Option<(usize, usize)> = None;
+
+    for (position, (byte, c)) in text.char_indices().enumerate() {
+        match (keep(c), start) {
+            (true, None) => start = Some((byte, position)),
+            (false, Some((from, token_position))) => {
+                tokens.push(Token { text: &text[from..byte], position: token_position });
+                start = None;
+            },
+            _ => {},
+        }
+    }
+
+    if let Some((from, position)) = start {
+        tokens.push(Token { text: &text[from..], position });
+    }
+
+    tokens
+}
+
+/// Splits `text` into words (see `UnicodeWordTokenizer`).
+fn words(text: &str) -> Vec<Token<'_>> {
+    let chars: Vec<char> = text.chars().collect();
+
+    let is_word_char = |c: char| c.is_alphanumeric() || c == '_' || is_mark(c);
+
+    let mut tokens = Vec::new();
+    let mut byte = 0;
+    let mut start: Option<(usize, usize)> = None;
+
+    for (i, &c) in chars.iter().enumerate() {
+        let inner_apostrophe = matches!(c, '\'' | '’')
+            && start.is_some()
+            && chars.get(i + 1).is_some_and(|next| next.is_alphabetic());
+
+        match (is_word_char(c) || inner_apostrophe, start) {
+            (true, None) => start = Some((byte, i)),
+            (false, Some((from, position))) => {
+                tokens.push(Token { text: &text[from..byte], position });
+                start = None;
+            },
+            _ => {},
+        }
+
+        byte += c.len_utf8();
+    }
+
+    if let Some((from, position)) = start {
+        tokens.push(Token { text: &text[from..], position });
+    }
+
+    tokens
+}
+
+/// Splits a word into runs of CJK and non-CJK characters.
+fn split_script_runs(word: Token<'_>) -> Vec<Token<'_>> {
+    let mut runs = Vec::new();
+    let mut from = 0;
+    let mut run_position = word.position;
+    let mut previous: Option<bool> = None;
+
+    for (i, (byte, c)) in word.text.char_indices().enumerate() {
+        // Combining marks stay with the preceding character
+        let cjk = if is_mark(c) { previous.unwrap_or(false) } else { is_cjk(c) };
+
+        if previous.is_some_and(|p| p != cjk) {
+            runs.push(Token { text: &word.text[from..byte], position: run_position });
+            from = byte;
+            run_position = word.position + i;
+        }
+
+        previous = Some(cjk);
+    }
+
+    if from < word.text.len() {
+        runs.push(Token { text: &word.text[from..], position: run_position });
+    }
+
+    runs
+}
+
+/// Returns `true` for Han, Hiragana, Katakana and Hangul characters.
+fn is_cjk(c: char) -> bool {
+    matches!(c,
+        '\u{1100}'..='\u{11FF}'     // Hangul Jamo
+        | '\u{3040}'..='\u{30FF}'   // Hiragana, Katakana
+        | '\u{3131}'..='\u{318F}'   // Hangul Compatibility Jamo
+        | '\u{3400}'..='\u{4DBF}'   // CJK Extension A
+        | '\u{4E00}'..='\u{9FFF}'   // CJK Unified Ideographs
+        | '\u{AC00}'..='\u{D7AF}'   // Hangul Syllables
+        | '\u{F900}'..='\u{FAFF}'   // CJK Compatibility Ideographs
+        | '\u{FF66}'..='\u{FF9F}'   // Halfwidth Katakana
+        | '\u{20000}'..='\u{2FA1F}' // CJK Extensions B-F, Compatibility Supplement
+    )
+}
+
+/// Returns `true` for combining marks, which belong to the preceding letter.
+fn is_mark(c: char) -> bool {
+    crate::helpers::diacritics::is_diacritic(c)
+}
+
+//----------------------------------------------------------------------
+// Tests
+//----------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn texts<'t>(tokens: &[Token<'t>]) -> Vec<&'t str> {
+        tokens.iter().map(|t| t.text).collect()
+    }
+
+    #[test]
+    fn test_whitespace_tokenizer() {
+        let tokens = WhitespaceTokenizer.tokenize("  old\tman's  war ");
+
+        assert_eq!(texts(&tokens), vec!["old", "man's", "war"]);
+        assert_eq!(tokens[1].position, 6);
+        assert_eq!(WhitespaceTokenizer.count("  old\tman's  war "), 3);
+    }
+
+    #[test]
+    fn test_unicode_word_tokenizer() {
+        let tokens = UnicodeWordTokenizer.tokenize("Don't panic—it's “only” a test_case, café!");
+
+        assert_eq!(texts(&tokens), vec!["Don't", "panic", "it's", "only", "a", "test_case", "café"]);
+        assert_eq!(UnicodeWordTokenizer.count("rock 'n' roll"), 3);
+    }
+
+    #[test]
+    fn test_cjk_bigram_tokenizer() {
+        let tokens = CjkBigramTokenizer.tokenize("我爱北京 hello 한국어 猫");
+
+        assert_eq!(
+            texts(&tokens),
+            vec!["我爱", "爱北", "北京", "hello", "한국", "국어", "猫"]
+        );
+        assert_eq!(tokens[2].position, 2);
+        assert_eq!(tokens[4].position, 11);
+    }
+
+    #[test]
+    fn test_fn_tokenizer() {
+        let tokenizer = FnTokenizer::new(|text| {
+            let mut tokens: Vec<&str> = text.split('/').collect();
+            tokens.push("not in the text");
+            tokens
+        });
+        let tokens = tokenizer.tokenize("é/path/to");
+
+        assert_eq!(texts(&tokens), vec!["é", "path", "to"]);
+        assert_eq!(tokens[2].position, 7);
+    }
+
+    #[test]
+    fn test_cjk_bigram_mixed_word() {
+        let tokens = CjkBigramTokenizer.tokenize("iPhone手机壳");
+
+        assert_eq!(texts(&tokens), vec!["iPhone", "手机", "机壳"]);
+        assert_eq!(tokens[1].position, 6);
+    }
+}
@@ -0,0 +1,102 @@
+//! Optional `tracing` instrumentation
+//!
+//! With the `tracing` feature these macros forward to the `tracing` crate.
+//! Without it they expand to nothing, so their arguments are not evaluated
+//! and instrumented code pays no cost.
+//!
+//! Spans are emitted at `DEBUG` level and named after the component
+//! (`fuse.search`, `fuse_index.build`, ...), so a subscriber can report the
+//! time spent in each step of a search.
+
+//----------------------------------------------------------------------
+// Macros
+//----------------------------------------------------------------------
+
+/// Enters a `DEBUG` span until the end of the enclosing block.
+macro_rules! enter_span {
+    ($($arg:tt)+) => {
+        #[cfg(feature = "tracing")]
+        let _span = ::tracing::debug_span!($($arg)+).entered();
+    };
+}
+
+/// Emits a `DEBUG` event.
+macro_rules! debug {
+    ($($arg:tt)+) => {
+        #[cfg(feature = "tracing")]
+        ::tracing::debug!($($arg)+);
+    };
+}
+
+/// Emits a `TRACE` event, for hot paths such as cache lookups.
+macro_rules! trace {
+    ($($arg:tt)+) => {
+        #[cfg(feature = "tracing")]
+        ::tracing::trace!($($arg)+);
+    };
+}
+
+pub(crate) use {debug, enter_span, trace};
+
+//----------------------------------------------------------------------
+// Tests
+//----------------------------------------------------------------------
+
+#[cfg(all(test, feature = "tracing"))]
+mod tests {
+    use std::sync::Mutex;
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    use serde_json::json;
+    use tracing::span::{Attributes, Id, Record};
+    use tracing::{Event, Metadata, Subscriber};
+
+    use crate::{Fuse, FuseOptions, FuseSearchOptions};
+
+    /// Records the names of the spans and the messages of the events
+    #[derive(Default)]
+    struct Recorder {
+        next_id: AtomicU64,
+        names: Mutex<Vec<String>>,
+    }
+
+    impl Subscriber for Recorder {
+        fn enabled(&self, _: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, span: &Attributes<'_>) -> Id {
+            self.names.lock().unwrap().push(span.metadata().name().to_string());
+            Id::from_u64(self.next_id.fetch_add(1, Ordering::Relaxed) + 1)
+        }
+
+        fn record(&self, _: &Id, _: &Record<'_>) {}
+
+        fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+        fn event(&self, event: &Event<'_>) {
+            self.names.lock().unwrap().push(format!("event {}", event.metadata().target()));
+        }
+
+        fn enter(&self, _: &Id) {}
+
+        fn exit(&self, _: &Id) {}
+    }
+
+    #[test]
+    fn test_search_is_instrumented() {
+        let recorder = std::sync::Arc::new(Recorder::default());
+        let docs = vec![json!("apple"), json!("banana")];
+
+        tracing::subscriber::with_default(recorder.clone(), || {
+            let fuse = Fuse::new(&docs, &FuseOptions::default(), None);
+            fuse.search_results("apple", &FuseSearchOptions::default()).unwrap();
+        });
+
+        let names = recorder.names.lock().unwrap();
+        for name in ["fuse_index.build", "fuse.search", "fuse.match", "fuse.rank", "fuse.format"] {
+            assert!(names.iter().any(|n| n == name), "missing span {}", name);
+        }
+        assert!(names.iter().any(|n| n.starts_with("event fuse_rs::core::fuse")));
+    }
+}
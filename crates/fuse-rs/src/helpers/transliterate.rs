@@ -0,0 +1,266 @@
+//! Transliteration of characters into their Latin equivalents
+//!
+//! Some letters have no canonical decomposition (`ß`, `æ`, `ł`, ...) and
+//! letters of non-Latin scripts can't be matched by a Latin pattern at all.
+//! A transliteration table maps such characters to Latin text so that, for
+//! example, "strasse" finds "Straße", "moskva" finds "Москва" and "abc"
+//! finds the full-width "ＡＢＣ" of East Asian text.
+//!
+//! The tables for non-Latin scripts are behind the `transliteration` feature
+//! (enabled by default). Other scripts can be plugged in with [`script_table`].
+
+use std::collections::HashMap;
+
+//----------------------------------------------------------------------
+// Types
+//----------------------------------------------------------------------
+
+/// Mapping from a character to the text it is transliterated to
+///
+/// Entries are looked up after case folding, so a table used with
+/// case-insensitive search only needs lowercase keys. Characters missing from
+/// the table are kept as is.
+pub type TransliterationTable = HashMap<char, String>;
+
+/// Scripts with a built-in transliteration table
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransliterationScript {
+    /// Latin letters without a canonical decomposition (`ß`, `æ`, `ø`, `ł`, ...)
+    Latin,
+
+    /// Russian Cyrillic
+    #[cfg(feature = "transliteration")]
+    Cyrillic,
+
+    /// Greek
+    #[cfg(feature = "transliteration")]
+    Greek,
+
+    /// Full-width forms of ASCII characters (`Ａ`, `１`, `！`, ...) and the
+    /// ideographic space
+    FullWidth,
+}
+
+//----------------------------------------------------------------------
+// Constants
+//----------------------------------------------------------------------
+
+/// Latin letters without a canonical decomposition
+const LATIN: &[(char, &str)] = &[
+    ('ß', "ss"), ('ẞ', "SS"),
+    ('æ', "ae"), ('Æ', "AE"),
+    ('œ', "oe"), ('Œ', "OE"),
+    ('ø', "o"), ('Ø', "O"),
+    ('đ', "d"), ('Đ', "D"),
+    ('ð', "d"), ('Ð', "D"),
+    ('ł', "l"), ('Ł', "L"),
+    ('þ', "th"), ('Þ', "TH"),
+    ('ı', "i"),
+];
+
+/// Russian Cyrillic letters (lowercase; uppercase entries are derived)
+#[cfg(feature = "transliteration")]
+const CYRILLIC: &[(char, &str)] = &[
+    ('а', "a"), ('б', "b"), ('в', "v"), ('г', "g"), ('д', "d"),
+    ('е', "e"), ('ё', "e"), ('ж', "zh"), ('з', "z"), ('и', "i"),
+    ('й', "y"), ('к', "k"), ('л', "l"), ('м', "m"), ('н', "n"),
+    ('о', "o"), ('п', "p"), ('р', "r"), ('с', "s"), ('т', "t"),
+    ('у', "u"), ('ф', "f"), ('х', "kh"), ('ц', "ts"), ('ч', "ch"),
+    ('ш', "sh"), ('щ', "shch"), ('ъ', ""), ('ы', "y"), ('ь', ""),
+    ('э', "e"), ('ю', "yu"), ('я', "ya"),
+];
+
+/// Greek letters, following ELOT 743 (lowercase; uppercase entries are derived)
+#[cfg(feature = "transliteration")]
+const GREEK: &[(char, &str)] = &[
+    ('α', "a"), ('β', "v"), ('γ', "g"), ('δ', "d"), ('ε', "e"),
+    ('ζ', "z"), ('η', "i"), ('θ', "th"), ('ι', "i"), ('κ', "k"),
+    ('λ', "l"), ('μ', "m"), ('ν', "n"), ('ξ', "x"), ('ο', "o"),
+    ('π', "p"), ('ρ', "r"), ('σ', "s"), ('ς', "s"), ('τ', "t"),
+    ('υ', "y"), ('φ', "f"), ('χ', "ch"), ('ψ', "ps"), ('ω', "o"),
+    ('ά', "a"), ('έ', "e"), ('ή', "i"), ('ί', "i"), ('ό', "o"),
+    ('ύ', "y"), ('ώ', "o"), ('ϊ', "i"), ('ϋ', "y"), ('ΐ', "i"),
+    ('ΰ', "y"),
+];
+
+/// Full-width forms, `U+FF01..=U+FF5E`, are this far above ASCII
+const FULL_WIDTH_OFFSET: u32 = 0xFEE0;
+
+//----------------------------------------------------------------------
+// Public API
+//----------------------------------------------------------------------
+
+/// Returns the built-in transliteration table
+///
+/// It covers Latin letters that diacritics stripping can't simplify (`ß`,
+/// `æ`, `ø`, `ł`, ...), full-width forms and, with the `transliteration`
+/// feature, the Russian Cyrillic and Greek alphabets. The returned table can be extended or
+/// overridden before being set on
+/// [`FuseOptions::transliteration`](crate::FuseOptions::transliteration).
+///
+/// # Example
+///
+/// ```
+/// use fuse_rs::{Fuse, FuseOptions, default_transliteration_table};
+/// use serde_json::json;
+///
+/// let mut table = default_transliteration_table();
+/// table.insert('ñ', "ny".to_string());
+///
+/// let options = FuseOptions {
+///     transliteration: Some(table),
+///     ..Default::default()
+/// };
+///
+/// let docs = vec![json!("Straße"), json!("Espanya")];
+/// let fuse = Fuse::new(&docs, &options, None);
+///
+/// assert_eq!(fuse.search("strasse").unwrap()[0].item, json!("Straße"));
+/// assert_eq!(fuse.search("españa").unwrap()[0].item, json!("Espanya"));
+/// ```
+pub fn default_transliteration_table() -> TransliterationTable {
+    transliteration_table(&[
+        TransliterationScript::Latin,
+        TransliterationScript::FullWidth,
+        #[cfg(feature = "transliteration")]
+        TransliterationScript::Cyrillic,
+        #[cfg(feature = "transliteration")]
+        TransliterationScript::Greek,
+    ])
+}
+
+/// Returns the transliteration table for the given scripts
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "transliteration")] {
+/// use fuse_rs::{Fuse, FuseOptions, TransliterationScript, transliteration_table};
+/// use serde_json::json;
+///
+/// let options = FuseOptions {
+///     transliteration: Some(transliteration_table(&[TransliterationScript::Cyrillic])),
+///     ..Default::default()
+/// };
+///
+/// let docs = vec![json!("Москва"), json!("Αθήνα")];
+/// let fuse = Fuse::new(&docs, &options, None);
+///
+/// assert_eq!(fuse.search("moskva").unwrap()[0].item, json!("Москва"));
+/// assert!(fuse.search("athina").unwrap().is_empty());
+/// # }
+/// ```
+pub fn transliteration_table(scripts: &[TransliterationScript]) -> TransliterationTable {
+    let mut table = TransliterationTable::new();
+
+    for script in scripts {
+        match script {
+            TransliterationScript::Latin => {
+                table.extend(LATIN.iter().map(|&(c, latin)| (c, latin.to_string())));
+            },
+            #[cfg(feature = "transliteration")]
+            TransliterationScript::Cyrillic => table.extend(script_table(CYRILLIC.iter().copied())),
+            #[cfg(feature = "transliteration")]
+            TransliterationScript::Greek => table.extend(script_table(GREEK.iter().copied())),
+            TransliterationScript::FullWidth => {
+                let ascii = ('!'..='~').map(|c| (char::from_u32(c as u32 + FULL_WIDTH_OFFSET).unwrap(), c.to_string()));
+                table.extend(ascii.chain([('\u{3000}', " ".to_string())]));
+            },
+        }
+    }
+
+    table
+}
+
+/// Builds a table for another script from its lowercase letters
+///
+/// An uppercase entry with a capitalized transliteration is added for every
+/// letter, e.g. `('ж', "zh")` also adds `('Ж', "Zh")`.
+pub fn script_table<'s, I>(entries: I) -> TransliterationTable
+where
+    I: IntoIterator<Item = (char, &'s str)>,
+{
+    let mut table = TransliterationTable::new();
+
+    for (c, latin) in entries {
+        table.insert(c, latin.to_string());
+
+        let mut upper = c.to_uppercase();
+        if let (Some(u), None) = (upper.next(), upper.next())
+            && u != c
+        {
+            table.entry(u).or_insert_with(|| capitalize(latin));
+        }
+    }
+
+    table
+}
+
+/// Calls `f` with the transliteration of `c`, one character at a time.
+pub fn transliterate_char<F: FnMut(char)>(c: char, table: &TransliterationTable, mut f: F) {
+    match table.get(&c) {
+        Some(latin) => latin.chars().for_each(f),
+        None => f(c),
+    }
+}
+
+//----------------------------------------------------------------------
+// Implementation Details
+//----------------------------------------------------------------------
+
+/// Uppercases the first character of `s`.
+fn capitalize(s: &str) -> String {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+//----------------------------------------------------------------------
+// Tests
+//----------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn transliterate(text: &str, table: &TransliterationTable) -> String {
+        let mut out = String::new();
+        for c in text.chars() {
+            transliterate_char(c, table, |t| out.push(t));
+        }
+        out
+    }
+
+    #[test]
+    fn test_default_table() {
+        let table = default_transliteration_table();
+
+        assert_eq!(transliterate("Straße", &table), "Strasse");
+        assert_eq!(transliterate("Ærøskøbing", &table), "AEroskobing");
+        assert_eq!(transliterate("Łódź", &table), "Lódź");
+        assert_eq!(transliterate("ＡＢＣ－１２３\u{3000}ｘｙｚ！", &table), "ABC-123 xyz!");
+        #[cfg(feature = "transliteration")]
+        assert_eq!(transliterate("Щука и Ёж", &table), "Shchuka i Ezh");
+        #[cfg(feature = "transliteration")]
+        assert_eq!(transliterate("Αθήνα, Ψαρά", &table), "Athina, Psara");
+    }
+
+    #[test]
+    fn test_script_table() {
+        let table = script_table([('ա', "a"), ('բ', "b"), ('ղ', "gh")]);
+
+        assert_eq!(transliterate("Աբղ", &table), "Abgh");
+        assert_eq!(table.len(), 6);
+    }
+
+    #[test]
+    fn test_custom_entries() {
+        let mut table = default_transliteration_table();
+        table.insert('ß', "sz".to_string());
+        table.insert('ä', "ae".to_string());
+
+        assert_eq!(transliterate("Mäße", &table), "Maesze");
+    }
+}
@@ -7,8 +7,19 @@
 //!
 //! Fuse-rs provides fuzzy searching capability with tunable options for pattern matching,
 //! scoring, and result sorting.
+//!
+//! ## `no_std`
+//!
+//! Without the default `std` feature the crate only depends on `alloc` and
+//! provides the bitap matcher (`BitapPattern`) and a basic in-memory index
+//! of strings (`StringIndex`).
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
 
 // Internal module structure
+#[cfg(feature = "std")]
 mod helpers;
 mod core;
 mod tools;
@@ -18,26 +29,141 @@ mod search;
 // Public API Exports
 //----------------------------------------------------------------------
 
-// Main functionality
-pub use crate::core::fuse::Fuse;
-pub use crate::core::options::config::FuseOptions;
-pub use crate::core::options::keys::FuseOptionKey;
-pub use crate::core::options::sort::FuseSortFunction;
+// Bitap matching (available without `std`)
+pub use crate::search::bitmap::compute_score::DistanceCurve;
+pub use crate::search::bitmap::options::BitapOptions;
+pub use crate::search::bitmap::pattern::BitapPattern;
+pub use crate::search::bitmap::search::SearchResult;
+pub use crate::tools::string_index::{StringIndex, StringMatch};
 
 // Error types
 pub use crate::core::error_messages::FuseError;
 
+// Main functionality
+#[cfg(feature = "std")]
+pub use crate::core::fuse::{Fuse, try_search};
+#[cfg(feature = "std")]
+pub use crate::core::builder::FuseBuilder;
+#[cfg(feature = "std")]
+pub use crate::core::compat::{DEFAULT_SCORE_TOLERANCE, Divergence, Fixture, FixtureMatch, FixtureResult, load_fixtures};
+#[cfg(feature = "std")]
+pub use crate::core::query_parser::{Comparison, ParsedExpression, parse_query};
+#[cfg(feature = "std")]
+pub use crate::core::search_cursor::SearchCursor;
+#[cfg(feature = "std")]
+pub use crate::core::search_iter::SearchIter;
+#[cfg(feature = "std")]
+pub use crate::core::fuse_handle::FuseHandle;
+#[cfg(feature = "std")]
+pub use crate::core::suggest::Suggestion;
+#[cfg(feature = "std")]
+pub use crate::core::cancel::CancellationToken;
+#[cfg(feature = "std")]
+pub use crate::core::explain::{MatchExplanation, ScoreExplanation};
+#[cfg(feature = "std")]
+pub use crate::core::compute_score::{DefaultScorer, MatchScore, Scorer, default_scorer};
+#[cfg(feature = "tokio")]
+pub use crate::core::async_search::SearchStream;
+#[cfg(feature = "std")]
+pub use crate::core::observer::{SearchMetrics, SearchObserver, SearchProgress};
+#[cfg(feature = "std")]
+pub use crate::core::searchable::Searchable;
+#[cfg(feature = "std")]
+pub use crate::core::typed_fuse::TypedFuse;
+#[cfg(feature = "derive")]
+pub use fuse_rs_derive::Searchable;
+#[cfg(feature = "std")]
+pub use crate::core::options::config::FuseOptions;
+#[cfg(feature = "std")]
+pub use crate::core::options::keys::{
+    FuseOptionKey,
+    FuseOptionKeyObject,
+    FuseOptionKeyName,
+    FuseKeyFallback,
+    KeyAnalyzer,
+    KeyGetFn
+};
+#[cfg(feature = "std")]
+pub use crate::core::options::sort::{FuseSortFunction, default_sort_fn};
+#[cfg(feature = "std")]
+pub use crate::helpers::transliterate::{
+    TransliterationScript,
+    TransliterationTable,
+    default_transliteration_table,
+    script_table,
+    transliteration_table
+};
+#[cfg(feature = "std")]
+pub use crate::helpers::normalize::{Normalizer, project_indices};
+#[cfg(feature = "std")]
+pub use crate::helpers::interner::Interner;
+#[cfg(feature = "std")]
+pub use crate::helpers::phonetic::PhoneticAlgorithm;
+#[cfg(feature = "std")]
+pub use crate::helpers::stemmer::Stemmer;
+#[cfg(feature = "std")]
+pub use crate::helpers::stop_words::{StopWordLanguage, StopWords, StopWordTokenizer};
+#[cfg(feature = "std")]
+pub use crate::helpers::synonyms::{SynonymMap, SynonymProvider};
+#[cfg(feature = "std")]
+pub use crate::search::edit_distance::MatchAlgorithm;
+#[cfg(feature = "std")]
+pub use crate::search::extended::matchers::MatchKind;
+#[cfg(feature = "std")]
+pub use crate::search::extended::query::{ExtendedQuery, ExtendedToken};
+#[cfg(feature = "stemming")]
+pub use crate::helpers::stemmer::SnowballStemmer;
+#[cfg(feature = "stemming")]
+pub use rust_stemmers::Algorithm as StemmerLanguage;
+#[cfg(feature = "std")]
+pub use crate::helpers::tokenizer::{
+    FnTokenizer,
+    Token,
+    Tokenizer,
+    WhitespaceTokenizer,
+    UnicodeWordTokenizer,
+    CjkBigramTokenizer
+};
+
+// Documents and indexing
+#[cfg(feature = "std")]
+pub use crate::tools::fuse_index::{FuseIndex, create_index, parse_index};
+#[cfg(feature = "std")]
+pub use crate::tools::doc_store::{DocStore, MemoryDocStore};
+#[cfg(feature = "std")]
+pub use crate::helpers::document::Document;
+#[cfg(feature = "std")]
+pub use crate::helpers::get::{GetFn, GetFnPath, GetValue, get_document, get_strict, try_get_document};
+#[cfg(feature = "std")]
+pub use crate::helpers::path::Path;
+
 // Search results
+#[cfg(feature = "std")]
 pub use crate::core::results::search_result::{
     RangeTuple,
     FuseResultMatch,
     FuseSearchOptions,
-    FuseResult
+    FuseSearchFilter,
+    FuseResult,
+    SearchPage
 };
+#[cfg(feature = "std")]
+pub use crate::core::results::jsonl::JsonLinesWriter;
+#[cfg(feature = "std")]
+pub use crate::helpers::char_offsets::{CharOffsets, char_slice};
+#[cfg(feature = "std")]
 pub use crate::core::results::match_result::{
     FuseSortFunctionArg,
     FuseSortFunctionItem,
     FuseSortFunctionMatch,
     FuseSortFunctionMatchList, 
     FuseSortFunctionMatchType
-};
\ No newline at end of file
+};
+
+/// Items used by the code generated by `#[derive(Searchable)]`
+#[cfg(feature = "std")]
+#[doc(hidden)]
+pub mod __private {
+    pub use crate::core::searchable::{field_key, field_value};
+    pub use serde_json::{Map, Value};
+}
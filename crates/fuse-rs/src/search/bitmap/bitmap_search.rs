@@ -0,0 +1,180 @@
+//! Pattern searcher built on top of the bitap algorithm
+//!
+//! `BitmapSearch` prepares a pattern once (normalization, chunking and
+//! alphabet creation) so it can be matched against many texts.
+
+use crate::FuseError;
+use crate::FuseOptions;
+#[cfg(feature = "grapheme-clusters")]
+use crate::helpers::graphemes::GraphemeEncoder;
+use crate::helpers::interner::{NormalizedText, share};
+use crate::helpers::normalize::Normalizer;
+use crate::search::edit_distance::{self, MatchAlgorithm};
+use super::options::BitapOptions;
+use super::pattern::BitapPattern;
+use super::search::SearchResult;
+
+//----------------------------------------------------------------------
+// Types
+//----------------------------------------------------------------------
+
+/// Fuzzy searcher for a single pattern
+///
+/// Normalizes the pattern and the texts it is matched against according to
+/// the options, and matches them with a `BitapPattern`, or by edit distance
+/// with another `algorithm`.
+#[derive(Debug, Clone)]
+pub struct BitmapSearch<'o, 'a> {
+    /// The normalized pattern
+    pattern: BitapPattern,
+
+    /// Options controlling matching
+    options: &'o FuseOptions<'a>,
+
+    /// The options read by the bitap algorithm
+    bitap_options: BitapOptions,
+
+    /// Normalizes the texts searched in
+    normalizer: Normalizer<'o>,
+
+    /// Encodes the pattern and the texts by grapheme cluster, with
+    /// `use_grapheme_clusters`
+    #[cfg(feature = "grapheme-clusters")]
+    graphemes: Option<GraphemeEncoder>,
+}
+
+//----------------------------------------------------------------------
+// Implementation
+//----------------------------------------------------------------------
+
+impl<'o, 'a> BitmapSearch<'o, 'a> {
+    /// Creates a searcher for `pattern` using the given options.
+    pub fn new(pattern: &str, options: &'o FuseOptions<'a>) -> Self {
+        let normalizer = options.normalizer();
+        let interner = options.interner.as_deref();
+        let pattern = share(normalizer.normalize(pattern), interner);
+
+        #[cfg(feature = "grapheme-clusters")]
+        let (pattern, graphemes) = match options.use_grapheme_clusters {
+            true => {
+                let graphemes = GraphemeEncoder::new(&pattern);
+                (share(graphemes.encode(&pattern), interner), Some(graphemes))
+            }
+            false => (pattern, None),
+        };
+
+        Self {
+            pattern: BitapPattern::with_storage(pattern, |chunk| share(chunk, interner)),
+            options,
+            bitap_options: BitapOptions::from(options),
+            normalizer,
+            #[cfg(feature = "grapheme-clusters")]
+            graphemes,
+        }
+    }
+
+    /// Returns the normalized pattern, encoded by grapheme cluster with
+    /// `use_grapheme_clusters`.
+    pub fn pattern(&self) -> &str {
+        self.pattern.as_str()
+    }
+
+    /// Matches the pattern against `text`.
+    ///
+    /// # Returns
+    ///
+    /// The combined `SearchResult` of all chunks. The score is the average of
+    /// the chunk scores, or `1.0` if no chunk matched.
+    pub fn search_in(&self, text: &str) -> Result<SearchResult, FuseError> {
+        let text = NormalizedText::new(text, &self.normalizer, self.options.interner.as_deref());
+
+        #[cfg(feature = "grapheme-clusters")]
+        if let Some(graphemes) = &self.graphemes {
+            return self.match_text(&graphemes.encode(&text));
+        }
+
+        self.match_text(&text)
+    }
+
+    /// Matches the pattern against the normalized `text` with the algorithm
+    /// of the options.
+    fn match_text(&self, text: &str) -> Result<SearchResult, FuseError> {
+        let pattern = self.pattern.as_str();
+
+        match self.options.algorithm {
+            MatchAlgorithm::Bitap => self.pattern.search_in(text, &self.bitap_options),
+            MatchAlgorithm::Levenshtein => Ok(edit_distance::search(text, pattern, false, &self.bitap_options)),
+            MatchAlgorithm::DamerauLevenshtein => Ok(edit_distance::search(text, pattern, true, &self.bitap_options)),
+        }
+    }
+}
+
+//----------------------------------------------------------------------
+// Tests
+//----------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search::bitmap::constants::MAX_BITS;
+
+    #[test]
+    fn test_search_in_exact_match() {
+        let options = FuseOptions {
+            include_matches: true,
+            ..Default::default()
+        };
+        let searcher = BitmapSearch::new("Hello", &options);
+
+        let result = searcher.search_in("hello").unwrap();
+        assert!(result.is_match);
+        assert_eq!(result.score, 0.0);
+        assert_eq!(result.indices, vec![(0, 4)]);
+    }
+
+    #[test]
+    fn test_search_in_case_sensitive() {
+        let options = FuseOptions {
+            is_case_sensitive: true,
+            threshold: 0.0,
+            ..Default::default()
+        };
+        let searcher = BitmapSearch::new("Hello", &options);
+
+        assert!(!searcher.search_in("hello").unwrap().is_match);
+        assert!(searcher.search_in("Hello").unwrap().is_match);
+    }
+
+    #[test]
+    fn test_search_in_ignore_diacritics() {
+        let options = FuseOptions {
+            ignore_diacritics: true,
+            threshold: 0.0,
+            ..Default::default()
+        };
+        let searcher = BitmapSearch::new("cafe", &options);
+
+        assert!(searcher.search_in("Café").unwrap().is_match);
+    }
+
+    #[test]
+    fn test_search_in_long_pattern_is_chunked() {
+        let options = FuseOptions::default();
+        let pattern: String = "the quick brown fox jumps over the lazy dog ".chars().cycle().take(MAX_BITS + 11).collect();
+        let searcher = BitmapSearch::new(&pattern, &options);
+
+        assert_eq!(searcher.pattern.chunks.len(), 2);
+        assert_eq!(searcher.pattern.chunks[1].start_index, pattern.len() - MAX_BITS);
+
+        let result = searcher.search_in(&format!("{}!", pattern.to_uppercase())).unwrap();
+        assert!(result.is_match);
+    }
+
+    #[test]
+    fn test_search_in_empty_pattern() {
+        let options = FuseOptions::default();
+        let searcher = BitmapSearch::new("", &options);
+
+        assert!(!searcher.search_in("anything").unwrap().is_match);
+    }
+}
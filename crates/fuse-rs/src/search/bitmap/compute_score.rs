@@ -1,4 +1,54 @@
-use crate::core::options::config::FuseOptions;
+use super::options::BitapOptions;
+
+/// How the score of a match grows with its distance from the expected
+/// location
+///
+/// The penalty is a function of the distance divided by
+/// [`FuseOptions::distance`](crate::FuseOptions::distance), added to the
+/// share of errors in the match.
+///
+/// # Example
+///
+/// ```
+/// use fuse_rs::{BitapOptions, BitapPattern, DistanceCurve};
+///
+/// let text = format!("{}apple", "x".repeat(50));
+/// let pattern = BitapPattern::new("apple");
+///
+/// let linear = BitapOptions::default();
+/// assert!((pattern.search_in(&text, &linear).unwrap().score - 0.5).abs() < 1e-9);
+///
+/// // Half of `distance` away costs a quarter of a mismatch
+/// let quadratic = BitapOptions { distance_curve: DistanceCurve::Quadratic, ..Default::default() };
+/// assert!((pattern.search_in(&text, &quadratic).unwrap().score - 0.25).abs() < 1e-9);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum DistanceCurve {
+    /// The penalty grows with the distance, as in Fuse.js
+    #[default]
+    Linear,
+
+    /// The penalty grows with the square of the distance: matches within
+    /// `distance` are penalized less, and those further away more
+    Quadratic,
+
+    /// The distance doesn't count, though the search still starts at the
+    /// expected location
+    None,
+}
+
+impl DistanceCurve {
+    /// Returns the penalty of a match `ratio` times `distance` away.
+    fn penalty(self, ratio: f64) -> f64 {
+        match self {
+            Self::Linear => ratio,
+            Self::Quadratic => ratio * ratio,
+            Self::None => 0.0,
+        }
+    }
+}
 
 /// Computes the score for a match with a given pattern.
 ///
@@ -8,8 +58,8 @@ use crate::core::options::config::FuseOptions;
 /// * `errors` - Number of errors in the match
 /// * `current_location` - Position of the current match
 /// * `expected_location` - Position where the match was expected
-/// * `distance` - How far to look for matches (from FuseOptions)
-/// * `ignore_location` - Whether to ignore location matching (from FuseOptions)
+/// * `options` - Bitap options (`distance`, `distance_curve` and
+///   `ignore_location` are used)
 ///
 /// # Returns
 ///
@@ -19,13 +69,13 @@ pub fn compute_score(
     errors: usize,
     current_location: usize,
     expected_location: usize,
-    options: &FuseOptions,
+    options: &BitapOptions,
 ) -> f64 {
     // Calculate the score based on the error ratio
     let accuracy = errors as f64 / pattern_length as f64;
     
     // If location is ignored, just return the accuracy score
-    if options.ignore_location {
+    if options.ignore_location || options.distance_curve == DistanceCurve::None {
         return accuracy;
     }
     
@@ -38,17 +88,16 @@ pub fn compute_score(
     }
     
     // Calculate the final score as a combination of accuracy and proximity
-    accuracy + (proximity as f64 / options.distance as f64)
+    accuracy + options.distance_curve.penalty(proximity as f64 / options.distance as f64)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::core::options::config::FuseOptions;
 
     #[test]
     fn test_compute_score_with_exact_match() {
-        let options = FuseOptions {
+        let options = BitapOptions {
             distance: 100,
             ignore_location: false,
             ..Default::default()
@@ -60,7 +109,7 @@ mod tests {
     
     #[test]
     fn test_compute_score_with_errors() {
-        let options = FuseOptions {
+        let options = BitapOptions {
             distance: 100,
             ignore_location: false,
             ..Default::default()
@@ -72,7 +121,7 @@ mod tests {
     
     #[test]
     fn test_compute_score_with_location_difference() {
-        let options = FuseOptions {
+        let options = BitapOptions {
             distance: 100,
             ignore_location: false,
             ..Default::default()
@@ -84,7 +133,7 @@ mod tests {
     
     #[test]
     fn test_compute_score_with_ignore_location() {
-        let options = FuseOptions {
+        let options = BitapOptions {
             distance: 100,
             ignore_location: true,
             ..Default::default()
@@ -96,7 +145,7 @@ mod tests {
     
     #[test]
     fn test_compute_score_with_zero_distance() {
-        let options = FuseOptions {
+        let options = BitapOptions {
             distance: 0,
             ignore_location: false,
             ..Default::default()
@@ -110,4 +159,14 @@ mod tests {
         let score2 = compute_score(5, 1, 10, 10, &options);
         assert_eq!(score2, 0.2);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_compute_score_with_distance_curves() {
+        let options = BitapOptions { distance_curve: DistanceCurve::Quadratic, ..Default::default() };
+        assert_eq!(compute_score(5, 0, 60, 10, &options), 0.25);
+        assert_eq!(compute_score(5, 1, 210, 10, &options), 4.2);
+
+        let options = BitapOptions { distance_curve: DistanceCurve::None, distance: 0, ..Default::default() };
+        assert_eq!(compute_score(5, 1, 0, 10, &options), 0.2);
+    }
+}
@@ -1 +1,13 @@
-pub const MAX_BITS: usize = 32;
\ No newline at end of file
+//! Limits of the bitap matcher
+
+/// Bitmask with one bit per character of a pattern chunk
+///
+/// `u64` by default; the `wide-bitmap` feature widens it to `u128` so that
+/// longer patterns are matched in a single chunk.
+#[cfg(not(feature = "wide-bitmap"))]
+pub type Mask = u64;
+#[cfg(feature = "wide-bitmap")]
+pub type Mask = u128;
+
+/// Number of pattern characters matched in a single chunk
+pub const MAX_BITS: usize = Mask::BITS as usize;
@@ -1,5 +1,7 @@
 // filepath: /Users/yasinquraishy/RustroverProjects/fuse-rs/crates/fuse-rs/src/search/bitmap/convert_mask_to_indices.rs
 
+use alloc::vec::Vec;
+
 /// Converts a match mask to an array of index pairs.
 ///
 /// This function takes a boolean mask array where `true` represents a match at that position,
@@ -58,6 +60,7 @@ pub fn convert_mask_to_indices(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use alloc::vec;
 
     #[test]
     fn test_convert_mask_to_indices() {
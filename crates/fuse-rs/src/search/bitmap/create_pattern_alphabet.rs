@@ -1,8 +1,24 @@
+#[cfg(not(feature = "std"))]
+use alloc::collections::BTreeMap;
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+
+use super::constants::Mask;
+
+/// Bitmasks of the characters of a pattern, keyed by character
+///
+/// A `BTreeMap` without `std`, which has no hasher to seed a `HashMap` with.
+#[cfg(feature = "std")]
+pub type PatternAlphabet = HashMap<char, Mask>;
+#[cfg(not(feature = "std"))]
+pub type PatternAlphabet = BTreeMap<char, Mask>;
+
 /// Creates a pattern alphabet for bitap algorithm.
 ///
 /// This function generates a bitmap mask for each character in the pattern.
-/// Each mask is a 32-bit integer where each bit represents the presence of
-/// the character at a specific position in the pattern.
+/// Each mask is a [`Mask`] where each bit represents the presence of the
+/// character at a specific position in the pattern, which must be at most
+/// `MAX_BITS` characters long.
 ///
 /// # Arguments
 ///
@@ -10,10 +26,10 @@
 ///
 /// # Returns
 ///
-/// A HashMap where keys are characters and values are bitmasks
-pub fn create_pattern_alphabet(pattern: &str) -> std::collections::HashMap<char, u64> {
-    let mut mask = std::collections::HashMap::new();
-    let len = pattern.len();
+/// A map where keys are characters and values are bitmasks
+pub fn create_pattern_alphabet(pattern: &str) -> PatternAlphabet {
+    let mut mask = PatternAlphabet::new();
+    let len = pattern.chars().count();
     
     // Create a bit mask for each character in the pattern
     for (i, c) in pattern.chars().enumerate() {
@@ -61,4 +77,13 @@ mod tests {
         // 'o' is at position 4, so bit at position (5-4-1) = 0 should be set
         assert_eq!(alphabet.get(&'o'), Some(&(1 << 0)));
     }
+
+    #[test]
+    fn test_create_pattern_alphabet_multibyte() {
+        // Bit positions are based on characters, not bytes
+        let alphabet = create_pattern_alphabet("żółw");
+
+        assert_eq!(alphabet.get(&'ż'), Some(&(1 << 3)));
+        assert_eq!(alphabet.get(&'w'), Some(&(1 << 0)));
+    }
 }
@@ -1,5 +1,9 @@
+#[cfg(feature = "std")]
+pub(crate) mod bitmap_search;
 pub(crate) mod compute_score;
 pub(crate) mod constants;
 pub(crate) mod convert_mask_to_indices;
 pub(crate) mod create_pattern_alphabet;
-pub(crate) mod search;
\ No newline at end of file
+pub(crate) mod options;
+pub(crate) mod pattern;
+pub(crate) mod search;
@@ -0,0 +1,99 @@
+//! Options of the bitap algorithm
+//!
+//! `BitapOptions` holds the subset of `FuseOptions` that the matcher reads,
+//! so the matcher does not depend on the rest of the configuration (and can
+//! be used without `std`).
+
+#[cfg(feature = "std")]
+use crate::core::options::config::FuseOptions;
+use super::compute_score::DistanceCurve;
+
+//----------------------------------------------------------------------
+// Types
+//----------------------------------------------------------------------
+
+/// Options controlling how a pattern is matched against a text
+///
+/// The defaults are those of `FuseOptions`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BitapOptions {
+    /// Score at or below which a text is considered a match, between 0.0
+    /// (exact) and 1.0 (anything)
+    pub threshold: f64,
+
+    /// Character position where the pattern is expected to be found
+    pub location: usize,
+
+    /// How far from `location` a match may be before it scores 1.0
+    pub distance: usize,
+
+    /// How the distance from `location` adds to the score
+    pub distance_curve: DistanceCurve,
+
+    /// Whether to ignore `location` and `distance` when scoring
+    pub ignore_location: bool,
+
+    /// With `ignore_location`, whether only the text around the occurrences
+    /// of the pattern characters that a match must keep is scanned
+    pub search_window: bool,
+
+    /// Minimum length of a matched range
+    pub min_match_char_length: usize,
+
+    /// Whether to report the matched ranges
+    pub include_matches: bool,
+
+    /// Whether to keep searching after a perfect match
+    pub find_all_matches: bool,
+}
+
+//----------------------------------------------------------------------
+// Implementation
+//----------------------------------------------------------------------
+
+impl Default for BitapOptions {
+    fn default() -> Self {
+        Self {
+            threshold: 0.6,
+            location: 0,
+            distance: 100,
+            distance_curve: DistanceCurve::Linear,
+            ignore_location: false,
+            search_window: false,
+            min_match_char_length: 1,
+            include_matches: false,
+            find_all_matches: false,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<&FuseOptions<'_>> for BitapOptions {
+    fn from(options: &FuseOptions<'_>) -> Self {
+        Self {
+            threshold: options.threshold,
+            location: options.location,
+            distance: options.distance,
+            distance_curve: options.distance_curve,
+            ignore_location: options.ignore_location,
+            search_window: options.search_window,
+            min_match_char_length: options.min_match_char_length,
+            include_matches: options.include_matches,
+            find_all_matches: options.find_all_matches,
+        }
+    }
+}
+
+//----------------------------------------------------------------------
+// Tests
+//----------------------------------------------------------------------
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_defaults_match_fuse_options() {
+        assert_eq!(BitapOptions::default(), BitapOptions::from(&FuseOptions::default()));
+    }
+}
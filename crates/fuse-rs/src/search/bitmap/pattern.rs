@@ -0,0 +1,267 @@
+//! Patterns prepared for the bitap algorithm
+//!
+//! `BitapPattern` splits a pattern into bitmask-sized chunks and builds
+//! their alphabets once, so it can be matched against many texts. It does
+//! no normalization: texts and pattern are compared as given.
+
+use alloc::string::String;
+use alloc::sync::Arc;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::FuseError;
+use super::constants::MAX_BITS;
+use super::create_pattern_alphabet::{PatternAlphabet, create_pattern_alphabet};
+use super::options::BitapOptions;
+use super::search::{SearchResult, search};
+
+//----------------------------------------------------------------------
+// Types
+//----------------------------------------------------------------------
+
+/// A slice of the pattern that fits in a single bitmask
+#[derive(Debug, Clone)]
+pub(crate) struct Chunk {
+    /// The pattern slice
+    pub(crate) pattern: Arc<str>,
+
+    /// Bitmasks for every character of the slice
+    pub(crate) alphabet: PatternAlphabet,
+
+    /// Character offset of the slice within the full pattern
+    pub(crate) start_index: usize,
+}
+
+/// A pattern ready to be matched with the bitap algorithm
+///
+/// Patterns longer than `MAX_BITS` characters are split into chunks which are
+/// searched independently and combined into a single result.
+///
+/// # Example
+///
+/// ```
+/// use fuse_rs::{BitapOptions, BitapPattern};
+///
+/// let pattern = BitapPattern::new("od mn war");
+/// let result = pattern.search_in("old man's war", &BitapOptions::default()).unwrap();
+///
+/// assert!(result.is_match);
+/// ```
+#[derive(Debug, Clone)]
+pub struct BitapPattern {
+    /// The whole pattern
+    pattern: Arc<str>,
+
+    /// The pattern split into bitmask-sized chunks
+    pub(crate) chunks: Vec<Chunk>,
+}
+
+//----------------------------------------------------------------------
+// Implementation
+//----------------------------------------------------------------------
+
+impl BitapPattern {
+    /// Prepares `pattern` for matching.
+    pub fn new(pattern: &str) -> Self {
+        Self::with_storage(Arc::from(pattern), Arc::from)
+    }
+
+    /// Prepares `pattern`, storing the chunk slices with `store` (e.g. to
+    /// intern them).
+    pub(crate) fn with_storage(pattern: Arc<str>, store: impl Fn(String) -> Arc<str>) -> Self {
+        let chars: Vec<char> = pattern.chars().collect();
+        let len = chars.len();
+        let mut chunks = Vec::new();
+
+        let mut add_chunk = |slice: &[char], start_index: usize| {
+            let pattern = store(slice.iter().collect());
+            chunks.push(Chunk {
+                alphabet: create_pattern_alphabet(&pattern),
+                pattern,
+                start_index,
+            });
+        };
+
+        if len > MAX_BITS {
+            let remainder = len % MAX_BITS;
+            let end = len - remainder;
+
+            let mut i = 0;
+            while i < end {
+                add_chunk(&chars[i..i + MAX_BITS], i);
+                i += MAX_BITS;
+            }
+
+            if remainder > 0 {
+                let start_index = len - MAX_BITS;
+                add_chunk(&chars[start_index..], start_index);
+            }
+        } else if len > 0 {
+            add_chunk(&chars, 0);
+        }
+
+        Self { pattern, chunks }
+    }
+
+    /// Returns the pattern.
+    pub fn as_str(&self) -> &str {
+        &self.pattern
+    }
+
+    /// Matches the pattern against `text`.
+    ///
+    /// # Returns
+    ///
+    /// The combined `SearchResult` of all chunks, as Fuse.js combines them.
+    /// It is a match if any chunk matched, and its score is then the average
+    /// of the scores of all chunks, matched or not; otherwise it is `1.0`.
+    /// Each chunk is searched for at its offset in the pattern, so the indices
+    /// of the matched chunks are already positions in `text`.
+    pub fn search_in(&self, text: &str, options: &BitapOptions) -> Result<SearchResult, FuseError> {
+        // Exact match
+        if *self.pattern == *text {
+            let mut result = SearchResult {
+                is_match: true,
+                score: 0.0,
+                indices: Vec::new(),
+            };
+
+            if options.include_matches && !text.is_empty() {
+                result.indices = vec![(0, text.chars().count() - 1)];
+            }
+
+            return Ok(result);
+        }
+
+        // Otherwise, use Bitap algorithm
+        let mut all_indices = Vec::new();
+        let mut total_score = 0.0;
+        let mut has_matches = false;
+
+        for chunk in &self.chunks {
+            let result = search(
+                text,
+                &chunk.pattern,
+                &chunk.alphabet,
+                options.location + chunk.start_index,
+                options,
+            )?;
+
+            if result.is_match {
+                has_matches = true;
+                all_indices.extend(result.indices);
+            }
+
+            total_score += result.score;
+        }
+
+        Ok(SearchResult {
+            is_match: has_matches,
+            score: if has_matches { total_score / self.chunks.len() as f64 } else { 1.0 },
+            indices: if has_matches && options.include_matches { all_indices } else { Vec::new() },
+        })
+    }
+}
+
+//----------------------------------------------------------------------
+// Tests
+//----------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_search_in_is_case_sensitive() {
+        let pattern = BitapPattern::new("Hello");
+        let options = BitapOptions { threshold: 0.0, ..Default::default() };
+
+        assert!(!pattern.search_in("hello", &options).unwrap().is_match);
+        assert_eq!(pattern.search_in("Hello", &options).unwrap().score, 0.0);
+    }
+
+    #[test]
+    fn test_long_pattern_fits_one_chunk() {
+        // Patterns up to the width of the mask aren't split, so a single typo
+        // costs its share of the whole pattern
+        let text = "the quick brown fox jumps over the lazy dog";
+        let pattern = BitapPattern::new("the quick brown fox jumps over the lazy dig");
+        let options = BitapOptions { include_matches: true, ..Default::default() };
+
+        assert_eq!(pattern.chunks.len(), 1);
+        let result = pattern.search_in(text, &options).unwrap();
+        assert!(result.is_match);
+        assert!((result.score - 1.0 / 43.0).abs() < 1e-9);
+        assert_eq!(result.indices, vec![(0, 42)]);
+    }
+
+    #[test]
+    fn test_chunks_split_on_characters() {
+        // Two-byte characters throughout, so byte offsets would split them
+        let text: String = "żółw ".repeat(MAX_BITS / 5 + 3);
+        let pattern = BitapPattern::new(text.trim_end());
+        let len = pattern.as_str().chars().count();
+
+        assert_eq!(pattern.chunks.len(), 2);
+        assert_eq!(pattern.chunks[1].start_index, len - MAX_BITS);
+        assert!(pattern.chunks.iter().all(|chunk| chunk.pattern.chars().count() == MAX_BITS));
+
+        // Indices are character positions too
+        let options = BitapOptions { include_matches: true, ..Default::default() };
+        let result = pattern.search_in(&text, &options).unwrap();
+        assert!(result.is_match);
+        assert_eq!(result.indices, vec![(0, MAX_BITS - 1), (len - MAX_BITS, len - 1)]);
+    }
+
+    #[test]
+    fn test_chunks_combine_like_fuse_js() {
+        let text = "abcdefghijklmnopqrstuvwxyz".repeat(MAX_BITS / 26 + 2);
+        let prefix: String = text.chars().take(MAX_BITS).collect();
+        // The first chunk is in the text, the second hardly
+        let pattern = BitapPattern::new(&(prefix + &"#".repeat(MAX_BITS - 4)));
+        let options = BitapOptions { include_matches: true, ..Default::default() };
+
+        assert_eq!(pattern.chunks.len(), 2);
+        let chunks: Vec<SearchResult> = pattern
+            .chunks
+            .iter()
+            .map(|chunk| search(&text, &chunk.pattern, &chunk.alphabet, chunk.start_index, &options).unwrap())
+            .collect();
+        assert!(chunks[0].is_match && !chunks[1].is_match);
+
+        // The failed chunk still counts towards the score, but not the indices
+        let result = pattern.search_in(&text, &options).unwrap();
+        assert!(result.is_match);
+        assert_eq!(result.score, (chunks[0].score + chunks[1].score) / 2.0);
+        assert_eq!(result.score, (0.001 + 1.0) / 2.0);
+        assert_eq!(result.indices, chunks[0].indices);
+
+        // Without any matching chunk there is no score to average
+        let result = pattern.search_in(&"#".repeat(10), &options).unwrap();
+        assert_eq!(result, SearchResult { is_match: false, score: 1.0, indices: Vec::new() });
+    }
+
+    #[test]
+    fn test_chunk_indices_are_text_positions() {
+        // Each chunk reports where it was found in the text, which already
+        // accounts for its offset in the pattern
+        let filler = "0123456789".repeat(MAX_BITS / 10 + 1);
+        let pattern: String = filler.chars().take(MAX_BITS).chain("tail".chars()).collect();
+        let text = pattern.clone() + ", and then some";
+        let pattern = BitapPattern::new(&pattern);
+        let options = BitapOptions { include_matches: true, ..Default::default() };
+
+        let result = pattern.search_in(&text, &options).unwrap();
+        assert!(result.is_match);
+        assert_eq!(result.indices, vec![(0, MAX_BITS - 1), (4, MAX_BITS + 3)]);
+    }
+
+    #[test]
+    fn test_search_in_reports_indices() {
+        let pattern = BitapPattern::new("war");
+        let options = BitapOptions { include_matches: true, min_match_char_length: 2, ..Default::default() };
+
+        let result = pattern.search_in("old man's war", &options).unwrap();
+        assert_eq!(result.indices, vec![(10, 12)]);
+    }
+}
@@ -1,36 +1,880 @@
-use std::collections::HashMap;
-use crate::FuseOptions;
+//! Bitap (shift-or) approximate string matching
+//!
+//! This module contains the core fuzzy matching routine used by the bitmap
+//! searcher. It mirrors the `search` function of Fuse.js.
+
+use alloc::vec;
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use memchr::memmem;
+
 use crate::FuseError;
+use super::compute_score::compute_score;
+use super::constants::{MAX_BITS, Mask};
+use super::convert_mask_to_indices::convert_mask_to_indices;
+use super::create_pattern_alphabet::PatternAlphabet;
+use super::options::BitapOptions;
+
+//----------------------------------------------------------------------
+// Types
+//----------------------------------------------------------------------
 
+/// Outcome of matching a single pattern chunk against a text
+#[derive(Debug, Clone, PartialEq)]
 pub struct SearchResult {
     /// Whether the pattern was found in the text
     pub is_match: bool,
-    
+
     /// The match quality score (lower is better)
     pub score: f64,
-    
+
     /// List of match position ranges as (start, end) tuples
     pub indices: Vec<(usize, usize)>,
 }
 
+//----------------------------------------------------------------------
+// Public API
+//----------------------------------------------------------------------
+
+/// Runs the bitap algorithm for `pattern` over `text`.
+///
+/// # Arguments
+///
+/// * `text` - The text to search in
+/// * `pattern` - The pattern to look for (at most `MAX_BITS` characters)
+/// * `pattern_alphabet` - Bitmasks produced by `create_pattern_alphabet` for `pattern`
+/// * `location` - Character position where the pattern is expected to be found
+/// * `options` - Search options (threshold, distance, match reporting, ...)
+///
+/// # Returns
+///
+/// A `SearchResult` describing whether and how well the pattern matched, or
+/// `FuseError::PatternLengthTooLarge` if the pattern does not fit in the bitmask.
 pub fn search(
     text: &str,
     pattern: &str,
-    pattern_alphabet: &HashMap<char, u64>,
-    options: &FuseOptions,
+    pattern_alphabet: &PatternAlphabet,
+    location: usize,
+    options: &BitapOptions,
 ) -> Result<SearchResult, FuseError> {
-    // Check pattern length against maximum allowed
-    if let Some(max_pattern_length) = options.max_pattern_length {
-        if pattern.len() > max_pattern_length {
-            return Err(FuseError::PatternLengthTooLarge(max_pattern_length));
-        }
-    }
-    
-    // TODO: Implement actual bitmap-based search
-    // This is a placeholder that returns an empty result
-    Ok(SearchResult {
-        is_match: false,
-        score: 1.0,
-        indices: vec![],
-    })
-}
\ No newline at end of file
+    let pattern_len = pattern.chars().count();
+
+    if pattern_len > MAX_BITS {
+        return Err(FuseError::PatternLengthTooLarge(MAX_BITS));
+    }
+
+    if pattern_len == 0 {
+        return Ok(SearchResult { is_match: false, score: 1.0, indices: Vec::new() });
+    }
+
+    // Performance: only compute matches when the min_match_char_length > 1
+    // or if include_matches is true
+    let compute_matches = options.min_match_char_length > 1 || options.include_matches;
+
+    // When an exact occurrence beats any match with errors, the bitap loop
+    // ends after its first pass, which only has to visit the occurrences
+    // (speedup)
+    if !compute_matches && let Some(score) = exact_pass(text, pattern, pattern_len, location, options) {
+        return Ok(SearchResult { is_match: true, score: score.max(0.001), indices: Vec::new() });
+    }
+
+    let chars: Vec<char> = text.chars().collect();
+    let pattern: Vec<char> = pattern.chars().collect();
+
+    // Without a location to stay close to, the bitap loop looks for the
+    // fewest errors anywhere in the text, which a single scan can find for
+    // all error levels at once (speedup)
+    if options.ignore_location && (!compute_matches || location.min(chars.len()) == 0) {
+        let whole = 0..chars.len();
+        let anchors = options.search_window.then(|| Anchors::pick(text, &pattern, max_errors(pattern_len, options)));
+        let windows = match anchors {
+            Some(anchors) if anchors.are_sparse(pattern_len) => anchors.windows(text, pattern_len, chars.len()),
+            _ => vec![whole],
+        };
+        return Ok(bitap_anywhere(&chars, &pattern, pattern_alphabet, &windows, options));
+    }
+
+    Ok(bitap(&chars, &pattern, pattern_alphabet, location, options))
+}
+
+//----------------------------------------------------------------------
+// Implementation Details
+//----------------------------------------------------------------------
+
+/// The bitap loop proper, for a non-empty pattern of at most `MAX_BITS`
+/// characters.
+fn bitap(
+    text: &[char],
+    pattern: &[char],
+    pattern_alphabet: &PatternAlphabet,
+    location: usize,
+    options: &BitapOptions,
+) -> SearchResult {
+    let pattern_len = pattern.len();
+    let text_len = text.len();
+
+    // Set starting location at beginning text and initialize the alphabet
+    let expected_location = location.min(text_len);
+
+    // Highest score beyond which we give up
+    let mut current_threshold = options.threshold;
+
+    // Is there a nearby exact match? (speedup)
+    let mut best_location = expected_location;
+
+    let compute_matches = options.min_match_char_length > 1 || options.include_matches;
+
+    // A mask of the matches, used for building the indices
+    let mut match_mask = vec![false; if compute_matches { text_len } else { 0 }];
+
+    // Get all exact matches, here for speed up
+    while let Some(index) = index_of(text, pattern, best_location) {
+        let score = compute_score(pattern_len, 0, index, expected_location, options);
+        current_threshold = current_threshold.min(score);
+        best_location = index + pattern_len;
+
+        if compute_matches {
+            match_mask[index..index + pattern_len].fill(true);
+        }
+    }
+
+    // Reset the best location
+    let mut best_location: Option<usize> = None;
+
+    // The masks of the text's characters, looked up once for every error level
+    let mut text_masks = TextMasks::new(text, pattern_alphabet);
+
+    let mut last_bit_arr: Vec<Mask> = Vec::new();
+    let mut final_score = 1.0;
+    let mut bin_max = pattern_len + text_len;
+
+    let mask: Mask = 1 << (pattern_len - 1);
+
+    for i in 0..pattern_len {
+        // Scan for the best match; each iteration allows for one more error.
+        // Run a binary search to determine how far from the match location we can stray
+        // at this error level.
+        let bin_mid = search_radius(pattern_len, i, bin_max, expected_location, current_threshold, options);
+
+        // Use the result from this iteration as the maximum for the next.
+        bin_max = bin_mid;
+
+        let mut start = (expected_location as isize - bin_mid as isize + 1).max(1) as usize;
+        let finish = if options.find_all_matches {
+            text_len
+        } else {
+            (expected_location + bin_mid).min(text_len) + pattern_len
+        };
+
+        // Initialize the bit array
+        let mut bit_arr: Vec<Mask> = vec![0; finish + 2];
+        bit_arr[finish + 1] = (1 << i) - 1;
+
+        text_masks.fill(finish);
+
+        let mut j = finish;
+        while j >= start {
+            let current_location = j - 1;
+            let char_match = text_masks.get(current_location);
+
+            if compute_matches && current_location < text_len {
+                // Speed up: quick bool to int conversion (i.e, `char_match ? 1 : 0`)
+                match_mask[current_location] = char_match != 0;
+            }
+
+            // First pass: exact match
+            bit_arr[j] = ((bit_arr[j + 1] << 1) | 1) & char_match;
+
+            // Subsequent passes: fuzzy match
+            if i > 0 {
+                bit_arr[j] |= ((last_bit_arr[j + 1] | last_bit_arr[j]) << 1) | 1 | last_bit_arr[j + 1];
+            }
+
+            if bit_arr[j] & mask != 0 {
+                final_score = compute_score(pattern_len, i, current_location, expected_location, options);
+
+                // This match will almost certainly be better than any existing match.
+                // But check anyway.
+                if final_score <= current_threshold {
+                    // Indeed it is
+                    current_threshold = final_score;
+                    best_location = Some(current_location);
+
+                    // Already passed `loc`, downhill from here on in.
+                    if current_location <= expected_location {
+                        break;
+                    }
+
+                    // When passing `best_location`, don't exceed our current distance from `expected_location`.
+                    start = (2 * expected_location as isize - current_location as isize).max(1) as usize;
+                }
+            }
+
+            j -= 1;
+        }
+
+        // No hope for a (better) match at greater error levels.
+        let score = compute_score(pattern_len, i + 1, expected_location, expected_location, options);
+
+        if score > current_threshold {
+            break;
+        }
+
+        last_bit_arr = bit_arr;
+    }
+
+    let mut result = SearchResult {
+        is_match: best_location.is_some(),
+        // Count exact matches (those with a score of 0) to be "almost" exact
+        score: final_score.max(0.001),
+        indices: Vec::new(),
+    };
+
+    if compute_matches {
+        let indices = convert_mask_to_indices(&match_mask, options.min_match_char_length);
+        if indices.is_empty() {
+            result.is_match = false;
+        } else if options.include_matches {
+            result.indices = indices;
+        }
+    }
+
+    result
+}
+
+/// The bitap loop with `ignore_location`, for a non-empty pattern of at most
+/// `MAX_BITS` characters, and an expected location of `0` when matches are
+/// computed.
+///
+/// Scores don't depend on where a match is then, so the bitap loop scans
+/// the whole text once per error level, until a level has a match. Here a
+/// single scan advances the states of all error levels together, and stops
+/// advancing those that can no longer beat the best match found.
+///
+/// Only the characters in `windows`, sorted ranges that don't overlap, are
+/// scanned for matches; the result is that of the bitap loop as long as the
+/// windows hold every match within the threshold (see [`Anchors`]).
+fn bitap_anywhere(
+    text: &[char],
+    pattern: &[char],
+    pattern_alphabet: &PatternAlphabet,
+    windows: &[Range<usize>],
+    options: &BitapOptions,
+) -> SearchResult {
+    let pattern_len = pattern.len();
+    let mask: Mask = 1 << (pattern_len - 1);
+    let text_masks = TextMasks::new(text, pattern_alphabet);
+
+    // The error levels scoring within the threshold; the first pass, for
+    // exact matches, always runs
+    let mut levels = 1 + max_errors(pattern_len, options);
+
+    let mut best_errors = None;
+
+    for window in windows.iter().rev() {
+        if levels == 0 {
+            break;
+        }
+
+        // The bit array entry of every error level at the last position
+        // scanned, starting past the end of the window
+        let mut states: Vec<Mask> = (0..levels).map(|errors| (1 << errors) - 1).collect();
+
+        for &c in text[window.clone()].iter().rev() {
+            let char_match = text_masks.lookup(c);
+
+            // The entries of the level below, at the last and current positions
+            let (mut below_last, mut below) = (0, 0);
+
+            for (errors, entry) in states[..levels].iter_mut().enumerate() {
+                let last = *entry;
+                let mut state = ((last << 1) | 1) & char_match;
+                if errors > 0 {
+                    state |= ((below_last | below) << 1) | 1 | below_last;
+                }
+
+                (below_last, below) = (last, state);
+                *entry = state;
+
+                // The fewest errors at this position
+                if state & mask != 0 {
+                    best_errors = Some(errors);
+                    levels = errors;
+                    break;
+                }
+            }
+
+            if levels == 0 {
+                break;
+            }
+        }
+    }
+
+    let mut result = SearchResult {
+        is_match: best_errors.is_some(),
+        score: best_errors.map_or(1.0, |errors| compute_score(pattern_len, errors, 0, 0, options)).max(0.001),
+        indices: Vec::new(),
+    };
+
+    if options.min_match_char_length > 1 || options.include_matches {
+        // Every pass of the bitap loop marks the characters of the pattern,
+        // in the windows or not
+        let match_mask: Vec<bool> = text.iter().map(|&c| text_masks.lookup(c) != 0).collect();
+        let indices = convert_mask_to_indices(&match_mask, options.min_match_char_length);
+        if indices.is_empty() {
+            result.is_match = false;
+        } else if options.include_matches {
+            result.indices = indices;
+        }
+    }
+
+    result
+}
+
+/// Returns the most errors a match of a `pattern_len` characters long
+/// pattern can have with `ignore_location`, within the threshold.
+fn max_errors(pattern_len: usize, options: &BitapOptions) -> usize {
+    (1..pattern_len)
+        .take_while(|&errors| compute_score(pattern_len, errors, 0, 0, options) <= options.threshold)
+        .count()
+}
+
+/// Number of bytes at the start of a text in which the occurrences of the
+/// pattern characters are counted, to pick the anchors of the windows
+const ANCHOR_SAMPLE: usize = 4096;
+
+/// The pattern characters around whose occurrences a text is scanned
+///
+/// A match with at most `max_errors` errors keeps at least one of any
+/// `max_errors + 1` characters of the pattern, so only the text around
+/// their occurrences can hold it.
+struct Anchors {
+    /// The anchor characters, with their first and last positions in the
+    /// pattern among the anchors
+    chars: Vec<(char, usize, usize)>,
+
+    /// Most errors of a match
+    max_errors: usize,
+
+    /// Occurrences of the anchor characters in the sample of the text
+    sampled: usize,
+
+    /// Number of characters of the sample
+    sample_len: usize,
+}
+
+impl Anchors {
+    /// Picks the `max_errors + 1` characters of `pattern` occurring the
+    /// least at the start of `text`.
+    fn pick(text: &str, pattern: &[char], max_errors: usize) -> Self {
+        let mut sample_end = text.len().min(ANCHOR_SAMPLE);
+        while !text.is_char_boundary(sample_end) {
+            sample_end -= 1;
+        }
+
+        // The occurrences of every pattern character in the sample
+        let mut counts: Vec<(char, usize)> = Vec::new();
+        for &c in pattern {
+            if !counts.iter().any(|&(counted, _)| counted == c) {
+                counts.push((c, 0));
+            }
+        }
+        let mut sample_len = 0;
+        for c in text[..sample_end].chars() {
+            sample_len += 1;
+            if let Some((_, count)) = counts.iter_mut().find(|(counted, _)| *counted == c) {
+                *count += 1;
+            }
+        }
+        let count = |c: char| counts.iter().find(|&&(counted, _)| counted == c).map_or(0, |&(_, count)| count);
+
+        let mut positions: Vec<(usize, char)> = pattern.iter().copied().enumerate().collect();
+        positions.sort_by_key(|&(k, c)| (count(c), k));
+        positions.truncate(max_errors + 1);
+        positions.sort_by_key(|&(k, c)| (c, k));
+
+        let mut chars: Vec<(char, usize, usize)> = Vec::new();
+        for (k, c) in positions {
+            match chars.last_mut() {
+                Some((anchor, _, last)) if *anchor == c => *last = k,
+                _ => chars.push((c, k, k)),
+            }
+        }
+
+        let sampled = chars.iter().map(|&(c, _, _)| count(c)).sum();
+        Self { chars, max_errors, sampled, sample_len }
+    }
+
+    /// Returns `true` if windows around the anchors are estimated to cover
+    /// less than half of the text, from their occurrences in the sample.
+    /// Otherwise scanning the whole text costs less than finding them.
+    fn are_sparse(&self, pattern_len: usize) -> bool {
+        2 * self.sampled * (pattern_len + 2 * self.max_errors) < self.sample_len
+    }
+
+    /// Returns the ranges of the characters of `text` around the anchors,
+    /// sorted and merged where they overlap.
+    ///
+    /// A match keeping the character at `k` in the pattern starts at most
+    /// `k + max_errors` characters before it and ends at most
+    /// `pattern_len - k + max_errors` characters after it, so every match
+    /// is inside a single window.
+    fn windows(&self, text: &str, pattern_len: usize, text_len: usize) -> Vec<Range<usize>> {
+        // The byte offset of every occurrence of an anchor, found with memmem
+        let mut offsets: Vec<(usize, usize, usize)> = Vec::new();
+        for &(c, first, last) in &self.chars {
+            let mut needle = [0; 4];
+            let needle = c.encode_utf8(&mut needle).as_bytes();
+            offsets.extend(memmem::find_iter(text.as_bytes(), needle).map(|offset| (offset, first, last)));
+        }
+        offsets.sort_unstable();
+
+        // An occurrence of a whole character starts on a character boundary
+        let mut windows: Vec<Range<usize>> = Vec::with_capacity(offsets.len());
+        let (mut from, mut position) = (0, 0);
+        for (offset, first, last) in offsets {
+            position += text[from..offset].chars().count();
+            from = offset;
+
+            let start = position.saturating_sub(last + self.max_errors);
+            let end = (position + pattern_len + self.max_errors - first).min(text_len);
+            windows.push(start..end);
+        }
+
+        windows.sort_unstable_by_key(|window| window.start);
+        let mut merged: Vec<Range<usize>> = Vec::with_capacity(windows.len());
+        for window in windows {
+            match merged.last_mut() {
+                Some(last) if last.end >= window.start => last.end = last.end.max(window.end),
+                _ => merged.push(window),
+            }
+        }
+
+        merged
+    }
+}
+
+/// The masks of the characters of a text, looked up as far as it is scanned
+///
+/// ASCII characters, the bulk of most texts, are read from a table instead
+/// of the alphabet map, in batches the compiler can vectorize.
+struct TextMasks<'t> {
+    text: &'t [char],
+    alphabet: &'t PatternAlphabet,
+    ascii: [Mask; 128],
+    masks: Vec<Mask>,
+}
+
+impl<'t> TextMasks<'t> {
+    fn new(text: &'t [char], alphabet: &'t PatternAlphabet) -> Self {
+        let mut ascii: [Mask; 128] = [0; 128];
+        for (&c, &mask) in alphabet {
+            if c.is_ascii() {
+                ascii[c as usize] = mask;
+            }
+        }
+
+        Self { text, alphabet, ascii, masks: Vec::new() }
+    }
+
+    /// Looks up the masks of the characters before `end`.
+    fn fill(&mut self, end: usize) {
+        let end = end.min(self.text.len());
+        let Some(missing) = self.text.get(self.masks.len()..end) else {
+            return;
+        };
+
+        let (ascii, alphabet) = (&self.ascii, self.alphabet);
+        self.masks.extend(missing.iter().map(|&c| char_mask(ascii, alphabet, c)));
+    }
+
+    /// Returns the mask of `c`.
+    fn lookup(&self, c: char) -> Mask {
+        char_mask(&self.ascii, self.alphabet, c)
+    }
+
+    /// Returns the mask of the character at `index`, or `0` past the end of
+    /// the text.
+    fn get(&self, index: usize) -> Mask {
+        self.masks.get(index).copied().unwrap_or(0)
+    }
+}
+
+/// Returns the mask of `c`, from the ASCII table if it is ASCII.
+fn char_mask(ascii: &[Mask; 128], alphabet: &PatternAlphabet, c: char) -> Mask {
+    match ascii.get(c as usize) {
+        Some(&mask) => mask,
+        None => alphabet.get(&c).copied().unwrap_or(0),
+    }
+}
+
+/// Replays the first, exact pass of the bitap loop over the occurrences of
+/// `pattern` in `text`, returning the score it reports if no match with
+/// errors can do better.
+fn exact_pass(
+    text: &str,
+    pattern: &str,
+    pattern_len: usize,
+    location: usize,
+    options: &BitapOptions,
+) -> Option<f64> {
+    let text_len = text.chars().count();
+    let expected_location = location.min(text_len);
+
+    // The occurrences up to the first one at or after the expected location;
+    // the pass stops before it gets to any further ones
+    let mut occurrences = Vec::new();
+    let (mut from, mut position) = (0, 0);
+
+    // A match of valid UTF-8 starts on a character boundary
+    while let Some(offset) = memmem::find(&text.as_bytes()[from..], pattern.as_bytes()) {
+        position += text[from..from + offset].chars().count();
+        from += offset;
+        occurrences.push(position);
+
+        if position >= expected_location {
+            break;
+        }
+
+        // Occurrences may overlap, so only step over the first character
+        from += text[from..].chars().next().map_or(1, char::len_utf8);
+        position += 1;
+    }
+
+    let &last = occurrences.last()?;
+    let mut current_threshold = options.threshold;
+    if last >= expected_location {
+        current_threshold = current_threshold.min(compute_score(pattern_len, 0, last, expected_location, options));
+    }
+
+    let bin_mid = search_radius(pattern_len, 0, pattern_len + text_len, expected_location, current_threshold, options);
+    let mut start = (expected_location as isize - bin_mid as isize + 1).max(1) as usize;
+    let finish = if options.find_all_matches {
+        text_len
+    } else {
+        (expected_location + bin_mid).min(text_len) + pattern_len
+    };
+
+    let mut final_score = 1.0;
+    let mut is_match = false;
+
+    for &current_location in occurrences.iter().rev() {
+        if current_location + pattern_len > finish {
+            continue;
+        }
+        if current_location + 1 < start {
+            break;
+        }
+
+        final_score = compute_score(pattern_len, 0, current_location, expected_location, options);
+        if final_score <= current_threshold {
+            current_threshold = final_score;
+            is_match = true;
+
+            if current_location <= expected_location {
+                break;
+            }
+            start = (2 * expected_location as isize - current_location as isize).max(1) as usize;
+        }
+    }
+
+    let score = compute_score(pattern_len, 1, expected_location, expected_location, options);
+    (is_match && score > current_threshold).then_some(final_score)
+}
+
+/// Binary searches how far from `expected_location` a match with `errors`
+/// errors can be and still score within `threshold`, below `bin_max`.
+fn search_radius(
+    pattern_len: usize,
+    errors: usize,
+    mut bin_max: usize,
+    expected_location: usize,
+    threshold: f64,
+    options: &BitapOptions,
+) -> usize {
+    let mut bin_min = 0;
+    let mut bin_mid = bin_max;
+
+    while bin_min < bin_mid {
+        let score = compute_score(
+            pattern_len,
+            errors,
+            expected_location + bin_mid,
+            expected_location,
+            options,
+        );
+
+        if score <= threshold {
+            bin_min = bin_mid;
+        } else {
+            bin_max = bin_mid;
+        }
+
+        bin_mid = (bin_max - bin_min) / 2 + bin_min;
+    }
+
+    bin_mid
+}
+
+/// Finds the first occurrence of `pattern` in `text` at or after `from`.
+fn index_of(text: &[char], pattern: &[char], from: usize) -> Option<usize> {
+    if pattern.is_empty() || from + pattern.len() > text.len() {
+        return None;
+    }
+
+    text[from..]
+        .windows(pattern.len())
+        .position(|window| window == pattern)
+        .map(|pos| pos + from)
+}
+
+//----------------------------------------------------------------------
+// Tests
+//----------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search::bitmap::create_pattern_alphabet::create_pattern_alphabet;
+    use alloc::format;
+    use alloc::string::{String, ToString};
+
+    fn run(text: &str, pattern: &str, options: &BitapOptions) -> SearchResult {
+        let alphabet = create_pattern_alphabet(pattern);
+        search(text, pattern, &alphabet, options.location, options).unwrap()
+    }
+
+    #[test]
+    fn test_search_exact_match() {
+        let options = BitapOptions {
+            include_matches: true,
+            ..Default::default()
+        };
+
+        let result = run("hello world", "world", &options);
+        assert!(result.is_match);
+        assert!(result.score < 0.1);
+        assert!(result.indices.contains(&(6, 10)));
+    }
+
+    #[test]
+    fn test_search_fuzzy_match() {
+        let options = BitapOptions::default();
+
+        let result = run("old man's war", "od mn war", &options);
+        assert!(result.is_match);
+        assert!(result.score > 0.0 && result.score <= options.threshold);
+    }
+
+    #[test]
+    fn test_search_no_match() {
+        let options = BitapOptions {
+            threshold: 0.2,
+            ..Default::default()
+        };
+
+        let result = run("hello world", "xyz", &options);
+        assert!(!result.is_match);
+        assert_eq!(result.score, 1.0);
+    }
+
+    #[test]
+    fn test_text_masks() {
+        let alphabet = create_pattern_alphabet("łab");
+        let text: Vec<char> = "abłc".chars().collect();
+        let mut masks = TextMasks::new(&text, &alphabet);
+
+        masks.fill(2);
+        assert_eq!(masks.masks, vec![0b010, 0b001]);
+        masks.fill(10);
+        assert_eq!(masks.masks, vec![0b010, 0b001, 0b100, 0]);
+        assert_eq!(masks.get(10), 0);
+    }
+
+    #[test]
+    fn test_exact_pass_agrees_with_bitap() {
+        let texts = ["banana bandana", "aaaa", "the cat sat on the mat", "łódź łódka łódź", "xxxxxxxxxxcatxxxcat"];
+        let patterns = ["an", "ana", "aa", "cat", "at", "łódź", "ódk", "the", "x"];
+
+        for text in texts {
+            for pattern in patterns {
+                for location in [0, 3, 8, 40] {
+                    for (threshold, distance) in [(0.0, 100), (0.3, 10), (0.6, 100), (0.6, 0), (1.0, 3)] {
+                        for (ignore_location, find_all_matches) in [(false, false), (true, false), (false, true)] {
+                            let options = BitapOptions {
+                                location,
+                                threshold,
+                                distance,
+                                ignore_location,
+                                find_all_matches,
+                                ..Default::default()
+                            };
+                            let chars: Vec<char> = text.chars().collect();
+                            let pattern_chars: Vec<char> = pattern.chars().collect();
+                            let alphabet = create_pattern_alphabet(pattern);
+
+                            let expected = bitap(&chars, &pattern_chars, &alphabet, location, &options);
+                            assert_eq!(run(text, pattern, &options), expected, "{pattern:?} in {text:?}, {options:?}");
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_bitap_anywhere_agrees_with_bitap() {
+        let texts = ["banana bandana", "aaaa", "the cat sat on the mat", "łódź łódka łódź", "xxxxxxxxxxcatxxxcat", ""];
+        let patterns = ["an", "bnd", "aab", "cta", "hte mta", "łdóź", "ódk", "x", "zzz"];
+
+        for text in texts {
+            for pattern in patterns {
+                for threshold in [0.0, 0.3, 0.4, 0.6, 1.0] {
+                    for (include_matches, min_match_char_length) in [(false, 1), (true, 1), (true, 2), (false, 3)] {
+                        let options = BitapOptions {
+                            threshold,
+                            ignore_location: true,
+                            include_matches,
+                            min_match_char_length,
+                            ..Default::default()
+                        };
+                        let chars: Vec<char> = text.chars().collect();
+                        let pattern_chars: Vec<char> = pattern.chars().collect();
+                        let alphabet = create_pattern_alphabet(pattern);
+
+                        let expected = bitap(&chars, &pattern_chars, &alphabet, 0, &options);
+                        let whole = 0..chars.len();
+                        let actual = bitap_anywhere(&chars, &pattern_chars, &alphabet, &[whole], &options);
+                        assert_eq!(actual, expected, "{pattern:?} in {text:?}, {options:?}");
+                    }
+                }
+            }
+        }
+    }
+
+    fn windows(text: &str, pattern: &str, max_errors: usize) -> Vec<Range<usize>> {
+        let pattern: Vec<char> = pattern.chars().collect();
+        Anchors::pick(text, &pattern, max_errors).windows(text, pattern.len(), text.chars().count())
+    }
+
+    #[test]
+    fn test_anchor_windows() {
+        // With one error, a match of "lazy" keeps its 'l' or its 'a', the
+        // rarest characters
+        let text = "xxxxkazyxxxx lazy";
+        assert_eq!(windows(text, "lazy", 1), vec![3..9, 12..17]);
+        assert_eq!(windows("łódź łódka", "ód", 0), vec![1..3, 6..8]);
+        assert!(windows(text, "qr", 1).is_empty());
+        // Every character is kept by a match with fewer errors than characters
+        assert_eq!(windows("ab", "ab", 1), vec![0..2]);
+
+        // Windows are only used when they leave most of the text out
+        let lazy: Vec<char> = "lazy".chars().collect();
+        assert!(!Anchors::pick(text, &lazy, 1).are_sparse(4));
+        assert!(Anchors::pick(&(text.to_string() + &"x".repeat(100)), &lazy, 1).are_sparse(4));
+    }
+
+    #[test]
+    fn test_search_window_agrees_with_whole_text() {
+        // A match around a window edge: its first character is substituted,
+        // so it starts before the characters it keeps
+        let straddling = "x".repeat(100) + "kazy dgo" + &"q".repeat(100) + "ldo";
+        let mut cases = vec![(straddling.clone(), "lazy dog".to_string())];
+
+        fn next(state: &mut u32, bound: usize) -> usize {
+            *state = state.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+            (*state >> 16) as usize % bound
+        }
+        let alphabet: Vec<char> = "abcdeł ".chars().collect();
+        let random = |state: &mut u32, len: usize| -> String {
+            (0..len).map(|_| alphabet[next(state, alphabet.len())]).collect()
+        };
+
+        let mut state = 7;
+        for _ in 0..600 {
+            let len = 1 + next(&mut state, 8);
+            let pattern = random(&mut state, len);
+
+            // Half of the texts hold the pattern with random edits
+            let len = next(&mut state, 40);
+            let mut text = random(&mut state, len);
+            if next(&mut state, 2) == 0 {
+                for c in pattern.chars() {
+                    match next(&mut state, 6) {
+                        0 => {
+                            let len = 1 + next(&mut state, 2);
+                            text.push_str(&random(&mut state, len));
+                        }
+                        1 => continue,
+                        2 => text.push_str(&random(&mut state, 1)),
+                        _ => {}
+                    }
+                    text.push(c);
+                }
+                let len = next(&mut state, 20);
+                text.push_str(&random(&mut state, len));
+            }
+            cases.push((text, pattern));
+        }
+
+        for (text, pattern) in &cases {
+            let chars: Vec<char> = text.chars().collect();
+            let pattern_chars: Vec<char> = pattern.chars().collect();
+            let alphabet = create_pattern_alphabet(pattern);
+
+            for threshold in [0.0, 0.2, 0.4, 0.6, 1.0] {
+                for (include_matches, min_match_char_length) in [(false, 1), (true, 1), (false, 2)] {
+                    let options = BitapOptions {
+                        threshold,
+                        ignore_location: true,
+                        include_matches,
+                        min_match_char_length,
+                        ..Default::default()
+                    };
+                    let context = format!("{pattern:?} in {text:?}, {options:?}");
+
+                    let max_errors = max_errors(pattern_chars.len(), &options);
+                    let windows = windows(text, pattern, max_errors);
+                    let whole = 0..chars.len();
+                    let whole = bitap_anywhere(&chars, &pattern_chars, &alphabet, &[whole], &options);
+                    let windowed = bitap_anywhere(&chars, &pattern_chars, &alphabet, &windows, &options);
+                    assert_eq!(windowed, whole, "{context}");
+
+                    let searched = run(text, pattern, &BitapOptions { search_window: true, ..options });
+                    assert_eq!(searched, run(text, pattern, &options), "{context}");
+                }
+            }
+        }
+
+        let lazy_dog: Vec<char> = "lazy dog".chars().collect();
+        assert!(Anchors::pick(&straddling, &lazy_dog, 4).are_sparse(lazy_dog.len()));
+        assert!(run(&straddling, "lazy dog", &BitapOptions { ignore_location: true, search_window: true, ..Default::default() }).is_match);
+    }
+
+    #[test]
+    fn test_exact_pass() {
+        let options = BitapOptions::default();
+
+        assert_eq!(exact_pass("aaaa", "aa", 2, 2, &options), Some(0.0));
+        assert_eq!(exact_pass("łaa łaa", "aa", 2, 5, &options), Some(0.0));
+        assert_eq!(exact_pass("xx cat cat", "cat", 3, 6, &options), Some(0.01));
+        // Like Fuse.js, the score is that of the last occurrence visited,
+        // one past the distance of the closest
+        assert_eq!(exact_pass("the cat sat on the mat", "the", 3, 8, &options), Some(0.08));
+        // A match with an error right at the location could score better
+        assert_eq!(exact_pass(&("x".repeat(40) + " cat"), "cat", 3, 0, &options), None);
+        assert_eq!(exact_pass("hello", "xyz", 3, 0, &options), None);
+    }
+
+    #[test]
+    fn test_search_pattern_too_long() {
+        let options = BitapOptions::default();
+        let pattern = "a".repeat(MAX_BITS + 1);
+        // The alphabet of the whole pattern wouldn't fit in a mask either
+        let alphabet = create_pattern_alphabet("a");
+
+        let result = search("aaa", &pattern, &alphabet, 0, &options);
+        assert!(matches!(result, Err(FuseError::PatternLengthTooLarge(MAX_BITS))));
+    }
+}
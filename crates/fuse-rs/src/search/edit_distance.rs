@@ -0,0 +1,256 @@
+//! Edit-distance matching
+//!
+//! An alternative to the bitap matcher: the pattern is aligned against the
+//! substring of the text it is closest to, counting Levenshtein edits and,
+//! optionally, transpositions of adjacent characters. Scores combine the
+//! edits with the distance from the expected location exactly like bitap
+//! scores, so thresholds mean the same with either algorithm.
+
+use serde::{Deserialize, Serialize};
+
+use super::bitmap::compute_score::compute_score;
+use super::bitmap::options::BitapOptions;
+use super::bitmap::search::SearchResult;
+
+//----------------------------------------------------------------------
+// Types
+//----------------------------------------------------------------------
+
+/// Algorithm used to match patterns fuzzily
+///
+/// # Example
+///
+/// ```
+/// use fuse_rs::{Fuse, FuseOptions, FuseSearchOptions, MatchAlgorithm};
+/// use serde_json::json;
+///
+/// let options = FuseOptions {
+///     algorithm: MatchAlgorithm::DamerauLevenshtein,
+///     include_score: true,
+///     ..Default::default()
+/// };
+/// let fuse = Fuse::new(&[json!("frist")], &options, None);
+///
+/// // A transposition is a single edit
+/// let results = fuse.search_results("first", &FuseSearchOptions::default()).unwrap();
+/// assert!((results[0].score.unwrap() - 0.2).abs() < 1e-9);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum MatchAlgorithm {
+    /// Bitap (shift-or) matching, as in Fuse.js
+    #[default]
+    Bitap,
+
+    /// Levenshtein distance: insertions, deletions and substitutions
+    Levenshtein,
+
+    /// Damerau-Levenshtein distance (optimal string alignment): Levenshtein
+    /// edits and transpositions of adjacent characters
+    DamerauLevenshtein,
+}
+
+/// A cell of the alignment matrix
+#[derive(Debug, Clone, Copy)]
+struct Cell {
+    /// Edits needed to align the pattern so far
+    errors: usize,
+
+    /// Text position the alignment starts at
+    start: usize,
+}
+
+//----------------------------------------------------------------------
+// Public API
+//----------------------------------------------------------------------
+
+/// Finds the approximate occurrence of `pattern` in `text` that scores best.
+///
+/// Every substring of `text` is aligned with `pattern`, and the edits of an
+/// alignment are scored with `compute_score` at the position it starts.
+///
+/// # Arguments
+///
+/// * `text` - The text to search in
+/// * `pattern` - The pattern to look for
+/// * `transpositions` - Whether swapping adjacent characters is one edit
+/// * `options` - Search options (threshold, location, match reporting, ...)
+///
+/// # Returns
+///
+/// A `SearchResult` for the best occurrence within the threshold. With
+/// `find_all_matches`, the indices cover every occurrence within the
+/// threshold that doesn't overlap a better one.
+pub fn search(text: &str, pattern: &str, transpositions: bool, options: &BitapOptions) -> SearchResult {
+    let no_match = SearchResult { is_match: false, score: 1.0, indices: Vec::new() };
+
+    if pattern.is_empty() {
+        return no_match;
+    }
+
+    let text: Vec<char> = text.chars().collect();
+    let pattern: Vec<char> = pattern.chars().collect();
+    let expected_location = options.location.min(text.len());
+
+    // Occurrences within the threshold, as (score, start, end)
+    let mut occurrences = Vec::new();
+    for (end, cell) in alignments(&text, &pattern, transpositions) {
+        if end - cell.start < options.min_match_char_length.max(1) {
+            continue;
+        }
+
+        let score = compute_score(pattern.len(), cell.errors, cell.start, expected_location, options);
+        if score <= options.threshold {
+            occurrences.push((score, cell.start, end));
+        }
+    }
+
+    // The best first, then the leftmost and longest of equally good ones
+    occurrences.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.cmp(&b.1)).then(b.2.cmp(&a.2)));
+    let Some(&(score, start, end)) = occurrences.first() else {
+        return no_match;
+    };
+
+    let mut indices = Vec::new();
+    if options.include_matches {
+        indices.push((start, end - 1));
+
+        if options.find_all_matches {
+            for &(_, start, end) in &occurrences[1..] {
+                if indices.iter().all(|&(s, e)| end <= s || start > e) {
+                    indices.push((start, end - 1));
+                }
+            }
+            indices.sort_unstable();
+        }
+    }
+
+    SearchResult {
+        is_match: true,
+        // Count exact matches (those with a score of 0) to be "almost" exact,
+        // unless the text is the pattern
+        score: if text == pattern { 0.0 } else { score.max(0.001) },
+        indices,
+    }
+}
+
+//----------------------------------------------------------------------
+// Implementation Details
+//----------------------------------------------------------------------
+
+/// Returns, for every end position in `text`, the cheapest alignment of
+/// `pattern` with a substring of `text` ending there.
+///
+/// This is the edit-distance matrix of Sellers' algorithm, where an
+/// alignment may start anywhere in the text for free, computed one text
+/// position at a time. Of equally cheap alignments, the one matching or
+/// substituting the last character is kept.
+fn alignments(text: &[char], pattern: &[char], transpositions: bool) -> Vec<(usize, Cell)> {
+    let m = pattern.len();
+
+    // Columns for the previous two text positions and the current one
+    let mut before: Vec<Cell> = Vec::new();
+    let mut previous: Vec<Cell> = (0..=m).map(|i| Cell { errors: i, start: 0 }).collect();
+    let mut current = previous.clone();
+
+    let mut ends = Vec::with_capacity(text.len() + 1);
+    ends.push((0, previous[m]));
+
+    for j in 1..=text.len() {
+        current[0] = Cell { errors: 0, start: j };
+
+        for i in 1..=m {
+            let cost = usize::from(pattern[i - 1] != text[j - 1]);
+            let mut best = Cell { errors: previous[i - 1].errors + cost, ..previous[i - 1] };
+
+            if transpositions
+                && i > 1
+                && j > 1
+                && pattern[i - 1] == text[j - 2]
+                && pattern[i - 2] == text[j - 1]
+                && before[i - 2].errors + 1 < best.errors
+            {
+                best = Cell { errors: before[i - 2].errors + 1, ..before[i - 2] };
+            }
+
+            // A character of the text left out of the pattern, or one of
+            // the pattern missing from the text
+            for cell in [previous[i], current[i - 1]] {
+                if cell.errors + 1 < best.errors {
+                    best = Cell { errors: cell.errors + 1, ..cell };
+                }
+            }
+
+            current[i] = best;
+        }
+
+        ends.push((j, current[m]));
+        before = core::mem::replace(&mut previous, current.clone());
+    }
+
+    ends
+}
+
+//----------------------------------------------------------------------
+// Tests
+//----------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn distance(text: &str, pattern: &str, transpositions: bool) -> usize {
+        let text: Vec<char> = text.chars().collect();
+        let pattern: Vec<char> = pattern.chars().collect();
+        alignments(&text, &pattern, transpositions).iter().map(|(_, cell)| cell.errors).min().unwrap()
+    }
+
+    #[test]
+    fn test_substring_distance() {
+        assert_eq!(distance("old man's war", "war", false), 0);
+        assert_eq!(distance("old man's war", "wr", false), 1);
+        assert_eq!(distance("old man's war", "wart", false), 1);
+        assert_eq!(distance("old man's war", "wbr", false), 1);
+        assert_eq!(distance("kitten", "sitting", false), 3);
+
+        // A transposition is two Levenshtein edits, or one Damerau edit
+        assert_eq!(distance("one frist", "first", false), 2);
+        assert_eq!(distance("one frist", "first", true), 1);
+        assert_eq!(distance("łódź", "łdóź", true), 1);
+    }
+
+    #[test]
+    fn test_search_scores_edits_and_location() {
+        let options = BitapOptions { include_matches: true, ..Default::default() };
+
+        let result = search("old man's war", "wrr", false, &options);
+        assert!(result.is_match);
+        assert!((result.score - (1.0 / 3.0 + 0.1)).abs() < 1e-9);
+        assert_eq!(result.indices, vec![(10, 12)]);
+
+        let result = search("war", "war", false, &options);
+        assert_eq!((result.is_match, result.score), (true, 0.0));
+
+        let options = BitapOptions { ignore_location: true, ..options };
+        assert_eq!(search("old man's war", "war", false, &options).score, 0.001);
+        assert!(!search("old man's war", "xyz", false, &options).is_match);
+    }
+
+    #[test]
+    fn test_search_finds_all_matches() {
+        let options = BitapOptions {
+            include_matches: true,
+            find_all_matches: true,
+            ignore_location: true,
+            threshold: 0.4,
+            ..Default::default()
+        };
+
+        let result = search("cat, hat and cart", "cat", false, &options);
+        assert_eq!(result.indices, vec![(0, 2), (5, 7), (13, 16)]);
+
+        // Spans shorter than `min_match_char_length` don't count
+        let options = BitapOptions { min_match_char_length: 4, ..options };
+        assert_eq!(search("cat, hat and cart", "cat", false, &options).indices, vec![(0, 3), (13, 16)]);
+    }
+}
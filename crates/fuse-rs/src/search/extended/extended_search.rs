@@ -0,0 +1,168 @@
+//! Extended search queries
+//!
+//! An extended query is a list of alternatives separated by `|`; each
+//! alternative is a list of whitespace-separated tokens that must all match.
+//! See [`matchers`](super::matchers) for the token operators.
+
+use crate::FuseError;
+use crate::FuseOptions;
+use crate::helpers::interner::NormalizedText;
+use crate::helpers::normalize::Normalizer;
+use crate::search::bitmap::search::SearchResult;
+use super::matchers::Matcher;
+
+//----------------------------------------------------------------------
+// Types
+//----------------------------------------------------------------------
+
+/// Searcher for an extended query
+#[derive(Debug, Clone)]
+pub struct ExtendedSearch<'o, 'a> {
+    /// Options controlling matching
+    options: &'o FuseOptions<'a>,
+
+    /// Normalizes the texts searched in
+    normalizer: Normalizer<'o>,
+
+    /// Alternatives (`|`), each a list of tokens that must all match
+    query: Vec<Vec<Matcher<'o, 'a>>>,
+}
+
+//----------------------------------------------------------------------
+// Implementation
+//----------------------------------------------------------------------
+
+impl<'o, 'a> ExtendedSearch<'o, 'a> {
+    /// Parses `pattern` into a searcher using the given options.
+    pub fn new(pattern: &str, options: &'o FuseOptions<'a>) -> Self {
+        let query = pattern
+            .split('|')
+            .map(|alternative| {
+                split_tokens(alternative.trim())
+                    .into_iter()
+                    .filter_map(|token| Matcher::parse(token, options))
+                    .collect::<Vec<_>>()
+            })
+            .filter(|tokens| !tokens.is_empty())
+            .collect();
+
+        Self { options, normalizer: options.normalizer(), query }
+    }
+
+    /// Matches the query against `text`.
+    ///
+    /// # Returns
+    ///
+    /// The result of the first alternative whose tokens all match. Its score
+    /// is the average of the token scores.
+    pub fn search_in(&self, text: &str) -> Result<SearchResult, FuseError> {
+        // Case alone is ignored by the matchers, without copying the text
+        let normalized;
+        let text = if self.normalizer.is_case_folding_only() {
+            text
+        } else {
+            normalized = NormalizedText::new(text, &self.normalizer, self.options.interner.as_deref());
+            &*normalized
+        };
+
+        'alternatives: for tokens in &self.query {
+            let mut total_score = 0.0;
+            let mut indices = Vec::new();
+
+            for matcher in tokens {
+                let result = matcher.search(text)?;
+                if !result.is_match {
+                    continue 'alternatives;
+                }
+
+                total_score += result.score;
+                if self.options.include_matches && !matcher.is_inverse() {
+                    indices.extend(result.indices);
+                }
+            }
+
+            return Ok(SearchResult {
+                is_match: true,
+                score: total_score / tokens.len() as f64,
+                indices,
+            });
+        }
+
+        Ok(SearchResult { is_match: false, score: 1.0, indices: Vec::new() })
+    }
+}
+
+//----------------------------------------------------------------------
+// Implementation Details
+//----------------------------------------------------------------------
+
+/// Splits an alternative on spaces that are not inside double quotes.
+fn split_tokens(alternative: &str) -> Vec<&str> {
+    let mut tokens = Vec::new();
+    let mut start = 0;
+    let mut quoted = false;
+
+    for (i, c) in alternative.char_indices() {
+        match c {
+            '"' => quoted = !quoted,
+            ' ' if !quoted => {
+                tokens.push(&alternative[start..i]);
+                start = i + 1;
+            },
+            _ => {},
+        }
+    }
+    tokens.push(&alternative[start..]);
+
+    tokens.into_iter().filter(|t| !t.is_empty()).collect()
+}
+
+//----------------------------------------------------------------------
+// Tests
+//----------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_tokens() {
+        assert_eq!(split_tokens("^core  go$"), vec!["^core", "go$"]);
+        assert_eq!(split_tokens("=\"old man\" 'war"), vec!["=\"old man\"", "'war"]);
+    }
+
+    #[test]
+    fn test_and_or() {
+        let options = FuseOptions::default();
+        let search = ExtendedSearch::new("^core go$ | rb$ | ~~smith", &options);
+
+        assert!(search.search_in("core.go").unwrap().is_match);
+        assert!(!search.search_in("core.rs").unwrap().is_match);
+        assert!(search.search_in("app.rb").unwrap().is_match);
+        assert!(search.search_in("Smyth").unwrap().is_match);
+    }
+
+    #[test]
+    fn test_score_and_indices() {
+        let options = FuseOptions {
+            include_matches: true,
+            ..Default::default()
+        };
+        let search = ExtendedSearch::new("'man !boy", &options);
+
+        let result = search.search_in("Old Man's War").unwrap();
+        assert!(result.is_match);
+        assert_eq!(result.score, 0.0);
+        assert_eq!(result.indices, vec![(4, 6)]);
+    }
+
+    #[test]
+    fn test_fuzzy_token() {
+        let options = FuseOptions::default();
+        let search = ExtendedSearch::new("^old wor", &options);
+
+        let result = search.search_in("Old Man's War").unwrap();
+        assert!(result.is_match);
+        assert!(result.score > 0.0);
+    }
+}
@@ -0,0 +1,393 @@
+//! Single-token matchers of the extended search syntax
+//!
+//! Every token of an extended query is turned into one `Matcher`. The
+//! operators mirror Fuse.js, with the addition of `~~` for phonetic matches:
+//!
+//! | Token         | Matches values that                      |
+//! |---------------|------------------------------------------|
+//! | `jscript`     | fuzzy match `jscript`                    |
+//! | `=scheme`     | are exactly `scheme`                     |
+//! | `'python`     | include `python`                         |
+//! | `!ruby`       | do not include `ruby`                    |
+//! | `^java`       | start with `java`                        |
+//! | `!^earlang`   | do not start with `earlang`              |
+//! | `.js$`        | end with `.js`                           |
+//! | `!.go$`       | do not end with `.go`                    |
+//! | `~~smith`     | contain a word that sounds like `smith`  |
+
+use crate::FuseError;
+use crate::FuseOptions;
+use crate::helpers::caseless::{
+    caseless_contains, caseless_ends_with, caseless_eq, caseless_match_indices,
+    caseless_starts_with, folded_len,
+};
+use crate::helpers::char_offsets::{CharOffsets, char_len};
+use crate::helpers::phonetic::PhoneticAlgorithm;
+use crate::search::bitmap::bitmap_search::BitmapSearch;
+use crate::search::bitmap::search::SearchResult;
+
+//----------------------------------------------------------------------
+// Types
+//----------------------------------------------------------------------
+
+/// Kind of comparison performed by a matcher
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum MatchKind {
+    /// `=scheme`
+    Exact,
+    /// `!ruby`
+    InverseExact,
+    /// `'python`
+    Include,
+    /// `^java`
+    PrefixExact,
+    /// `!^earlang`
+    InversePrefixExact,
+    /// `.js$`
+    SuffixExact,
+    /// `!.go$`
+    InverseSuffixExact,
+    /// `~~smith`
+    Phonetic,
+    /// `jscript`
+    Fuzzy,
+}
+
+/// A single query token ready to be matched against normalized text
+#[derive(Debug, Clone)]
+pub struct Matcher<'o, 'a> {
+    /// The comparison performed
+    pub kind: MatchKind,
+
+    /// The normalized token, without its operator
+    pattern: String,
+
+    /// Phonetic code of the pattern (`Phonetic` only)
+    code: String,
+
+    /// Algorithm used for phonetic codes
+    algorithm: PhoneticAlgorithm,
+
+    /// Whether texts are compared ignoring case instead of being normalized
+    caseless: bool,
+
+    /// Shortest matched span that counts as a match
+    min_match_char_length: usize,
+
+    /// Bitap searcher (`Fuzzy` only)
+    fuzzy: Option<BitmapSearch<'o, 'a>>,
+}
+
+//----------------------------------------------------------------------
+// Implementation
+//----------------------------------------------------------------------
+
+impl<'o, 'a> Matcher<'o, 'a> {
+    /// Parses a single query token.
+    ///
+    /// Returns `None` for tokens that consist of an operator only (e.g. `'`
+    /// or `!^`), which are ignored like in Fuse.js, and for tokens whose
+    /// pattern normalizes to nothing (e.g. only combining marks).
+    pub fn parse(token: &str, options: &'o FuseOptions<'a>) -> Option<Self> {
+        let (kind, pattern) = parse_operator(token);
+        let pattern = unquote(pattern);
+        let normalizer = options.normalizer();
+        let normalized = normalizer.normalize(pattern);
+
+        if normalized.is_empty() {
+            return None;
+        }
+
+        let algorithm = options.phonetic_algorithm;
+
+        Some(Self {
+            kind,
+            code: if kind == MatchKind::Phonetic { algorithm.encode(pattern) } else { String::new() },
+            algorithm,
+            caseless: !options.is_case_sensitive && normalizer.is_case_folding_only(),
+            min_match_char_length: options.min_match_char_length.max(1),
+            fuzzy: (kind == MatchKind::Fuzzy).then(|| BitmapSearch::new(pattern, options)),
+            pattern: normalized,
+        })
+    }
+
+    /// Returns `true` if the matcher matches when the pattern is absent.
+    pub fn is_inverse(&self) -> bool {
+        matches!(
+            self.kind,
+            MatchKind::InverseExact | MatchKind::InversePrefixExact | MatchKind::InverseSuffixExact
+        )
+    }
+
+    /// Matches the token against `text`.
+    ///
+    /// `text` must be normalized, unless the options only fold case (see
+    /// [`Normalizer::is_case_folding_only`](crate::Normalizer::is_case_folding_only));
+    /// case is then ignored while comparing.
+    ///
+    /// Like the fuzzy matcher, the other matchers that look for the pattern
+    /// only count occurrences at least `min_match_char_length` long. As in
+    /// Fuse.js, they report every such occurrence whether or not
+    /// `find_all_matches` is set. Inverse matchers match the whole text and
+    /// ignore `min_match_char_length`.
+    pub fn search(&self, text: &str) -> Result<SearchResult, FuseError> {
+        if let (MatchKind::Fuzzy, Some(fuzzy)) = (self.kind, &self.fuzzy) {
+            return fuzzy.search_in(text);
+        }
+
+        let pattern = self.pattern.as_str();
+        let (len, pattern_len) = if self.caseless {
+            (folded_len(text), folded_len(pattern))
+        } else {
+            (char_len(text), char_len(pattern))
+        };
+        let whole = || if len > 0 { vec![(0, len - 1)] } else { Vec::new() };
+
+        let (is_match, indices) = match self.kind {
+            MatchKind::Fuzzy => (false, Vec::new()),
+            MatchKind::Exact => (self.equals(text), whole()),
+            MatchKind::InverseExact => (!self.contains(text), whole()),
+            MatchKind::Include => {
+                let indices = if self.caseless {
+                    caseless_match_indices(text, pattern)
+                } else {
+                    include_indices(text, pattern)
+                };
+                (!indices.is_empty(), indices)
+            },
+            MatchKind::PrefixExact => (self.starts_with(text), vec![(0, pattern_len - 1)]),
+            MatchKind::InversePrefixExact => (!self.starts_with(text), whole()),
+            MatchKind::SuffixExact => {
+                let start = len.saturating_sub(pattern_len);
+                (self.ends_with(text), vec![(start, len.saturating_sub(1))])
+            },
+            MatchKind::InverseSuffixExact => (!self.ends_with(text), whole()),
+            MatchKind::Phonetic => {
+                let indices = self.phonetic_indices(text);
+                (!indices.is_empty(), indices)
+            },
+        };
+
+        let (is_match, indices) = match self.is_inverse() {
+            true => (is_match, indices),
+            false => self.accept(is_match, indices),
+        };
+
+        Ok(if is_match {
+            SearchResult { is_match, score: 0.0, indices }
+        } else {
+            no_match()
+        })
+    }
+
+    /// Keeps the occurrences long enough to count, and matches only if some
+    /// are left.
+    fn accept(&self, is_match: bool, mut indices: Vec<(usize, usize)>) -> (bool, Vec<(usize, usize)>) {
+        if !is_match {
+            return (false, Vec::new());
+        }
+
+        indices.retain(|&(start, end)| end + 1 - start >= self.min_match_char_length);
+        (!indices.is_empty(), indices)
+    }
+
+    fn equals(&self, text: &str) -> bool {
+        if self.caseless { caseless_eq(text, &self.pattern) } else { text == self.pattern }
+    }
+
+    fn contains(&self, text: &str) -> bool {
+        if self.caseless { caseless_contains(text, &self.pattern) } else { text.contains(&self.pattern) }
+    }
+
+    fn starts_with(&self, text: &str) -> bool {
+        if self.caseless { caseless_starts_with(text, &self.pattern) } else { text.starts_with(&self.pattern) }
+    }
+
+    fn ends_with(&self, text: &str) -> bool {
+        if self.caseless { caseless_ends_with(text, &self.pattern) } else { text.ends_with(&self.pattern) }
+    }
+
+    /// Returns the character ranges of the words of `text` whose phonetic
+    /// code equals the pattern's.
+    fn phonetic_indices(&self, text: &str) -> Vec<(usize, usize)> {
+        if self.code.is_empty() {
+            return Vec::new();
+        }
+
+        let offsets = CharOffsets::new(text);
+
+        text.split(|c: char| !c.is_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .filter(|word| self.algorithm.encode(word) == self.code)
+            .filter_map(|word| {
+                let byte = word.as_ptr() as usize - text.as_ptr() as usize;
+                let start = offsets.char_index(byte)?;
+                Some((start, start + char_len(word) - 1))
+            })
+            .collect()
+    }
+}
+
+//----------------------------------------------------------------------
+// Implementation Details
+//----------------------------------------------------------------------
+
+/// Splits a token into its match kind and pattern.
+fn parse_operator(token: &str) -> (MatchKind, &str) {
+    if let Some(rest) = token.strip_prefix("~~") {
+        (MatchKind::Phonetic, rest)
+    } else if let Some(rest) = token.strip_prefix('=') {
+        (MatchKind::Exact, rest)
+    } else if let Some(rest) = token.strip_prefix('\'') {
+        (MatchKind::Include, rest)
+    } else if let Some(rest) = token.strip_prefix("!^") {
+        (MatchKind::InversePrefixExact, rest)
+    } else if let Some(rest) = token.strip_prefix('!') {
+        match rest.strip_suffix('$') {
+            Some(rest) => (MatchKind::InverseSuffixExact, rest),
+            None => (MatchKind::InverseExact, rest),
+        }
+    } else if let Some(rest) = token.strip_prefix('^') {
+        (MatchKind::PrefixExact, rest)
+    } else if let Some(rest) = token.strip_suffix('$') {
+        (MatchKind::SuffixExact, rest)
+    } else {
+        (MatchKind::Fuzzy, token)
+    }
+}
+
+/// Removes the double quotes around a quoted pattern (`="foo bar"`).
+fn unquote(pattern: &str) -> &str {
+    pattern
+        .strip_prefix('"')
+        .and_then(|p| p.strip_suffix('"'))
+        .unwrap_or(pattern)
+}
+
+/// Returns the character ranges of all occurrences of `pattern` in `text`.
+fn include_indices(text: &str, pattern: &str) -> Vec<(usize, usize)> {
+    let offsets = CharOffsets::new(text);
+    let pattern_len = char_len(pattern);
+
+    text.match_indices(pattern)
+        .filter_map(|(byte, _)| offsets.char_index(byte))
+        .map(|start| (start, start + pattern_len - 1))
+        .collect()
+}
+
+fn no_match() -> SearchResult {
+    SearchResult { is_match: false, score: 1.0, indices: Vec::new() }
+}
+
+//----------------------------------------------------------------------
+// Tests
+//----------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matches(token: &str, text: &str) -> bool {
+        let options = FuseOptions::default();
+        let matcher = Matcher::parse(token, &options).unwrap();
+        matcher.search(text).unwrap().is_match
+    }
+
+    #[test]
+    fn test_parse_operators() {
+        let options = FuseOptions::default();
+        let kind = |token: &str| Matcher::parse(token, &options).map(|m| m.kind);
+
+        assert_eq!(kind("=scheme"), Some(MatchKind::Exact));
+        assert_eq!(kind("'python"), Some(MatchKind::Include));
+        assert_eq!(kind("!ruby"), Some(MatchKind::InverseExact));
+        assert_eq!(kind("^java"), Some(MatchKind::PrefixExact));
+        assert_eq!(kind("!^erlang"), Some(MatchKind::InversePrefixExact));
+        assert_eq!(kind(".js$"), Some(MatchKind::SuffixExact));
+        assert_eq!(kind("!.go$"), Some(MatchKind::InverseSuffixExact));
+        assert_eq!(kind("~~smith"), Some(MatchKind::Phonetic));
+        assert_eq!(kind("jscript"), Some(MatchKind::Fuzzy));
+        assert_eq!(kind("!^"), None);
+    }
+
+    #[test]
+    fn test_exact_matchers() {
+        assert!(matches("=scheme", "Scheme"));
+        assert!(!matches("=scheme", "schemes"));
+        assert!(matches("=\"old man\"", "Old Man"));
+        assert!(matches("'yth", "python"));
+        assert!(matches("!ruby", "python"));
+        assert!(!matches("!ruby", "rubyist"));
+        assert!(matches("^java", "javascript"));
+        assert!(matches("!^erlang", "elixir"));
+        assert!(matches(".js$", "index.js"));
+        assert!(matches("!.go$", "main.rs"));
+    }
+
+    #[test]
+    fn test_include_indices() {
+        let options = FuseOptions::default();
+        let matcher = Matcher::parse("'an", &options).unwrap();
+
+        assert_eq!(matcher.search("BANANA").unwrap().indices, vec![(1, 2), (3, 4)]);
+    }
+
+    #[test]
+    fn test_min_match_char_length() {
+        let options = FuseOptions { min_match_char_length: 4, ..Default::default() };
+        let matches = |token: &str, text: &str| Matcher::parse(token, &options).unwrap().search(text).unwrap();
+
+        assert!(!matches("'an", "banana").is_match);
+        assert!(!matches("^jav", "java").is_match);
+        assert!(!matches("=go", "go").is_match);
+        assert_eq!(matches("'anan", "banana").indices, vec![(1, 4)]);
+        assert_eq!(matches("~~smith", "Smt Smyth").indices, vec![(4, 8)]);
+
+        // Inverse matchers don't look for spans
+        assert!(matches("!go", "rust").is_match);
+    }
+
+    #[test]
+    fn test_normalized_text() {
+        let options = FuseOptions {
+            ignore_diacritics: true,
+            ..Default::default()
+        };
+        let matcher = Matcher::parse("^creme", &options).unwrap();
+
+        assert!(!matcher.caseless);
+        assert!(matcher.search(&options.normalizer().normalize("Crème brûlée")).unwrap().is_match);
+    }
+
+    #[test]
+    fn test_phonetic_matcher() {
+        assert!(matches("~~smith", "John Smyth"));
+        assert!(matches("~~robert", "Rupert Giles"));
+        assert!(!matches("~~smith", "Jane Doe"));
+
+        let options = FuseOptions::default();
+        let matcher = Matcher::parse("~~smith", &options).unwrap();
+        assert_eq!(matcher.search("jöhn smyth").unwrap().indices, vec![(5, 9)]);
+    }
+
+    #[test]
+    fn test_phonetic_matcher_metaphone() {
+        let options = FuseOptions {
+            phonetic_algorithm: PhoneticAlgorithm::Metaphone,
+            ..Default::default()
+        };
+        let matcher = Matcher::parse("~~catherine", &options).unwrap();
+
+        assert!(matcher.search("kathryn").unwrap().is_match);
+        assert!(!matcher.search("katie").unwrap().is_match);
+    }
+
+    #[test]
+    fn test_parse_ignores_patterns_normalized_away() {
+        let options = FuseOptions { ignore_diacritics: true, ..Default::default() };
+
+        assert!(Matcher::parse("^\u{301}", &options).is_none());
+        assert!(Matcher::parse("'\u{301}\u{308}", &options).is_none());
+    }
+}
@@ -0,0 +1,3 @@
+pub(crate) mod extended_search;
+pub(crate) mod matchers;
+pub(crate) mod query;
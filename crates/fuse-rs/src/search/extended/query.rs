@@ -0,0 +1,133 @@
+//! Extended search queries built from parts
+//!
+//! `ExtendedQuery` is the structured form of the extended search syntax: it
+//! renders to a pattern string with `Display`. With the `arbitrary` feature
+//! it implements `arbitrary::Arbitrary`, so fuzzers generate queries that
+//! reach every matcher instead of mostly-invalid strings.
+
+use std::fmt;
+
+use super::matchers::MatchKind;
+
+//----------------------------------------------------------------------
+// Types
+//----------------------------------------------------------------------
+
+/// A single token of an extended query
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct ExtendedToken {
+    /// The comparison performed
+    pub kind: MatchKind,
+
+    /// The pattern, without operator or quotes
+    pub pattern: String,
+}
+
+/// An extended query: alternatives (`|`) of tokens that must all match
+///
+/// # Example
+///
+/// ```
+/// use fuse_rs::{ExtendedQuery, ExtendedToken, MatchKind};
+///
+/// let query = ExtendedQuery {
+///     alternatives: vec![
+///         vec![
+///             ExtendedToken { kind: MatchKind::PrefixExact, pattern: "old".into() },
+///             ExtendedToken { kind: MatchKind::InverseExact, pattern: "new war".into() },
+///         ],
+///         vec![ExtendedToken { kind: MatchKind::SuffixExact, pattern: ".js".into() }],
+///     ],
+/// };
+///
+/// assert_eq!(query.to_string(), r#"^old !"new war" | .js$"#);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct ExtendedQuery {
+    /// The alternatives, each a list of tokens
+    pub alternatives: Vec<Vec<ExtendedToken>>,
+}
+
+//----------------------------------------------------------------------
+// Implementation
+//----------------------------------------------------------------------
+
+/// Writes the token in the extended syntax.
+///
+/// Patterns with spaces are quoted. The syntax has no escapes, so patterns
+/// containing `|` or `"`, or fuzzy patterns starting with an operator, are
+/// written as is and read back differently.
+impl fmt::Display for ExtendedToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (prefix, suffix) = match self.kind {
+            MatchKind::Exact => ("=", ""),
+            MatchKind::InverseExact => ("!", ""),
+            MatchKind::Include => ("'", ""),
+            MatchKind::PrefixExact => ("^", ""),
+            MatchKind::InversePrefixExact => ("!^", ""),
+            MatchKind::SuffixExact => ("", "$"),
+            MatchKind::InverseSuffixExact => ("!", "$"),
+            MatchKind::Phonetic => ("~~", ""),
+            MatchKind::Fuzzy => ("", ""),
+        };
+
+        if self.pattern.contains(' ') {
+            write!(f, "{}\"{}\"{}", prefix, self.pattern, suffix)
+        } else {
+            write!(f, "{}{}{}", prefix, self.pattern, suffix)
+        }
+    }
+}
+
+impl fmt::Display for ExtendedQuery {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, tokens) in self.alternatives.iter().enumerate() {
+            if i > 0 {
+                f.write_str(" | ")?;
+            }
+
+            for (j, token) in tokens.iter().enumerate() {
+                if j > 0 {
+                    f.write_str(" ")?;
+                }
+                write!(f, "{}", token)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+//----------------------------------------------------------------------
+// Tests
+//----------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::FuseOptions;
+    use crate::search::extended::matchers::Matcher;
+
+    #[test]
+    fn test_tokens_parse_back() {
+        let options = FuseOptions::default();
+        let kinds = [
+            MatchKind::Exact,
+            MatchKind::InverseExact,
+            MatchKind::Include,
+            MatchKind::PrefixExact,
+            MatchKind::InversePrefixExact,
+            MatchKind::SuffixExact,
+            MatchKind::InverseSuffixExact,
+            MatchKind::Phonetic,
+            MatchKind::Fuzzy,
+        ];
+
+        for kind in kinds {
+            let token = ExtendedToken { kind, pattern: "old man".into() }.to_string();
+            assert_eq!(Matcher::parse(&token, &options).map(|m| m.kind), Some(kind), "{}", token);
+        }
+    }
+}
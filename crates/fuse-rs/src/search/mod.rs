@@ -1 +1,7 @@
-pub(crate) mod bitmap;
\ No newline at end of file
+pub(crate) mod bitmap;
+#[cfg(feature = "std")]
+pub(crate) mod edit_distance;
+#[cfg(feature = "std")]
+pub(crate) mod extended;
+#[cfg(feature = "std")]
+pub(crate) mod searcher;
@@ -0,0 +1,165 @@
+//! Searcher selection
+//!
+//! A pattern is matched either with the plain bitap searcher or, when
+//! `use_extended_search` is enabled, parsed as an extended query. Synonyms
+//! of the pattern are searched as alternatives of it, and with `tokenize`
+//! its tokens are also matched against the tokens of the text. Keys with
+//! their own analyzer get their own searcher.
+
+use crate::FuseError;
+use crate::FuseOptions;
+use crate::helpers::synonyms::expand_synonyms;
+use super::bitmap::bitmap_search::BitmapSearch;
+use super::bitmap::search::SearchResult;
+use super::extended::extended_search::ExtendedSearch;
+
+//----------------------------------------------------------------------
+// Types
+//----------------------------------------------------------------------
+
+/// Searcher for a single pattern, selected by the options
+#[derive(Debug, Clone)]
+pub enum Searcher<'o, 'a> {
+    /// Fuzzy match of the whole pattern
+    Bitmap(BitmapSearch<'o, 'a>),
+
+    /// Extended query (`use_extended_search`)
+    Extended(ExtendedSearch<'o, 'a>),
+
+    /// Fuzzy match of the pattern or any of its synonyms
+    Alternatives(Vec<BitmapSearch<'o, 'a>>),
+
+    /// Fuzzy match of the whole pattern, combined with matches of each of
+    /// its tokens against each token of the text (`tokenize`)
+    Tokens {
+        full: Box<Searcher<'o, 'a>>,
+        tokens: Vec<BitmapSearch<'o, 'a>>,
+        options: &'o FuseOptions<'a>,
+    },
+
+    /// Searchers of the keys with their own analyzer, by key position, and
+    /// of the other keys and of string records
+    Keyed {
+        default: Box<Searcher<'o, 'a>>,
+        keys: Vec<Option<Searcher<'o, 'a>>>,
+    },
+}
+
+//----------------------------------------------------------------------
+// Implementation
+//----------------------------------------------------------------------
+
+impl<'o, 'a> Searcher<'o, 'a> {
+    /// Creates the searcher for `pattern` using the given options.
+    pub fn new(pattern: &str, options: &'o FuseOptions<'a>) -> Self {
+        let variants = match &options.synonyms {
+            Some(provider) => expand_synonyms(pattern, provider.as_ref()),
+            None => vec![pattern.to_string()],
+        };
+
+        if options.use_extended_search {
+            // Synonyms become additional `|` alternatives of the query
+            return Self::Extended(ExtendedSearch::new(&variants.join(" | "), options));
+        }
+
+        let full = if variants.len() > 1 {
+            Self::Alternatives(variants.iter().map(|v| BitmapSearch::new(v, options)).collect())
+        } else {
+            Self::Bitmap(BitmapSearch::new(pattern, options))
+        };
+
+        if !options.tokenize {
+            return full;
+        }
+
+        let tokens = options.tokenizer.tokenize(pattern).iter().map(|token| BitmapSearch::new(token.text, options)).collect();
+        Self::Tokens { full: Box::new(full), tokens, options }
+    }
+
+    /// Returns the searcher of the values of the key at `key_index`.
+    pub fn for_key(&self, key_index: usize) -> &Self {
+        match self {
+            Self::Keyed { default, keys } => keys.get(key_index).and_then(Option::as_ref).unwrap_or(default),
+            _ => self,
+        }
+    }
+
+    /// Matches the pattern against `text`.
+    pub fn search_in(&self, text: &str) -> Result<SearchResult, FuseError> {
+        match self {
+            Self::Keyed { default, .. } => default.search_in(text),
+            Self::Bitmap(searcher) => searcher.search_in(text),
+            Self::Extended(searcher) => searcher.search_in(text),
+            Self::Alternatives(searchers) => {
+                let mut best = SearchResult { is_match: false, score: 1.0, indices: Vec::new() };
+
+                for searcher in searchers {
+                    let result = searcher.search_in(text)?;
+                    if result.is_match && (!best.is_match || result.score < best.score) {
+                        best = result;
+                    }
+                }
+
+                Ok(best)
+            },
+            Self::Tokens { full, tokens, options } => search_tokens(full, tokens, options, text),
+        }
+    }
+}
+
+//----------------------------------------------------------------------
+// Implementation Details
+//----------------------------------------------------------------------
+
+/// Matches the whole pattern and each of its `tokens` against `text`, like
+/// the `tokenize` option of Fuse.js 3.
+///
+/// Every pair of a pattern token and a text token is scored, pairs that
+/// don't match counting as `1.0` unless `match_all_tokens` is set, and the
+/// average is averaged with the score of the whole pattern. The text matches
+/// if the whole pattern or any token does; with `match_all_tokens`, every
+/// token of the pattern must match a token of the text as well. Match
+/// indices are those of the whole pattern.
+fn search_tokens(
+    full: &Searcher,
+    tokens: &[BitmapSearch],
+    options: &FuseOptions,
+    text: &str,
+) -> Result<SearchResult, FuseError> {
+    let mut result = full.search_in(text)?;
+    let words = options.tokenizer.tokenize(text);
+
+    let (mut total, mut count) = (0.0, 0);
+    let mut matched_tokens = 0;
+
+    for token in tokens {
+        let mut matched = false;
+
+        for word in &words {
+            let word_result = token.search_in(word.text)?;
+            if word_result.is_match {
+                matched = true;
+                total += word_result.score;
+                count += 1;
+            } else if !options.match_all_tokens {
+                total += 1.0;
+                count += 1;
+            }
+        }
+
+        matched_tokens += usize::from(matched);
+    }
+
+    if count > 0 {
+        result.score = (result.score + total / count as f64) / 2.0;
+    }
+
+    result.is_match = (result.is_match || matched_tokens > 0)
+        && (!options.match_all_tokens || matched_tokens == tokens.len());
+
+    if !result.is_match {
+        result = SearchResult { is_match: false, score: 1.0, indices: Vec::new() };
+    }
+
+    Ok(result)
+}
@@ -0,0 +1,201 @@
+//! Storage of the searched documents
+//!
+//! A `Fuse` instance only needs its documents to filter candidates and to
+//! return the matching items; searching itself works on the index. The
+//! documents are therefore read through the [`DocStore`] trait, so they can
+//! live in an embedded key-value store (sled, RocksDB, ...) instead of
+//! memory. [`MemoryDocStore`] is the default, used by [`Fuse::new`].
+//!
+//! [`Fuse::new`]: crate::Fuse::new
+
+use std::borrow::Cow;
+use std::fmt;
+
+use serde_json::Value;
+
+use crate::core::error_messages::FuseError;
+
+//----------------------------------------------------------------------
+// Types
+//----------------------------------------------------------------------
+
+/// A collection of documents addressed by their position
+///
+/// Ids are the positions of the documents in the collection, from `0` to
+/// `len() - 1`, and `get` must return a document for each of them; they are
+/// the `ref_index` of search results. Stores backed by another database map
+/// these ids to their own keys.
+///
+/// # Example
+///
+/// ```
+/// use std::borrow::Cow;
+/// use fuse_rs::{DocStore, Fuse, FuseOptions};
+/// use serde_json::Value;
+///
+/// /// Documents kept serialized, as in a key-value store
+/// #[derive(Debug)]
+/// struct SerializedStore(Vec<String>);
+///
+/// impl DocStore for SerializedStore {
+///     fn len(&self) -> usize {
+///         self.0.len()
+///     }
+///
+///     fn get(&self, id: usize) -> Option<Cow<'_, Value>> {
+///         let doc = serde_json::from_str(self.0.get(id)?).ok()?;
+///         Some(Cow::Owned(doc))
+///     }
+/// }
+///
+/// let store = SerializedStore(vec![r#""apple""#.into(), r#""banana""#.into()]);
+/// let fuse = Fuse::with_store(store, &FuseOptions::default(), None);
+///
+/// assert_eq!(fuse.search("banana").unwrap()[0].item, Value::from("banana"));
+/// ```
+pub trait DocStore: fmt::Debug + Send + Sync {
+    /// Returns the number of documents.
+    fn len(&self) -> usize;
+
+    /// Returns the document `id`, or `None` if it does not exist.
+    fn get(&self, id: usize) -> Option<Cow<'_, Value>>;
+
+    /// Returns `true` if the store holds no document.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Iterates over the documents with their ids, in order.
+    ///
+    /// The default implementation calls `get` for every id.
+    fn iter(&self) -> Box<dyn Iterator<Item = (usize, Cow<'_, Value>)> + '_> {
+        Box::new((0..self.len()).filter_map(move |id| Some((id, self.get(id)?))))
+    }
+
+    /// Appends `doc`, whose id is the previous `len()`.
+    ///
+    /// The default implementation fails with `FuseError::ReadOnlyStore`.
+    fn push(&mut self, _doc: Value) -> Result<(), FuseError> {
+        Err(FuseError::ReadOnlyStore)
+    }
+
+    /// Removes the document `id`, shifting the ids of the following documents
+    /// down by one.
+    ///
+    /// Returns the removed document, or `None` if `id` does not exist. The
+    /// default implementation fails with `FuseError::ReadOnlyStore`.
+    fn remove(&mut self, _id: usize) -> Result<Option<Value>, FuseError> {
+        Err(FuseError::ReadOnlyStore)
+    }
+}
+
+/// Documents held in memory
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MemoryDocStore {
+    /// The documents, by id
+    docs: Vec<Value>,
+}
+
+//----------------------------------------------------------------------
+// Implementation
+//----------------------------------------------------------------------
+
+impl MemoryDocStore {
+    /// Creates a store holding `docs`.
+    pub fn new(docs: Vec<Value>) -> Self {
+        Self { docs }
+    }
+
+    /// Returns the documents.
+    pub fn as_slice(&self) -> &[Value] {
+        &self.docs
+    }
+}
+
+impl From<Vec<Value>> for MemoryDocStore {
+    fn from(docs: Vec<Value>) -> Self {
+        Self::new(docs)
+    }
+}
+
+impl DocStore for MemoryDocStore {
+    fn len(&self) -> usize {
+        self.docs.len()
+    }
+
+    fn get(&self, id: usize) -> Option<Cow<'_, Value>> {
+        self.docs.get(id).map(Cow::Borrowed)
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = (usize, Cow<'_, Value>)> + '_> {
+        Box::new(self.docs.iter().map(Cow::Borrowed).enumerate())
+    }
+
+    fn push(&mut self, doc: Value) -> Result<(), FuseError> {
+        self.docs.push(doc);
+        Ok(())
+    }
+
+    fn remove(&mut self, id: usize) -> Result<Option<Value>, FuseError> {
+        Ok((id < self.docs.len()).then(|| self.docs.remove(id)))
+    }
+}
+
+//----------------------------------------------------------------------
+// Tests
+//----------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    /// Documents kept serialized
+    #[derive(Debug)]
+    struct Serialized(Vec<String>);
+
+    impl DocStore for Serialized {
+        fn len(&self) -> usize {
+            self.0.len()
+        }
+
+        fn get(&self, id: usize) -> Option<Cow<'_, Value>> {
+            serde_json::from_str(self.0.get(id)?).ok().map(Cow::Owned)
+        }
+    }
+
+    #[test]
+    fn test_memory_store() {
+        let store = MemoryDocStore::from(vec![json!("apple"), json!("banana")]);
+
+        assert_eq!(store.len(), 2);
+        assert_eq!(store.get(1).as_deref(), Some(&json!("banana")));
+        assert_eq!(store.get(2), None);
+        assert!(matches!(store.get(0), Some(Cow::Borrowed(_))));
+
+        let ids: Vec<usize> = store.iter().map(|(id, _)| id).collect();
+        assert_eq!(ids, vec![0, 1]);
+    }
+
+    #[test]
+    fn test_push_and_remove() {
+        let mut store = MemoryDocStore::default();
+        store.push(json!("apple")).unwrap();
+        store.push(json!("banana")).unwrap();
+
+        assert_eq!(store.remove(0).unwrap(), Some(json!("apple")));
+        assert_eq!(store.remove(1).unwrap(), None);
+        assert_eq!(store.as_slice(), &[json!("banana")]);
+
+        let mut serialized = Serialized(vec![]);
+        assert!(matches!(serialized.push(json!("apple")), Err(FuseError::ReadOnlyStore)));
+    }
+
+    #[test]
+    fn test_default_iter() {
+        let store = Serialized(vec![r#""apple""#.into(), r#"{"name": "cherry"}"#.into()]);
+        let docs: Vec<(usize, Value)> = store.iter().map(|(id, doc)| (id, doc.into_owned())).collect();
+
+        assert_eq!(docs, vec![(0, json!("apple")), (1, json!({"name": "cherry"}))]);
+    }
+}
@@ -5,14 +5,24 @@
 
 use std::borrow::Cow;
 use std::collections::HashMap;
+use std::sync::Arc;
 
-use serde_json::Value;
+use serde::Deserialize;
+use serde_json::{Value, json};
 
+use super::doc_store::DocStore;
 use super::fuse_index_record::*;
-use super::key_store::{Key, create_key};
+use super::key_store::{Key, KeyFallback, create_key};
 use super::norm::Norm;
-use crate::helpers::get::{GetFnPath, GetValue};
+use crate::helpers::caseless::fold;
+use crate::helpers::document::Document;
+use crate::helpers::get::{GetFnPath, GetValue, get_document};
+use crate::helpers::path::Path;
+use crate::helpers::stop_words::StopWordTokenizer;
+use crate::helpers::tokenizer::Tokenizer;
+use crate::helpers::trace;
 use crate::{FuseOptions, helpers::get::GetFn};
+use crate::core::error_messages::FuseError;
 use crate::core::options::keys::FuseOptionKey;
 
 //----------------------------------------------------------------------
@@ -22,6 +32,13 @@ use crate::core::options::keys::FuseOptionKey;
 /// Default size for the n-gram indexing
 const DEFAULT_NGRAM_SIZE: usize = 3;
 
+/// The records are compacted once the removed documents make up
+/// `1 / COMPACTION_RATIO` of them
+const COMPACTION_RATIO: usize = 8;
+
+/// Batches of documents smaller than this are indexed on the calling thread
+const PARALLEL_MIN_DOCS: usize = 4096;
+
 /// Search index for fast fuzzy search operations
 ///
 /// This structure maintains an inverted index mapping tokens to document IDs,
@@ -33,6 +50,58 @@ pub struct FuseIndex<'a> {
     records: FuseIndexRecords,
     keys: Vec<Key<'a>>,
     keys_map: HashMap<String, usize>,
+
+    /// Ids of the documents removed since the records were last compacted,
+    /// sorted. Records keep the id their document had when it was added
+    /// until then; its position is the id minus the removed ids below it.
+    removed: Vec<usize>,
+
+    /// Posting lists of the n-grams of the records, if enabled
+    ngrams: Option<NgramIndex>,
+
+    /// Number of threads large batches are indexed on, `0` for one per
+    /// available CPU
+    threads: usize,
+}
+
+/// An inverted index from the n-grams of the indexed values to the records
+/// containing them
+///
+/// Values are case-folded before being split into n-grams. A record is
+/// referred to by its position in the records, so the posting lists are
+/// rebuilt whenever records are compacted or replaced.
+#[derive(Debug, Clone)]
+struct NgramIndex {
+    /// Number of characters per n-gram
+    size: usize,
+
+    /// Positions of the records containing each n-gram, in ascending order
+    postings: HashMap<String, Vec<usize>>,
+}
+
+/// The JSON form of an index, as produced by Fuse.js' `index.toJSON()`
+#[derive(Deserialize)]
+struct IndexJson {
+    /// The keys the records were indexed with
+    keys: Vec<IndexKeyJson>,
+
+    /// The indexed records
+    records: FuseIndexRecords,
+}
+
+/// The JSON form of an index key; its id is derived from `path`
+#[derive(Deserialize)]
+struct IndexKeyJson {
+    /// The field path components
+    path: Vec<String>,
+
+    /// The weight of the key
+    #[serde(default = "default_key_weight")]
+    weight: f64,
+
+    /// The source of the key, a string unless it was given as an array
+    #[serde(default)]
+    src: Value,
 }
 
 //----------------------------------------------------------------------
@@ -42,25 +111,61 @@ pub struct FuseIndex<'a> {
 impl<'a> FuseIndex<'a> {
     pub fn new(options: &FuseOptions) -> Self {
         FuseIndex {
-            norm: Norm::new(options.field_norm_weight, 3),
-            get_fn: options.get_fn,
+            norm: Norm::new(options.field_norm_weight, 3)
+                .with_tokenizer(norm_tokenizer(options))
+                .with_cache_size(options.norm_cache_size),
+            get_fn: options.get_fn.clone(),
             records: FuseIndexRecords::new(),
             keys: Vec::new(),
             keys_map: HashMap::new(),
+            removed: Vec::new(),
+            ngrams: options.use_ngram_index.then(|| NgramIndex::new(DEFAULT_NGRAM_SIZE)),
+            threads: options.index_threads,
         }
     }
 
     pub fn set_source(&mut self, source: Vec<Value>) {
+        self.set_docs(&source);
+    }
+
+    /// Indexes `docs`, replacing the existing records, without taking a copy
+    /// of them.
+    pub fn set_docs(&mut self, docs: &[Value]) {
+        trace::enter_span!("fuse_index.build", docs = docs.len(), keys = self.keys.len());
+
         // Clear existing records and documents
-        self.records.clear();
+        self.clear_records();
 
-        source.iter().for_each(|doc| {
-            self.add(doc);
-        });
+        self.add_all_at(docs, 0);
+
+        trace::debug!(records = self.records.len(), "index built");
+    }
+
+    /// Indexes the documents of `store`, replacing the existing records.
+    pub fn set_store(&mut self, store: &dyn DocStore) {
+        trace::enter_span!("fuse_index.build", docs = store.len(), keys = self.keys.len());
+        self.clear_records();
+
+        for (idx, doc) in store.iter() {
+            self.add_at(&doc, idx);
+        }
+
+        trace::debug!(records = self.records.len(), "index built");
     }
 
-    pub fn set_index_records(&mut self, records: FuseIndexRecords) {
+    pub fn set_index_records(&mut self, mut records: FuseIndexRecords) {
+        // Records are looked up by id with binary searches
+        records.sort_by_key(FuseIndexRecord::index);
         self.records = records;
+        self.removed.clear();
+        self.rebuild_ngrams();
+    }
+
+    /// Removes every record.
+    fn clear_records(&mut self) {
+        self.records.clear();
+        self.removed.clear();
+        self.rebuild_ngrams();
     }
 
     pub fn set_keys(&mut self, keys: Vec<Key<'a>>) {
@@ -75,60 +180,337 @@ impl<'a> FuseIndex<'a> {
 
     pub fn add(&mut self, doc: &Value) {
         // add a new record at the end of the records
+        self.add_with_id(doc, self.size());
+    }
+
+    /// Adds the document at position `idx` of the collection.
+    ///
+    /// Unlike [`FuseIndex::add`], this stays correct when earlier documents
+    /// produced no record (such as empty strings). Documents must be added
+    /// in the order of the collection.
+    pub fn add_at(&mut self, doc: &Value, idx: usize) {
+        // Removed documents still hold their ids until the next compaction
+        self.add_with_id(doc, idx + self.removed.len());
+    }
+
+    /// Adds the document whose record id is `idx`.
+    fn add_with_id(&mut self, doc: &Value, idx: usize) {
+        let first = self.records.len();
+        self.records.extend(self.create_record(doc, idx));
+        self.index_ngrams(first);
+    }
+
+    /// Adds `docs` at the end of the records, like [`FuseIndex::add`] for
+    /// each of them.
+    ///
+    /// Room for the records is reserved up front, and large batches are
+    /// indexed on `FuseOptions::index_threads` threads; the records are the
+    /// same as when adding the documents one at a time.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fuse_rs::{FuseIndex, FuseOptions};
+    /// use serde_json::json;
+    ///
+    /// let mut index = FuseIndex::new(&FuseOptions::default());
+    /// index.add_all(&[json!("apple"), json!("banana")]);
+    /// assert_eq!(index.records().len(), 2);
+    /// ```
+    pub fn add_all(&mut self, docs: &[Value]) {
+        self.add_all_with_ids(docs, self.size());
+    }
+
+    /// Adds `docs` at positions `idx..idx + docs.len()` of the collection,
+    /// like [`FuseIndex::add_at`] for each of them.
+    pub(crate) fn add_all_at(&mut self, docs: &[Value], idx: usize) {
+        self.add_all_with_ids(docs, idx + self.removed.len());
+    }
+
+    /// Adds `docs`, the first one with the record id `first_id`.
+    fn add_all_with_ids(&mut self, docs: &[Value], first_id: usize) {
+        trace::enter_span!("fuse_index.add_all", docs = docs.len(), keys = self.keys.len());
+        let first = self.records.len();
+        self.records.reserve(docs.len());
+
+        let threads = match self.threads {
+            0 => std::thread::available_parallelism().map_or(1, |threads| threads.get()),
+            threads => threads,
+        };
+        if threads == 1 || docs.len() < PARALLEL_MIN_DOCS {
+            let records = self.create_records(docs, first_id);
+            self.records.extend(records);
+            self.index_ngrams(first);
+            return;
+        }
+
+        // Each thread indexes a contiguous chunk, so the chunks are appended
+        // in the order of the documents
+        let chunk_size = docs.len().div_ceil(threads);
+        let index = &*self;
+        let chunks: Vec<Vec<FuseIndexRecord>> = std::thread::scope(|scope| {
+            let handles: Vec<_> = docs
+                .chunks(chunk_size)
+                .enumerate()
+                .map(|(chunk, docs)| scope.spawn(move || index.create_records(docs, first_id + chunk * chunk_size)))
+                .collect();
+
+            handles
+                .into_iter()
+                .map(|handle| handle.join().unwrap_or_else(|panic| std::panic::resume_unwind(panic)))
+                .collect()
+        });
+
+        for records in chunks {
+            self.records.extend(records);
+        }
+        self.index_ngrams(first);
+    }
+
+    /// Creates the records of `docs`, the first one with the id `first_id`.
+    fn create_records(&self, docs: &[Value], first_id: usize) -> Vec<FuseIndexRecord> {
+        docs.iter()
+            .enumerate()
+            .filter_map(|(offset, doc)| self.create_record(doc, first_id + offset))
+            .collect()
+    }
+
+    /// Creates the record of `doc` with the id `idx`, or `None` if the
+    /// document has nothing to index.
+    fn create_record(&self, doc: &Value, idx: usize) -> Option<FuseIndexRecord> {
+        if let Some(value) = doc.as_str() {
+            self.create_string_record(value, idx).map(FuseIndexRecord::String)
+        } else {
+            let record = self.create_object_record(idx, |index, key| index.get_value_for_key(doc, key));
+            Some(FuseIndexRecord::Object(record))
+        }
+    }
+
+    /// Adds a document of any [`Document`] representation.
+    ///
+    /// Values are looked up with the keys' paths directly; the custom getters
+    /// (`get_fn` of the options and of the keys) only apply to
+    /// `serde_json::Value` documents added with [`FuseIndex::add`].
+    pub fn add_document<D: Document>(&mut self, doc: &D) {
         let idx = self.size();
+        let first = self.records.len();
 
-        if doc.is_string() {
-            self.add_string(doc, idx);
+        if let Some(value) = doc.as_text() {
+            if let Some(record) = self.create_string_record(value, idx) {
+                self.records.add_string(record);
+            }
         } else {
-            self.add_object(doc, idx);
+            let record = self.create_object_record(idx, |_, key| {
+                get_document(doc, &key.compiled_path).or_else(|| match key.fallback.as_ref()? {
+                    KeyFallback::Value(value) => GetValue::from_json(value),
+                    KeyFallback::Paths(paths) => paths.iter().find_map(|path| get_document(doc, path)),
+                })
+            });
+            self.records.add_object(record);
         }
+        self.index_ngrams(first);
     }
 
+    /// Removes the record of the document at position `idx` of the
+    /// collection, shifting the positions of the following documents down by
+    /// one.
+    ///
+    /// The record is only marked as removed: records are compacted once the
+    /// removed documents make up a fixed share of them, so that removing a
+    /// document does not rewrite every following record.
     pub fn remove_at(&mut self, idx: usize) {
-        // Remove the record at the specified index
-        self.records.remove(idx);
+        let id = self.id_at(idx);
 
-        // Update the index of all records after the removed index
-        for i in idx..self.size() {
-            let record = self.records.get_mut(i).unwrap();
-            match record {
-                FuseIndexRecord::String(r) => r.i -= 1,
-                FuseIndexRecord::Object(r) => r.i -= 1,
-            };
+        // Records are added in the order of their ids; documents past the
+        // last record have nothing to remove, and no later record to shift
+        if self.records.last().is_none_or(|record| record.index() < id) {
+            return;
+        }
+
+        if let Err(rank) = self.removed.binary_search(&id) {
+            self.removed.insert(rank, id);
+        }
+
+        if self.removed.len() * COMPACTION_RATIO >= self.records.len() {
+            self.compact();
         }
     }
 
-    fn add_string(&mut self, doc: &Value, idx: usize) {
-        if let Some(value) = doc.as_str() {
-            if value.is_empty() {
-                return;
+    /// Drops the records of removed documents and renumbers the others with
+    /// the current positions of their documents.
+    pub fn compact(&mut self) {
+        if self.removed.is_empty() {
+            return;
+        }
+
+        trace::enter_span!("fuse_index.compact", records = self.records.len(), removed = self.removed.len());
+        compact_records(&mut self.records, &self.removed);
+        self.removed.clear();
+        self.rebuild_ngrams();
+    }
+
+    /// Returns the position in the collection of the document of `record`,
+    /// or `None` if the document was removed.
+    ///
+    /// Until the index is compacted, records keep the position their
+    /// document had when it was added.
+    pub fn position(&self, record: &FuseIndexRecord) -> Option<usize> {
+        let id = record.index();
+        match self.removed.binary_search(&id) {
+            Ok(_) => None,
+            Err(rank) => Some(id - rank),
+        }
+    }
+
+    /// Returns the record of the document at position `idx`, or `None` if
+    /// it has no record.
+    pub fn record_at(&self, idx: usize) -> Option<&FuseIndexRecord> {
+        let id = self.id_at(idx);
+        match self.records.get(id) {
+            // Records are at the position of their id unless some documents
+            // (such as empty strings) produced no record
+            Some(record) if record.index() == id => Some(record),
+            // Otherwise they are still in the order of their ids
+            _ => {
+                let pos = self.records.binary_search_by_key(&id, FuseIndexRecord::index).ok()?;
+                Some(&self.records[pos])
+            }
+        }
+    }
+
+    /// Returns the records of the documents in the collection, numbered with
+    /// their current positions, without modifying the index.
+    pub fn compacted_records(&self) -> Cow<'_, FuseIndexRecords> {
+        if self.removed.is_empty() {
+            return Cow::Borrowed(&self.records);
+        }
+
+        let mut records = self.records.clone();
+        compact_records(&mut records, &self.removed);
+        Cow::Owned(records)
+    }
+
+    /// Enables or disables the n-gram posting lists of the records.
+    ///
+    /// When enabled, the index maps every trigram of the case-folded values
+    /// of a record to the records containing it, and searches only score
+    /// the records that share enough trigrams with the pattern to possibly
+    /// match. The posting lists take memory in proportion to the indexed
+    /// text and are kept up to date as documents are added and removed.
+    /// [`FuseOptions::use_ngram_index`] enables them for an instance.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fuse_rs::{FuseIndex, FuseOptions};
+    /// use serde_json::json;
+    ///
+    /// let mut index = FuseIndex::new(&FuseOptions::default());
+    /// index.add_all(&[json!("apple"), json!("banana")]);
+    ///
+    /// index.set_ngram_index(true);
+    /// assert!(index.has_ngram_index());
+    /// ```
+    pub fn set_ngram_index(&mut self, enabled: bool) {
+        if enabled == self.ngrams.is_some() {
+            return;
+        }
+
+        self.ngrams = enabled.then(|| NgramIndex::new(DEFAULT_NGRAM_SIZE));
+        self.index_ngrams(0);
+    }
+
+    /// Returns `true` if the index keeps n-gram posting lists.
+    pub fn has_ngram_index(&self) -> bool {
+        self.ngrams.is_some()
+    }
+
+    /// Returns which records may contain an approximate occurrence of
+    /// `pattern` with at most `max_errors` edits, by position in the
+    /// records.
+    ///
+    /// `pattern` must be case-folded. An occurrence with `k` edits keeps at
+    /// least `m - n + 1 - k * n` of the `n`-grams of a pattern of `m`
+    /// characters, so records sharing fewer of them cannot match.
+    ///
+    /// # Returns
+    ///
+    /// The flags of the candidate records, or `None` if every record is a
+    /// candidate: there are no posting lists, or the pattern is too short
+    /// for the number of edits to rule any record out.
+    pub(crate) fn ngram_candidates(&self, pattern: &str, max_errors: usize) -> Option<Vec<bool>> {
+        self.ngrams.as_ref()?.candidates(pattern, max_errors, self.records.len())
+    }
+
+    /// Adds the records from position `first` on to the posting lists.
+    fn index_ngrams(&mut self, first: usize) {
+        if let Some(ngrams) = &mut self.ngrams {
+            for (position, record) in self.records.iter().enumerate().skip(first) {
+                ngrams.add(position, record);
             }
+        }
+    }
 
-            let norm = self.norm.get(value);
-            let record = FuseIndexStringRecord::new(idx, value.to_string(), norm);
-            self.records.add_string(record);
+    /// Rebuilds the posting lists after records were replaced or moved.
+    fn rebuild_ngrams(&mut self) {
+        if let Some(ngrams) = &mut self.ngrams {
+            ngrams.postings.clear();
         }
+        self.index_ngrams(0);
     }
 
-    fn add_object(&mut self, doc: &Value, idx: usize) {
+    /// Returns the record id of the document at position `idx`.
+    fn id_at(&self, idx: usize) -> usize {
+        // `removed[k] - k` only grows, so the number of removed ids below the
+        // result is found by binary search
+        let (mut low, mut high) = (0, self.removed.len());
+        while low < high {
+            let mid = (low + high) / 2;
+            if self.removed[mid] - mid <= idx {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+
+        idx + low
+    }
+
+    /// Creates the record of a string document, or `None` if it is empty.
+    fn create_string_record(&self, value: &str, idx: usize) -> Option<FuseIndexStringRecord> {
+        if value.is_empty() {
+            return None;
+        }
+
+        let norm = self.norm.get(value);
+        Some(FuseIndexStringRecord::new(idx, value.to_string(), norm))
+    }
+
+    /// Creates the record of an object document, whose values are looked up
+    /// with `get_value_for_key`.
+    fn create_object_record<F: Fn(&Self, &Key) -> Option<GetValue>>(
+        &self,
+        idx: usize,
+        get_value_for_key: F,
+    ) -> FuseIndexObjectRecord {
         let mut record = FuseIndexObjectRecord::new(idx);
 
-        self.keys.iter().enumerate().for_each(|(key_index, key)| {
-            let get_value = self.get_value_for_key(doc, key);
+        self.keys.iter().enumerate().filter(|(_, key)| key.index).for_each(|(key_index, key)| {
+            let get_value = get_value_for_key(self, key);
 
             if let Some(value) = get_value {
                 match value {
-                    GetValue::String(s) => {
-                        self.process_string_value(s, key_index, &mut record);
-                    }
                     GetValue::Array(arr) => {
                         self.process_array_value(arr, key_index, &mut record);
                     }
+                    scalar => {
+                        self.process_scalar_value(scalar, key_index, &mut record);
+                    }
                 }
             }
         });
 
-        self.records.add_object(record);
+        record
     }
 
     /// Get the number of records in the index
@@ -142,28 +524,46 @@ impl<'a> FuseIndex<'a> {
 
     /// Get the value for a specific key from a document
     fn get_value_for_key(&self, doc: &Value, key: &Key) -> Option<GetValue> {
-        if let Some(get_fn) = key.get_fn {
-            Some(GetValue::String(get_fn(doc).to_string()))
-        } else {
-            let path: Vec<Cow<'_, str>> =
-                key.path.iter().map(|s| Cow::Borrowed(s.as_str())).collect();
-            let get_fn_path = GetFnPath::StringArray(path);
-            (self.get_fn)(doc, &get_fn_path)
+        match &key.get_fn {
+            Some(get_fn) => get_fn(doc),
+            None => self.get_value_at_path(doc, &key.compiled_path),
+        }
+        .or_else(|| self.get_fallback_value(doc, key.fallback.as_ref()?))
+    }
+
+    /// Get the value at a path using the configured getter
+    fn get_value_at_path(&self, doc: &Value, path: &Path) -> Option<GetValue> {
+        (self.get_fn)(doc, &GetFnPath::Compiled(Cow::Borrowed(path)))
+    }
+
+    /// Get the value a key falls back to when its path finds nothing
+    fn get_fallback_value(&self, doc: &Value, fallback: &KeyFallback) -> Option<GetValue> {
+        match fallback {
+            KeyFallback::Value(value) => GetValue::from_json(value),
+            KeyFallback::Paths(paths) => paths
+                .iter()
+                .find_map(|path| self.get_value_at_path(doc, path)),
         }
     }
 
-    /// Process a single string value and add it to the record
-    fn process_string_value(
+    /// Process a single scalar value and add it to the record
+    fn process_scalar_value(
         &self,
-        s: String,
+        value: GetValue,
         key_index: usize,
         record: &mut FuseIndexObjectRecord,
     ) {
+        let Some(s) = value.as_text() else {
+            return;
+        };
+
         let norm = self.norm.get(&s);
         let entry = RecordEntryValue::Single(IndexValue {
             v: s,
             n: norm,
             i: None,
+            t: value.typed_value(),
+            d: value.timestamp(),
         });
         record.entries.insert(key_index.to_string(), entry);
     }
@@ -171,7 +571,7 @@ impl<'a> FuseIndex<'a> {
     /// Process an array of values and add them to the record
     fn process_array_value(
         &self,
-        arr: Vec<String>,
+        arr: Vec<GetValue>,
         key_index: usize,
         record: &mut FuseIndexObjectRecord,
     ) {
@@ -184,28 +584,29 @@ impl<'a> FuseIndex<'a> {
     }
 
     /// Collect sub-records from an array of values
-    fn collect_sub_records(&self, arr: Vec<String>) -> Vec<IndexValue> {
+    fn collect_sub_records(&self, arr: Vec<GetValue>) -> Vec<IndexValue> {
         let mut sub_records = Vec::new();
         let mut stack = Vec::new();
 
         // Initialize stack with all array elements (with their indices)
-        for (k, item) in arr.iter().enumerate() {
-            stack.push((k, item.clone()));
+        for (k, item) in arr.into_iter().enumerate() {
+            stack.push((k, item));
         }
 
         // Process the stack
         while let Some((nested_arr_index, value)) = stack.pop() {
-            // Skip empty values
-            if value.is_empty() {
+            // Skip empty and non-scalar values
+            let Some(text) = value.as_text().filter(|text| !text.is_empty()) else {
                 continue;
-            }
+            };
 
-            // Process string values
-            let norm = self.norm.get(&value);
+            let norm = self.norm.get(&text);
             let sub_record = IndexValue {
-                v: value,
+                v: text,
                 n: norm,
                 i: Some(nested_arr_index),
+                t: value.typed_value(),
+                d: value.timestamp(),
             };
             sub_records.push(sub_record);
         }
@@ -217,6 +618,21 @@ impl<'a> FuseIndex<'a> {
         self.records.len()
     }
 
+    /// Get the indexed records
+    ///
+    /// Until the index is [compacted](FuseIndex::compact), this includes the
+    /// records of removed documents, and records keep the position their
+    /// document had when it was added; [`FuseIndex::position`] gives the
+    /// current one.
+    pub fn records(&self) -> &FuseIndexRecords {
+        &self.records
+    }
+
+    /// Get the keys the records were indexed with
+    pub fn keys(&self) -> &[Key<'a>] {
+        &self.keys
+    }
+
     /// Creates a new FuseIndex from keys and docs with optional configuration.
     ///
     /// # Arguments
@@ -245,7 +661,19 @@ impl<'a> FuseIndex<'a> {
             options.field_norm_weight = weight;
         }
         
-        let mut index = FuseIndex::new(&options);
+        Self::create_index_with_options(keys, docs, &options)
+    }
+
+    /// Creates an index of `docs` configured by all of `options`.
+    ///
+    /// Unlike `create_index`, the tokenizer and stop words of `options` are
+    /// used when computing field-length norms.
+    pub fn create_index_with_options(
+        keys: &[FuseOptionKey<'a>],
+        docs: &[Value],
+        options: &FuseOptions,
+    ) -> Self {
+        let mut index = FuseIndex::new(options);
         
         // Create keys using the key_store's create_key function
         // Handle the Result by unwrapping or panicking with error message
@@ -255,8 +683,7 @@ impl<'a> FuseIndex<'a> {
         index.set_keys(keys_vec);
         
         // Set the documents to be indexed
-        let docs_vec = docs.to_vec();
-        index.set_source(docs_vec);
+        index.set_docs(docs);
         
         index
     }
@@ -295,6 +722,272 @@ impl<'a> FuseIndex<'a> {
         
         index
     }
+
+    /// Returns the keys and records of the index as JSON, in the shape of
+    /// Fuse.js' `index.toJSON()`.
+    ///
+    /// An index built in Rust can thus be loaded by a Fuse.js frontend with
+    /// `Fuse.parseIndex`, and turned back into a `FuseIndex` with
+    /// [`FuseIndex::from_json`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fuse_rs::{FuseIndex, FuseOptionKey};
+    /// use serde_json::json;
+    ///
+    /// let docs = vec![json!({"title": "Old Man's War"})];
+    /// let index = FuseIndex::create_index(&[FuseOptionKey::String("title".into())], &docs, None, None);
+    ///
+    /// let json = index.to_json();
+    /// assert_eq!(json["keys"][0]["id"], "title");
+    /// assert_eq!(json["records"][0]["$"]["0"]["v"], "Old Man's War");
+    /// ```
+    pub fn to_json(&self) -> Value {
+        json!({ "keys": self.keys, "records": self.compacted_records() })
+    }
+
+    /// Parses an index from its JSON form, as produced by
+    /// [`FuseIndex::to_json`] or by Fuse.js' `index.toJSON()`.
+    ///
+    /// Keys are rebuilt from their `path` and `weight`; fallbacks and value
+    /// getters of the original keys are not part of the JSON form.
+    ///
+    /// # Arguments
+    ///
+    /// * `json` - The keys and records of the index.
+    /// * `get_fn` - Optional function for getting values from documents (defaults to options' get_fn).
+    /// * `field_norm_weight` - Optional field normalization weight (defaults to options' field_norm_weight).
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the index, or an error if `json` does not have
+    /// the shape of an index.
+    pub fn from_json(
+        json: Value,
+        get_fn: Option<GetFn>,
+        field_norm_weight: Option<f64>,
+    ) -> Result<Self, serde_json::Error> {
+        let mut options = FuseOptions::default();
+
+        if let Some(get_fn_value) = get_fn {
+            options.get_fn = get_fn_value;
+        }
+
+        if let Some(weight) = field_norm_weight {
+            options.field_norm_weight = weight;
+        }
+
+        Self::from_json_with_options(json, &options)
+    }
+
+    /// Parses an index from its JSON form like [`FuseIndex::from_json`],
+    /// configured by all of `options` for the documents added afterwards.
+    pub fn from_json_with_options(json: Value, options: &FuseOptions) -> Result<Self, serde_json::Error> {
+        let IndexJson { keys, records } = serde_json::from_value(json)?;
+
+        let keys = keys
+            .into_iter()
+            .map(|key| {
+                let path = key.path.into_iter().map(Cow::Owned).collect();
+                let mut parsed = create_key(&FuseOptionKey::StringArray(path)).map_err(serde::de::Error::custom)?;
+
+                if !(key.weight > 0.0 && key.weight.is_finite()) {
+                    return Err(serde::de::Error::custom(FuseError::InvalidKeyWeightValue(parsed.id)));
+                }
+                parsed.weight = key.weight;
+                if let Value::String(src) = key.src {
+                    parsed.src = Cow::Owned(src);
+                }
+
+                Ok(parsed)
+            })
+            .collect::<Result<Vec<_>, serde_json::Error>>()?;
+
+        let mut index = FuseIndex::new(options);
+        index.set_keys(keys);
+        index.set_index_records(records);
+        Ok(index)
+    }
+}
+
+impl NgramIndex {
+    /// Creates empty posting lists of `size`-character n-grams.
+    fn new(size: usize) -> Self {
+        Self {
+            size,
+            postings: HashMap::new(),
+        }
+    }
+
+    /// Adds the record at `position` to the lists of the n-grams of its
+    /// values.
+    fn add(&mut self, position: usize, record: &FuseIndexRecord) {
+        let mut grams: Vec<String> = Vec::new();
+        match record {
+            FuseIndexRecord::String(r) => grams.extend(self.grams(&r.v)),
+            FuseIndexRecord::Object(r) => {
+                for value in r.entries.values() {
+                    let values: &[IndexValue] = match value {
+                        RecordEntryValue::Single(v) => std::slice::from_ref(v),
+                        RecordEntryValue::Array(arr) => arr,
+                    };
+                    for v in values {
+                        grams.extend(self.grams(&v.v));
+                    }
+                }
+            }
+        }
+
+        grams.sort_unstable();
+        grams.dedup();
+        for gram in grams {
+            self.postings.entry(gram).or_default().push(position);
+        }
+    }
+
+    /// Returns the candidates among `records` records, as described in
+    /// [`FuseIndex::ngram_candidates`].
+    fn candidates(&self, pattern: &str, max_errors: usize, records: usize) -> Option<Vec<bool>> {
+        let chars: Vec<char> = pattern.chars().collect();
+        let required = (chars.len() + 1).checked_sub(self.size + max_errors * self.size)?;
+        if required == 0 {
+            return None;
+        }
+
+        // Count, for every record, the n-grams of the pattern it contains;
+        // an n-gram occurring twice in the pattern counts twice
+        let mut occurrences: HashMap<String, usize> = HashMap::new();
+        for gram in chars.windows(self.size) {
+            *occurrences.entry(gram.iter().collect()).or_default() += 1;
+        }
+
+        let mut counts = vec![0; records];
+        for (gram, occurrences) in &occurrences {
+            for &position in self.postings.get(gram).into_iter().flatten() {
+                counts[position] += occurrences;
+            }
+        }
+
+        Some(counts.into_iter().map(|count| count >= required).collect())
+    }
+
+    /// Returns the n-grams of the case-folded `text`.
+    fn grams(&self, text: &str) -> impl Iterator<Item = String> {
+        let chars: Vec<char> = fold(text).collect();
+        let size = self.size;
+        (0..(chars.len() + 1).saturating_sub(size)).map(move |start| chars[start..start + size].iter().collect())
+    }
+}
+
+//----------------------------------------------------------------------
+// Free Functions
+//----------------------------------------------------------------------
+
+/// Indexes `docs` for `keys`, like Fuse.js' `Fuse.createIndex`.
+///
+/// The index can be passed to [`Fuse::new`](crate::Fuse::new), or stored
+/// with [`FuseIndex::to_json`] and loaded again with [`parse_index`].
+/// `options` configure how values are read and their norms computed, as for
+/// [`FuseIndex::create_index_with_options`].
+///
+/// # Returns
+///
+/// The index, or the error of the first invalid key (e.g.
+/// `FuseError::InvalidKeyWeightValue`).
+///
+/// # Example
+///
+/// ```
+/// use fuse_rs::{FuseOptions, create_index, parse_index};
+/// use serde_json::json;
+///
+/// let docs = vec![json!({"title": "Old Man's War"}), json!({"title": "The Lock Artist"})];
+/// let index = create_index(&["title".into()], &docs, &FuseOptions::default()).unwrap();
+///
+/// let parsed = parse_index(index.to_json(), &FuseOptions::default()).unwrap();
+/// assert_eq!(parsed.records().len(), 2);
+/// ```
+pub fn create_index<'a>(
+    keys: &[FuseOptionKey<'a>],
+    docs: &[Value],
+    options: &FuseOptions,
+) -> Result<FuseIndex<'a>, FuseError> {
+    let keys = keys.iter().map(create_key).collect::<Result<Vec<_>, _>>()?;
+
+    let mut index = FuseIndex::new(options);
+    index.set_keys(keys);
+    index.set_docs(docs);
+    Ok(index)
+}
+
+/// Parses an index from its JSON form, like Fuse.js' `Fuse.parseIndex`.
+///
+/// `data` is the output of Fuse.js' `index.toJSON()` or of
+/// [`FuseIndex::to_json`], e.g. read from a file with
+/// `serde_json::from_reader`. `options` configure the documents added to
+/// the index afterwards.
+///
+/// # Format
+///
+/// The format is the one of Fuse.js and stays stable across versions:
+///
+/// ```json
+/// {
+///   "keys": [{ "path": ["author", "name"], "id": "author.name", "weight": 1, "src": "author.name" }],
+///   "records": [
+///     { "i": 0, "$": { "0": { "v": "John Scalzi", "n": 0.707 }, "1": [{ "v": "war", "i": 0, "n": 1 }] } },
+///     { "i": 1, "v": "a string document", "n": 0.577 }
+///   ]
+/// }
+/// ```
+///
+/// Keys need a `path` and a positive `weight`; `id` and `src` are derived
+/// from the path when missing. A record holds the position `i` of its
+/// document and either the `v`alue and `n`orm of a string document, or by
+/// key position (`$`) the value of an object document's key, or an array of
+/// values with their position `i` in the key's array. Values indexed by
+/// this library may also hold their typed value `t` (numbers and booleans)
+/// and the timestamp `d` of RFC 3339 dates; Fuse.js ignores both.
+///
+/// # Returns
+///
+/// The index, or `FuseError::InvalidIndex` if `data` does not have the shape
+/// of an index.
+pub fn parse_index<'a>(data: Value, options: &FuseOptions) -> Result<FuseIndex<'a>, FuseError> {
+    FuseIndex::from_json_with_options(data, options).map_err(|e| FuseError::InvalidIndex(e.to_string()))
+}
+
+//----------------------------------------------------------------------
+// Implementation Details
+//----------------------------------------------------------------------
+
+/// Drops the records whose id is in `removed` (sorted) and lowers the ids of
+/// the others by the number of removed ids below them.
+fn compact_records(records: &mut FuseIndexRecords, removed: &[usize]) {
+    records.retain_mut(|record| match removed.binary_search(&record.index()) {
+        Ok(_) => false,
+        Err(rank) => {
+            match record {
+                FuseIndexRecord::String(r) => r.i -= rank,
+                FuseIndexRecord::Object(r) => r.i -= rank,
+            }
+            true
+        }
+    });
+}
+
+/// Returns the weight of index keys that do not give one.
+fn default_key_weight() -> f64 {
+    1.0
+}
+
+/// Returns the tokenizer used for the field-length norm.
+fn norm_tokenizer(options: &FuseOptions) -> Arc<dyn Tokenizer> {
+    match &options.stop_words {
+        Some(stop_words) => Arc::new(StopWordTokenizer::new(options.tokenizer.clone(), stop_words.clone())),
+        None => options.tokenizer.clone(),
+    }
 }
 
 #[cfg(test)]
@@ -340,32 +1033,60 @@ mod tests {
         index.add(&empty_doc);
         assert_eq!(index.size(), 1); // Size shouldn't change
     }
-    
+
+    #[test]
+    fn test_add_string_ignores_stop_words_in_norm() {
+        use crate::helpers::stop_words::{StopWordLanguage, StopWords};
+
+        let options = FuseOptions {
+            stop_words: Some(StopWords::for_language(StopWordLanguage::English)),
+            ..Default::default()
+        };
+        let mut index = FuseIndex::new(&options);
+        index.add(&json!("The Lord of the Rings"));
+        index.add(&json!("Lord Rings"));
+
+        let norms: Vec<f64> = index.records.iter().map(|r| match r {
+            FuseIndexRecord::String(record) => record.n,
+            FuseIndexRecord::Object(_) => panic!("Expected string record"),
+        }).collect();
+        assert_eq!(norms[0], norms[1]);
+    }
+
     #[test]
     fn test_add_object() {
-        let mut options = FuseOptions::default();
-        
         // Set up keys for indexing
-        options.keys = vec![
-            FuseOptionKey::String("title".into()),
-            FuseOptionKey::String("author".into()),
-        ];
+        let options = FuseOptions {
+            keys: vec![
+                FuseOptionKey::String("title".into()),
+                FuseOptionKey::String("author".into()),
+            ],
+            ..Default::default()
+        };
         
         let mut index = FuseIndex::new(&options);
         index.set_keys(vec![
             Key {
                 path: vec!["title".to_string()],
+                compiled_path: Path::from_segments(["title".to_string()]),
                 id: "title".to_string(),
                 weight: 1.0,
                 src: "title".into(),
                 get_fn: None,
+                fallback: None,
+                index: true,
+                searchable: true,
             },
             Key {
                 path: vec!["author".to_string()],
+                compiled_path: Path::from_segments(["author".to_string()]),
                 id: "author".to_string(),
                 weight: 1.0,
                 src: "author".into(),
                 get_fn: None,
+                fallback: None,
+                index: true,
+                searchable: true,
             },
         ]);
         
@@ -406,29 +1127,38 @@ mod tests {
     
     #[test]
     fn test_add_object_with_array() {
-        let mut options = FuseOptions::default();
-        
         // Set up keys for indexing
-        options.keys = vec![
-            FuseOptionKey::String("title".into()),
-            FuseOptionKey::String("tags".into()),
-        ];
+        let options = FuseOptions {
+            keys: vec![
+                FuseOptionKey::String("title".into()),
+                FuseOptionKey::String("tags".into()),
+            ],
+            ..Default::default()
+        };
         
         let mut index = FuseIndex::new(&options);
         index.set_keys(vec![
             Key {
                 path: vec!["title".to_string()],
+                compiled_path: Path::from_segments(["title".to_string()]),
                 id: "title".to_string(),
                 weight: 1.0,
                 src: "title".into(),
                 get_fn: None,
+                fallback: None,
+                index: true,
+                searchable: true,
             },
             Key {
                 path: vec!["tags".to_string()],
+                compiled_path: Path::from_segments(["tags".to_string()]),
                 id: "tags".to_string(),
                 weight: 1.0,
                 src: "tags".into(),
                 get_fn: None,
+                fallback: None,
+                index: true,
+                searchable: true,
             },
         ]);
         
@@ -466,6 +1196,188 @@ mod tests {
         }
     }
     
+    #[test]
+    fn test_add_object_keeps_typed_values() {
+        let options = FuseOptions::default();
+        let mut index = FuseIndex::new(&options);
+        index.set_keys(vec![
+            create_key(&FuseOptionKey::String("year".into())).unwrap(),
+            create_key(&FuseOptionKey::String("flags".into())).unwrap(),
+        ]);
+
+        index.add(&json!({ "year": 1925, "flags": [true, "draft"] }));
+
+        let FuseIndexRecord::Object(record) = &index.records[0] else {
+            panic!("Expected object record");
+        };
+
+        match record.entries.get("0") {
+            Some(RecordEntryValue::Single(year)) => {
+                assert_eq!(year.v, "1925");
+                assert_eq!(year.t, Some(json!(1925)));
+            }
+            _ => panic!("Year should be a Single value"),
+        }
+
+        match record.entries.get("1") {
+            Some(RecordEntryValue::Array(flags)) => {
+                let flag = flags.iter().find(|f| f.i == Some(0)).unwrap();
+                assert_eq!(flag.v, "true");
+                assert_eq!(flag.t, Some(json!(true)));
+
+                let draft = flags.iter().find(|f| f.i == Some(1)).unwrap();
+                assert_eq!(draft.t, None);
+            }
+            _ => panic!("Flags should be an Array value"),
+        }
+
+        // Strings don't carry a typed value, so serialized indexes are unchanged
+        let serialized = serde_json::to_value(&index.records[0]).unwrap();
+        assert_eq!(serialized["$"]["0"], json!({ "v": "1925", "n": 1.0, "t": 1925 }));
+    }
+
+    #[test]
+    fn test_add_object_with_fallback() {
+        use crate::core::options::keys::{FuseKeyFallback, FuseOptionKeyName, FuseOptionKeyObject};
+
+        let key = |name: &'static str, fallback| {
+            FuseOptionKey::KeyObject(FuseOptionKeyObject {
+                name: Cow::Owned(FuseOptionKeyName::String(name.into())),
+                weight: None,
+                get_fn: None,
+                fallback: Some(fallback),
+                analyzer: None,
+                index: true,
+                searchable: true,
+            })
+        };
+
+        let keys = vec![
+            key("title", FuseKeyFallback::Paths(vec![
+                FuseOptionKeyName::String("meta.title".into()),
+                FuseOptionKeyName::String("name".into()),
+            ])),
+            key("status", FuseKeyFallback::Value(json!("unknown"))),
+        ];
+        let docs = vec![
+            json!({ "title": "Dune", "status": "read" }),
+            json!({ "name": "Hyperion" }),
+            json!({ "meta": { "title": "Ubik" }, "name": "ignored" }),
+        ];
+
+        let index = FuseIndex::create_index(&keys, &docs, None, None);
+
+        let value_at = |record: usize, key: &str| match &index.records[record] {
+            FuseIndexRecord::Object(r) => match r.entries.get(key) {
+                Some(RecordEntryValue::Single(v)) => v.v.clone(),
+                other => panic!("Expected a single value, got {:?}", other),
+            },
+            _ => panic!("Expected object record"),
+        };
+
+        assert_eq!(value_at(0, "0"), "Dune");
+        assert_eq!(value_at(0, "1"), "read");
+        assert_eq!(value_at(1, "0"), "Hyperion");
+        assert_eq!(value_at(1, "1"), "unknown");
+        assert_eq!(value_at(2, "0"), "Ubik");
+    }
+
+    #[test]
+    fn test_add_object_with_key_get_fn() {
+        use crate::core::options::keys::{FuseKeyFallback, FuseOptionKeyName, FuseOptionKeyObject, KeyGetFn};
+
+        // Tags are kept comma-separated and split when indexing
+        let key = FuseOptionKey::KeyObject(FuseOptionKeyObject {
+            name: Cow::Owned(FuseOptionKeyName::String("tags".into())),
+            weight: None,
+            get_fn: Some(KeyGetFn::new(|doc| {
+                let tags = doc["tags"].as_str()?.split(',').map(|tag| GetValue::String(tag.trim().into()));
+                Some(GetValue::Array(tags.collect()))
+            })),
+            fallback: Some(FuseKeyFallback::Value(json!("untagged"))),
+            analyzer: None,
+            index: true,
+            searchable: true,
+        });
+        let docs = vec![json!({ "tags": "sci-fi, space" }), json!({ "title": "Dune" })];
+
+        let index = FuseIndex::create_index(&[key], &docs, None, None);
+        let values = |record: usize| match &index.records[record] {
+            FuseIndexRecord::Object(r) => match r.entries.get("0") {
+                Some(RecordEntryValue::Array(values)) => values.iter().map(|v| v.v.clone()).collect(),
+                Some(RecordEntryValue::Single(v)) => vec![v.v.clone()],
+                None => vec![],
+            },
+            _ => panic!("Expected object record"),
+        };
+
+        // Arrays are indexed last value first, like in Fuse.js
+        assert_eq!(values(0), ["space", "sci-fi"]);
+        assert_eq!(values(1), ["untagged"]);
+    }
+
+    #[test]
+    fn test_add_document_custom_representation() {
+        /// Minimal document type backed by a map and vectors
+        enum Doc {
+            Map(HashMap<&'static str, Doc>),
+            List(Vec<Doc>),
+            Text(&'static str),
+        }
+
+        impl Document for Doc {
+            fn field(&self, name: &str) -> Option<&Self> {
+                match self {
+                    Doc::Map(map) => map.get(name),
+                    _ => None,
+                }
+            }
+
+            fn array_len(&self) -> Option<usize> {
+                match self {
+                    Doc::List(items) => Some(items.len()),
+                    _ => None,
+                }
+            }
+
+            fn element(&self, index: usize) -> Option<&Self> {
+                match self {
+                    Doc::List(items) => items.get(index),
+                    _ => None,
+                }
+            }
+
+            fn scalar(&self) -> Option<GetValue> {
+                match self {
+                    Doc::Text(s) => Some(GetValue::String(s.to_string())),
+                    _ => None,
+                }
+            }
+        }
+
+        let keys = vec![
+            FuseOptionKey::String("title".into()),
+            FuseOptionKey::String("tags".into()),
+        ];
+        let mut from_document = FuseIndex::create_index(&keys, &[], None, None);
+        from_document.add_document(&Doc::Map(HashMap::from([
+            ("title", Doc::Text("Dune")),
+            ("tags", Doc::List(vec![Doc::Text("sci-fi"), Doc::Text("desert")])),
+        ])));
+
+        let from_value = FuseIndex::create_index(
+            &keys,
+            &[json!({ "title": "Dune", "tags": ["sci-fi", "desert"] })],
+            None,
+            None,
+        );
+
+        assert_eq!(
+            serde_json::to_value(from_document.records()).unwrap(),
+            serde_json::to_value(from_value.records()).unwrap()
+        );
+    }
+
     #[test]
     fn test_remove_at() {
         let options = FuseOptions::default();
@@ -495,32 +1407,146 @@ mod tests {
             assert_eq!(third.v, "third");
         }
     }
+
+    #[test]
+    fn test_add_all_matches_add() {
+        // Enough documents to be indexed on several threads
+        let docs: Vec<Value> = (0..PARALLEL_MIN_DOCS + 100)
+            .map(|i| match i % 3 {
+                0 => json!({"title": format!("book {}", i), "tags": ["a b", "c"]}),
+                1 => json!({"title": ""}),
+                _ => json!({"tags": [format!("tag {}", i)]}),
+            })
+            .collect();
+        let keys = [FuseOptionKey::String("title".into()), FuseOptionKey::String("tags".into())];
+
+        let mut one_by_one = FuseIndex::create_index(&keys, &[], None, None);
+        for doc in &docs {
+            one_by_one.add(doc);
+        }
+        let options = FuseOptions {
+            index_threads: 4,
+            ..Default::default()
+        };
+        let mut batched = FuseIndex::create_index_with_options(&keys, &[], &options);
+        batched.add_all(&docs[..10]);
+        batched.add_all(&docs[10..]);
+
+        assert_eq!(
+            serde_json::to_value(batched.records()).unwrap(),
+            serde_json::to_value(one_by_one.records()).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_ngram_candidates() {
+        let keys = [FuseOptionKey::String("title".into())];
+        let docs = vec![json!({"title": "Apple pie"}), json!({"title": "Banana"}), json!({"title": "Pineapple"})];
+        let mut index = FuseIndex::create_index(&keys, &docs, None, None);
+        assert!(index.ngram_candidates("apple", 0).is_none());
+
+        index.set_ngram_index(true);
+        assert_eq!(index.ngram_candidates("apple", 0), Some(vec![true, false, true]));
+        // One error may break every trigram of a 5-character pattern
+        assert!(index.ngram_candidates("apple", 1).is_none());
+
+        // Positions follow the records as documents are added and removed
+        index.add_at(&json!({"title": "apple"}), 3);
+        index.remove_at(0);
+        index.compact();
+        assert_eq!(index.ngram_candidates("apple", 0), Some(vec![false, true, true]));
+        assert_eq!(index.ngram_candidates("banana", 0), Some(vec![true, false, false]));
+    }
+
+    #[test]
+    fn test_remove_at_defers_compaction() {
+        let docs: Vec<Value> = (0..20).map(|i| json!(format!("doc {}", i))).collect();
+        let mut index = FuseIndex::create_index(&[], &docs, None, None);
+
+        // Docs 3 and 5 (5 is at position 4 once 3 is gone)
+        index.remove_at(3);
+        index.remove_at(4);
+        assert_eq!(index.size(), 20);
+        assert_eq!(index.position(&index.records[3]), None);
+        assert_eq!(index.position(&index.records[6]), Some(4));
+        assert!(matches!(index.record_at(4), Some(FuseIndexRecord::String(r)) if r.v == "doc 6"));
+
+        // Documents added meanwhile follow the remaining ones
+        index.add_at(&json!("doc 20"), 18);
+        assert!(matches!(index.record_at(18), Some(FuseIndexRecord::String(r)) if r.v == "doc 20"));
+
+        let compacted = index.compacted_records().into_owned();
+        index.compact();
+        assert_eq!(index.size(), 19);
+        assert_eq!(serde_json::to_value(&compacted).unwrap(), serde_json::to_value(index.records()).unwrap());
+        assert!(index.records.iter().enumerate().all(|(position, record)| record.index() == position));
+
+        // Compacts by itself once enough documents were removed
+        for _ in 0..3 {
+            index.remove_at(0);
+        }
+        assert_eq!(index.size(), 16);
+    }
+
+    #[test]
+    fn test_record_at_with_missing_records() {
+        // Empty strings produce no record, so the records are not at the
+        // position of their id
+        let docs: Vec<Value> = (0..12).map(|i| json!(if i < 2 { String::new() } else { format!("doc {}", i) })).collect();
+        let mut index = FuseIndex::create_index(&[], &docs, None, None);
+        index.remove_at(2);
+        assert_eq!(index.size(), 10);
+
+        let value = |index: &FuseIndex, idx| match index.record_at(idx) {
+            Some(FuseIndexRecord::String(record)) => Some(record.v.clone()),
+            _ => None,
+        };
+        assert_eq!(value(&index, 1), None);
+        assert_eq!(value(&index, 2).as_deref(), Some("doc 3"));
+        assert_eq!(value(&index, 10).as_deref(), Some("doc 11"));
+        assert_eq!(value(&index, 11), None);
+
+        // Parsed records are put back in the order of their ids
+        let mut records = index.records().clone();
+        records.reverse();
+        let parsed = FuseIndex::parse_index((Vec::new(), records), None, None);
+        assert_eq!(value(&parsed, 5).as_deref(), Some("doc 5"));
+    }
     
     #[test]
     fn test_get_value_for_item_at_key_id() {
-        let mut options = FuseOptions::default();
-        
         // Set up keys for indexing
-        options.keys = vec![
-            FuseOptionKey::String("title".into()),
-            FuseOptionKey::String("author".into()),
-        ];
+        let options = FuseOptions {
+            keys: vec![
+                FuseOptionKey::String("title".into()),
+                FuseOptionKey::String("author".into()),
+            ],
+            ..Default::default()
+        };
         
         let mut index = FuseIndex::new(&options);
         index.set_keys(vec![
             Key {
                 path: vec!["title".to_string()],
+                compiled_path: Path::from_segments(["title".to_string()]),
                 id: "title".to_string(),
                 weight: 1.0,
                 src: "title".into(),
                 get_fn: None,
+                fallback: None,
+                index: true,
+                searchable: true,
             },
             Key {
                 path: vec!["author".to_string()],
+                compiled_path: Path::from_segments(["author".to_string()]),
                 id: "author".to_string(),
                 weight: 1.0,
                 src: "author".into(),
                 get_fn: None,
+                fallback: None,
+                index: true,
+                searchable: true,
             },
         ]);
         
@@ -658,17 +1684,25 @@ mod tests {
         let keys = vec![
             Key {
                 path: vec!["title".to_string()],
+                compiled_path: Path::from_segments(["title".to_string()]),
                 id: "title".to_string(),
                 weight: 1.0,
                 src: "title".into(),
                 get_fn: None,
+                fallback: None,
+                index: true,
+                searchable: true,
             },
             Key {
                 path: vec!["author".to_string()],
+                compiled_path: Path::from_segments(["author".to_string()]),
                 id: "author".to_string(),
                 weight: 1.0,
                 src: "author".into(),
                 get_fn: None,
+                fallback: None,
+                index: true,
+                searchable: true,
             },
         ];
         
@@ -689,6 +1723,8 @@ mod tests {
                 v: "The Great Gatsby".to_string(),
                 n: 1.0,
                 i: None,
+                t: None,
+                d: None,
             }),
         );
         
@@ -699,6 +1735,8 @@ mod tests {
                 v: "F. Scott Fitzgerald".to_string(),
                 n: 1.0,
                 i: None,
+                t: None,
+                d: None,
             }),
         );
         
@@ -757,17 +1795,83 @@ mod tests {
         }
     }
     
+    #[test]
+    fn test_json_round_trip() {
+        let docs = vec![
+            json!({"title": "Old Man's War", "tags": ["scifi", "war"]}),
+            json!({"title": "The Lock Artist", "tags": ["thriller"]}),
+        ];
+        let keys = [FuseOptionKey::String("title".into()), FuseOptionKey::String("tags".into())];
+        let index = FuseIndex::create_index(&keys, &docs, None, None);
+
+        let parsed = FuseIndex::from_json(index.to_json(), None, None).unwrap();
+        assert_eq!(parsed.to_json(), index.to_json());
+        assert_eq!(parsed.keys_map, index.keys_map);
+    }
+
+    #[test]
+    fn test_from_fuse_js_json() {
+        // Output of `Fuse.createIndex(['title', 'author.firstName'], books).toJSON()`
+        let json = json!({
+            "keys": [
+                {"path": ["title"], "id": "title", "weight": 1, "src": "title", "getFn": null},
+                {"path": ["author", "firstName"], "id": "author.firstName", "weight": 1, "src": "author.firstName", "getFn": null}
+            ],
+            "records": [
+                {"i": 0, "$": {"0": {"v": "Old Man's War", "n": 0.5}, "1": {"v": "John", "n": 1}}}
+            ]
+        });
+
+        let index = FuseIndex::from_json(json, None, None).unwrap();
+        assert_eq!(index.keys[1].id, "author.firstName");
+        assert_eq!(index.keys[1].path, vec!["author", "firstName"]);
+        let FuseIndexRecord::Object(record) = &index.records[0] else {
+            panic!("Expected object record");
+        };
+        assert!(matches!(&record.entries["1"], RecordEntryValue::Single(value) if value.v == "John"));
+
+        assert!(FuseIndex::from_json(json!({"keys": [{"path": ["title"], "weight": 0}], "records": []}), None, None).is_err());
+        assert!(FuseIndex::from_json(json!({"records": []}), None, None).is_err());
+    }
+
+    #[test]
+    fn test_create_and_parse_index() {
+        use crate::core::options::keys::{FuseOptionKeyName, FuseOptionKeyObject};
+
+        let docs = vec![json!({"title": "Old Man's War", "tags": ["scifi"]}), json!("The Lock Artist")];
+        let keys = [FuseOptionKey::String("title".into()), FuseOptionKey::String("tags".into())];
+        let options = FuseOptions { field_norm_weight: 0.5, ..Default::default() };
+
+        let index = create_index(&keys, &docs, &options).unwrap();
+        assert_eq!(index.to_json(), FuseIndex::create_index_with_options(&keys, &docs, &options).to_json());
+
+        let parsed = parse_index(index.to_json(), &options).unwrap();
+        assert_eq!(parsed.to_json(), index.to_json());
+
+        let key = FuseOptionKey::KeyObject(FuseOptionKeyObject {
+            name: Cow::Owned(FuseOptionKeyName::String("title".into())),
+            weight: Some(0.0),
+            get_fn: None,
+            fallback: None,
+            analyzer: None,
+            index: true,
+            searchable: true,
+        });
+        assert!(matches!(create_index(&[key], &docs, &options), Err(FuseError::InvalidKeyWeightValue(_))));
+        assert!(matches!(parse_index(json!({"keys": []}), &options), Err(FuseError::InvalidIndex(_))));
+    }
+
     #[test]
     fn test_create_index_with_custom_get_fn() {
         // Define a custom get_fn that transforms values to uppercase
-        let custom_get_fn: GetFn = |doc, path| {
+        let custom_get_fn = GetFn::new(|doc, path| {
             let default_fn = FuseOptions::default().get_fn;
             if let Some(GetValue::String(value)) = default_fn(doc, path) {
                 Some(GetValue::String(value.to_uppercase()))
             } else {
                 default_fn(doc, path)
             }
-        };
+        });
         
         // Define test keys
         let keys = vec![FuseOptionKey::String("title".into())];
@@ -790,26 +1894,53 @@ mod tests {
         }
     }
     
+    #[test]
+    fn test_get_fn_captures_state() {
+        use std::sync::Arc;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        // Clones of the options share the closure and what it captured
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = calls.clone();
+        let options = FuseOptions {
+            keys: vec![FuseOptionKey::String("title".into())],
+            get_fn: GetFn::new(move |doc, path| {
+                counter.fetch_add(1, Ordering::Relaxed);
+                crate::helpers::get::get(doc, path)
+            }),
+            ..Default::default()
+        };
+        let docs = vec![json!({"title": "a"}), json!({"title": "b"})];
+
+        create_index(&options.keys, &docs, &options).unwrap();
+        create_index(&options.keys, &docs, &options.clone()).unwrap();
+        assert_eq!(calls.load(Ordering::Relaxed), 4);
+    }
+
     #[test]
     fn test_parse_index_with_custom_get_fn() {
         // Define a custom get_fn for testing
-        let custom_get_fn: GetFn = |doc, path| {
+        let custom_get_fn = GetFn::new(|doc, path| {
             let default_fn = FuseOptions::default().get_fn;
             if let Some(GetValue::String(value)) = default_fn(doc, path) {
                 Some(GetValue::String(value.to_uppercase()))
             } else {
                 default_fn(doc, path)
             }
-        };
+        });
         
         // Create keys
         let keys = vec![
             Key {
                 path: vec!["title".to_string()],
+                compiled_path: Path::from_segments(["title".to_string()]),
                 id: "title".to_string(),
                 weight: 1.0,
                 src: "title".into(),
                 get_fn: None,
+                fallback: None,
+                index: true,
+                searchable: true,
             },
         ];
         
@@ -823,6 +1954,8 @@ mod tests {
                 v: "test title".to_string(),
                 n: 1.0,
                 i: None,
+                t: None,
+                d: None,
             }),
         );
         
@@ -5,6 +5,7 @@
 
 use std::collections::HashMap;
 use serde::{Serialize, Deserialize};
+use serde_json::Value;
 
 //----------------------------------------------------------------------
 // Types and Implementations
@@ -28,6 +29,14 @@ pub struct IndexValue {
     /// Optional index, used in arrays of values
     #[serde(skip_serializing_if = "Option::is_none")]
     pub i: Option<usize>,
+    /// The original typed value for numbers and booleans (`v` holds its
+    /// searchable string form)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub t: Option<Value>,
+    /// Milliseconds between the Unix epoch and the date held by `v`, for
+    /// RFC 3339 dates
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub d: Option<i64>,
 }
 
 /// Entry in a record, which can be a single value or an array of values
@@ -102,6 +111,8 @@ impl FuseIndexObjectRecord {
                 v: value,
                 n: norm,
                 i: None,
+                t: None,
+                d: None,
             }),
         );
     }
@@ -114,6 +125,8 @@ impl FuseIndexObjectRecord {
                 v: value,
                 n: norm,
                 i: Some(index),
+                t: None,
+                d: None,
             })
             .collect();
         
@@ -162,6 +175,16 @@ pub enum FuseIndexRecord {
     String(FuseIndexStringRecord),
 }
 
+impl FuseIndexRecord {
+    /// Returns the position of the record's document in the collection.
+    pub fn index(&self) -> usize {
+        match self {
+            Self::Object(r) => r.i,
+            Self::String(r) => r.i,
+        }
+    }
+}
+
 /// Collection of records in the search index
 /// 
 /// This can be either a collection of object records or string records,
@@ -1,8 +1,10 @@
 use std::borrow::Cow;
 use std::collections::HashMap;
 use serde::Serialize;
-use crate::core::options::keys::{FuseOptionKey, FuseOptionKeyName, FuseOptionKeyObject, FuseKeyValueGetter};
+use serde_json::Value;
+use crate::core::options::keys::{FuseOptionKey, FuseOptionKeyName, FuseOptionKeyObject, FuseKeyValueGetter, FuseKeyFallback};
 use crate::core::error_messages::FuseError;
+use crate::helpers::path::{Path, join_path, split_path};
 
 //----------------------------------------------------------------------
 // Key and KeyStore Implementation
@@ -25,6 +27,10 @@ pub struct Key<'a> {
     /// The field path components to access the data
     pub path: Vec<String>,
 
+    /// `path`, parsed once for fast lookups while indexing
+    #[serde(skip)]
+    pub compiled_path: Path,
+
     /// A unique identifier for the key (dot-joined path)
     pub id: String,
 
@@ -37,6 +43,28 @@ pub struct Key<'a> {
     /// Function to retrieve values from the target document
     #[serde(skip)]
     pub get_fn: FuseKeyValueGetter,
+
+    /// Used when the path finds no value in a document
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fallback: Option<KeyFallback>,
+
+    /// Whether the key's values are indexed
+    #[serde(skip)]
+    pub index: bool,
+
+    /// Whether searches that don't name the key match its values
+    #[serde(skip)]
+    pub searchable: bool,
+}
+
+/// Resolved form of a `FuseKeyFallback`, with paths split into components
+#[derive(Debug, Clone, Serialize)]
+pub enum KeyFallback {
+    /// A fixed value indexed in place of the missing one
+    Value(Value),
+
+    /// Alternative paths tried in order
+    Paths(Vec<Path>),
 }
 
 /// A container and manager for a collection of searchable `Key` objects.
@@ -144,6 +172,8 @@ pub fn create_key<'a>(key: &FuseOptionKey<'a>) -> Result<Key<'a>, FuseError> {
     let (src, path): (Cow<str>, Vec<String>);
     let mut weight = 1.0;
     let mut get_fn = None;
+    let mut fallback = None;
+    let (mut index, mut searchable) = (true, true);
 
     match key {
         FuseOptionKey::String(s) => {
@@ -152,7 +182,7 @@ pub fn create_key<'a>(key: &FuseOptionKey<'a>) -> Result<Key<'a>, FuseError> {
         },
         FuseOptionKey::StringArray(arr) => {
             path = arr.iter().map(|s| s.to_string()).collect();
-            src = Cow::Owned(path.join("."));
+            src = Cow::Owned(join_path(&path));
         },
         FuseOptionKey::KeyObject(obj) => {
             // Check if the name property exists
@@ -170,41 +200,70 @@ pub fn create_key<'a>(key: &FuseOptionKey<'a>) -> Result<Key<'a>, FuseError> {
                         return Err(FuseError::MissingKeyProperty("name.path".to_string()));
                     }
                     path = arr.iter().map(|s| s.to_string()).collect();
-                    src = Cow::Owned(path.join("."));
+                    src = Cow::Owned(join_path(&path));
                 }
             }
 
             if let Some(w) = obj.weight {
-                if w <= 0.0 {
+                if !(w > 0.0 && w.is_finite()) {
                     return Err(FuseError::InvalidKeyWeightValue(path.join(".")));
                 }
                 weight = w;
             }
 
-            get_fn = obj.get_fn;
+            get_fn = obj.get_fn.clone();
+            fallback = obj.fallback.as_ref().map(create_key_fallback).transpose()?;
+            index = obj.index;
+            searchable = obj.searchable;
         }
     }
 
     let id = create_key_id(&path);
+    let compiled_path = Path::from_segments(&path);
 
-    Ok(Key { path, id, weight, src, get_fn })
+    Ok(Key { path, compiled_path, id, weight, src, get_fn, fallback, index, searchable })
+}
+
+/// Resolves a key's fallback, splitting fallback paths into components.
+fn create_key_fallback(fallback: &FuseKeyFallback) -> Result<KeyFallback, FuseError> {
+    match fallback {
+        FuseKeyFallback::Value(value) => Ok(KeyFallback::Value(value.clone())),
+        FuseKeyFallback::Paths(names) => names
+            .iter()
+            .map(|name| {
+                if name.is_empty() {
+                    return Err(FuseError::MissingKeyProperty("fallback.path".to_string()));
+                }
+
+                Ok(match name {
+                    FuseOptionKeyName::String(s) => Path::parse(s),
+                    FuseOptionKeyName::StringArray(arr) => Path::from_segments(arr),
+                })
+            })
+            .collect::<Result<_, _>>()
+            .map(KeyFallback::Paths),
+    }
 }
 
 /// Converts a dotted key string into a vector of path components.
 ///
 /// # Arguments
 ///
-/// * `key` - A dot-delimited string (e.g., `"author.name"`).
+/// * `key` - A dot-delimited string (e.g., `"author.name"`). Dots that are part
+///   of a property name can be escaped with a backslash (e.g., `"first\\.name"`).
 ///
 /// # Returns
 ///
 /// A `Vec<String>` of path components.
 pub fn create_key_path(key: &str) -> Vec<String> {
-    key.split('.').map(str::to_owned).collect()
+    split_path(key)
 }
 
 /// Generates a key ID by joining path components with a dot.
 ///
+/// Dots inside a component are escaped, so the ID of `["first.name"]`
+/// (`"first\\.name"`) differs from the ID of `["first", "name"]`.
+///
 /// # Arguments
 ///
 /// * `path` - A slice of strings representing path components.
@@ -213,7 +272,7 @@ pub fn create_key_path(key: &str) -> Vec<String> {
 ///
 /// A `String` representing the dot-joined key ID.
 pub fn create_key_id(path: &[String]) -> String {
-    path.join(".")
+    join_path(path)
 }
 
 #[cfg(test)]
@@ -231,6 +290,10 @@ mod tests {
                 name: Cow::Borrowed(&FuseOptionKeyName::String(Cow::Borrowed("title"))),
                 weight: Some(2.0),
                 get_fn: None,
+                fallback: None,
+                analyzer: None,
+                index: true,
+                searchable: true,
             }),
         ];
 
@@ -245,4 +308,15 @@ mod tests {
         assert_eq!(title_key.src, "title");
         assert!(title_key.weight > 0.0);
     }
+
+    #[test]
+    fn test_create_key_with_escaped_dot() {
+        let key = create_key(&FuseOptionKey::String(Cow::Borrowed("meta.first\\.name"))).unwrap();
+        assert_eq!(key.path, vec!["meta".to_string(), "first.name".to_string()]);
+        assert_eq!(key.id, "meta.first\\.name");
+
+        let literal = create_key(&FuseOptionKey::StringArray(vec![Cow::Borrowed("first.name")])).unwrap();
+        assert_eq!(literal.path, vec!["first.name".to_string()]);
+        assert_ne!(literal.id, create_key(&FuseOptionKey::String(Cow::Borrowed("first.name"))).unwrap().id);
+    }
 }
@@ -4,7 +4,16 @@
 //! including key management, indexing, and normalization.
 
 // Internal module structure
+#[cfg(feature = "std")]
 pub(crate) mod key_store;
+#[cfg(feature = "std")]
 pub(crate) mod norm;
+#[cfg(feature = "std")]
 pub(crate) mod fuse_index;
-pub(crate) mod fuse_index_record;
\ No newline at end of file
+#[cfg(feature = "std")]
+pub(crate) mod fuse_index_record;
+#[cfg(feature = "std")]
+pub(crate) mod doc_store;
+pub(crate) mod string_index;
+#[cfg(feature = "std")]
+pub(crate) mod token_trie;
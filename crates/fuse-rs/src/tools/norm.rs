@@ -4,18 +4,21 @@
 //! the scoring process of fuzzy search results. The normalization ensures
 //! that field length is appropriately factored into relevance scoring.
 
-use std::collections::HashMap;
-use std::sync::Mutex;
-use lazy_static::lazy_static;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::helpers::tokenizer::{Tokenizer, default_tokenizer};
+use crate::helpers::trace;
 
 //----------------------------------------------------------------------
-// Constants & Statics
+// Constants
 //----------------------------------------------------------------------
 
-lazy_static! {
-    /// Regular expression to split text into tokens by whitespace
-    static ref SPACE_REGEX: regex::Regex = regex::Regex::new(r"\s+").unwrap();
-}
+/// Number of token counts whose normalization factor is cached by default
+pub(crate) const DEFAULT_CACHE_SIZE: usize = 256;
+
+/// Bits of an empty cache slot (a NaN, which is never a normalization factor)
+const EMPTY: u64 = u64::MAX;
 
 //----------------------------------------------------------------------
 // Normalization Implementation
@@ -43,9 +46,17 @@ pub struct Norm {
     
     /// Precision control for calculations
     mantissa: u32,
+
+    /// Splits values into the tokens that are counted
+    tokenizer: Arc<dyn Tokenizer>,
     
-    /// Cache of previously calculated normalization values by token count
-    cache: Mutex<HashMap<usize, f64>>,
+    /// Previously calculated normalization values (as bits), indexed by
+    /// token count
+    ///
+    /// Slots are atomics so concurrent index builds never wait on each other;
+    /// two threads may compute the same value, which is harmless. Values
+    /// with more tokens than there are slots are cheap enough to recompute.
+    cache: Box<[AtomicU64]>,
 }
 
 impl Norm {
@@ -63,10 +74,24 @@ impl Norm {
         Norm {
             weight,
             mantissa,
-            cache: Mutex::new(HashMap::new()),
+            tokenizer: default_tokenizer(),
+            cache: empty_cache(DEFAULT_CACHE_SIZE),
         }
     }
 
+    /// Caches the normalization factors of values of up to `size - 1`
+    /// tokens (256 by default); `0` disables the cache.
+    pub fn with_cache_size(mut self, size: usize) -> Self {
+        self.cache = empty_cache(size);
+        self
+    }
+
+    /// Replaces the tokenizer used to count tokens (whitespace by default).
+    pub fn with_tokenizer(mut self, tokenizer: Arc<dyn Tokenizer>) -> Self {
+        self.tokenizer = tokenizer;
+        self
+    }
+
     /// Calculates the normalization factor for a given string value.
     ///
     /// This method counts the tokens in the input string and returns
@@ -81,26 +106,32 @@ impl Norm {
     ///
     /// A normalization factor as a float value
     pub fn get(&self, value: &str) -> f64 {
-        // Count non-empty tokens in the string
-        let num_tokens = SPACE_REGEX
-            .split(value.trim())
-            .filter(|s| !s.is_empty())
-            .count();
-        
+        let num_tokens = self.tokenizer.count(value);
+
+        let Some(slot) = self.cache.get(num_tokens) else {
+            return self.compute(num_tokens);
+        };
+
         // Check cache first
-        let mut cache = self.cache.lock().unwrap();
-        if let Some(&n) = cache.get(&num_tokens) {
-            return n;
+        let bits = slot.load(Ordering::Relaxed);
+        if bits != EMPTY {
+            trace::trace!(tokens = num_tokens, "norm cache hit");
+            return f64::from_bits(bits);
         }
-        
-        // Calculate normalization factor
+
+        trace::trace!(tokens = num_tokens, "norm cache miss");
+        let n = self.compute(num_tokens);
+        slot.store(n.to_bits(), Ordering::Relaxed);
+        n
+    }
+
+    /// Calculates the normalization factor for `num_tokens` tokens, rounded
+    /// to `mantissa` decimal places.
+    fn compute(&self, num_tokens: usize) -> f64 {
         let m = 10f64.powi(self.mantissa as i32);
         let norm = 1.0 / (num_tokens as f64).powf(0.5 * self.weight);
-        
-        // Round to specified precision and cache result
-        let n = ((norm * m).round() / m) as f64;
-        cache.insert(num_tokens, n);
-        n
+
+        (norm * m).round() / m
     }
 
     /// Clears the internal cache of normalization values.
@@ -108,10 +139,17 @@ impl Norm {
     /// This can be useful if memory usage is a concern or if
     /// normalization parameters have been changed.
     pub fn clear(&self) {
-        self.cache.lock().unwrap().clear();
+        for slot in self.cache.iter() {
+            slot.store(EMPTY, Ordering::Relaxed);
+        }
     }
 }
 
+/// Returns `size` empty cache slots.
+fn empty_cache(size: usize) -> Box<[AtomicU64]> {
+    (0..size).map(|_| AtomicU64::new(EMPTY)).collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -139,6 +177,49 @@ mod tests {
         assert_eq!(n1, n3); // Should recompute but same value
     }
 
+    #[test]
+    fn test_norm_uncached_token_counts() {
+        let norm = Norm::new(1.0, 3);
+        let value = vec!["a"; DEFAULT_CACHE_SIZE + 1].join(" ");
+
+        assert_eq!(norm.get(&value), 0.062);
+        assert_eq!(norm.get(&value), 0.062);
+    }
+
+    #[test]
+    fn test_norm_cache_size() {
+        let value = vec!["a"; 1000].join(" ");
+        let expected = Norm::new(1.0, 3).get(&value);
+
+        for size in [0, 1, 1001, 4096] {
+            let norm = Norm::new(1.0, 3).with_cache_size(size);
+            assert_eq!(norm.get(&value), expected);
+            assert_eq!(norm.get(&value), expected);
+            assert_eq!(norm.get("a b"), Norm::new(1.0, 3).get("a b"));
+        }
+    }
+
+    #[test]
+    fn test_norm_concurrent_get() {
+        let norm = Norm::new(0.5, 3);
+        let values: Vec<String> = (1..=64).map(|n| vec!["word"; n].join(" ")).collect();
+        let expected: Vec<f64> = values.iter().map(|v| Norm::new(0.5, 3).get(v)).collect();
+
+        // Threads share the cache without locking; every thread must see the
+        // same values as a single-threaded computation
+        std::thread::scope(|scope| {
+            for _ in 0..8 {
+                scope.spawn(|| {
+                    for _ in 0..100 {
+                        for (value, &n) in values.iter().zip(&expected) {
+                            assert_eq!(norm.get(value), n);
+                        }
+                    }
+                });
+            }
+        });
+    }
+
     #[test]
     fn test_norm_single_token() {
         let norm = Norm::new(1.0, 2);
@@ -146,4 +227,13 @@ mod tests {
         let n = norm.get(value);
         assert!((n - 1.0).abs() < 0.001);
     }
+
+    #[test]
+    fn test_norm_with_tokenizer() {
+        use crate::helpers::tokenizer::CjkBigramTokenizer;
+
+        let norm = Norm::new(1.0, 2).with_tokenizer(Arc::new(CjkBigramTokenizer));
+        // Three bigrams, same as three whitespace-separated words
+        assert_eq!(norm.get("北京大学"), Norm::new(1.0, 2).get("a b c"));
+    }
 }
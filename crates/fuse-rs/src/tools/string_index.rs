@@ -0,0 +1,174 @@
+//! A basic index of strings
+//!
+//! `StringIndex` is the search engine available without `std`: it holds a
+//! list of strings and fuzzy matches them with the bitap algorithm, without
+//! keys, weights or normalization beyond case folding.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::FuseError;
+#[cfg(feature = "std")]
+use crate::helpers::caseless;
+use crate::search::bitmap::options::BitapOptions;
+use crate::search::bitmap::pattern::BitapPattern;
+
+//----------------------------------------------------------------------
+// Types
+//----------------------------------------------------------------------
+
+/// An in-memory list of strings searched with the bitap algorithm
+///
+/// # Example
+///
+/// ```
+/// use fuse_rs::{BitapOptions, StringIndex};
+///
+/// let mut index = StringIndex::new(BitapOptions::default());
+/// index.extend(["Old Man's War", "The Lock Artist"]);
+///
+/// let matches = index.search("od man").unwrap();
+/// assert_eq!(matches[0].ref_index, 0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct StringIndex {
+    /// The indexed strings, lowercased unless the index is case-sensitive
+    texts: Vec<String>,
+
+    /// Options of the bitap algorithm
+    options: BitapOptions,
+
+    /// Whether the case of the strings matters
+    is_case_sensitive: bool,
+}
+
+/// A string of a `StringIndex` matching a pattern
+#[derive(Debug, Clone, PartialEq)]
+pub struct StringMatch {
+    /// Index of the string in the index
+    pub ref_index: usize,
+
+    /// Match score (lower is better)
+    pub score: f64,
+
+    /// Inclusive character ranges that matched, when `include_matches` is set
+    pub indices: Vec<(usize, usize)>,
+}
+
+//----------------------------------------------------------------------
+// Implementation
+//----------------------------------------------------------------------
+
+impl StringIndex {
+    /// Creates an empty, case-insensitive index.
+    pub fn new(options: BitapOptions) -> Self {
+        Self { texts: Vec::new(), options, is_case_sensitive: false }
+    }
+
+    /// Creates an empty, case-sensitive index.
+    pub fn case_sensitive(options: BitapOptions) -> Self {
+        Self { texts: Vec::new(), options, is_case_sensitive: true }
+    }
+
+    /// Adds `text` at the end of the index.
+    pub fn add(&mut self, text: &str) {
+        let text = self.fold(text);
+        self.texts.push(text);
+    }
+
+    /// Returns the number of indexed strings.
+    pub fn len(&self) -> usize {
+        self.texts.len()
+    }
+
+    /// Returns `true` if no string is indexed.
+    pub fn is_empty(&self) -> bool {
+        self.texts.is_empty()
+    }
+
+    /// Searches the index for `pattern`.
+    ///
+    /// # Returns
+    ///
+    /// The matching strings, best first, or
+    /// `FuseError::PatternLengthTooLarge` if the pattern cannot be matched.
+    pub fn search(&self, pattern: &str) -> Result<Vec<StringMatch>, FuseError> {
+        let pattern = BitapPattern::new(&self.fold(pattern));
+        let mut matches = Vec::new();
+
+        for (ref_index, text) in self.texts.iter().enumerate() {
+            let result = pattern.search_in(text, &self.options)?;
+
+            if result.is_match {
+                matches.push(StringMatch { ref_index, score: result.score, indices: result.indices });
+            }
+        }
+
+        matches.sort_by(|a, b| a.score.total_cmp(&b.score).then(a.ref_index.cmp(&b.ref_index)));
+        Ok(matches)
+    }
+
+    /// Case-folds `text` unless the index is case-sensitive.
+    ///
+    /// Without the `std` feature, `text` is lowercased instead.
+    fn fold(&self, text: &str) -> String {
+        if self.is_case_sensitive {
+            return String::from(text);
+        }
+
+        #[cfg(feature = "std")]
+        return caseless::fold(text).collect();
+        #[cfg(not(feature = "std"))]
+        return text.to_lowercase();
+    }
+}
+
+impl<S: AsRef<str>> Extend<S> for StringIndex {
+    fn extend<I: IntoIterator<Item = S>>(&mut self, texts: I) {
+        for text in texts {
+            self.add(text.as_ref());
+        }
+    }
+}
+
+//----------------------------------------------------------------------
+// Tests
+//----------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn books(options: BitapOptions) -> StringIndex {
+        let mut index = StringIndex::new(options);
+        index.extend(["Old Man's War", "The Lock Artist", "HTML5"]);
+        index
+    }
+
+    #[test]
+    fn test_search_orders_by_score() {
+        let index = books(BitapOptions::default());
+        let matches = index.search("lock").unwrap();
+
+        assert_eq!(matches[0].ref_index, 1);
+        assert!(matches.windows(2).all(|pair| pair[0].score <= pair[1].score));
+    }
+
+    #[test]
+    fn test_search_case_sensitivity() {
+        let options = BitapOptions { threshold: 0.0, ..Default::default() };
+        assert_eq!(books(options).search("html5").unwrap()[0].score, 0.0);
+
+        let mut index = StringIndex::case_sensitive(options);
+        index.add("HTML5");
+        assert!(index.search("html5").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_search_indices() {
+        let index = books(BitapOptions { include_matches: true, ..Default::default() });
+        let matches = index.search("war").unwrap();
+
+        assert!(matches[0].indices.contains(&(10, 12)));
+    }
+}
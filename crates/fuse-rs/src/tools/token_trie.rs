@@ -0,0 +1,170 @@
+//! Prefix trie of the tokens of a collection
+//!
+//! Autocomplete looks up the tokens beginning with what has been typed so
+//! far. [`TokenTrie`] stores every token once along with the number of
+//! times it occurs, and walks its branches with a row of the edit distance
+//! table, so a prefix with typos still finds its completions.
+
+use std::collections::BTreeMap;
+
+//----------------------------------------------------------------------
+// Types
+//----------------------------------------------------------------------
+
+/// A trie of tokens and their frequencies
+#[derive(Debug, Clone)]
+pub(crate) struct TokenTrie {
+    /// The nodes of the trie, the root first
+    nodes: Vec<TrieNode>,
+}
+
+/// A node of the trie, standing for the characters on the path to it
+#[derive(Debug, Clone, Default)]
+struct TrieNode {
+    /// Child nodes by their character, in character order
+    children: BTreeMap<char, usize>,
+
+    /// Number of occurrences of the token ending at this node
+    count: usize,
+}
+
+/// A token whose beginning approximately matches a prefix
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Completion {
+    /// The token
+    pub(crate) token: String,
+
+    /// The smallest number of edits turning the prefix into a beginning of
+    /// the token
+    pub(crate) errors: usize,
+
+    /// Number of occurrences of the token
+    pub(crate) count: usize,
+}
+
+//----------------------------------------------------------------------
+// Implementation
+//----------------------------------------------------------------------
+
+impl TokenTrie {
+    /// Creates an empty trie.
+    pub(crate) fn new() -> Self {
+        Self {
+            nodes: vec![TrieNode::default()],
+        }
+    }
+
+    /// Adds an occurrence of `token`.
+    pub(crate) fn insert(&mut self, token: &str) {
+        let mut node = 0;
+        for c in token.chars() {
+            node = match self.nodes[node].children.get(&c) {
+                Some(&child) => child,
+                None => {
+                    self.nodes.push(TrieNode::default());
+                    let child = self.nodes.len() - 1;
+                    self.nodes[node].children.insert(c, child);
+                    child
+                }
+            };
+        }
+
+        self.nodes[node].count += 1;
+    }
+
+    /// Returns the tokens beginning with `prefix` with at most `max_errors`
+    /// insertions, deletions or substitutions, in character order.
+    pub(crate) fn completions(&self, prefix: &str, max_errors: usize) -> Vec<Completion> {
+        let prefix: Vec<char> = prefix.chars().collect();
+        let mut completions = Vec::new();
+
+        // Each entry holds a node, its token, the distances between the
+        // token and every beginning of the prefix, and the smallest distance
+        // between the whole prefix and a beginning of the token
+        let row: Vec<usize> = (0..=prefix.len()).collect();
+        let mut stack = vec![(0, String::new(), row, prefix.len())];
+
+        while let Some((node, token, row, errors)) = stack.pop() {
+            let node = &self.nodes[node];
+            if node.count > 0 && errors <= max_errors {
+                completions.push(Completion {
+                    token: token.clone(),
+                    errors,
+                    count: node.count,
+                });
+            }
+
+            // Pushed in reverse so that the tokens come out in order
+            for (&c, &child) in node.children.iter().rev() {
+                let mut next = Vec::with_capacity(row.len());
+                next.push(row[0] + 1);
+                for (j, &p) in prefix.iter().enumerate() {
+                    let substitution = row[j] + usize::from(p != c);
+                    next.push(substitution.min(row[j + 1] + 1).min(next[j] + 1));
+                }
+
+                let child_errors = errors.min(next[prefix.len()]);
+                // Distances along a branch never decrease below the row's
+                // minimum, so a branch is dropped once it exceeds the budget
+                if child_errors > max_errors && next.iter().min().is_some_and(|&min| min > max_errors) {
+                    continue;
+                }
+
+                let mut child_token = token.clone();
+                child_token.push(c);
+                stack.push((child, child_token, next, child_errors));
+            }
+        }
+
+        completions
+    }
+}
+
+impl Default for TokenTrie {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+//----------------------------------------------------------------------
+// Tests
+//----------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trie(tokens: &[&str]) -> TokenTrie {
+        let mut trie = TokenTrie::new();
+        for token in tokens {
+            trie.insert(token);
+        }
+        trie
+    }
+
+    fn tokens(completions: &[Completion]) -> Vec<(&str, usize, usize)> {
+        completions.iter().map(|c| (c.token.as_str(), c.errors, c.count)).collect()
+    }
+
+    #[test]
+    fn test_exact_prefix() {
+        let trie = trie(&["old", "older", "oldest", "old", "man"]);
+
+        assert_eq!(
+            tokens(&trie.completions("old", 0)),
+            vec![("old", 0, 2), ("older", 0, 1), ("oldest", 0, 1)]
+        );
+        assert_eq!(tokens(&trie.completions("olde", 0)), vec![("older", 0, 1), ("oldest", 0, 1)]);
+        assert!(trie.completions("x", 0).is_empty());
+    }
+
+    #[test]
+    fn test_prefix_with_errors() {
+        let trie = trie(&["hobbit", "habit", "rabbit", "lord"]);
+
+        assert_eq!(tokens(&trie.completions("hob", 0)), vec![("hobbit", 0, 1)]);
+        assert_eq!(tokens(&trie.completions("hob", 1)), vec![("habit", 1, 1), ("hobbit", 0, 1)]);
+        assert_eq!(tokens(&trie.completions("rob", 1)), vec![("hobbit", 1, 1), ("rabbit", 1, 1)]);
+        assert_eq!(trie.completions("", 0).len(), 4);
+    }
+}
@@ -0,0 +1,111 @@
+//! WebAssembly bindings for fuse-rs
+//!
+//! Exposes a `Fuse` class to JavaScript that mirrors the Fuse.js API: it is
+//! constructed from a list and Fuse.js-style options (`keys`, `includeScore`,
+//! `threshold`, ...) and `search` returns `{ item, refIndex, score, matches }`
+//! objects.
+//!
+//! ```javascript
+//! import init, { Fuse } from 'fuse-rs-wasm';
+//!
+//! await init();
+//! const fuse = new Fuse(books, { keys: ['title', 'author'], includeScore: true });
+//! const results = fuse.search('old man', { limit: 10 });
+//! ```
+
+use fuse_rs::{Fuse as FuseEngine, FuseOptions, FuseSearchOptions};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use serde_wasm_bindgen::Serializer;
+use wasm_bindgen::prelude::*;
+
+//----------------------------------------------------------------------
+// Types
+//----------------------------------------------------------------------
+
+/// Fuzzy searcher over a list of JavaScript values
+#[wasm_bindgen(js_name = Fuse)]
+pub struct JsFuse {
+    /// The search engine
+    engine: FuseEngine<'static>,
+}
+
+/// Per-search options of `Fuse.search`
+#[derive(Debug, Default, Deserialize)]
+struct SearchOptions {
+    /// Maximum number of results
+    limit: Option<usize>,
+}
+
+//----------------------------------------------------------------------
+// Public API
+//----------------------------------------------------------------------
+
+#[wasm_bindgen(js_class = Fuse)]
+impl JsFuse {
+    /// Creates a searcher for `list` with Fuse.js-style `options`.
+    #[wasm_bindgen(constructor)]
+    pub fn new(list: JsValue, options: JsValue) -> Result<JsFuse, JsError> {
+        console_error_panic_hook::set_once();
+
+        let docs: Vec<Value> = serde_wasm_bindgen::from_value(list)?;
+        let options = FuseOptions::from_json(from_js(options)?)?;
+
+        Ok(Self { engine: FuseEngine::from_docs(docs, &options, None) })
+    }
+
+    /// Searches the collection for `pattern`.
+    ///
+    /// `options` may set a `limit` on the number of results. Returns an array
+    /// of `{ item, refIndex, score?, matches? }` objects.
+    pub fn search(&self, pattern: &str, options: JsValue) -> Result<JsValue, JsError> {
+        let options: SearchOptions = match from_js(options)? {
+            Value::Null => SearchOptions::default(),
+            value => serde_json::from_value(value)?,
+        };
+
+        let search_options = FuseSearchOptions {
+            limit: options.limit.unwrap_or(usize::MAX),
+            ..Default::default()
+        };
+
+        let results = self.engine.search_results(pattern, &search_options)?;
+        Ok(results.serialize(&Serializer::json_compatible())?)
+    }
+
+    /// Replaces the searched collection.
+    #[wasm_bindgen(js_name = setCollection)]
+    pub fn set_collection(&mut self, list: JsValue) -> Result<(), JsError> {
+        let docs: Vec<Value> = serde_wasm_bindgen::from_value(list)?;
+        self.engine.set_collection(&docs, None)?;
+        Ok(())
+    }
+
+    /// Adds an item to the collection.
+    pub fn add(&mut self, doc: JsValue) -> Result<(), JsError> {
+        let doc: Value = serde_wasm_bindgen::from_value(doc)?;
+        self.engine.add(doc)?;
+        Ok(())
+    }
+
+    /// Removes the item at `index` from the collection and returns it.
+    #[wasm_bindgen(js_name = removeAt)]
+    pub fn remove_at(&mut self, index: usize) -> Result<JsValue, JsError> {
+        match self.engine.remove_at(index)? {
+            Some(removed) => Ok(removed.serialize(&Serializer::json_compatible())?),
+            None => Ok(JsValue::UNDEFINED),
+        }
+    }
+}
+
+//----------------------------------------------------------------------
+// Implementation Details
+//----------------------------------------------------------------------
+
+/// Converts a JavaScript value to JSON; `undefined` becomes `null`.
+fn from_js(value: JsValue) -> Result<Value, JsError> {
+    if value.is_undefined() {
+        return Ok(Value::Null);
+    }
+    Ok(serde_wasm_bindgen::from_value(value)?)
+}